@@ -0,0 +1,647 @@
+app-heading = OBS Control
+
+login-address = Ip address
+login-port = Port
+login-password = Password
+login-log-in = Log In
+login-not-logged-in = Not Logged In
+
+log-clear = Clear
+
+panel-mixer = Mixer
+panel-scenes = Scenes
+panel-buttons = Buttons
+panel-history = History
+panel-plugins = Plugins
+panel-settings = Settings
+panel-log = Log
+panel-popped-out = { $panel } is in its own window
+panel-dock = Dock
+panel-pop-out = Open in a separate window
+
+layout-layouts = Layouts
+layout-name = Layout name
+layout-save = Save
+layout-delete = Delete
+layout-reset = Reset
+
+status-streaming = Streaming
+status-not-streaming = Not Streaming
+status-recording = Recording
+status-not-recording = Not Recording
+status-offline = Offline
+status-pending = { $count } pending
+
+scenes-queue-offline = Queue while offline
+scenes-mini = Mini
+scenes-mini-hint = Compact always-on-top strip (Ctrl+Shift+M)
+scenes-log-out = Log Out
+
+mixer-mic-volume = Mic Volume
+mixer-desktop-volume = Desktop Volume
+mixer-mute-mic = Mute Mic
+mixer-unmute-mic = Unmute Mic
+mixer-no-mic = No Mic Selected
+mixer-mute-desktop = Mute Desktop
+mixer-unmute-desktop = Unmute Desktop
+mixer-no-desktop = No Desktop Selected
+
+grid-scripts = Scripts
+grid-button-name = Button name
+grid-run = Run
+grid-stop-scripts = Stop Scripts
+grid-add-button = Add Button
+
+history-volume = { $input } volume { $volume }
+history-mute = Mute { $input }
+history-unmute = Unmute { $input }
+history-scene = Switch to { $scene }
+history-show-source = Show { $source } in { $scene }
+history-hide-source = Hide { $source } in { $scene }
+history-other = Other action
+history-undo = Undo
+
+mini-mic = Mic
+mini-desktop = Desktop
+mini-live = ● LIVE
+mini-rec = ● REC
+mini-full-window = Full window
+
+palette-hint = Type a command...
+palette-scene = Scene: { $scene }
+palette-mute = Mute: { $input }
+palette-unmute = Unmute: { $input }
+palette-macro = Macro: { $name }
+palette-start-stream = Start Stream
+palette-stop-stream = Stop Stream
+palette-start-record = Start Record
+palette-stop-record = Stop Record
+palette-undo = Undo
+palette-log-out = Log Out
+
+recovery-title = Resume previous session?
+recovery-unclean = REC did not shut down cleanly last time.
+recovery-reconnect = • Reconnect to OBS
+recovery-restart-services = { $count ->
+    [one] • Restart { $count } remote control service
+   *[other] • Restart { $count } remote control services
+}
+recovery-send-queued = { $count ->
+    [one] • Send { $count } queued action
+   *[other] • Send { $count } queued actions
+}
+recovery-interrupted = These macros were interrupted:
+recovery-run-again = Run again
+recovery-resume = Resume
+recovery-discard = Discard
+recovery-macro-name = Resume session
+
+settings-language = Language
+settings-theme = Theme
+settings-accent = Accent
+settings-background = Background
+settings-sliders = Sliders
+settings-touch-mode = Touchscreen mode
+settings-minimize-to-tray = Minimize to tray
+settings-ui-scale = UI scale
+settings-ui-scale-hint = Ctrl+= / Ctrl+- to zoom, Ctrl+0 to reset
+settings-relay-port = Relay server port
+settings-start-relay = Start Relay
+settings-stop-relay = Stop Relay
+settings-osc-port = OSC port
+settings-osc-feedback = Feedback ip:port
+settings-start-osc = Start OSC
+settings-stop-osc = Stop OSC
+settings-remote-port = Web remote port
+settings-start-remote = Start web remote
+settings-stop-remote = Stop web remote
+settings-remote-hint = Open http://this-computer:{ $port } on a phone on the same network
+remote-title = REC remote
+remote-offline = Can't reach REC, or this link was revoked
+remote-output = Output
+remote-mute = Mute
+remote-scenes = Scenes
+remote-start-stream = Start stream
+remote-stop-stream = Stop stream
+remote-start-recording = Start recording
+remote-stop-recording = Stop recording
+remote-confirm-stop-stream = Stop the stream?
+remote-confirm-stop-recording = Stop recording?
+settings-mqtt-broker = MQTT broker
+settings-mqtt-host = Host
+settings-mqtt-port = Port
+settings-mqtt-prefix = Topic prefix
+settings-mqtt-username = Username
+settings-mqtt-password = Password
+settings-start-mqtt = Start MQTT
+settings-stop-mqtt = Stop MQTT
+
+theme-dark = Dark
+theme-light = Light
+theme-high-contrast = High contrast
+theme-custom = Custom
+
+tray-live = REC - live
+tray-recording = REC - recording
+tray-live-recording = REC - live and recording
+tray-mute-mic = Mute mic
+tray-unmute-mic = Unmute mic
+tray-start-recording = Start recording
+tray-stop-recording = Stop recording
+tray-window = Show/hide window
+tray-quit = Quit
+
+toast-invalid-port = invalid port: { $error }
+toast-invalid-feedback = invalid feedback address: { $error }
+toast-send-failed = failed to send action: { $error }
+toast-unknown-shortcut = unknown shortcut key "{ $key }"
+
+a11y-muted = { $input } muted
+a11y-unmuted = { $input } unmuted
+a11y-scene = Scene changed to { $scene }
+a11y-stream-started = Stream started
+a11y-stream-stopped = Stream stopped
+a11y-record-started = Recording started
+a11y-record-stopped = Recording stopped
+a11y-connection-lost = Connection to OBS lost
+a11y-dismiss = Dismiss
+
+tooltip-volume = { $db } dB (multiplier { $mul })
+tooltip-input = OBS name: { $name }
+    Kind: { $kind }
+    Unversioned kind: { $unversioned }
+tooltip-scene-switched = Last switched to at { $time }
+tooltip-scene-not-switched = Not switched to since connecting
+
+settings-confirm = Ask before
+confirm-stop-stream = Stopping the stream
+confirm-stop-stream-title = Stop the stream?
+confirm-stop-stream-body = The broadcast will end for everyone watching.
+confirm-stop-stream-button = Stop stream
+confirm-stop-record = Stopping the recording
+confirm-stop-record-title = Stop recording?
+confirm-stop-record-body = The recording file will be closed.
+confirm-stop-record-button = Stop recording
+confirm-dont-ask = Don't ask again
+confirm-cancel = Cancel
+
+status-connected = Connected
+status-obs-version = OBS { $version }
+status-stream-timer = LIVE { $elapsed }
+status-record-timer = REC { $elapsed }
+
+notifications-title = Notifications
+notice-recording-started = Recording started
+notice-recording-started-path = Recording to { $path }
+notice-recording-stopped = Recording stopped
+notice-recording-saved = Recording saved to { $path }
+notice-replay-saved = Replay saved to { $path }
+notice-stream-reconnecting = Stream connection dropped, reconnecting...
+notice-stream-reconnected = Stream reconnected
+notice-input-added = Input added: { $input }
+notice-input-removed = Input removed: { $input }
+
+channel-label = Label shown in REC
+channel-reset = Reset
+
+settings-orientation = Orientation
+orientation-auto = Automatic
+orientation-horizontal = Horizontal
+orientation-vertical = Vertical
+
+kiosk-pin = Kiosk PIN
+kiosk-enter = Enter kiosk mode
+kiosk-enter-hint = Locks REC fullscreen with settings hidden until the PIN is entered
+kiosk-unlock-title = Leave kiosk mode
+kiosk-unlock = Unlock
+kiosk-wrong-pin = Wrong PIN
+kiosk-backspace = Delete digit
+kiosk-settings-locked = Settings are locked in kiosk mode.
+kiosk-not-connected = Not connected to OBS. Ask an administrator to check the connection.
+section-channels = Channels and volume
+section-scenes = Scene list
+section-buttons = Button grid
+panel-twitch = Twitch
+twitch-no-client-id = Set client_id under [twitch] in rec.toml to connect a Twitch account.
+twitch-log-in = Log in to Twitch
+twitch-log-out = Log Out
+twitch-enter-code = Enter the code { $code } at:
+twitch-logged-in = Logged in as { $login }
+twitch-title = Title
+twitch-category = Category
+twitch-search = Search categories
+twitch-update = Update channel
+twitch-scene-presets = Applied automatically when switching to:
+toast-twitch-error = Twitch: { $error }
+panel-youtube = YouTube
+youtube-no-client-id = Set client_id and client_secret under [youtube] in rec.toml to connect a YouTube channel.
+youtube-log-in = Log in to YouTube
+youtube-log-out = Log Out
+youtube-enter-code = Enter the code { $code } at:
+youtube-refresh = Refresh
+youtube-no-broadcasts = No upcoming or live broadcasts.
+youtube-bind = Stream to this broadcast
+youtube-bound = OBS is now set up to stream to the YouTube broadcast.
+youtube-bound-label = OBS streams here
+youtube-new-title = New broadcast title
+youtube-create = Create
+youtube-public = Public
+youtube-unlisted = Unlisted
+youtube-private = Private
+status-viewers = { $count } watching
+toast-youtube-error = YouTube: { $error }
+grid-marker = 🔖 Marker
+grid-marker-hint = Mark this moment on the Twitch stream and in the log
+notice-marker = Marker added
+notice-marker-recording = Marker added at { $position } into the recording
+notice-twitch-marker = Twitch marker at { $position }
+twitch-clip = 🎬 Clip that
+twitch-recent-clips = Recent clips
+notice-twitch-clip = Clip created: { $url }
+twitch-chat-commands = Listening in chat for:
+panel-lights = Lights
+lights-none = Looking for Elgato Key Lights on the network…
+lights-all-on = All on
+lights-all-off = All off
+lights-brightness = Brightness
+lights-temperature = Temperature
+lighting-hue-bridge = Hue bridge
+lighting-pair = Pair
+lighting-pair-hint = Press the link button on the bridge first
+lighting-paired = Paired
+lighting-hue-group = Hue room
+lighting-wled = WLED
+lighting-openrgb = OpenRGB
+lighting-group-hint = Group id
+lighting-host-hint = Host
+lighting-preset = Scene id, preset or profile
+lighting-raise = Move up
+lighting-remove = Remove mapping
+lighting-add = Add lighting mapping
+lighting-order-hint = Higher rows win when several match the same lights
+lighting-when-streaming = While streaming
+lighting-when-recording = While recording
+lighting-when-scene = On scene
+lighting-when-muted = While muted
+lighting-when-live = While live
+lighting-when-other = Custom condition
+notice-dropped-frames = { $percent }% of frames dropped in the last 30 seconds
+panel-timers = Timers
+countdown = Countdown
+countdown-minutes = Minutes
+countdown-start = Start
+countdown-pause = Pause
+countdown-reset = Reset
+countdown-none = None
+countdown-text-source = Text source
+countdown-then = At zero run
+countdown-then-hint = “{ $macro }” runs when the countdown reaches zero.
+countdown-finished = Countdown finished
+stopwatch = Stopwatch
+stopwatch-lap = Lap
+stopwatch-copy = Copy segments
+stopwatch-current = Current
+stopwatch-text-source = Text source
+stopwatch-report-line = Segment { $segment }: { $length } (at { $total })
+panel-lower-thirds = Lower thirds
+lower-third-show = Show
+lower-third-showing = Showing
+lower-third-hide = Hide now
+lower-third-remove = Remove lower third
+lower-third-name = Name
+lower-third-title = Title
+lower-third-add = Add
+lower-third-setup = Setup
+lower-third-scene = Scene
+lower-third-source = Source to show
+lower-third-name-source = Name text source
+lower-third-title-source = Title text source
+lower-third-seconds = Hide after (seconds)
+panel-scoreboard = Scoreboard
+scoreboard-left = Left team
+scoreboard-right = Right team
+scoreboard-minus = One less
+scoreboard-plus = One more
+scoreboard-map = Map
+scoreboard-swap = Swap sides
+scoreboard-reset = Reset scores
+scoreboard-sources = Text sources
+scoreboard-left-name = Left name
+scoreboard-left-score = Left score
+scoreboard-right-name = Right name
+scoreboard-right-score = Right score
+scoreboard-map-source = Map number
+panel-teleprompter = Teleprompter
+teleprompter-play = Play
+teleprompter-pause = Pause
+teleprompter-restart = Back to top
+teleprompter-speed = Speed
+teleprompter-size = Size
+teleprompter-mirror = Mirror
+teleprompter-edit = Edit script
+teleprompter-path = Script file
+teleprompter-load = Load
+teleprompter-load-failed = Couldn't load the script: { $error }
+panel-preview = Program
+preview-enabled = Live preview
+preview-fps = Frames per second
+preview-off = Turn on the live preview to see what's on program.
+panel-sources = Sources
+sources-browser = Browser sources
+sources-none = None in this scene collection
+sources-url = URL
+sources-refresh = Refresh
+sources-refresh-hint = Reload the page without cache
+sources-interact = Interact
+sources-capture = Capture devices
+sources-device = Device
+sources-resolution = Resolution
+sources-fps = Frame rate
+sources-preset = Preset
+sources-reactivate = Reactivate
+sources-reactivate-hint = Close and reopen the device, which usually fixes a black frame
+sources-slideshows = Slideshows
+sources-slide = Slide { $slide } of { $count }
+sources-previous = Previous slide
+sources-play = Play
+sources-pause = Pause
+sources-next = Next slide
+sources-restart = Back to the first slide
+grid-hotkeys = OBS hotkeys
+grid-hotkey-search = Search hotkeys
+grid-hotkeys-none = Hotkeys are listed once connected to OBS.
+panel-advanced = Advanced
+advanced-disconnected = Connect to OBS to edit its profile.
+advanced-profile = Profile parameters
+advanced-refresh = Refresh
+advanced-hint = Changes apply the next time the stream or recording starts.
+advanced-delay-enable = Stream delay on
+advanced-delay-seconds = Stream delay (seconds)
+advanced-delay-preserve = Keep delay cut point on reconnect
+advanced-reconnect = Reconnect automatically
+advanced-retry-delay = Reconnect delay (seconds)
+advanced-max-retries = Reconnect attempts
+advanced-filename = Recording file name format
+advanced-simple-path = Recording folder (simple output)
+advanced-advanced-path = Recording folder (advanced output)
+advanced-set = Set
+advanced-reset = Default
+advanced-reset-hint = Remove the value so OBS uses its default
+advanced-raw = Any parameter
+advanced-category = Category
+advanced-name = Name
+advanced-look-up = Look up
+console = Developer console
+console-request = Request type, e.g. GetVersion
+console-send = Send
+console-payload = Request data as JSON (optional)
+console-waiting = Waiting for OBS…
+console-bad-json = Request data isn't valid JSON: { $error }
+panel-events = OBS events
+events-filter = Filter events
+events-export = Export JSON
+events-exported = Events saved to { $path }
+events-count = { $shown } of { $total } events
+settings-slider-mode = Volume sliders
+slider-continuous = Send while dragging
+slider-throttled = Send while dragging, 20 times a second at most
+slider-on-release = Send on release
+mixer-selected = { $count } selected
+mixer-mute-all = Mute all
+mixer-unmute-all = Unmute all
+mixer-level = Level for all
+mixer-set-all = Set all
+mixer-group = Group name
+mixer-add-to-group = Add to group
+mixer-clear-selection = Clear selection
+mixer-selection = Selected channels
+mixer-group-mute = Mute group { $group }
+channel-leave-group = Leave { $group }
+sources-app-audio = Application audio
+sources-app-audio-name = New input name, e.g. Discord
+sources-app-audio-scene = Add to scene
+sources-app-audio-create = Create
+sources-app-audio-pick = Pick a program
+mixer-calibrate-gate = Calibrate noise gate
+mixer-calibrate-gate-hint = Stay quiet for three seconds after clicking; the gate is set just above the noise this mic picks up.
+notice-gate-calibrated = Noise gate on { $input } opens at { $open } dB and closes at { $close } dB
+mixer-voice-chain = Add voice processing
+mixer-voice-chain-hint = Adds noise suppression, a noise gate, a compressor and a limiter with settings that suit most voices. Filters this mic already has are kept.
+mixer-more = ⋯ More
+notice-voice-chain = { $added ->
+    [0] { $input } already has the whole voice chain
+    [one] Added one voice filter to { $input }
+   *[other] Added { $added } voice filters to { $input }
+}
+mixer-audio-preset = Audio preset
+mixer-audio-preset-pick = Apply…
+ducking-title = Ducking
+ducking-hint = Lowers one input while another is heard, using a compressor in OBS keyed off the trigger. OBS's compressor has no hold, so the hold is added to its release.
+ducking-pair = { $ducked } under { $trigger }
+ducking-threshold = Threshold
+ducking-reduction = Reduction
+ducking-attack = Attack
+ducking-hold = Hold
+ducking-release = Release
+ducking-sketch = Drops by { $reduction } dB while the trigger talks (ratio { $ratio }:1)
+ducking-apply = Apply
+ducking-remove = Remove
+ducking-ducked = Duck
+ducking-trigger = Under
+ducking-add = Add
+session-pressed = { $operator } pressed { $button }
+session-yours = You control this panel
+session-release = Hand back
+session-owned = { $operator } controls this panel
+session-take-over = Take over
+session-take-over-hint = Take control of this panel from the other operator
+session-claim = Claim
+session-operator = Operator name
+session-host = Join session at
+session-join = Join
+session-join-hint = Connect to another REC's relay to share ownership, button presses and channel picks. The other instance starts its relay and hosts the session.
+session-leave = Leave session
+volunteer-pin = Volunteer PIN
+volunteer-enter = Lock for volunteers
+volunteer-enter-hint = Leaves only the mixer and the chosen scenes until the PIN is entered; connection, scene and output settings are hidden
+volunteer-unlock-title = Leave volunteer mode
+volunteer-locked = This panel is locked in volunteer mode.
+volunteer-scenes = Scenes volunteers see
+volunteer-scenes-hint = With none ticked, volunteers see every scene.
+panel-rundown = Rundown
+rundown-on-air = On air
+rundown-standby = Standby
+rundown-none = Nothing
+rundown-go = GO
+rundown-back = Back one cue
+rundown-reset = Back to the top
+rundown-summary = { $scene } · { $audio } channels · { $texts } texts and media
+rundown-jump = Next
+rundown-jump-hint = Make this the cue GO runs next
+rundown-up = Move up
+rundown-remove = Remove cue
+rundown-add = Add a cue
+rundown-name = Name
+rundown-notes = Notes
+rundown-scene = Scene
+rundown-text-input = Text source
+rundown-text = Text
+rundown-media = Restart media
+rundown-capture-audio = Capture the mixer as it is now
+rundown-capture-audio-hint = The cue sets every channel's volume and mute back to what they are at this moment
+rundown-add-cue = Add cue
+rehearsal = Rehearsal
+rehearsal-hint = Scenes and audio work as usual, but the stream and recording won't start, webhooks don't fire and chat commands are ignored
+rehearsal-watermark = REHEARSAL
+notice-rehearsal-blocked = Rehearsing: the stream or recording was not started
+palette-start-rehearsal = Start rehearsal
+palette-stop-rehearsal = End rehearsal
+rundown-duration = Planned length
+rundown-segment-input = Segment clock text source
+rundown-segment-prompter = Show the segment clock on the teleprompter
+templates-title = Show templates
+templates-hint = Macros, the rundown, audio presets, ducking and the OBS address, to start the next event from
+templates-name = Template name
+templates-export = Export
+templates-exported = Show template saved to { $path }
+templates-saved = Saved templates
+templates-open = Open
+templates-open-hint = Replaces this show's macros, rundown, audio presets and ducking
+templates-opened = Opened show template { $name }
+templates-keep = Keep the name
+templates-remap-title = Open { $name }
+templates-remap-hint = These aren't in the current scene collection. Pick what to use instead, or keep the name to set them up later.
+templates-scenes = Scenes
+templates-sources = Sources
+templates-apply = Open template
+rundown-fade = Audio fade
+rundown-fade-hint = The scene cuts straight away while the captured levels are faded to over this long
+panel-iso = ISO recording
+iso-refresh = Refresh
+iso-arm-all = Arm all
+iso-disarm-all = Disarm all
+iso-none = No Source Record filters found. Add one to a camera or scene in OBS to record it on its own.
+iso-recording = ● Recording
+iso-armed = Armed
+iso-off = Off
+iso-arm = Arm
+iso-disarm = Disarm
+iso-mode-never = Never records
+iso-mode-always = Always
+iso-mode-streaming = While streaming
+iso-mode-recording = While recording
+iso-mode-streaming-or-recording = While streaming or recording
+iso-mode-virtual-camera = With the virtual camera
+intermission = Intermission
+intermission-hint = Go to the break scene, mute the mics and start the countdown; click again to bring everything back
+palette-start-intermission = Start intermission
+palette-end-intermission = End intermission
+usage-title = Usage statistics
+usage-enabled = Count which features I use
+usage-hint = Counts actions and error types on this computer only. Nothing is sent anywhere; export or copy the report yourself if you'd like to share it
+usage-local = Stored on this computer only. Scene and source names and error messages are never recorded.
+usage-report = Report
+usage-export = Export report
+usage-copy = Copy report
+usage-copy-hint = Copy the report to paste into an issue or email to the developers
+usage-clear = Clear
+usage-exported = Usage report saved to { $path }
+panel-diagnostics = Diagnostics
+diagnostics-enabled = Measure
+diagnostics-hint = Time every frame and how long scene, mute and volume changes take to come back from OBS. Leave off during a show
+diagnostics-clear = Clear
+diagnostics-frames = Frame time
+diagnostics-round-trips = Action round trip (REC → OBS → REC)
+diagnostics-no-samples = Nothing measured yet
+diagnostics-summary = { $count } samples · median { $median } ms · 95% { $p95 } ms · worst { $max } ms
+diagnostics-ms = milliseconds
+diagnostics-lost = { $count } actions got no event back within 5 seconds
+features-title = Experimental features
+features-hint = Still being worked on, so they're off unless switched on here or under [features] in rec.toml
+feature-scripting = Scripting
+feature-scripting-hint = Run Rhai scripts from the buttons panel and from macros
+feature-relay = Relay server
+feature-relay-hint = Let remote panels and other operators connect to this instance
+notice-feature-disabled = { $feature } is switched off in the experimental features
+update-available = REC { $version } is available
+update-download = Download
+update-dismiss = Dismiss
+update-changelog = What's new
+migrate-upgraded = Saved settings were brought up to date for this version of REC. The old file was kept at { $path }
+migrate-newer = Saved settings came from a newer version of REC; anything this version doesn't know about was reset. The old file was kept at { $path }
+migrate-dropped = "{ $setting }" in the saved settings couldn't be read and was reset. The old value is still in { $path }
+migrate-unreadable = The saved settings file couldn't be read, so REC started from its defaults. The old file was kept at { $path }
+migrate-no-backup = (backup failed, see the log)
+trace-title = Session trace
+trace-record = Record trace
+trace-record-hint = Write every action and everything OBS reports to a file, to attach to a bug report. Passwords are left out, but scene and source names are included
+trace-saved = Trace saved to { $path }
+trace-replay-title = Replay a trace
+trace-replay-hint = Step through a recorded session without OBS. Nothing is sent while replaying.
+trace-replay = Replay
+trace-replaying = Replaying { $name }: { $at } of { $count }
+trace-stop = Stop replay
+demo-badge = DEMO
+demo-hint = Demo mode: OBS is simulated and nothing is saved. Log out to leave it.
+cli-usage =
+    Usage: rec [command]
+
+    Without a command, REC opens its window. Add --demo to run it without OBS.
+
+    Commands:
+      scene <name>            Switch to a scene
+      mute <input> [--toggle] Mute an input, or flip it with --toggle
+      unmute <input>          Unmute an input
+      record start|stop|toggle
+      stream start|stop|toggle
+      macro <name>            Run a saved macro or rec.toml button
+      help                    Show this
+
+    Inputs can be given by name or as mic and desktop. The connection comes
+    from rec.toml, or else from the one last used in the window.
+cli-scene = Scene: { $scene }
+cli-muted = { $input }: muted
+cli-unmuted = { $input }: unmuted
+cli-recording = Recording
+cli-not-recording = Not recording
+cli-streaming = Streaming
+cli-not-streaming = Not streaming
+cli-macro = Ran { $name }
+cli-no-scene = OBS has no scene called "{ $scene }"
+cli-no-input = OBS has no audio input called "{ $input }"
+cli-no-macro = No macro called "{ $name }"
+
+global-keys-title = Global keys
+global-keys-hint = Macro pad keys (F13 to F24) and media keys that work even while REC isn't focused. Bound keys are kept from other programs.
+global-keys-action = When pressed
+global-keys-bind = Bind a key
+global-keys-press = Press the key to bind...
+global-keys-cancel = Cancel
+global-keys-remove = Remove
+shortcut-mute-mic = Mute/unmute mic
+shortcut-mute-desktop = Mute/unmute desktop audio
+shortcut-record = Start/stop recording
+shortcut-stream = Start/stop streaming
+shortcut-scene = Scene { $number }: { $scene }
+shortcut-button = Run { $name }
+shortcut-teleprompter-toggle = Start/pause teleprompter
+shortcut-teleprompter-faster = Teleprompter faster
+shortcut-teleprompter-slower = Teleprompter slower
+shortcut-teleprompter-restart = Restart teleprompter
+shortcut-go = Rundown: go
+shortcut-intermission = Start/end intermission
+
+pairing-title = Paired devices
+pairing-hint = With pairing off, anyone on the network can watch through the relay and the web remote but change nothing. With it on, only paired links work, even once every device is revoked.
+pairing-enabled = Only allow paired devices
+pairing-name = Name
+pairing-pair = Pair
+pairing-viewer = Viewer
+pairing-operator = Operator
+pairing-admin = Admin
+pairing-viewer-hint = Sees what's live but can't change anything
+pairing-operator-hint = Can switch scenes and change audio, but can't start or end the stream or recording
+pairing-admin-hint = Can do everything
+pairing-copy-remote = Copy web remote link
+pairing-copy-relay = Copy relay address
+pairing-revoke = Revoke
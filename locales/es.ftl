@@ -0,0 +1,647 @@
+app-heading = Control de OBS
+
+login-address = Dirección IP
+login-port = Puerto
+login-password = Contraseña
+login-log-in = Conectar
+login-not-logged-in = Sin conexión
+
+log-clear = Borrar
+
+panel-mixer = Mezclador
+panel-scenes = Escenas
+panel-buttons = Botones
+panel-history = Historial
+panel-plugins = Complementos
+panel-settings = Ajustes
+panel-log = Registro
+panel-popped-out = { $panel } está en su propia ventana
+panel-dock = Acoplar
+panel-pop-out = Abrir en una ventana aparte
+
+layout-layouts = Diseños
+layout-name = Nombre del diseño
+layout-save = Guardar
+layout-delete = Eliminar
+layout-reset = Restablecer
+
+status-streaming = Transmitiendo
+status-not-streaming = Sin transmitir
+status-recording = Grabando
+status-not-recording = Sin grabar
+status-offline = Desconectado
+status-pending = { $count } pendientes
+
+scenes-queue-offline = Encolar sin conexión
+scenes-mini = Mini
+scenes-mini-hint = Barra compacta siempre visible (Ctrl+Shift+M)
+scenes-log-out = Desconectar
+
+mixer-mic-volume = Volumen del micrófono
+mixer-desktop-volume = Volumen del escritorio
+mixer-mute-mic = Silenciar micrófono
+mixer-unmute-mic = Activar micrófono
+mixer-no-mic = Ningún micrófono seleccionado
+mixer-mute-desktop = Silenciar escritorio
+mixer-unmute-desktop = Activar escritorio
+mixer-no-desktop = Ningún escritorio seleccionado
+
+grid-scripts = Scripts
+grid-button-name = Nombre del botón
+grid-run = Ejecutar
+grid-stop-scripts = Detener scripts
+grid-add-button = Añadir botón
+
+history-volume = Volumen de { $input } a { $volume }
+history-mute = Silenciar { $input }
+history-unmute = Activar { $input }
+history-scene = Cambiar a { $scene }
+history-show-source = Mostrar { $source } en { $scene }
+history-hide-source = Ocultar { $source } en { $scene }
+history-other = Otra acción
+history-undo = Deshacer
+
+mini-mic = Micrófono
+mini-desktop = Escritorio
+mini-live = ● EN VIVO
+mini-rec = ● GRAB
+mini-full-window = Ventana completa
+
+palette-hint = Escribe un comando...
+palette-scene = Escena: { $scene }
+palette-mute = Silenciar: { $input }
+palette-unmute = Activar: { $input }
+palette-macro = Macro: { $name }
+palette-start-stream = Iniciar transmisión
+palette-stop-stream = Detener transmisión
+palette-start-record = Iniciar grabación
+palette-stop-record = Detener grabación
+palette-undo = Deshacer
+palette-log-out = Desconectar
+
+recovery-title = ¿Reanudar la sesión anterior?
+recovery-unclean = REC no se cerró correctamente la última vez.
+recovery-reconnect = • Volver a conectar con OBS
+recovery-restart-services = { $count ->
+    [one] • Reiniciar { $count } servicio de control remoto
+   *[other] • Reiniciar { $count } servicios de control remoto
+}
+recovery-send-queued = { $count ->
+    [one] • Enviar { $count } acción en cola
+   *[other] • Enviar { $count } acciones en cola
+}
+recovery-interrupted = Estas macros se interrumpieron:
+recovery-run-again = Ejecutar de nuevo
+recovery-resume = Reanudar
+recovery-discard = Descartar
+recovery-macro-name = Reanudar sesión
+
+settings-language = Idioma
+settings-theme = Tema
+settings-accent = Acento
+settings-background = Fondo
+settings-sliders = Deslizadores
+settings-touch-mode = Modo pantalla táctil
+settings-minimize-to-tray = Minimizar a la bandeja
+settings-ui-scale = Escala de la interfaz
+settings-ui-scale-hint = Ctrl+= / Ctrl+- para ampliar, Ctrl+0 para restablecer
+settings-relay-port = Puerto del servidor de retransmisión
+settings-start-relay = Iniciar retransmisión
+settings-stop-relay = Detener retransmisión
+settings-osc-port = Puerto OSC
+settings-osc-feedback = Respuesta ip:puerto
+settings-start-osc = Iniciar OSC
+settings-stop-osc = Detener OSC
+settings-remote-port = Puerto del control web
+settings-start-remote = Iniciar control web
+settings-stop-remote = Detener control web
+settings-remote-hint = Abre http://este-equipo:{ $port } en un teléfono de la misma red
+remote-title = Mando de REC
+remote-offline = No se puede conectar con REC, o este enlace se revocó
+remote-output = Salida
+remote-mute = Silenciar
+remote-scenes = Escenas
+remote-start-stream = Iniciar transmisión
+remote-stop-stream = Detener transmisión
+remote-start-recording = Iniciar grabación
+remote-stop-recording = Detener grabación
+remote-confirm-stop-stream = ¿Detener la transmisión?
+remote-confirm-stop-recording = ¿Detener la grabación?
+settings-mqtt-broker = Bróker MQTT
+settings-mqtt-host = Servidor
+settings-mqtt-port = Puerto
+settings-mqtt-prefix = Prefijo de tema
+settings-mqtt-username = Usuario
+settings-mqtt-password = Contraseña
+settings-start-mqtt = Iniciar MQTT
+settings-stop-mqtt = Detener MQTT
+
+theme-dark = Oscuro
+theme-light = Claro
+theme-high-contrast = Alto contraste
+theme-custom = Personalizado
+
+tray-live = REC - en vivo
+tray-recording = REC - grabando
+tray-live-recording = REC - en vivo y grabando
+tray-mute-mic = Silenciar micrófono
+tray-unmute-mic = Activar micrófono
+tray-start-recording = Iniciar grabación
+tray-stop-recording = Detener grabación
+tray-window = Mostrar/ocultar ventana
+tray-quit = Salir
+
+toast-invalid-port = puerto no válido: { $error }
+toast-invalid-feedback = dirección de respuesta no válida: { $error }
+toast-send-failed = no se pudo enviar la acción: { $error }
+toast-unknown-shortcut = tecla de atajo desconocida "{ $key }"
+
+a11y-muted = { $input } silenciado
+a11y-unmuted = { $input } activado
+a11y-scene = Escena cambiada a { $scene }
+a11y-stream-started = Transmisión iniciada
+a11y-stream-stopped = Transmisión detenida
+a11y-record-started = Grabación iniciada
+a11y-record-stopped = Grabación detenida
+a11y-connection-lost = Se perdió la conexión con OBS
+a11y-dismiss = Descartar
+
+tooltip-volume = { $db } dB (multiplicador { $mul })
+tooltip-input = Nombre en OBS: { $name }
+    Tipo: { $kind }
+    Tipo sin versión: { $unversioned }
+tooltip-scene-switched = Última vez activada a las { $time }
+tooltip-scene-not-switched = No se ha activado desde la conexión
+
+settings-confirm = Preguntar antes de
+confirm-stop-stream = Detener la transmisión
+confirm-stop-stream-title = ¿Detener la transmisión?
+confirm-stop-stream-body = La transmisión terminará para todos los espectadores.
+confirm-stop-stream-button = Detener transmisión
+confirm-stop-record = Detener la grabación
+confirm-stop-record-title = ¿Detener la grabación?
+confirm-stop-record-body = Se cerrará el archivo de grabación.
+confirm-stop-record-button = Detener grabación
+confirm-dont-ask = No volver a preguntar
+confirm-cancel = Cancelar
+
+status-connected = Conectado
+status-obs-version = OBS { $version }
+status-stream-timer = EN VIVO { $elapsed }
+status-record-timer = GRAB { $elapsed }
+
+notifications-title = Notificaciones
+notice-recording-started = Grabación iniciada
+notice-recording-started-path = Grabando en { $path }
+notice-recording-stopped = Grabación detenida
+notice-recording-saved = Grabación guardada en { $path }
+notice-replay-saved = Repetición guardada en { $path }
+notice-stream-reconnecting = Se cayó la transmisión, reconectando...
+notice-stream-reconnected = Transmisión reconectada
+notice-input-added = Entrada añadida: { $input }
+notice-input-removed = Entrada eliminada: { $input }
+
+channel-label = Etiqueta en REC
+channel-reset = Restablecer
+
+settings-orientation = Orientación
+orientation-auto = Automática
+orientation-horizontal = Horizontal
+orientation-vertical = Vertical
+
+kiosk-pin = PIN de quiosco
+kiosk-enter = Entrar en modo quiosco
+kiosk-enter-hint = Bloquea REC en pantalla completa con los ajustes ocultos hasta introducir el PIN
+kiosk-unlock-title = Salir del modo quiosco
+kiosk-unlock = Desbloquear
+kiosk-wrong-pin = PIN incorrecto
+kiosk-backspace = Borrar dígito
+kiosk-settings-locked = Los ajustes están bloqueados en modo quiosco.
+kiosk-not-connected = Sin conexión con OBS. Pide a un administrador que revise la conexión.
+section-channels = Canales y volumen
+section-scenes = Lista de escenas
+section-buttons = Cuadrícula de botones
+panel-twitch = Twitch
+twitch-no-client-id = Define client_id en [twitch] de rec.toml para conectar una cuenta de Twitch.
+twitch-log-in = Iniciar sesión en Twitch
+twitch-log-out = Cerrar sesión
+twitch-enter-code = Introduce el código { $code } en:
+twitch-logged-in = Sesión iniciada como { $login }
+twitch-title = Título
+twitch-category = Categoría
+twitch-search = Buscar categorías
+twitch-update = Actualizar canal
+twitch-scene-presets = Se aplica automáticamente al cambiar a:
+toast-twitch-error = Twitch: { $error }
+panel-youtube = YouTube
+youtube-no-client-id = Define client_id y client_secret en [youtube] de rec.toml para conectar un canal de YouTube.
+youtube-log-in = Iniciar sesión en YouTube
+youtube-log-out = Cerrar sesión
+youtube-enter-code = Introduce el código { $code } en:
+youtube-refresh = Actualizar
+youtube-no-broadcasts = No hay emisiones programadas ni en directo.
+youtube-bind = Emitir en esta emisión
+youtube-bound = OBS ya está configurado para emitir en la emisión de YouTube.
+youtube-bound-label = OBS emite aquí
+youtube-new-title = Título de la nueva emisión
+youtube-create = Crear
+youtube-public = Pública
+youtube-unlisted = Oculta
+youtube-private = Privada
+status-viewers = { $count } viendo
+toast-youtube-error = YouTube: { $error }
+grid-marker = 🔖 Marcador
+grid-marker-hint = Marca este momento en la emisión de Twitch y en el registro
+notice-marker = Marcador añadido
+notice-marker-recording = Marcador añadido en { $position } de la grabación
+notice-twitch-marker = Marcador de Twitch en { $position }
+twitch-clip = 🎬 Crear clip
+twitch-recent-clips = Clips recientes
+notice-twitch-clip = Clip creado: { $url }
+twitch-chat-commands = Escuchando en el chat:
+panel-lights = Luces
+lights-none = Buscando Elgato Key Lights en la red…
+lights-all-on = Encender todas
+lights-all-off = Apagar todas
+lights-brightness = Brillo
+lights-temperature = Temperatura
+lighting-hue-bridge = Puente Hue
+lighting-pair = Vincular
+lighting-pair-hint = Pulsa primero el botón de enlace del puente
+lighting-paired = Vinculado
+lighting-hue-group = Habitación Hue
+lighting-wled = WLED
+lighting-openrgb = OpenRGB
+lighting-group-hint = Id del grupo
+lighting-host-hint = Host
+lighting-preset = Id de escena, preset o perfil
+lighting-raise = Subir
+lighting-remove = Quitar asignación
+lighting-add = Añadir asignación de luces
+lighting-order-hint = Las filas de arriba ganan si varias coinciden para las mismas luces
+lighting-when-streaming = Al transmitir
+lighting-when-recording = Al grabar
+lighting-when-scene = En la escena
+lighting-when-muted = Mientras está silenciado
+lighting-when-live = Mientras está activo
+lighting-when-other = Condición personalizada
+notice-dropped-frames = Se perdió el { $percent }% de los fotogramas en los últimos 30 segundos
+panel-timers = Temporizadores
+countdown = Cuenta atrás
+countdown-minutes = Minutos
+countdown-start = Iniciar
+countdown-pause = Pausar
+countdown-reset = Reiniciar
+countdown-none = Ninguno
+countdown-text-source = Fuente de texto
+countdown-then = Al llegar a cero
+countdown-then-hint = «{ $macro }» se ejecuta cuando la cuenta atrás llega a cero.
+countdown-finished = Cuenta atrás terminada
+stopwatch = Cronómetro
+stopwatch-lap = Vuelta
+stopwatch-copy = Copiar segmentos
+stopwatch-current = Actual
+stopwatch-text-source = Fuente de texto
+stopwatch-report-line = Segmento { $segment }: { $length } (en { $total })
+panel-lower-thirds = Rótulos
+lower-third-show = Mostrar
+lower-third-showing = En pantalla
+lower-third-hide = Ocultar ya
+lower-third-remove = Quitar rótulo
+lower-third-name = Nombre
+lower-third-title = Cargo
+lower-third-add = Añadir
+lower-third-setup = Configuración
+lower-third-scene = Escena
+lower-third-source = Fuente a mostrar
+lower-third-name-source = Fuente de texto del nombre
+lower-third-title-source = Fuente de texto del cargo
+lower-third-seconds = Ocultar tras (segundos)
+panel-scoreboard = Marcador
+scoreboard-left = Equipo izquierdo
+scoreboard-right = Equipo derecho
+scoreboard-minus = Uno menos
+scoreboard-plus = Uno más
+scoreboard-map = Mapa
+scoreboard-swap = Cambiar lados
+scoreboard-reset = Reiniciar marcador
+scoreboard-sources = Fuentes de texto
+scoreboard-left-name = Nombre izquierdo
+scoreboard-left-score = Puntos izquierdo
+scoreboard-right-name = Nombre derecho
+scoreboard-right-score = Puntos derecho
+scoreboard-map-source = Número de mapa
+panel-teleprompter = Teleprompter
+teleprompter-play = Reproducir
+teleprompter-pause = Pausar
+teleprompter-restart = Volver al inicio
+teleprompter-speed = Velocidad
+teleprompter-size = Tamaño
+teleprompter-mirror = Espejo
+teleprompter-edit = Editar guion
+teleprompter-path = Archivo del guion
+teleprompter-load = Cargar
+teleprompter-load-failed = No se pudo cargar el guion: { $error }
+panel-preview = Programa
+preview-enabled = Vista previa en vivo
+preview-fps = Fotogramas por segundo
+preview-off = Activa la vista previa en vivo para ver lo que está en programa.
+panel-sources = Fuentes
+sources-browser = Fuentes de navegador
+sources-none = Ninguna en esta colección de escenas
+sources-url = URL
+sources-refresh = Recargar
+sources-refresh-hint = Recargar la página sin caché
+sources-interact = Interactuar
+sources-capture = Dispositivos de captura
+sources-device = Dispositivo
+sources-resolution = Resolución
+sources-fps = Fotogramas por segundo
+sources-preset = Preajuste
+sources-reactivate = Reactivar
+sources-reactivate-hint = Cierra y vuelve a abrir el dispositivo, lo que suele arreglar una imagen en negro
+sources-slideshows = Presentaciones
+sources-slide = Diapositiva { $slide } de { $count }
+sources-previous = Diapositiva anterior
+sources-play = Reproducir
+sources-pause = Pausar
+sources-next = Diapositiva siguiente
+sources-restart = Volver a la primera diapositiva
+grid-hotkeys = Atajos de OBS
+grid-hotkey-search = Buscar atajos
+grid-hotkeys-none = Los atajos aparecen al conectarse a OBS.
+panel-advanced = Avanzado
+advanced-disconnected = Conéctate a OBS para editar su perfil.
+advanced-profile = Parámetros del perfil
+advanced-refresh = Actualizar
+advanced-hint = Los cambios se aplican la próxima vez que empiece la emisión o la grabación.
+advanced-delay-enable = Retraso de emisión activado
+advanced-delay-seconds = Retraso de emisión (segundos)
+advanced-delay-preserve = Mantener el punto de corte al reconectar
+advanced-reconnect = Reconectar automáticamente
+advanced-retry-delay = Espera entre reconexiones (segundos)
+advanced-max-retries = Intentos de reconexión
+advanced-filename = Formato del nombre de grabación
+advanced-simple-path = Carpeta de grabación (salida simple)
+advanced-advanced-path = Carpeta de grabación (salida avanzada)
+advanced-set = Aplicar
+advanced-reset = Por defecto
+advanced-reset-hint = Quita el valor para que OBS use el predeterminado
+advanced-raw = Cualquier parámetro
+advanced-category = Categoría
+advanced-name = Nombre
+advanced-look-up = Consultar
+console = Consola de desarrollo
+console-request = Tipo de petición, p. ej. GetVersion
+console-send = Enviar
+console-payload = Datos de la petición en JSON (opcional)
+console-waiting = Esperando a OBS…
+console-bad-json = Los datos de la petición no son JSON válido: { $error }
+panel-events = Eventos de OBS
+events-filter = Filtrar eventos
+events-export = Exportar JSON
+events-exported = Eventos guardados en { $path }
+events-count = { $shown } de { $total } eventos
+settings-slider-mode = Deslizadores de volumen
+slider-continuous = Enviar mientras se arrastra
+slider-throttled = Enviar mientras se arrastra, como mucho 20 veces por segundo
+slider-on-release = Enviar al soltar
+mixer-selected = { $count } seleccionados
+mixer-mute-all = Silenciar todos
+mixer-unmute-all = Activar todos
+mixer-level = Nivel para todos
+mixer-set-all = Aplicar a todos
+mixer-group = Nombre del grupo
+mixer-add-to-group = Añadir al grupo
+mixer-clear-selection = Quitar selección
+mixer-selection = Canales seleccionados
+mixer-group-mute = Silenciar el grupo { $group }
+channel-leave-group = Salir de { $group }
+sources-app-audio = Audio de aplicaciones
+sources-app-audio-name = Nombre de la entrada, p. ej. Discord
+sources-app-audio-scene = Añadir a la escena
+sources-app-audio-create = Crear
+sources-app-audio-pick = Elige un programa
+mixer-calibrate-gate = Calibrar puerta de ruido
+mixer-calibrate-gate-hint = Guarda silencio durante tres segundos después de hacer clic; la puerta se ajusta justo por encima del ruido que capta este micrófono.
+notice-gate-calibrated = La puerta de ruido de { $input } abre a { $open } dB y cierra a { $close } dB
+mixer-voice-chain = Añadir procesado de voz
+mixer-voice-chain-hint = Añade supresión de ruido, una puerta de ruido, un compresor y un limitador con ajustes que sirven para la mayoría de voces. Se conservan los filtros que ya tenga este micrófono.
+mixer-more = ⋯ Más
+notice-voice-chain = { $added ->
+    [0] { $input } ya tiene toda la cadena de voz
+    [one] Se añadió un filtro de voz a { $input }
+   *[other] Se añadieron { $added } filtros de voz a { $input }
+}
+mixer-audio-preset = Preajuste de audio
+mixer-audio-preset-pick = Aplicar…
+ducking-title = Atenuación automática
+ducking-hint = Baja una entrada mientras se oye otra, con un compresor en OBS controlado por la entrada de disparo. El compresor de OBS no tiene retención, así que la retención se suma a su liberación.
+ducking-pair = { $ducked } bajo { $trigger }
+ducking-threshold = Umbral
+ducking-reduction = Reducción
+ducking-attack = Ataque
+ducking-hold = Retención
+ducking-release = Liberación
+ducking-sketch = Baja { $reduction } dB mientras habla la entrada de disparo (relación { $ratio }:1)
+ducking-apply = Aplicar
+ducking-remove = Quitar
+ducking-ducked = Atenuar
+ducking-trigger = Bajo
+ducking-add = Añadir
+session-pressed = { $operator } pulsó { $button }
+session-yours = Controlas este panel
+session-release = Liberar
+session-owned = { $operator } controla este panel
+session-take-over = Tomar el control
+session-take-over-hint = Tomar el control de este panel del otro operador
+session-claim = Reclamar
+session-operator = Nombre del operador
+session-host = Unirse a la sesión en
+session-join = Unirse
+session-join-hint = Conéctate al relé de otro REC para compartir el control de los paneles, las pulsaciones de botones y los canales elegidos. La otra instancia inicia su relé y aloja la sesión.
+session-leave = Salir de la sesión
+volunteer-pin = PIN de voluntarios
+volunteer-enter = Bloquear para voluntarios
+volunteer-enter-hint = Deja solo el mezclador y las escenas elegidas hasta que se introduzca el PIN; se ocultan los ajustes de conexión, escenas y salida
+volunteer-unlock-title = Salir del modo voluntario
+volunteer-locked = Este panel está bloqueado en el modo voluntario.
+volunteer-scenes = Escenas que ven los voluntarios
+volunteer-scenes-hint = Si no marcas ninguna, los voluntarios ven todas las escenas.
+panel-rundown = Escaleta
+rundown-on-air = En el aire
+rundown-standby = En espera
+rundown-none = Nada
+rundown-go = GO
+rundown-back = Volver una entrada
+rundown-reset = Volver al principio
+rundown-summary = { $scene } · { $audio } canales · { $texts } textos y medios
+rundown-jump = Siguiente
+rundown-jump-hint = Hacer que GO ejecute esta entrada a continuación
+rundown-up = Subir
+rundown-remove = Quitar entrada
+rundown-add = Añadir una entrada
+rundown-name = Nombre
+rundown-notes = Notas
+rundown-scene = Escena
+rundown-text-input = Fuente de texto
+rundown-text = Texto
+rundown-media = Reiniciar medio
+rundown-capture-audio = Capturar el mezclador tal como está ahora
+rundown-capture-audio-hint = La entrada devuelve el volumen y el silencio de cada canal a como están en este momento
+rundown-add-cue = Añadir entrada
+rehearsal = Ensayo
+rehearsal-hint = Las escenas y el audio funcionan con normalidad, pero la transmisión y la grabación no se inician, los webhooks no se envían y los comandos del chat se ignoran
+rehearsal-watermark = ENSAYO
+notice-rehearsal-blocked = Ensayando: no se inició la transmisión ni la grabación
+palette-start-rehearsal = Empezar ensayo
+palette-stop-rehearsal = Terminar ensayo
+rundown-duration = Duración prevista
+rundown-segment-input = Fuente de texto del reloj del segmento
+rundown-segment-prompter = Mostrar el reloj del segmento en el teleprompter
+templates-title = Plantillas de programa
+templates-hint = Macros, la escaleta, los preajustes de audio, la atenuación y la dirección de OBS, para empezar el próximo evento
+templates-name = Nombre de la plantilla
+templates-export = Exportar
+templates-exported = Plantilla de programa guardada en { $path }
+templates-saved = Plantillas guardadas
+templates-open = Abrir
+templates-open-hint = Reemplaza las macros, la escaleta, los preajustes de audio y la atenuación de este programa
+templates-opened = Plantilla de programa { $name } abierta
+templates-keep = Mantener el nombre
+templates-remap-title = Abrir { $name }
+templates-remap-hint = No están en la colección de escenas actual. Elige qué usar en su lugar o mantén el nombre para configurarlos más tarde.
+templates-scenes = Escenas
+templates-sources = Fuentes
+templates-apply = Abrir plantilla
+rundown-fade = Fundido de audio
+rundown-fade-hint = La escena cambia al instante mientras los niveles capturados se alcanzan con un fundido de esta duración
+panel-iso = Grabación ISO
+iso-refresh = Actualizar
+iso-arm-all = Armar todo
+iso-disarm-all = Desarmar todo
+iso-none = No se encontraron filtros de Source Record. Añade uno a una cámara o escena en OBS para grabarla por separado.
+iso-recording = ● Grabando
+iso-armed = Armado
+iso-off = Apagado
+iso-arm = Armar
+iso-disarm = Desarmar
+iso-mode-never = Nunca graba
+iso-mode-always = Siempre
+iso-mode-streaming = Al transmitir
+iso-mode-recording = Al grabar
+iso-mode-streaming-or-recording = Al transmitir o grabar
+iso-mode-virtual-camera = Con la cámara virtual
+intermission = Intermedio
+intermission-hint = Pasa a la escena de pausa, silencia los micrófonos e inicia la cuenta atrás; vuelve a pulsar para restaurarlo todo
+palette-start-intermission = Empezar intermedio
+palette-end-intermission = Terminar intermedio
+usage-title = Estadísticas de uso
+usage-enabled = Contar qué funciones uso
+usage-hint = Cuenta acciones y tipos de error solo en este equipo. No se envía nada; exporta o copia el informe tú mismo si quieres compartirlo
+usage-local = Se guarda solo en este equipo. Nunca se registran nombres de escenas o fuentes ni mensajes de error.
+usage-report = Informe
+usage-export = Exportar informe
+usage-copy = Copiar informe
+usage-copy-hint = Copia el informe para pegarlo en una incidencia o un correo a los desarrolladores
+usage-clear = Borrar
+usage-exported = Informe de uso guardado en { $path }
+panel-diagnostics = Diagnóstico
+diagnostics-enabled = Medir
+diagnostics-hint = Mide cada fotograma y cuánto tardan en volver de OBS los cambios de escena, silencio y volumen. Déjalo apagado durante un programa
+diagnostics-clear = Borrar
+diagnostics-frames = Tiempo de fotograma
+diagnostics-round-trips = Ida y vuelta de acciones (REC → OBS → REC)
+diagnostics-no-samples = Aún no se ha medido nada
+diagnostics-summary = { $count } muestras · mediana { $median } ms · 95 % { $p95 } ms · peor { $max } ms
+diagnostics-ms = milisegundos
+diagnostics-lost = { $count } acciones no recibieron evento en 5 segundos
+features-title = Funciones experimentales
+features-hint = Aún están en desarrollo, así que están apagadas salvo que se activen aquí o en [features] de rec.toml
+feature-scripting = Scripts
+feature-scripting-hint = Ejecuta scripts Rhai desde el panel de botones y desde macros
+feature-relay = Servidor de retransmisión
+feature-relay-hint = Permite que paneles remotos y otros operadores se conecten a esta instancia
+notice-feature-disabled = { $feature } está desactivado en las funciones experimentales
+update-available = REC { $version } está disponible
+update-download = Descargar
+update-dismiss = Descartar
+update-changelog = Novedades
+migrate-upgraded = La configuración guardada se actualizó para esta versión de REC. El archivo anterior se guardó en { $path }
+migrate-newer = La configuración guardada proviene de una versión más reciente de REC; lo que esta versión no conoce se restableció. El archivo anterior se guardó en { $path }
+migrate-dropped = «{ $setting }» de la configuración guardada no se pudo leer y se restableció. El valor anterior sigue en { $path }
+migrate-unreadable = No se pudo leer el archivo de configuración guardada, así que REC empezó con los valores predeterminados. El archivo anterior se guardó en { $path }
+migrate-no-backup = (la copia de seguridad falló, consulta el registro)
+trace-title = Traza de sesión
+trace-record = Grabar traza
+trace-record-hint = Escribe cada acción y todo lo que informa OBS en un archivo para adjuntarlo a un informe de error. Las contraseñas se omiten, pero se incluyen los nombres de escenas y fuentes
+trace-saved = Traza guardada en { $path }
+trace-replay-title = Reproducir una traza
+trace-replay-hint = Recorre una sesión grabada sin OBS. No se envía nada durante la reproducción.
+trace-replay = Reproducir
+trace-replaying = Reproduciendo { $name }: { $at } de { $count }
+trace-stop = Detener reproducción
+demo-badge = DEMO
+demo-hint = Modo demo: OBS está simulado y no se guarda nada. Cierra sesión para salir.
+cli-usage =
+    Uso: rec [comando]
+
+    Sin comando, REC abre su ventana. Añade --demo para usarlo sin OBS.
+
+    Comandos:
+      scene <nombre>          Cambia a una escena
+      mute <entrada> [--toggle] Silencia una entrada, o la alterna con --toggle
+      unmute <entrada>        Quita el silencio de una entrada
+      record start|stop|toggle
+      stream start|stop|toggle
+      macro <nombre>          Ejecuta una macro guardada o un botón de rec.toml
+      help                    Muestra esto
+
+    Las entradas se indican por nombre o como mic y desktop. La conexión se
+    toma de rec.toml o, si no, de la última usada en la ventana.
+cli-scene = Escena: { $scene }
+cli-muted = { $input }: silenciado
+cli-unmuted = { $input }: con sonido
+cli-recording = Grabando
+cli-not-recording = Sin grabar
+cli-streaming = Emitiendo
+cli-not-streaming = Sin emitir
+cli-macro = Ejecutada { $name }
+cli-no-scene = OBS no tiene ninguna escena llamada "{ $scene }"
+cli-no-input = OBS no tiene ninguna entrada de audio llamada "{ $input }"
+cli-no-macro = No hay ninguna macro llamada "{ $name }"
+
+global-keys-title = Teclas globales
+global-keys-hint = Teclas de un teclado de macros (F13 a F24) y teclas multimedia que funcionan aunque REC no tenga el foco. Las teclas asignadas no llegan a otros programas.
+global-keys-action = Al pulsar
+global-keys-bind = Asignar una tecla
+global-keys-press = Pulsa la tecla que quieres asignar...
+global-keys-cancel = Cancelar
+global-keys-remove = Quitar
+shortcut-mute-mic = Silenciar/activar micrófono
+shortcut-mute-desktop = Silenciar/activar audio del escritorio
+shortcut-record = Iniciar/detener grabación
+shortcut-stream = Iniciar/detener transmisión
+shortcut-scene = Escena { $number }: { $scene }
+shortcut-button = Ejecutar { $name }
+shortcut-teleprompter-toggle = Iniciar/pausar teleprompter
+shortcut-teleprompter-faster = Teleprompter más rápido
+shortcut-teleprompter-slower = Teleprompter más lento
+shortcut-teleprompter-restart = Reiniciar teleprompter
+shortcut-go = Escaleta: siguiente
+shortcut-intermission = Iniciar/terminar intermedio
+
+pairing-title = Dispositivos vinculados
+pairing-hint = Sin vinculación, cualquiera en la red puede ver a través del relay y el control web, pero sin cambiar nada. Con ella activada, solo funcionan los enlaces vinculados, aunque se hayan revocado todos los dispositivos.
+pairing-enabled = Permitir solo dispositivos vinculados
+pairing-name = Nombre
+pairing-pair = Vincular
+pairing-viewer = Espectador
+pairing-operator = Operador
+pairing-admin = Administrador
+pairing-viewer-hint = Ve lo que está en vivo pero no puede cambiar nada
+pairing-operator-hint = Puede cambiar escenas y el audio, pero no iniciar ni terminar la transmisión o la grabación
+pairing-admin-hint = Puede hacerlo todo
+pairing-copy-remote = Copiar enlace del control web
+pairing-copy-relay = Copiar dirección del relay
+pairing-revoke = Revocar
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+// The compressor REC puts on an input to duck it under another.
+pub const DUCKING_FILTER: &str = "REC Ducking";
+
+// A named set of audio settings applied to several inputs at once, from the
+// `[[audio_presets]]` tables of rec.toml or the built-in ones.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AudioPreset {
+    pub name: String,
+    // Input name or alias ("mic", "desktop") to what it's set to.
+    #[serde(default)]
+    pub inputs: BTreeMap<String, InputPreset>,
+    // Lowers the desktop audio while the mic is in use.
+    #[serde(default)]
+    pub ducking: bool,
+}
+
+// Anything left out stays as it is.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputPreset {
+    pub volume_db: Option<f32>,
+    pub monitor: Option<Monitor>,
+    // 1-based tracks the input goes to; the others are turned off.
+    pub tracks: Option<Vec<usize>>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Monitor {
+    Off,
+    MonitorOnly,
+    MonitorAndOutput,
+}
+
+impl Monitor {
+    // As the obs-websocket protocol names it.
+    pub fn obs_name(self) -> &'static str {
+        match self {
+            Monitor::Off => "OBS_MONITORING_TYPE_NONE",
+            Monitor::MonitorOnly => "OBS_MONITORING_TYPE_MONITOR_ONLY",
+            Monitor::MonitorAndOutput => "OBS_MONITORING_TYPE_MONITOR_AND_OUTPUT",
+        }
+    }
+}
+
+impl InputPreset {
+    fn new(volume_db: f32, tracks: &[usize]) -> Self {
+        Self {
+            volume_db: Some(volume_db),
+            monitor: Some(Monitor::Off),
+            tracks: Some(tracks.to_vec()),
+        }
+    }
+
+    // Every track as OBS takes them, on or off, keyed "1" to "6".
+    pub fn track_map(tracks: &[usize]) -> serde_json::Map<String, serde_json::Value> {
+        (1..=6)
+            .map(|track| (track.to_string(), tracks.contains(&track).into()))
+            .collect()
+    }
+}
+
+// The mic goes to track 2 and the desktop to track 3 on their own, so either
+// can be fixed up in editing.
+fn builtin() -> Vec<AudioPreset> {
+    let preset = |name: &str, mic_db, desktop_db, ducking| AudioPreset {
+        name: name.to_string(),
+        inputs: BTreeMap::from([
+            ("mic".to_string(), InputPreset::new(mic_db, &[1, 2])),
+            ("desktop".to_string(), InputPreset::new(desktop_db, &[1, 3])),
+        ]),
+        ducking,
+    };
+    vec![
+        preset("Podcast", 0.0, -20.0, true),
+        preset("Gaming", 0.0, -8.0, true),
+        preset("Music", -6.0, 0.0, false),
+    ]
+}
+
+// The built-in presets followed by the configured ones; a configured preset
+// with a built-in's name replaces it.
+pub fn presets(configured: Vec<AudioPreset>) -> Vec<AudioPreset> {
+    let mut presets: Vec<AudioPreset> = builtin()
+        .into_iter()
+        .filter(|preset| !configured.iter().any(|other| other.name == preset.name))
+        .collect();
+    presets.extend(configured);
+    presets
+}
+
+// How one input ducks under another. OBS does the work with a compressor
+// keyed off the trigger, so these are turned into its settings.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DuckingPair {
+    pub ducked: String,
+    pub trigger: String,
+    pub ducking: Ducking,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ducking {
+    // Trigger level in dB above which the ducked input drops.
+    pub threshold: f32,
+    // How far it drops, in dB, for speech well over the threshold.
+    pub reduction: f32,
+    pub attack_ms: u32,
+    pub hold_ms: u32,
+    pub release_ms: u32,
+}
+
+impl Default for Ducking {
+    fn default() -> Self {
+        Self {
+            threshold: -30.0,
+            reduction: 12.0,
+            attack_ms: 10,
+            hold_ms: 200,
+            release_ms: 400,
+        }
+    }
+}
+
+// How far over the threshold the trigger is assumed to be while talking; the
+// reduction is reached there.
+const SPEECH_HEADROOM: f32 = 20.0;
+
+impl Ducking {
+    // The compressor ratio that takes the trigger's headroom down by the
+    // reduction, within what OBS's compressor accepts.
+    pub fn ratio(&self) -> f32 {
+        let left = (SPEECH_HEADROOM - self.reduction).max(SPEECH_HEADROOM / 32.0);
+        (SPEECH_HEADROOM / left).clamp(1.0, 32.0)
+    }
+
+    // Gain in dB the compressor applies for a trigger level in dB.
+    pub fn gain(&self, level: f32) -> f32 {
+        let over = (level - self.threshold).max(0.0);
+        -(over - over / self.ratio())
+    }
+
+    // The compressor has no hold stage, so the hold is folded into its
+    // release instead.
+    pub fn settings(&self, trigger: &str) -> serde_json::Value {
+        json!({
+            "sidechain_source": trigger,
+            "ratio": self.ratio(),
+            "threshold": self.threshold,
+            "attack_time": self.attack_ms,
+            "release_time": self.hold_ms + self.release_ms,
+            "output_gain": 0.0,
+        })
+    }
+}
+
+// A scene change with the audio moving along with it, the way a show cue
+// goes: cut to the interview while the music fades down.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneCue {
+    pub scene: Option<String>,
+    // Input name or alias to where it ends up.
+    #[serde(default)]
+    pub audio: BTreeMap<String, Level>,
+    #[serde(default)]
+    pub fade_ms: u64,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Level {
+    Db(f32),
+    // Up or down from wherever the input is, in dB.
+    Change(f32),
+}
+
+// The range OBS takes for an input's volume.
+pub const MIN_DB: f32 = -100.0;
+pub const MAX_DB: f32 = 26.0;
+
+impl Level {
+    pub fn target(self, from_db: f32) -> f32 {
+        match self {
+            Level::Db(db) => db,
+            Level::Change(change) => from_db + change,
+        }
+        .clamp(MIN_DB, MAX_DB)
+    }
+
+    // From the mixer's 0-100 volume, silence included.
+    pub fn from_volume(volume: f32) -> Self {
+        match volume {
+            volume if volume <= 0.0 => Level::Db(MIN_DB),
+            volume => Level::Db(20.0 * (volume / 100.0).log10()),
+        }
+    }
+}
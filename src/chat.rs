@@ -0,0 +1,172 @@
+use crate::obs_worker::{Action, Macro, MacroStep};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+const CHAT_URL: &str = "wss://irc-ws.chat.twitch.tv:443";
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Role {
+    Broadcaster,
+    Moderator,
+    Vip,
+    Subscriber,
+    Everyone,
+}
+
+// A `[[twitch.commands]]` entry of rec.toml.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChatCommand {
+    pub command: String,
+    // Who may use it. The broadcaster always can.
+    #[serde(default = "default_roles")]
+    pub roles: Vec<Role>,
+    // Seconds before the command runs again, whoever sends it.
+    #[serde(default)]
+    pub cooldown: u64,
+    pub steps: Vec<MacroStep>,
+}
+
+fn default_roles() -> Vec<Role> {
+    vec![Role::Moderator]
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChatConfig {
+    pub login: String,
+    pub token: String,
+    pub commands: Vec<ChatCommand>,
+}
+
+// Like the MQTT bridge, the worker stops this by aborting the task. Commands
+// are ignored while `rehearsal` is set.
+pub async fn serve(
+    config: ChatConfig,
+    action_tx: mpsc::Sender<Action>,
+    rehearsal: Arc<AtomicBool>,
+) {
+    let mut last_run = HashMap::new();
+    loop {
+        match connect(&config, &action_tx, &rehearsal, &mut last_run).await {
+            Ok(()) => return,
+            Err(err) => tracing::warn!("twitch chat connection failed: {:#}", err),
+        }
+        tokio::time::sleep(RECONNECT_INTERVAL).await;
+    }
+}
+
+// Returns Ok only once the worker is gone.
+async fn connect(
+    config: &ChatConfig,
+    action_tx: &mpsc::Sender<Action>,
+    rehearsal: &AtomicBool,
+    last_run: &mut HashMap<String, Instant>,
+) -> anyhow::Result<()> {
+    let (stream, _) = tokio_tungstenite::connect_async(CHAT_URL).await?;
+    let (mut sink, mut source) = stream.split();
+    let channel = config.login.to_lowercase();
+    for line in [
+        "CAP REQ :twitch.tv/tags twitch.tv/commands".to_string(),
+        format!("PASS oauth:{}", config.token),
+        format!("NICK {}", channel),
+        format!("JOIN #{}", channel),
+    ] {
+        sink.send(Message::Text(line)).await?;
+    }
+    tracing::info!(channel, "joined twitch chat");
+
+    while let Some(message) = source.next().await {
+        let Message::Text(text) = message? else {
+            continue;
+        };
+        for line in text.lines() {
+            if let Some(server) = line.strip_prefix("PING ") {
+                sink.send(Message::Text(format!("PONG {}", server))).await?;
+                continue;
+            }
+            if line.contains(" RECONNECT") {
+                anyhow::bail!("twitch asked to reconnect");
+            }
+            let Some((roles, text)) = parse_privmsg(line) else {
+                continue;
+            };
+            let word = text.split_whitespace().next().unwrap_or_default();
+            let Some(command) = config
+                .commands
+                .iter()
+                .find(|command| command.command.eq_ignore_ascii_case(word))
+            else {
+                continue;
+            };
+            if !roles.contains(&Role::Broadcaster)
+                && !command.roles.iter().any(|role| roles.contains(role))
+            {
+                tracing::debug!(command = command.command, "chat command not permitted");
+                continue;
+            }
+            if rehearsal.load(Ordering::Relaxed) {
+                tracing::debug!(
+                    command = command.command,
+                    "chat command ignored while rehearsing"
+                );
+                continue;
+            }
+            let cooldown = Duration::from_secs(command.cooldown);
+            if last_run
+                .get(&command.command)
+                .is_some_and(|last| last.elapsed() < cooldown)
+            {
+                tracing::debug!(command = command.command, "chat command on cooldown");
+                continue;
+            }
+            last_run.insert(command.command.clone(), Instant::now());
+            tracing::info!(command = command.command, "chat command triggered");
+            let action = Action::RunMacro(Macro {
+                name: command.command.clone(),
+                steps: command.steps.clone(),
+            });
+            if action_tx.send(action).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+    anyhow::bail!("twitch chat closed the connection")
+}
+
+// `@badges=moderator/1,subscriber/6;mod=1;... :nick!nick@nick.tmi.twitch.tv
+// PRIVMSG #channel :!brb`
+fn parse_privmsg(line: &str) -> Option<(Vec<Role>, &str)> {
+    let (tags, rest) = match line.strip_prefix('@') {
+        Some(line) => line.split_once(' ')?,
+        None => ("", line),
+    };
+    let (_, rest) = rest.split_once(" PRIVMSG ")?;
+    let (_, text) = rest.split_once(" :")?;
+
+    let mut roles = vec![Role::Everyone];
+    for tag in tags.split(';') {
+        let Some(("badges", badges)) = tag.split_once('=') else {
+            continue;
+        };
+        for badge in badges.split(',') {
+            match badge.split('/').next() {
+                Some("broadcaster") => roles.push(Role::Broadcaster),
+                Some("moderator") => roles.push(Role::Moderator),
+                Some("vip") => roles.push(Role::Vip),
+                Some("subscriber" | "founder") => roles.push(Role::Subscriber),
+                _ => {}
+            }
+        }
+    }
+    Some((roles, text))
+}
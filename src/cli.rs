@@ -0,0 +1,275 @@
+use crate::{
+    config::{ConnectionConfig, RecConfig, SavedState},
+    i18n::{self, I18n},
+    obs_worker::{Action, ObsInfo, Worker},
+    state::ObsState,
+};
+use anyhow::{bail, Context, Result};
+use fluent::fluent_args;
+use std::{sync::Arc, thread, time::Duration};
+use tokio::sync::mpsc;
+
+// Long enough for OBS to answer over a slow network, short enough that a
+// launcher doesn't hang when it's closed.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy)]
+pub enum Change {
+    On,
+    Off,
+    Toggle,
+}
+
+impl Change {
+    fn parse(word: &str) -> Result<Self> {
+        Ok(match word {
+            "start" | "on" => Change::On,
+            "stop" | "off" => Change::Off,
+            "toggle" => Change::Toggle,
+            _ => bail!("expected start, stop or toggle, got {:?}", word),
+        })
+    }
+
+    pub fn apply(self, current: bool) -> bool {
+        match self {
+            Change::On => true,
+            Change::Off => false,
+            Change::Toggle => !current,
+        }
+    }
+}
+
+// One thing to do from the command line before exiting.
+pub enum Command {
+    Scene(String),
+    // Input name or alias ("mic", "desktop").
+    Mute(String, Change),
+    Record(Change),
+    Stream(Change),
+    Macro(String),
+    Help,
+}
+
+// None means there's no subcommand and the window should open. Flags meant
+// for the window, like --demo, are left to it.
+pub fn parse(args: &[String]) -> Result<Option<Command>> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    Ok(Some(match args.as_slice() {
+        [] => return Ok(None),
+        [flag, ..] if flag.starts_with("--") && *flag != "--help" => return Ok(None),
+        ["help"] | ["--help"] | ["-h"] => Command::Help,
+        ["scene", scene] => Command::Scene(scene.to_string()),
+        ["mute", input] => Command::Mute(input.to_string(), Change::On),
+        ["mute", input, "--toggle"] => Command::Mute(input.to_string(), Change::Toggle),
+        ["unmute", input] => Command::Mute(input.to_string(), Change::Off),
+        ["record", change] => Command::Record(Change::parse(change)?),
+        ["stream", change] => Command::Stream(Change::parse(change)?),
+        ["macro", name] => Command::Macro(name.to_string()),
+        _ => bail!("unknown command {:?}, see `rec help`", args.join(" ")),
+    }))
+}
+
+// The worker, driven the way the UI drives it but waiting on each answer.
+struct Session {
+    action_tx: mpsc::Sender<Action>,
+    obs_info_rx: mpsc::Receiver<ObsInfo>,
+    state: ObsState,
+}
+
+impl Session {
+    // No journal and no light discovery: neither means anything for a single
+    // command.
+    fn start() -> Self {
+        let (action_tx, action_rx) = mpsc::channel(10);
+        let (obs_info_tx, obs_info_rx) = mpsc::channel(10);
+        let worker_tx = action_tx.clone();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("failed to build runtime");
+            rt.block_on(Worker::new(obs_info_tx, worker_tx, Arc::new(|| {})).run(action_rx));
+        });
+        Self {
+            action_tx,
+            obs_info_rx,
+            state: ObsState::default(),
+        }
+    }
+
+    async fn send(&self, action: Action) -> Result<()> {
+        self.action_tx.send(action).await.context("worker stopped")
+    }
+
+    async fn wait_for<T>(&mut self, mut found: impl FnMut(&ObsInfo) -> Option<T>) -> Result<T> {
+        let waited = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                let info = self.obs_info_rx.recv().await.context("worker stopped")?;
+                match &info {
+                    ObsInfo::Error(err) => bail!("{}", err),
+                    ObsInfo::ConnectionLost => bail!("connection to OBS lost"),
+                    _ => {}
+                }
+                self.state.apply(&info);
+                if let Some(value) = found(&info) {
+                    return Ok(value);
+                }
+            }
+        })
+        .await;
+        waited.context("timed out waiting for OBS")?
+    }
+}
+
+// Connects with what the app would use, does the one thing, prints what OBS
+// made of it, and disconnects.
+pub fn run(command: Command) -> Result<()> {
+    let saved = SavedState::load();
+    let i18n = I18n::new(
+        saved
+            .language
+            .as_deref()
+            .unwrap_or_else(i18n::system_language),
+    );
+    if let Command::Help = command {
+        println!("{}", i18n.tr("cli-usage"));
+        return Ok(());
+    }
+    let config = RecConfig::load()
+        .map_err(|err| tracing::warn!("{:#}", err))
+        .unwrap_or_default();
+    let connection = match &config.connection {
+        Some(file) => ConnectionConfig {
+            addr: file.addr.clone(),
+            port: file.port.clone(),
+            pass: file.password.clone(),
+        },
+        None => saved.connection,
+    };
+    let login = connection.login_action()?;
+    let aliases = [
+        ("mic", config.ui.mic_input.clone().or(saved.mic_input_name)),
+        (
+            "desktop",
+            config.ui.desktop_input.clone().or(saved.desktop_input_name),
+        ),
+    ];
+    let macros: Vec<_> = saved.macros.into_iter().chain(config.buttons).collect();
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to build runtime")?;
+    rt.block_on(async {
+        let mut session = Session::start();
+        for (alias, input) in &aliases {
+            if let Some(input) = input {
+                session
+                    .send(Action::SetAlias(alias.to_string(), Some(input.clone())))
+                    .await?;
+            }
+        }
+        session.send(login).await?;
+        session
+            .wait_for(|info| matches!(info, ObsInfo::State(_)).then_some(()))
+            .await?;
+
+        let message = match command {
+            Command::Help => unreachable!(),
+            Command::Scene(scene) => {
+                session.send(Action::SetScene(scene.clone())).await?;
+                session
+                    .wait_for(|info| match info {
+                        ObsInfo::SceneChanged(changed) if *changed == scene => Some(()),
+                        _ => None,
+                    })
+                    .await?;
+                i18n.tr_args("cli-scene", &fluent_args!["scene" => scene])
+            }
+            Command::Mute(input, change) => {
+                let input = aliases
+                    .iter()
+                    .find(|(alias, _)| *alias == input)
+                    .and_then(|(_, name)| name.clone())
+                    .unwrap_or(input);
+                let Some(&current) = session.state.muted.get(&input) else {
+                    bail!("OBS has no audio input called {:?}", input);
+                };
+                let muted = change.apply(current);
+                // OBS says nothing when nothing changes.
+                if muted != current {
+                    session.send(Action::SetMute(input.clone(), muted)).await?;
+                    session
+                        .wait_for(|info| match info {
+                            ObsInfo::MuteChanged(changed, _) if *changed == input => Some(()),
+                            _ => None,
+                        })
+                        .await?;
+                }
+                let id = if muted { "cli-muted" } else { "cli-unmuted" };
+                i18n.tr_args(id, &fluent_args!["input" => input])
+            }
+            Command::Record(change) => {
+                let recording = change.apply(session.state.recording);
+                if recording != session.state.recording {
+                    let action = if recording {
+                        Action::StartRecord
+                    } else {
+                        Action::StopRecord
+                    };
+                    session.send(action).await?;
+                    session
+                        .wait_for(|info| match info {
+                            ObsInfo::RecordState(active) if *active == recording => Some(()),
+                            _ => None,
+                        })
+                        .await?;
+                }
+                i18n.tr(if recording {
+                    "cli-recording"
+                } else {
+                    "cli-not-recording"
+                })
+            }
+            Command::Stream(change) => {
+                let streaming = change.apply(session.state.streaming);
+                if streaming != session.state.streaming {
+                    let action = if streaming {
+                        Action::StartStream
+                    } else {
+                        Action::StopStream
+                    };
+                    session.send(action).await?;
+                    session
+                        .wait_for(|info| match info {
+                            ObsInfo::StreamState(active) if *active == streaming => Some(()),
+                            _ => None,
+                        })
+                        .await?;
+                }
+                i18n.tr(if streaming {
+                    "cli-streaming"
+                } else {
+                    "cli-not-streaming"
+                })
+            }
+            Command::Macro(name) => {
+                let Some(obs_macro) = macros.into_iter().find(|obs_macro| obs_macro.name == name)
+                else {
+                    bail!("no macro called {:?}", name);
+                };
+                session.send(Action::RunMacro(obs_macro)).await?;
+                i18n.tr_args("cli-macro", &fluent_args!["name" => name])
+            }
+        };
+
+        // Actions run in order, so once the logout is through everything
+        // before it is too, and any error along the way has been seen.
+        session.send(Action::LogOut).await?;
+        session
+            .wait_for(|info| matches!(info, ObsInfo::Disconnected).then_some(()))
+            .await?;
+        println!("{}", message);
+        Ok(())
+    })
+}
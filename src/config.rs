@@ -0,0 +1,574 @@
+use crate::{
+    audio::{AudioPreset, DuckingPair, Level},
+    features::{Feature, Flags},
+    layout::{Layout, Orientation, Panel},
+    lighting::LightingConfig,
+    metrics::MetricsConfig,
+    migrate::{self, Migration, STATE_VERSION},
+    now_playing::NowPlayingConfig,
+    obs_worker::{Action, Macro, MacroStep, Notifier},
+    pairing::Pairing,
+    state::Condition,
+    tally::TallyConfig,
+    theme::Theme,
+    twitch::{self, TwitchConfig},
+    webhooks::Webhook,
+    youtube::{self, YouTubeConfig},
+};
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::IpAddr,
+    path::PathBuf,
+    sync::mpsc,
+};
+
+const CONFIG_TEMPLATE: &str = include_str!("../rec.toml");
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    pub addr: String,
+    pub port: String,
+    #[serde(skip)]
+    pub pass: String,
+}
+
+impl ConnectionConfig {
+    pub fn login_action(&self) -> Result<Action> {
+        let addr = self.addr.parse::<IpAddr>().context("invalid ip")?;
+        let port = self.port.parse::<u16>().context("invalid port")?;
+        Ok(Action::LogIn(addr, port, self.pass.clone()))
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub position: Option<[f32; 2]>,
+    // The size to come back to after un-maximizing.
+    pub size: [f32; 2],
+    #[serde(default)]
+    pub maximized: bool,
+    // Size of the monitor the window was on, to notice when it's gone.
+    #[serde(default)]
+    pub monitor_size: Option<[f32; 2]>,
+}
+
+// How an input is shown in REC. The OBS input itself is never renamed.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ChannelStyle {
+    pub label: String,
+    pub color: Option<[u8; 3]>,
+    // Inputs sharing a group get one mute button together.
+    #[serde(default)]
+    pub group: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Confirmation {
+    StopStream,
+    StopRecord,
+}
+
+impl Confirmation {
+    pub const ALL: [Confirmation; 2] = [Confirmation::StopStream, Confirmation::StopRecord];
+
+    pub fn for_action(action: &Action) -> Option<Self> {
+        match action {
+            Action::StopStream => Some(Confirmation::StopStream),
+            Action::StopRecord => Some(Confirmation::StopRecord),
+            _ => None,
+        }
+    }
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Confirmation::StopStream => "confirm-stop-stream",
+            Confirmation::StopRecord => "confirm-stop-record",
+        }
+    }
+}
+
+// When dragging a volume slider reaches OBS.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SliderMode {
+    Continuous,
+    #[default]
+    Throttled,
+    OnRelease,
+}
+
+impl SliderMode {
+    pub const ALL: [SliderMode; 3] = [
+        SliderMode::Continuous,
+        SliderMode::Throttled,
+        SliderMode::OnRelease,
+    ];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            SliderMode::Continuous => "slider-continuous",
+            SliderMode::Throttled => "slider-throttled",
+            SliderMode::OnRelease => "slider-on-release",
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CountdownSettings {
+    pub minutes: u32,
+    // Text source kept showing the time left.
+    pub input: Option<String>,
+    // Macro run when it reaches zero.
+    pub then: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LowerThird {
+    pub name: String,
+    pub title: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LowerThirdSettings {
+    pub scene: Option<String>,
+    // The scene item shown and hidden, usually a group holding the graphics.
+    pub source: String,
+    pub name_input: Option<String>,
+    pub title_input: Option<String>,
+    pub seconds: u32,
+    pub entries: Vec<LowerThird>,
+}
+
+impl Default for LowerThirdSettings {
+    fn default() -> Self {
+        Self {
+            scene: None,
+            source: String::new(),
+            name_input: None,
+            title_input: None,
+            seconds: 8,
+            entries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Team {
+    pub name: String,
+    pub score: u32,
+    pub name_input: Option<String>,
+    pub score_input: Option<String>,
+}
+
+// Kept with the scores, so a restart mid-match picks up where it was.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scoreboard {
+    pub teams: [Team; 2],
+    pub map: u32,
+    pub map_input: Option<String>,
+}
+
+impl Default for Scoreboard {
+    fn default() -> Self {
+        Self {
+            teams: Default::default(),
+            map: 1,
+            map_input: None,
+        }
+    }
+}
+
+// One step of a scripted show, all of which happens on GO.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cue {
+    pub name: String,
+    pub scene: Option<String>,
+    // Input to its volume (0-100) and mute, as the mixer had them.
+    pub audio: BTreeMap<String, (f32, bool)>,
+    // Text sources and what they're set to.
+    pub texts: Vec<(String, String)>,
+    // Media sources started from the top.
+    pub media: Vec<String>,
+    // For the operator only.
+    pub notes: String,
+    // Planned length in seconds, timed from GO.
+    pub duration: Option<u64>,
+    // How long the audio takes to get to its levels; the scene cuts at once.
+    pub fade_ms: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeleprompterSettings {
+    pub script: String,
+    // Points per second.
+    pub speed: f32,
+    pub font_size: f32,
+    pub mirror: bool,
+}
+
+impl Default for TeleprompterSettings {
+    fn default() -> Self {
+        Self {
+            script: String::new(),
+            speed: 40.0,
+            font_size: 48.0,
+            mirror: false,
+        }
+    }
+}
+
+// Everything about the app worth keeping between runs. Passwords are left out.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedState {
+    pub version: u32,
+    // How the file was brought up to date, if it had to be.
+    #[serde(skip)]
+    pub migration: Option<Migration>,
+    pub connection: ConnectionConfig,
+    pub mic_input_name: Option<String>,
+    pub desktop_input_name: Option<String>,
+    // Keyed by OBS input name.
+    pub channels: BTreeMap<String, ChannelStyle>,
+    pub queue_offline: bool,
+    pub relay_port: String,
+    pub operator_name: String,
+    pub session_host: String,
+    pub osc_port: String,
+    pub osc_feedback: String,
+    pub mqtt_host: String,
+    pub mqtt_port: String,
+    pub mqtt_prefix: String,
+    pub mqtt_username: String,
+    pub remote_port: String,
+    // Devices paired with the relay and the web remote.
+    pub pairing: Pairing,
+    pub macros: Vec<Macro>,
+    pub window: Option<WindowGeometry>,
+    pub theme: Theme,
+    pub ui_scale: f32,
+    // None until the operator picks one, so the system language is used.
+    pub language: Option<String>,
+    pub touch_mode: bool,
+    pub minimize_to_tray: bool,
+    // Global key name (see global_keys::KEYS) to what it does.
+    pub global_keys: BTreeMap<String, Shortcut>,
+    // Set while locked in kiosk mode, so a restart comes back locked.
+    pub kiosk_pin: Option<String>,
+    // Likewise for volunteer mode.
+    pub volunteer_pin: Option<String>,
+    pub volunteer_scenes: BTreeSet<String>,
+    // Confirmations the operator chose not to be asked again.
+    pub skip_confirmations: BTreeSet<Confirmation>,
+    pub orientation: Orientation,
+    pub slider_mode: SliderMode,
+    pub ducking: Vec<DuckingPair>,
+    // From the last show template opened; rec.toml keeps its own.
+    pub show_audio_presets: Vec<AudioPreset>,
+    // Sections folded away, kept across restarts.
+    pub collapsed: BTreeSet<Panel>,
+    pub layout: Option<Layout>,
+    pub vertical_layout: Option<Layout>,
+    pub layouts: BTreeMap<String, Layout>,
+    // Window geometry saved along with each named layout.
+    pub layout_windows: BTreeMap<String, WindowGeometry>,
+    pub twitch_auth: Option<twitch::Auth>,
+    pub youtube_auth: Option<youtube::Auth>,
+    pub lighting: LightingConfig,
+    pub countdown: CountdownSettings,
+    pub stopwatch_input: Option<String>,
+    pub lower_thirds: LowerThirdSettings,
+    pub scoreboard: Scoreboard,
+    pub teleprompter: TeleprompterSettings,
+    pub rundown: Vec<Cue>,
+    // Where the time left in the segment on air is shown to talent.
+    pub segment_input: Option<String>,
+    pub segment_on_prompter: bool,
+    pub preview_enabled: bool,
+    pub preview_fps: u32,
+    // Off until the operator opts in to counting usage locally.
+    pub usage_stats: bool,
+    pub features: Flags,
+}
+
+impl Default for SavedState {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            migration: None,
+            connection: ConnectionConfig::default(),
+            mic_input_name: None,
+            desktop_input_name: None,
+            channels: BTreeMap::new(),
+            queue_offline: false,
+            relay_port: "4456".to_string(),
+            operator_name: "Operator".to_string(),
+            session_host: String::new(),
+            osc_port: "9000".to_string(),
+            osc_feedback: String::new(),
+            mqtt_host: String::new(),
+            mqtt_port: "1883".to_string(),
+            mqtt_prefix: "rec".to_string(),
+            mqtt_username: String::new(),
+            remote_port: "8080".to_string(),
+            pairing: Pairing::default(),
+            macros: vec![Macro::start_show(), Macro::instant_replay()],
+            window: None,
+            theme: Theme::default(),
+            ui_scale: 1.0,
+            language: None,
+            touch_mode: false,
+            minimize_to_tray: false,
+            global_keys: BTreeMap::new(),
+            kiosk_pin: None,
+            volunteer_pin: None,
+            volunteer_scenes: BTreeSet::new(),
+            skip_confirmations: BTreeSet::new(),
+            orientation: Orientation::default(),
+            slider_mode: SliderMode::default(),
+            ducking: Vec::new(),
+            show_audio_presets: Vec::new(),
+            collapsed: BTreeSet::new(),
+            layout: None,
+            vertical_layout: None,
+            layouts: BTreeMap::new(),
+            layout_windows: BTreeMap::new(),
+            twitch_auth: None,
+            youtube_auth: None,
+            lighting: LightingConfig::default(),
+            countdown: CountdownSettings {
+                minutes: 5,
+                ..CountdownSettings::default()
+            },
+            stopwatch_input: None,
+            lower_thirds: LowerThirdSettings::default(),
+            scoreboard: Scoreboard::default(),
+            teleprompter: TeleprompterSettings::default(),
+            rundown: Vec::new(),
+            segment_input: None,
+            segment_on_prompter: false,
+            preview_enabled: false,
+            preview_fps: 2,
+            usage_stats: false,
+            features: Flags::default(),
+        }
+    }
+}
+
+impl SavedState {
+    fn path() -> Option<PathBuf> {
+        let dirs = directories::ProjectDirs::from("", "", "rec")?;
+        Some(dirs.config_dir().join("state.json"))
+    }
+
+    // A missing or unreadable file falls back to the defaults rather than
+    // keeping the app from starting. Older files are migrated, and anything
+    // that can't be read is kept in a backup instead of lost.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                tracing::warn!("failed to read {}: {}", path.display(), err);
+                return Self::default();
+            }
+        };
+        let (mut state, migration): (Self, _) = migrate::load(&path, &contents);
+        state.migration = migration;
+        state
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().context("no home directory")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("failed to create config directory")?;
+        }
+        // Written next to the real file and renamed over it, so a crash mid-write
+        // can't leave a truncated state behind.
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+        Ok(())
+    }
+}
+
+// The hand-edited `rec.toml`. Unlike `SavedState` it is never written by the
+// app, apart from creating it from the template on first run.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct RecConfig {
+    pub connection: Option<ConnectionFile>,
+    pub ui: UiConfig,
+    pub buttons: Vec<Macro>,
+    pub rules: Vec<Rule>,
+    pub vendor_events: Vec<VendorTrigger>,
+    // Key name (as egui spells it, e.g. "M" or "1") to what it does.
+    pub shortcuts: Option<BTreeMap<String, Shortcut>>,
+    pub twitch: TwitchConfig,
+    pub youtube: YouTubeConfig,
+    pub tally: Option<TallyConfig>,
+    pub webhooks: Vec<Webhook>,
+    pub now_playing: Option<NowPlayingConfig>,
+    pub metrics: Option<MetricsConfig>,
+    pub audio_presets: Vec<AudioPreset>,
+    pub intermission: Option<IntermissionConfig>,
+    // Overrides the switches in the settings while present.
+    pub features: BTreeMap<Feature, bool>,
+}
+
+// The `[intermission]` table of rec.toml. Everything it changes is put back
+// when the break ends.
+#[derive(Clone, Deserialize)]
+pub struct IntermissionConfig {
+    pub scene: String,
+    // Inputs or aliases muted for the break.
+    #[serde(default = "default_intermission_mute")]
+    pub mute: Vec<String>,
+    // Levels faded to for the break, such as break music coming up.
+    #[serde(default)]
+    pub audio: BTreeMap<String, Level>,
+    #[serde(default)]
+    pub fade_ms: u64,
+    // Starts the countdown at this many minutes.
+    pub minutes: Option<u32>,
+    // Posted to Twitch chat as the break starts and ends.
+    pub chat_start: Option<String>,
+    pub chat_end: Option<String>,
+}
+
+fn default_intermission_mute() -> Vec<String> {
+    vec!["mic".to_string()]
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum Shortcut {
+    ToggleMuteMic,
+    ToggleMuteDesktop,
+    ToggleRecord,
+    ToggleStream,
+    // 1-based, in the order the scene buttons are shown.
+    Scene(usize),
+    Button(String),
+    TeleprompterToggle,
+    TeleprompterFaster,
+    TeleprompterSlower,
+    TeleprompterRestart,
+    RundownGo,
+    Intermission,
+}
+
+#[derive(Deserialize)]
+pub struct ConnectionFile {
+    pub addr: String,
+    pub port: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default)]
+    pub auto_connect: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    pub mic_input: Option<String>,
+    pub desktop_input: Option<String>,
+    pub queue_offline: Option<bool>,
+    pub kiosk_pin: Option<String>,
+    pub check_updates: Option<bool>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub when: Condition,
+    pub steps: Vec<MacroStep>,
+}
+
+// Steps run when a plugin sends a vendor event, like rules but for events
+// rather than state.
+#[derive(Clone, Deserialize)]
+pub struct VendorTrigger {
+    pub name: String,
+    pub vendor: String,
+    pub event: String,
+    pub steps: Vec<MacroStep>,
+}
+
+impl RecConfig {
+    pub fn path() -> Option<PathBuf> {
+        let dirs = directories::ProjectDirs::from("", "", "rec")?;
+        Some(dirs.config_dir().join("rec.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path().context("no home directory")?;
+        if !path.exists() {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).context("failed to create config directory")?;
+            }
+            std::fs::write(&path, CONFIG_TEMPLATE)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    }
+}
+
+pub struct ConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+    changed_rx: mpsc::Receiver<()>,
+}
+
+impl ConfigWatcher {
+    // Watches the whole directory, since editors usually save by replacing the
+    // file rather than writing to it.
+    pub fn new(notify: Notifier) -> Result<Self> {
+        let path = RecConfig::path().context("no home directory")?;
+        let dir = path
+            .parent()
+            .context("config file has no directory")?
+            .to_path_buf();
+        let (changed_tx, changed_rx) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                if !event.kind.is_access() && event.paths.iter().any(|changed| changed == &path) {
+                    let _ = changed_tx.send(());
+                    notify();
+                }
+            })
+            .context("failed to create config watcher")?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("failed to watch {}", dir.display()))?;
+        Ok(Self {
+            _watcher: watcher,
+            changed_rx,
+        })
+    }
+
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        while self.changed_rx.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
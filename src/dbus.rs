@@ -0,0 +1,362 @@
+use crate::obs_worker::Notifier;
+use anyhow::Result;
+use std::sync::mpsc;
+
+pub enum DbusCommand {
+    ToggleMic,
+    SetMicMuted(bool),
+    SetScene(String),
+    NextScene,
+    PreviousScene,
+    Raise,
+}
+
+// What's shown on the bus, both as REC's own properties and as MPRIS.
+#[derive(Clone, Default, PartialEq)]
+pub struct DbusStatus {
+    pub connected: bool,
+    pub streaming: bool,
+    pub recording: bool,
+    pub mic_muted: bool,
+    pub scene: Option<String>,
+    pub scenes: Vec<String>,
+}
+
+// REC on the session bus: its own interface for scripts and shell extensions,
+// and MPRIS so media keys and playerctl reach it. To MPRIS the mic is what's
+// playing: play-pause toggles it, and next and previous step through scenes.
+pub struct Dbus {
+    status_tx: mpsc::Sender<DbusStatus>,
+    command_rx: mpsc::Receiver<DbusCommand>,
+    last_status: Option<DbusStatus>,
+}
+
+impl Dbus {
+    pub fn new(notify: Notifier) -> Result<Self> {
+        let (command_tx, command_rx) = mpsc::channel();
+        let status_tx = serve(command_tx, notify)?;
+        Ok(Self {
+            status_tx,
+            command_rx,
+            last_status: None,
+        })
+    }
+
+    pub fn update(&mut self, status: DbusStatus) {
+        if self.last_status.as_ref() == Some(&status) {
+            return;
+        }
+        let _ = self.status_tx.send(status.clone());
+        self.last_status = Some(status);
+    }
+
+    pub fn commands(&self) -> Vec<DbusCommand> {
+        self.command_rx.try_iter().collect()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn serve(_: mpsc::Sender<DbusCommand>, _: Notifier) -> Result<mpsc::Sender<DbusStatus>> {
+    anyhow::bail!("D-Bus is only available on Linux")
+}
+
+#[cfg(target_os = "linux")]
+use linux::serve;
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{DbusCommand, DbusStatus};
+    use crate::obs_worker::Notifier;
+    use anyhow::{Context, Result};
+    use std::{
+        collections::HashMap,
+        sync::{mpsc, Arc, Mutex},
+    };
+    use zbus::{
+        blocking::{Connection, ConnectionBuilder},
+        dbus_interface,
+        zvariant::{ObjectPath, OwnedValue, Value},
+    };
+
+    // MPRIS finds players by this prefix.
+    const NAME: &str = "org.mpris.MediaPlayer2.rec";
+    const PATH: &str = "/org/mpris/MediaPlayer2";
+
+    #[derive(Clone)]
+    struct Shared {
+        status: Arc<Mutex<DbusStatus>>,
+        command_tx: mpsc::Sender<DbusCommand>,
+        notify: Notifier,
+    }
+
+    impl Shared {
+        fn status(&self) -> DbusStatus {
+            self.status.lock().unwrap().clone()
+        }
+
+        fn send(&self, command: DbusCommand) {
+            let _ = self.command_tx.send(command);
+            (self.notify)();
+        }
+    }
+
+    struct Control(Shared);
+
+    #[dbus_interface(name = "io.github.suhrm.Rec")]
+    impl Control {
+        fn toggle_mic(&self) {
+            self.0.send(DbusCommand::ToggleMic);
+        }
+
+        fn set_mic_muted(&self, muted: bool) {
+            self.0.send(DbusCommand::SetMicMuted(muted));
+        }
+
+        fn set_scene(&self, scene: String) {
+            self.0.send(DbusCommand::SetScene(scene));
+        }
+
+        #[dbus_interface(property)]
+        fn connected(&self) -> bool {
+            self.0.status().connected
+        }
+
+        #[dbus_interface(property)]
+        fn streaming(&self) -> bool {
+            self.0.status().streaming
+        }
+
+        #[dbus_interface(property)]
+        fn recording(&self) -> bool {
+            self.0.status().recording
+        }
+
+        #[dbus_interface(property)]
+        fn mic_muted(&self) -> bool {
+            self.0.status().mic_muted
+        }
+
+        // Empty while not connected.
+        #[dbus_interface(property)]
+        fn current_scene(&self) -> String {
+            self.0.status().scene.unwrap_or_default()
+        }
+
+        #[dbus_interface(property)]
+        fn scenes(&self) -> Vec<String> {
+            self.0.status().scenes
+        }
+    }
+
+    struct Root(Shared);
+
+    #[dbus_interface(name = "org.mpris.MediaPlayer2")]
+    impl Root {
+        fn raise(&self) {
+            self.0.send(DbusCommand::Raise);
+        }
+
+        // Closing REC mid-show from a media widget is too easy to do by mistake.
+        fn quit(&self) {}
+
+        #[dbus_interface(property)]
+        fn can_quit(&self) -> bool {
+            false
+        }
+
+        #[dbus_interface(property)]
+        fn can_raise(&self) -> bool {
+            true
+        }
+
+        #[dbus_interface(property)]
+        fn has_track_list(&self) -> bool {
+            false
+        }
+
+        #[dbus_interface(property)]
+        fn identity(&self) -> String {
+            "REC".to_string()
+        }
+
+        #[dbus_interface(property)]
+        fn supported_uri_schemes(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        #[dbus_interface(property)]
+        fn supported_mime_types(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    struct Player(Shared);
+
+    #[dbus_interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl Player {
+        fn play_pause(&self) {
+            self.0.send(DbusCommand::ToggleMic);
+        }
+
+        fn play(&self) {
+            self.0.send(DbusCommand::SetMicMuted(false));
+        }
+
+        fn pause(&self) {
+            self.0.send(DbusCommand::SetMicMuted(true));
+        }
+
+        fn stop(&self) {
+            self.0.send(DbusCommand::SetMicMuted(true));
+        }
+
+        fn next(&self) {
+            self.0.send(DbusCommand::NextScene);
+        }
+
+        fn previous(&self) {
+            self.0.send(DbusCommand::PreviousScene);
+        }
+
+        #[dbus_interface(property)]
+        fn playback_status(&self) -> String {
+            let status = self.0.status();
+            match (status.connected, status.mic_muted) {
+                (false, _) => "Stopped",
+                (true, true) => "Paused",
+                (true, false) => "Playing",
+            }
+            .to_string()
+        }
+
+        // The scene as the title, and whether it's live as the artist, which
+        // is what media widgets show.
+        #[dbus_interface(property)]
+        fn metadata(&self) -> HashMap<String, OwnedValue> {
+            let status = self.0.status();
+            let live = match (status.streaming, status.recording) {
+                (true, true) => "Live, recording",
+                (true, false) => "Live",
+                (false, true) => "Recording",
+                (false, false) => "Off air",
+            };
+            let track = ObjectPath::from_static_str_unchecked("/io/github/suhrm/Rec/scene");
+            HashMap::from([
+                ("mpris:trackid".to_string(), Value::from(track).into()),
+                (
+                    "xesam:title".to_string(),
+                    Value::from(status.scene.unwrap_or_default()).into(),
+                ),
+                (
+                    "xesam:artist".to_string(),
+                    Value::from(vec![live.to_string()]).into(),
+                ),
+            ])
+        }
+
+        #[dbus_interface(property)]
+        fn can_go_next(&self) -> bool {
+            self.0.status().scenes.len() > 1
+        }
+
+        #[dbus_interface(property)]
+        fn can_go_previous(&self) -> bool {
+            self.0.status().scenes.len() > 1
+        }
+
+        #[dbus_interface(property)]
+        fn can_play(&self) -> bool {
+            self.0.status().connected
+        }
+
+        #[dbus_interface(property)]
+        fn can_pause(&self) -> bool {
+            self.0.status().connected
+        }
+
+        #[dbus_interface(property)]
+        fn can_seek(&self) -> bool {
+            false
+        }
+
+        #[dbus_interface(property)]
+        fn can_control(&self) -> bool {
+            true
+        }
+
+        #[dbus_interface(property)]
+        fn rate(&self) -> f64 {
+            1.0
+        }
+
+        #[dbus_interface(property)]
+        fn minimum_rate(&self) -> f64 {
+            1.0
+        }
+
+        #[dbus_interface(property)]
+        fn maximum_rate(&self) -> f64 {
+            1.0
+        }
+
+        #[dbus_interface(property)]
+        fn position(&self) -> i64 {
+            0
+        }
+    }
+
+    // Tells listeners what changed. Every property goes out each time; the
+    // status only comes through when something in it did change.
+    fn announce(connection: &Connection) -> zbus::Result<()> {
+        let server = connection.object_server();
+        let player = server.interface::<_, Player>(PATH)?;
+        let control = server.interface::<_, Control>(PATH)?;
+        zbus::block_on(async {
+            let context = player.signal_context();
+            let player = player.get().await;
+            player.playback_status_changed(context).await?;
+            player.metadata_changed(context).await?;
+            player.can_go_next_changed(context).await?;
+            player.can_go_previous_changed(context).await?;
+            player.can_play_changed(context).await?;
+            player.can_pause_changed(context).await?;
+            let context = control.signal_context();
+            let control = control.get().await;
+            control.connected_changed(context).await?;
+            control.streaming_changed(context).await?;
+            control.recording_changed(context).await?;
+            control.mic_muted_changed(context).await?;
+            control.current_scene_changed(context).await?;
+            control.scenes_changed(context).await?;
+            Ok(())
+        })
+    }
+
+    pub(super) fn serve(
+        command_tx: mpsc::Sender<DbusCommand>,
+        notify: Notifier,
+    ) -> Result<mpsc::Sender<DbusStatus>> {
+        let shared = Shared {
+            status: Arc::default(),
+            command_tx,
+            notify,
+        };
+        let connection = ConnectionBuilder::session()
+            .and_then(|builder| builder.name(NAME))
+            .and_then(|builder| builder.serve_at(PATH, Root(shared.clone())))
+            .and_then(|builder| builder.serve_at(PATH, Player(shared.clone())))
+            .and_then(|builder| builder.serve_at(PATH, Control(shared.clone())))
+            .and_then(|builder| builder.build())
+            .context("failed to register on the session bus")?;
+        let (status_tx, status_rx) = mpsc::channel::<DbusStatus>();
+        std::thread::spawn(move || {
+            for status in status_rx {
+                *shared.status.lock().unwrap() = status;
+                if let Err(err) = announce(&connection) {
+                    tracing::warn!("failed to announce status on D-Bus: {}", err);
+                }
+            }
+        });
+        Ok(status_tx)
+    }
+}
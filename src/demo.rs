@@ -0,0 +1,277 @@
+use crate::{
+    audio::Level,
+    obs_worker::{Action, MacroStep, Notice, ObsInfo, SessionInfo},
+    state::ObsState,
+};
+use obws::responses::inputs::Input;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime},
+};
+
+const SCENES: [&str; 6] = [
+    "Starting Soon",
+    "Just Chatting",
+    "Gameplay",
+    "Interview",
+    "Be Right Back",
+    "Ending",
+];
+
+// Name, kind, volume (0-100) and whether it starts muted.
+const INPUTS: [(&str, &str, f32, bool); 8] = [
+    ("Mic/Aux", "wasapi_input_capture", 80.0, false),
+    ("Desktop Audio", "wasapi_output_capture", 60.0, false),
+    ("Guest Mic", "wasapi_input_capture", 75.0, true),
+    ("Music", "ffmpeg_source", 40.0, false),
+    ("Webcam", "dshow_input", 100.0, true),
+    ("Game Capture", "game_capture", 100.0, true),
+    ("Lower Third", "text_gdiplus_v2", 100.0, true),
+    ("Countdown", "text_gdiplus_v2", 100.0, true),
+];
+
+// Small talk from a pretend OBS, for the event log.
+const CHATTER: [(&str, &str); 4] = [
+    (
+        "InputActiveStateChanged",
+        r#"{"inputName":"Webcam","videoActive":true}"#,
+    ),
+    (
+        "SceneItemSelected",
+        r#"{"sceneName":"Gameplay","sceneItemId":3}"#,
+    ),
+    ("MediaInputPlaybackStarted", r#"{"inputName":"Music"}"#),
+    (
+        "InputShowStateChanged",
+        r#"{"inputName":"Lower Third","videoShowing":true}"#,
+    ),
+];
+
+// A made-up OBS that answers the UI directly, for demos and for working on the
+// UI with no OBS around. It keeps just enough state to echo actions back the
+// way OBS would and now and then says something on its own.
+pub struct Demo {
+    state: ObsState,
+    aliases: HashMap<String, String>,
+    next_chatter: Instant,
+    seed: u64,
+    outbox: Vec<ObsInfo>,
+}
+
+fn input(name: &str, kind: &str) -> Option<Input> {
+    // Built the way OBS sends it, so no field of obws's is assumed.
+    serde_json::from_value(serde_json::json!({
+        "inputName": name,
+        "inputKind": kind,
+        "unversionedInputKind": kind.trim_end_matches("_v2"),
+    }))
+    .ok()
+}
+
+fn volume_from_db(db: f32) -> f32 {
+    (100.0 * 10f32.powf(db / 20.0)).clamp(0.0, 100.0)
+}
+
+fn db_from_volume(volume: f32) -> f32 {
+    match Level::from_volume(volume) {
+        Level::Db(db) | Level::Change(db) => db,
+    }
+}
+
+impl Default for Demo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Demo {
+    pub fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(1, |since| since.as_nanos() as u64)
+            | 1;
+        let state = ObsState {
+            current_scene: Some(SCENES[0].to_string()),
+            scenes: SCENES.iter().map(|scene| scene.to_string()).collect(),
+            muted: INPUTS
+                .iter()
+                .map(|(name, _, _, muted)| (name.to_string(), *muted))
+                .collect(),
+            volumes: INPUTS
+                .iter()
+                .map(|(name, _, volume, _)| (name.to_string(), *volume))
+                .collect(),
+            ..ObsState::default()
+        };
+        let outbox = vec![
+            ObsInfo::InputInfo(
+                INPUTS
+                    .iter()
+                    .filter_map(|(name, kind, _, _)| input(name, kind))
+                    .collect(),
+            ),
+            ObsInfo::SceneList(state.scenes.clone()),
+            ObsInfo::State(state.clone()),
+            ObsInfo::Session(SessionInfo {
+                obs_version: "30.0.2".to_string(),
+                platform: std::env::consts::OS.to_string(),
+                stream_elapsed: Duration::ZERO,
+                record_elapsed: Duration::ZERO,
+            }),
+        ];
+        Self {
+            state,
+            aliases: HashMap::new(),
+            next_chatter: Instant::now() + Duration::from_secs(5),
+            seed,
+            outbox,
+        }
+    }
+
+    // xorshift; a demo doesn't need better.
+    fn random(&mut self, below: u64) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed % below
+    }
+
+    fn resolve(&self, name: &str) -> String {
+        self.aliases
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn emit(&mut self, info: ObsInfo) {
+        self.state.apply(&info);
+        self.outbox.push(info);
+    }
+
+    // What OBS would have sent back for an action. Anything the demo can't
+    // pretend to do is quietly ignored.
+    pub fn handle(&mut self, action: Action) {
+        match action {
+            Action::SetAlias(alias, Some(name)) => {
+                self.aliases.insert(alias, name);
+            }
+            Action::SetAlias(alias, None) => {
+                self.aliases.remove(&alias);
+            }
+            Action::SetMute(name, muted) => {
+                let name = self.resolve(&name);
+                if self.state.muted.get(&name) != Some(&muted) {
+                    self.emit(ObsInfo::MuteChanged(name, muted));
+                }
+            }
+            Action::SetVolume(name, volume) => {
+                let name = self.resolve(&name);
+                self.emit(ObsInfo::VolumeChanged(name, volume.clamp(0.0, 100.0)));
+            }
+            Action::SetScene(scene) if self.state.scenes.contains(&scene) => {
+                self.emit(ObsInfo::SceneChanged(scene));
+            }
+            Action::SetPreviewScene(scene) if self.state.scenes.contains(&scene) => {
+                self.emit(ObsInfo::PreviewSceneChanged(Some(scene)));
+            }
+            // Lands straight away; fading is the worker's job.
+            Action::SceneCue(cue) => {
+                for (name, level) in cue.audio {
+                    let name = self.resolve(&name);
+                    let from = self.state.volumes.get(&name).copied().unwrap_or(100.0);
+                    let volume = volume_from_db(level.target(db_from_volume(from)));
+                    self.emit(ObsInfo::VolumeChanged(name, volume));
+                }
+                if let Some(scene) = cue.scene {
+                    self.handle(Action::SetScene(scene));
+                }
+            }
+            Action::StartStream if !self.state.streaming => {
+                self.emit(ObsInfo::StreamState(true));
+            }
+            Action::StopStream if self.state.streaming => {
+                self.emit(ObsInfo::StreamState(false));
+            }
+            Action::StartRecord if !self.state.recording => {
+                self.emit(ObsInfo::RecordState(true));
+                self.emit(ObsInfo::Notice(Notice::RecordingStarted(None)));
+            }
+            Action::StopRecord if self.state.recording => {
+                self.emit(ObsInfo::RecordState(false));
+                self.emit(ObsInfo::Notice(Notice::RecordingStopped(None)));
+            }
+            Action::SetFilterEnabled(source, filter, enabled) => {
+                let source = self.resolve(&source);
+                self.emit(ObsInfo::FilterChanged(source, filter, enabled));
+            }
+            Action::ToggleFilter(source, filter) => {
+                let source = self.resolve(&source);
+                let enabled = self
+                    .state
+                    .filters
+                    .get(&source)
+                    .and_then(|filters| filters.get(&filter))
+                    .copied()
+                    .unwrap_or(false);
+                self.emit(ObsInfo::FilterChanged(source, filter, !enabled));
+            }
+            Action::SetSceneItemEnabled(scene, source, enabled) => {
+                let source = self.resolve(&source);
+                self.emit(ObsInfo::SceneItemChanged(scene, source, enabled));
+            }
+            Action::SetRehearsal(rehearsal) => self.outbox.push(ObsInfo::Rehearsal(rehearsal)),
+            Action::RunMacro(obs_macro) => self.run_steps(obs_macro.steps),
+            Action::Query(id, _) => self.outbox.push(ObsInfo::Response(
+                id,
+                Err("not available in demo mode".to_string()),
+            )),
+            Action::AddMarker(description) => {
+                self.outbox.push(ObsInfo::Marker(description, None));
+            }
+            Action::LogOut => self.outbox.push(ObsInfo::Disconnected),
+            _ => {}
+        }
+    }
+
+    fn run_steps(&mut self, steps: Vec<MacroStep>) {
+        for step in steps {
+            match step {
+                MacroStep::Do(action) => self.handle(action),
+                MacroStep::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let steps = if self.state.check(&condition) {
+                        then
+                    } else {
+                        otherwise
+                    };
+                    self.run_steps(steps);
+                }
+            }
+        }
+    }
+
+    // Everything due since the last call: answers to actions, and some
+    // chatter every few seconds.
+    pub fn tick(&mut self) -> Vec<ObsInfo> {
+        if Instant::now() >= self.next_chatter {
+            let wait = 4 + self.random(8);
+            self.next_chatter = Instant::now() + Duration::from_secs(wait);
+            let (event, data) = CHATTER[self.random(CHATTER.len() as u64) as usize];
+            self.outbox
+                .push(ObsInfo::Event(event.to_string(), data.to_string()));
+            if self.state.streaming && self.random(10) == 0 {
+                let dropped = 0.5 + self.random(30) as f64 / 10.0;
+                self.outbox
+                    .push(ObsInfo::Notice(Notice::DroppedFrames(dropped)));
+            }
+        }
+        std::mem::take(&mut self.outbox)
+    }
+
+    pub fn next_chatter(&self) -> Duration {
+        self.next_chatter.saturating_duration_since(Instant::now())
+    }
+}
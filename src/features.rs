@@ -0,0 +1,53 @@
+use crate::obs_worker::Action;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Subsystems still finding their feet. They ship switched off and are turned
+// on from the settings or the `[features]` table of rec.toml, no rebuild needed.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    Scripting,
+    Relay,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 2] = [Feature::Scripting, Feature::Relay];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Feature::Scripting => "feature-scripting",
+            Feature::Relay => "feature-relay",
+        }
+    }
+
+    pub fn hint_id(self) -> &'static str {
+        match self {
+            Feature::Scripting => "feature-scripting-hint",
+            Feature::Relay => "feature-relay-hint",
+        }
+    }
+
+    // The feature an action belongs to, if it's behind one.
+    pub fn of(action: &Action) -> Option<Feature> {
+        match action {
+            Action::RunScript(_) => Some(Feature::Scripting),
+            Action::StartRelay(_) => Some(Feature::Relay),
+            _ => None,
+        }
+    }
+}
+
+// Features never switched on or off stay off.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Flags(BTreeMap<Feature, bool>);
+
+impl Flags {
+    pub fn enabled(&self, feature: Feature) -> bool {
+        self.0.get(&feature).copied().unwrap_or(false)
+    }
+
+    pub fn set(&mut self, feature: Feature, enabled: bool) {
+        self.0.insert(feature, enabled);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::obs_worker::Notifier;
+use anyhow::Result;
+use std::{
+    collections::BTreeSet,
+    sync::{mpsc, Arc, Mutex},
+};
+
+// Keys nothing else uses, so taking them away from other programs costs
+// nothing: the extra function keys macro pads send, and the media keys.
+pub const KEYS: [(u32, &str); 20] = [
+    (0x7C, "F13"),
+    (0x7D, "F14"),
+    (0x7E, "F15"),
+    (0x7F, "F16"),
+    (0x80, "F17"),
+    (0x81, "F18"),
+    (0x82, "F19"),
+    (0x83, "F20"),
+    (0x84, "F21"),
+    (0x85, "F22"),
+    (0x86, "F23"),
+    (0x87, "F24"),
+    (0xAD, "VolumeMute"),
+    (0xB0, "MediaNext"),
+    (0xB1, "MediaPrevious"),
+    (0xB2, "MediaStop"),
+    (0xB3, "MediaPlayPause"),
+    (0xB5, "LaunchMedia"),
+    (0xB6, "LaunchApp1"),
+    (0xB7, "LaunchApp2"),
+];
+
+pub enum KeyEvent {
+    Pressed(String),
+    // The next key pressed while capturing, to be bound.
+    Captured(String),
+}
+
+#[derive(Default)]
+struct Hooked {
+    bound: BTreeSet<String>,
+    capturing: bool,
+    // Held keys repeat; only the first press counts.
+    held: BTreeSet<String>,
+}
+
+// Keys pressed anywhere, even while another window has focus. Only bound keys
+// are taken; everything else goes on to where it was headed.
+pub struct GlobalKeys {
+    hooked: Arc<Mutex<Hooked>>,
+    event_rx: mpsc::Receiver<KeyEvent>,
+}
+
+impl GlobalKeys {
+    pub fn new(notify: Notifier) -> Result<Self> {
+        let hooked = Arc::new(Mutex::new(Hooked::default()));
+        let (event_tx, event_rx) = mpsc::channel();
+        hook(hooked.clone(), event_tx, notify)?;
+        Ok(Self { hooked, event_rx })
+    }
+
+    pub fn set_bound(&self, keys: impl IntoIterator<Item = String>) {
+        self.hooked.lock().unwrap().bound = keys.into_iter().collect();
+    }
+
+    pub fn capture(&self, capturing: bool) {
+        self.hooked.lock().unwrap().capturing = capturing;
+    }
+
+    pub fn capturing(&self) -> bool {
+        self.hooked.lock().unwrap().capturing
+    }
+
+    pub fn events(&self) -> Vec<KeyEvent> {
+        self.event_rx.try_iter().collect()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn hook(_: Arc<Mutex<Hooked>>, _: mpsc::Sender<KeyEvent>, _: Notifier) -> Result<()> {
+    anyhow::bail!("global keys are only available on windows")
+}
+
+#[cfg(target_os = "windows")]
+use windows_hook::hook;
+
+#[cfg(target_os = "windows")]
+mod windows_hook {
+    use super::{Hooked, KeyEvent, KEYS};
+    use crate::obs_worker::Notifier;
+    use anyhow::{Context, Result};
+    use std::sync::{mpsc, Arc, Mutex, OnceLock};
+    use windows::Win32::{
+        Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM},
+        UI::WindowsAndMessaging::{
+            CallNextHookEx, GetMessageW, SetWindowsHookExW, HC_ACTION, KBDLLHOOKSTRUCT, MSG,
+            WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
+        },
+    };
+
+    struct Shared {
+        hooked: Arc<Mutex<Hooked>>,
+        event_tx: mpsc::Sender<KeyEvent>,
+        notify: Notifier,
+    }
+
+    // The hook procedure takes no context, so what it needs lives here.
+    static SHARED: OnceLock<Shared> = OnceLock::new();
+
+    pub(super) fn hook(
+        hooked: Arc<Mutex<Hooked>>,
+        event_tx: mpsc::Sender<KeyEvent>,
+        notify: Notifier,
+    ) -> Result<()> {
+        SHARED
+            .set(Shared {
+                hooked,
+                event_tx,
+                notify,
+            })
+            .ok()
+            .context("global keys are already hooked")?;
+        let (ready_tx, ready_rx) = mpsc::channel();
+        // Low-level hooks are called on the thread that set them, which has
+        // to keep pumping messages for as long as the hook stays.
+        std::thread::spawn(move || unsafe {
+            let hooked = SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard), HINSTANCE(0), 0);
+            let failed = hooked.is_err();
+            let _ = ready_tx.send(hooked.map(|_| ()));
+            if failed {
+                return;
+            }
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {}
+        });
+        ready_rx
+            .recv()
+            .context("keyboard hook thread stopped")?
+            .context("failed to hook the keyboard")
+    }
+
+    unsafe extern "system" fn keyboard(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if code == HC_ACTION as i32 {
+            let key = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+            let down = matches!(wparam.0 as u32, WM_KEYDOWN | WM_SYSKEYDOWN);
+            if let (Some(shared), Some((_, name))) =
+                (SHARED.get(), KEYS.iter().find(|(vk, _)| *vk == key.vkCode))
+            {
+                let mut hooked = shared.hooked.lock().unwrap();
+                if !hooked.capturing && !hooked.bound.contains(*name) {
+                    hooked.held.remove(*name);
+                    drop(hooked);
+                    return CallNextHookEx(None, code, wparam, lparam);
+                }
+                let ignored = if down {
+                    !hooked.held.insert(name.to_string())
+                } else {
+                    hooked.held.remove(*name);
+                    true
+                };
+                let event = match (ignored, hooked.capturing) {
+                    (true, _) => None,
+                    (false, true) => {
+                        hooked.capturing = false;
+                        Some(KeyEvent::Captured(name.to_string()))
+                    }
+                    (false, false) => Some(KeyEvent::Pressed(name.to_string())),
+                };
+                drop(hooked);
+                if let Some(event) = event {
+                    let _ = shared.event_tx.send(event);
+                    (shared.notify)();
+                }
+                // Taken, so the media player doesn't also act on it.
+                return LRESULT(1);
+            }
+        }
+        CallNextHookEx(None, code, wparam, lparam)
+    }
+}
@@ -0,0 +1,99 @@
+use fluent::{FluentArgs, FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+pub struct Language {
+    pub code: &'static str,
+    pub name: &'static str,
+    source: &'static str,
+}
+
+// The first entry is the base every other translation falls back to.
+pub static LANGUAGES: [Language; 2] = [
+    Language {
+        code: "en",
+        name: "English",
+        source: include_str!("../locales/en.ftl"),
+    },
+    Language {
+        code: "es",
+        name: "Español",
+        source: include_str!("../locales/es.ftl"),
+    },
+];
+
+pub fn system_language() -> &'static str {
+    sys_locale::get_locale()
+        .and_then(|locale| {
+            LANGUAGES
+                .iter()
+                .find(|language| locale.starts_with(language.code))
+        })
+        .map_or(LANGUAGES[0].code, |language| language.code)
+}
+
+fn bundle(language: &Language) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.code.parse().expect("invalid language code");
+    let resource = FluentResource::try_new(language.source.to_string()).unwrap_or_else(
+        |(resource, errors)| {
+            tracing::error!(language = language.code, "translation errors: {:?}", errors);
+            resource
+        },
+    );
+    let mut bundle = FluentBundle::new(vec![id]);
+    // egui's fonts have no glyphs for the bidi isolation marks.
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::error!(language = language.code, "translation errors: {:?}", errors);
+    }
+    bundle
+}
+
+pub struct I18n {
+    language: &'static str,
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl I18n {
+    pub fn new(code: &str) -> Self {
+        let language = LANGUAGES
+            .iter()
+            .find(|language| language.code == code)
+            .unwrap_or(&LANGUAGES[0]);
+        Self {
+            language: language.code,
+            bundle: bundle(language),
+            fallback: bundle(&LANGUAGES[0]),
+        }
+    }
+
+    pub fn language(&self) -> &'static str {
+        self.language
+    }
+
+    pub fn tr(&self, id: &str) -> String {
+        self.format(id, None)
+    }
+
+    pub fn tr_args(&self, id: &str, args: &FluentArgs) -> String {
+        self.format(id, Some(args))
+    }
+
+    // A message missing from the translation falls back to English, and one
+    // missing from both shows its id so it's easy to spot.
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in [&self.bundle, &self.fallback] {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!(id, "translation errors: {:?}", errors);
+            }
+            return text.into_owned();
+        }
+        tracing::warn!(id, "missing translation");
+        id.to_string()
+    }
+}
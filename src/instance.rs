@@ -0,0 +1,176 @@
+use crate::{
+    cli::{self, Command},
+    obs_worker::Notifier,
+    pairing,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+// How long a forwarded command may take before the second invocation gives
+// up, and the running instance stops waiting on its UI to answer.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// What a second invocation sends: its arguments, and the token from the
+// instance file to show it's the same user.
+#[derive(Serialize, Deserialize)]
+struct Request {
+    token: String,
+    args: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Reply {
+    Done(String),
+    Failed(String),
+}
+
+// A command from a second invocation, waiting for the UI to carry it out.
+// None is a bare `rec`, asking the window to come forward.
+pub struct Incoming {
+    pub command: Option<Command>,
+    reply_tx: mpsc::Sender<Reply>,
+}
+
+impl Incoming {
+    pub fn reply(self, reply: Reply) {
+        let _ = self.reply_tx.send(reply);
+    }
+}
+
+fn path() -> Result<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "rec").context("no home directory")?;
+    Ok(dirs.data_dir().join("instance"))
+}
+
+// Port and token, as "port token".
+fn read() -> Option<(u16, String)> {
+    let text = std::fs::read_to_string(path().ok()?).ok()?;
+    let (port, token) = text.trim().split_once(' ')?;
+    Some((port.parse().ok()?, token.to_string()))
+}
+
+// 128 bits from the OS's CSPRNG, as hex.
+pub fn new_token() -> String {
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes).expect("the OS has no random source");
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Hands the arguments to a REC that's already running. None means there's
+// none, and this one should carry on by itself.
+pub fn forward(args: &[String]) -> Option<Result<String>> {
+    let (port, token) = read()?;
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    // A stale file from a crash points at nothing.
+    let stream = TcpStream::connect_timeout(&addr, Duration::from_millis(300)).ok()?;
+    Some(send(
+        stream,
+        Request {
+            token,
+            args: args.to_vec(),
+        },
+    ))
+}
+
+fn send(mut stream: TcpStream, request: Request) -> Result<String> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    writeln!(stream, "{}", serde_json::to_string(&request)?)?;
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .context("no answer from the running REC")?;
+    match serde_json::from_str(&line).context("bad answer from the running REC")? {
+        Reply::Done(message) => Ok(message),
+        Reply::Failed(err) => bail!("{}", err),
+    }
+}
+
+// Listens for later invocations on loopback, so there's only ever one window
+// and one connection to OBS.
+pub struct Listener {
+    incoming_rx: mpsc::Receiver<Incoming>,
+    token: String,
+}
+
+impl Listener {
+    pub fn start(notify: Notifier) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .context("failed to listen for other instances")?;
+        let port = listener.local_addr()?.port();
+        let token = new_token();
+        let path = path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("failed to create data directory")?;
+        }
+        std::fs::write(&path, format!("{} {}", port, token))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+
+        let (incoming_tx, incoming_rx) = mpsc::channel();
+        let expected = token.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if let Err(err) = serve(stream, &expected, &incoming_tx, &notify) {
+                    tracing::warn!("forwarded command failed: {:#}", err);
+                }
+            }
+        });
+        Ok(Self { incoming_rx, token })
+    }
+
+    pub fn next(&self) -> Option<Incoming> {
+        self.incoming_rx.try_recv().ok()
+    }
+}
+
+// Another instance may have taken over the file since; only ours is removed.
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if read().is_some_and(|(_, token)| token == self.token) {
+            if let Ok(path) = path() {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    token: &str,
+    incoming_tx: &mpsc::Sender<Incoming>,
+    notify: &Notifier,
+) -> Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let request: Request = serde_json::from_str(&line)?;
+    let reply = if !pairing::same_token(token, &request.token) {
+        Reply::Failed("wrong instance token".to_string())
+    } else {
+        match cli::parse(&request.args) {
+            Ok(command) => {
+                let (reply_tx, reply_rx) = mpsc::channel();
+                incoming_tx
+                    .send(Incoming { command, reply_tx })
+                    .map_err(|_| anyhow!("REC is closing"))?;
+                notify();
+                reply_rx
+                    .recv_timeout(TIMEOUT)
+                    .unwrap_or_else(|_| Reply::Failed("REC didn't answer".to_string()))
+            }
+            Err(err) => Reply::Failed(format!("{:#}", err)),
+        }
+    };
+    writeln!(&stream, "{}", serde_json::to_string(&reply)?)?;
+    Ok(())
+}
@@ -0,0 +1,280 @@
+use crate::{
+    mqtt::MqttConfig,
+    obs_worker::{Action, Macro},
+    pairing::{Grant, Pairing},
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+// Append-only record of what the worker has been asked to keep doing, read
+// back on the next start to offer resuming after a crash.
+#[derive(Serialize, Deserialize)]
+pub enum JournalEntry {
+    Started,
+    Control(Action),
+    PendingActions(Vec<Action>),
+    MacroStarted(u64, Macro),
+    MacroFinished(u64),
+    Shutdown,
+}
+
+pub struct Recovery {
+    // Replaying these in order restores the previous connection and services.
+    pub controls: Vec<Action>,
+    pub pending_actions: Vec<Action>,
+    pub unfinished_macros: Vec<Macro>,
+}
+
+impl Recovery {
+    // Returns nothing if the previous run shut down cleanly or left nothing
+    // worth restoring.
+    fn from_journal(contents: &str) -> Option<Self> {
+        let mut login = None;
+        let mut queue_offline = false;
+        let mut aliases = HashMap::new();
+        let mut services = BTreeMap::new();
+        let mut pending_actions = Vec::new();
+        let mut macros = BTreeMap::new();
+
+        // A crash can leave the last line half written, so unreadable lines are
+        // skipped rather than failing the whole recovery.
+        for entry in contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<JournalEntry>(line).ok())
+        {
+            match entry {
+                JournalEntry::Started => {}
+                JournalEntry::Shutdown => return None,
+                JournalEntry::Control(action) => match action {
+                    Action::LogIn(..) => login = Some(action),
+                    Action::LogOut => login = None,
+                    Action::SetOfflineQueue(enabled) => queue_offline = enabled,
+                    Action::SetAlias(alias, Some(input)) => {
+                        aliases.insert(alias, input);
+                    }
+                    Action::SetAlias(alias, None) => {
+                        aliases.remove(&alias);
+                    }
+                    Action::StartRelay(_) => {
+                        services.insert("relay", action);
+                    }
+                    Action::StartOsc(..) => {
+                        services.insert("osc", action);
+                    }
+                    Action::StartMqtt(_) => {
+                        services.insert("mqtt", action);
+                    }
+                    Action::StartRemote(_) => {
+                        services.insert("remote", action);
+                    }
+                    Action::StopRelay => {
+                        services.remove("relay");
+                    }
+                    Action::StopOsc => {
+                        services.remove("osc");
+                    }
+                    Action::StopMqtt => {
+                        services.remove("mqtt");
+                    }
+                    Action::StopRemote => {
+                        services.remove("remote");
+                    }
+                    _ => {}
+                },
+                JournalEntry::PendingActions(actions) => pending_actions = actions,
+                JournalEntry::MacroStarted(id, obs_macro) => {
+                    macros.insert(id, obs_macro);
+                }
+                JournalEntry::MacroFinished(id) => {
+                    macros.remove(&id);
+                }
+            }
+        }
+
+        let mut controls = Vec::new();
+        if queue_offline {
+            controls.push(Action::SetOfflineQueue(true));
+        }
+        controls.extend(
+            aliases
+                .into_iter()
+                .map(|(alias, input)| Action::SetAlias(alias, Some(input))),
+        );
+        controls.extend(services.into_values());
+        controls.extend(login);
+
+        let recovery = Self {
+            controls,
+            pending_actions,
+            unfinished_macros: macros.into_values().collect(),
+        };
+        let empty = recovery.controls.is_empty()
+            && recovery.pending_actions.is_empty()
+            && recovery.unfinished_macros.is_empty();
+        (!empty).then_some(recovery)
+    }
+}
+
+pub fn is_control(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::LogIn(..)
+            | Action::LogOut
+            | Action::SetOfflineQueue(_)
+            | Action::SetAlias(..)
+            | Action::StartRelay(_)
+            | Action::StopRelay
+            | Action::StartOsc(..)
+            | Action::StopOsc
+            | Action::StartMqtt(_)
+            | Action::StopMqtt
+            | Action::StartRemote(_)
+            | Action::StopRemote
+    )
+}
+
+// Passwords are not written to the journal; they have to be supplied again
+// when resuming.
+pub fn redact(action: Action) -> Action {
+    match action {
+        Action::LogIn(addr, port, _) => Action::LogIn(addr, port, String::new()),
+        Action::StartMqtt(MqttConfig {
+            host,
+            port,
+            prefix,
+            credentials,
+        }) => Action::StartMqtt(MqttConfig {
+            host,
+            port,
+            prefix,
+            credentials: credentials.map(|(username, _)| (username, String::new())),
+        }),
+        Action::SetPairing(Pairing { enabled, grants }) => Action::SetPairing(Pairing {
+            enabled,
+            grants: grants
+                .into_iter()
+                .map(|grant| Grant {
+                    token: String::new(),
+                    ..grant
+                })
+                .collect(),
+        }),
+        action => action,
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "rec")?;
+    Some(dirs.data_dir().join("journal.jsonl"))
+}
+
+fn append(file: &mut File, entry: &JournalEntry) -> Result<()> {
+    // One write per line, so entries from the UI and the worker can't interleave.
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.sync_data()?;
+    Ok(())
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))
+}
+
+pub struct Journal {
+    file: File,
+    path: PathBuf,
+    // Where this run's entries begin, while the previous run's are still
+    // ahead of them waiting on the resume prompt.
+    kept: Option<u64>,
+}
+
+impl Journal {
+    // Reads what the previous run left behind. If there's anything to resume it
+    // stays in the file until `forget_previous`, so crashing again before the
+    // prompt is answered doesn't lose it.
+    pub fn open() -> Result<(Self, Option<Recovery>)> {
+        let path = path().context("no home directory")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("failed to create data directory")?;
+        }
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", path.display()))
+            }
+        };
+        let recovery = Recovery::from_journal(&contents);
+        let kept = match &recovery {
+            Some(_) => {
+                // A line the crash cut short is ended, so this run's first entry
+                // starts on a line of its own.
+                let mut end = contents.len() as u64;
+                if !contents.ends_with('\n') {
+                    open_append(&path)?.write_all(b"\n")?;
+                    end += 1;
+                }
+                Some(end)
+            }
+            None => {
+                File::create(&path)
+                    .with_context(|| format!("failed to reset {}", path.display()))?;
+                None
+            }
+        };
+        let file = open_append(&path)?;
+        let mut journal = Self { file, path, kept };
+        journal.write(JournalEntry::Started);
+        Ok((journal, recovery))
+    }
+
+    // Called once the resume prompt is answered either way. Only what this run
+    // has written is kept.
+    pub fn forget_previous(&mut self) {
+        let Some(kept) = self.kept.take() else {
+            return;
+        };
+        let result = std::fs::read(&self.path)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| {
+                let ours = contents.get(kept as usize..).unwrap_or_default();
+                std::fs::write(&self.path, ours)?;
+                open_append(&self.path)
+            });
+        match result {
+            Ok(file) => self.file = file,
+            Err(err) => tracing::warn!("failed to trim journal: {:#}", err),
+        }
+    }
+
+    pub fn write(&mut self, entry: JournalEntry) {
+        let entry = match entry {
+            JournalEntry::Control(action) => JournalEntry::Control(redact(action)),
+            JournalEntry::PendingActions(actions) => {
+                JournalEntry::PendingActions(actions.into_iter().map(redact).collect())
+            }
+            entry => entry,
+        };
+        if let Err(err) = append(&mut self.file, &entry) {
+            tracing::warn!("failed to write journal: {:#}", err);
+        }
+    }
+}
+
+// Called by the UI on exit. The worker thread is never joined, so it can't be
+// relied on to write this itself.
+pub fn mark_shutdown() -> Result<()> {
+    let path = path().context("no home directory")?;
+    let mut file = open_append(&path)?;
+    append(&mut file, &JournalEntry::Shutdown)
+}
@@ -0,0 +1,188 @@
+use egui_dock::{DockState, NodeIndex};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Panel {
+    Mixer,
+    Scenes,
+    Preview,
+    Sources,
+    Grid,
+    History,
+    Plugins,
+    Twitch,
+    YouTube,
+    Lights,
+    Timers,
+    LowerThirds,
+    Scoreboard,
+    Teleprompter,
+    Rundown,
+    Iso,
+    Advanced,
+    Events,
+    Diagnostics,
+    Settings,
+    Log,
+}
+
+impl Panel {
+    pub const ALL: [Panel; 21] = [
+        Panel::Mixer,
+        Panel::Scenes,
+        Panel::Preview,
+        Panel::Sources,
+        Panel::Grid,
+        Panel::History,
+        Panel::Plugins,
+        Panel::Twitch,
+        Panel::YouTube,
+        Panel::Lights,
+        Panel::Timers,
+        Panel::LowerThirds,
+        Panel::Scoreboard,
+        Panel::Teleprompter,
+        Panel::Rundown,
+        Panel::Iso,
+        Panel::Advanced,
+        Panel::Events,
+        Panel::Diagnostics,
+        Panel::Settings,
+        Panel::Log,
+    ];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Panel::Mixer => "panel-mixer",
+            Panel::Scenes => "panel-scenes",
+            Panel::Preview => "panel-preview",
+            Panel::Sources => "panel-sources",
+            Panel::Grid => "panel-buttons",
+            Panel::History => "panel-history",
+            Panel::Plugins => "panel-plugins",
+            Panel::Twitch => "panel-twitch",
+            Panel::YouTube => "panel-youtube",
+            Panel::Lights => "panel-lights",
+            Panel::Timers => "panel-timers",
+            Panel::LowerThirds => "panel-lower-thirds",
+            Panel::Scoreboard => "panel-scoreboard",
+            Panel::Teleprompter => "panel-teleprompter",
+            Panel::Rundown => "panel-rundown",
+            Panel::Iso => "panel-iso",
+            Panel::Advanced => "panel-advanced",
+            Panel::Events => "panel-events",
+            Panel::Diagnostics => "panel-diagnostics",
+            Panel::Settings => "panel-settings",
+            Panel::Log => "panel-log",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Orientation {
+    #[default]
+    Auto,
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 3] = [
+        Orientation::Auto,
+        Orientation::Horizontal,
+        Orientation::Vertical,
+    ];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Orientation::Auto => "orientation-auto",
+            Orientation::Horizontal => "orientation-horizontal",
+            Orientation::Vertical => "orientation-vertical",
+        }
+    }
+
+    pub fn is_vertical(self, size: egui::Vec2) -> bool {
+        match self {
+            Orientation::Auto => size.x < size.y,
+            Orientation::Horizontal => false,
+            Orientation::Vertical => true,
+        }
+    }
+}
+
+pub type Layout = DockState<Panel>;
+
+pub fn default_layout() -> Layout {
+    let mut layout = DockState::new(vec![Panel::Mixer]);
+    let surface = layout.main_surface_mut();
+    let [top, _] = surface.split_below(NodeIndex::root(), 0.75, vec![Panel::Log]);
+    let [left, _] = surface.split_right(
+        top,
+        0.45,
+        vec![
+            Panel::Grid,
+            Panel::History,
+            Panel::Plugins,
+            Panel::Twitch,
+            Panel::YouTube,
+            Panel::Sources,
+            Panel::Lights,
+            Panel::Timers,
+            Panel::LowerThirds,
+            Panel::Scoreboard,
+            Panel::Teleprompter,
+            Panel::Rundown,
+            Panel::Iso,
+            Panel::Advanced,
+            Panel::Events,
+            Panel::Diagnostics,
+            Panel::Settings,
+        ],
+    );
+    surface.split_below(left, 0.55, vec![Panel::Scenes, Panel::Preview]);
+    layout
+}
+
+// Phone-style: the mixer on top with the buttons right under it.
+pub fn vertical_layout() -> Layout {
+    let mut layout = DockState::new(vec![Panel::Mixer]);
+    let surface = layout.main_surface_mut();
+    let [_, below] = surface.split_below(
+        NodeIndex::root(),
+        0.35,
+        vec![Panel::Grid, Panel::Scenes, Panel::Preview],
+    );
+    surface.split_below(
+        below,
+        0.6,
+        vec![
+            Panel::History,
+            Panel::Plugins,
+            Panel::Twitch,
+            Panel::YouTube,
+            Panel::Sources,
+            Panel::Lights,
+            Panel::Timers,
+            Panel::LowerThirds,
+            Panel::Scoreboard,
+            Panel::Teleprompter,
+            Panel::Rundown,
+            Panel::Iso,
+            Panel::Advanced,
+            Panel::Events,
+            Panel::Diagnostics,
+            Panel::Settings,
+            Panel::Log,
+        ],
+    );
+    layout
+}
+
+// Layouts saved before a panel existed would otherwise never show it.
+pub fn complete(layout: &mut Layout) {
+    for panel in Panel::ALL {
+        if layout.find_tab(&panel).is_none() {
+            layout.push_to_first_leaf(panel);
+        }
+    }
+}
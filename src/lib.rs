@@ -0,0 +1,41 @@
+pub mod audio;
+pub mod chat;
+pub mod cli;
+pub mod config;
+pub mod dbus;
+pub mod demo;
+pub mod features;
+pub mod global_keys;
+pub mod i18n;
+pub mod instance;
+pub mod journal;
+pub mod layout;
+pub mod lighting;
+pub mod lights;
+pub mod logging;
+pub mod metrics;
+pub mod migrate;
+pub mod mqtt;
+pub mod now_playing;
+pub mod obs_worker;
+pub mod osc;
+pub mod pairing;
+pub mod plugin;
+pub mod raw_request;
+pub mod relay;
+pub mod remote;
+pub mod scripting;
+pub mod session;
+pub mod source_record;
+pub mod state;
+pub mod tally;
+pub mod template;
+pub mod theme;
+pub mod trace;
+pub mod tray;
+pub mod twitch;
+pub mod ui;
+pub mod update;
+pub mod usage;
+pub mod webhooks;
+pub mod youtube;
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use mdns_sd::{ServiceDaemon, ServiceEvent};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+    thread,
+};
+
+const SERVICE: &str = "_elg._tcp.local.";
+
+// Discovered Key Lights by name, shared between the discovery thread and the
+// worker the same way aliases are.
+pub type Lights = Arc<RwLock<BTreeMap<String, SocketAddr>>>;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LightChange {
+    pub on: Option<bool>,
+    // 3-100 percent.
+    pub brightness: Option<u8>,
+    // 2900-7000 kelvin.
+    pub temperature: Option<u32>,
+}
+
+#[derive(Clone)]
+pub struct KeyLight {
+    pub name: String,
+    pub on: bool,
+    pub brightness: u8,
+    pub temperature: u32,
+}
+
+// What the light's HTTP API speaks. Its temperature is in mireds.
+#[derive(Serialize, Deserialize)]
+struct LightsBody {
+    lights: Vec<LightBody>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct LightBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    brightness: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<u32>,
+}
+
+fn to_mireds(kelvin: u32) -> u32 {
+    (1_000_000 / kelvin.max(1)).clamp(143, 344)
+}
+
+fn to_kelvin(mireds: u32) -> u32 {
+    1_000_000 / mireds.max(1)
+}
+
+// Browses for lights for as long as the app runs; each one found is looked up
+// and handed to `found`.
+pub fn discover(lights: Lights, found: impl Fn(Result<KeyLight>) + Send + 'static) -> Result<()> {
+    let mdns = ServiceDaemon::new().context("failed to start mdns")?;
+    let events = mdns
+        .browse(SERVICE)
+        .context("failed to browse for key lights")?;
+    thread::spawn(move || {
+        // Keeps the daemon alive along with the thread.
+        let _mdns = mdns;
+        while let Ok(event) = events.recv() {
+            let ServiceEvent::ServiceResolved(info) = event else {
+                continue;
+            };
+            let Some(ip) = info.get_addresses().iter().next() else {
+                continue;
+            };
+            let addr = SocketAddr::new((*ip).into(), info.get_port());
+            let name = info
+                .get_fullname()
+                .trim_end_matches(SERVICE)
+                .trim_end_matches('.')
+                .to_string();
+            tracing::info!(name, %addr, "found key light");
+            lights.write().unwrap().insert(name.clone(), addr);
+            found(get(addr).map(|state| KeyLight { name, ..state }));
+        }
+    });
+    Ok(())
+}
+
+pub fn get(addr: SocketAddr) -> Result<KeyLight> {
+    let response = ureq::get(&url(addr))
+        .call()
+        .with_context(|| format!("failed to reach key light at {}", addr))?;
+    parse(response)
+}
+
+// The light answers a change with its new state.
+pub fn set(addr: SocketAddr, change: &LightChange) -> Result<KeyLight> {
+    let body = LightsBody {
+        lights: vec![LightBody {
+            on: change.on.map(u8::from),
+            brightness: change.brightness.map(|brightness| brightness.clamp(3, 100)),
+            temperature: change.temperature.map(to_mireds),
+        }],
+    };
+    let response = ureq::put(&url(addr))
+        .set("Content-Type", "application/json")
+        .send_string(&serde_json::to_string(&body)?)
+        .with_context(|| format!("failed to update key light at {}", addr))?;
+    parse(response)
+}
+
+fn url(addr: SocketAddr) -> String {
+    format!("http://{}/elgato/lights", addr)
+}
+
+fn parse(response: ureq::Response) -> Result<KeyLight> {
+    let body: LightsBody =
+        serde_json::from_reader(response.into_reader()).context("unexpected key light response")?;
+    let light = body
+        .lights
+        .into_iter()
+        .next()
+        .context("key light reported no lights")?;
+    Ok(KeyLight {
+        name: String::new(),
+        on: light.on.unwrap_or(0) != 0,
+        brightness: light.brightness.unwrap_or(0),
+        temperature: to_kelvin(light.temperature.unwrap_or(200)),
+    })
+}
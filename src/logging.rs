@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::{Arc, Mutex},
+};
+use tracing::{field::Field, Level, Subscriber};
+use tracing_appender::{non_blocking::WorkerGuard, rolling};
+use tracing_subscriber::{layer::Context as LayerContext, prelude::*, EnvFilter, Layer};
+
+const LOG_BUFFER_CAPACITY: usize = 200;
+const MAX_LOG_FILES: usize = 7;
+
+#[derive(Clone)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+// Recent warnings and errors, shared between the tracing layer and the log panel.
+#[derive(Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<LogEntry>>>);
+
+impl LogBuffer {
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.0.lock().unwrap();
+        if entries.len() == LOG_BUFFER_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+struct BufferLayer(LogBuffer);
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: LayerContext<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > Level::WARN {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.0.push(LogEntry {
+            time: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.0,
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+pub fn init() -> Result<(LogBuffer, WorkerGuard)> {
+    let dirs = directories::ProjectDirs::from("", "", "rec").context("no home directory")?;
+    let file_appender = rolling::RollingFileAppender::builder()
+        .rotation(rolling::Rotation::DAILY)
+        .filename_prefix("rec")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dirs.data_dir().join("logs"))
+        .context("failed to create log file")?;
+    let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
+    let buffer = LogBuffer::default();
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(file_writer)
+                .with_ansi(false),
+        )
+        .with(BufferLayer(buffer.clone()))
+        .init();
+
+    Ok((buffer, guard))
+}
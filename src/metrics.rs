@@ -0,0 +1,205 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::fmt::Write;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+
+// The `[metrics]` table of rec.toml.
+#[derive(Clone, Deserialize)]
+pub struct MetricsConfig {
+    pub port: u16,
+}
+
+// Kept by the worker and refreshed on an interval while the exporter runs.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    pub connected: bool,
+    pub streaming: bool,
+    pub recording: bool,
+    pub stream_bytes: u64,
+    pub stream_skipped_frames: u32,
+    pub stream_total_frames: u32,
+    // Worked out from the bytes sent between two refreshes.
+    pub stream_kbps: f64,
+    pub render_skipped_frames: u32,
+    pub render_total_frames: u32,
+    pub output_skipped_frames: u32,
+    pub output_total_frames: u32,
+    pub cpu_usage: f64,
+    pub memory_usage: f64,
+    pub active_fps: f64,
+    pub actions: u64,
+    pub queued_actions: usize,
+    pub reconnects: u64,
+}
+
+impl Metrics {
+    // The Prometheus text exposition format.
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: f64| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "{} {}", name, value);
+        };
+        let flag = |on: bool| if on { 1.0 } else { 0.0 };
+        metric(
+            "rec_obs_connected",
+            "gauge",
+            "Whether REC is connected to OBS.",
+            flag(self.connected),
+        );
+        metric(
+            "obs_streaming",
+            "gauge",
+            "Whether OBS is streaming.",
+            flag(self.streaming),
+        );
+        metric(
+            "obs_recording",
+            "gauge",
+            "Whether OBS is recording.",
+            flag(self.recording),
+        );
+        metric(
+            "obs_stream_bytes_total",
+            "counter",
+            "Bytes sent by the current stream.",
+            self.stream_bytes as f64,
+        );
+        metric(
+            "obs_stream_bitrate_kbps",
+            "gauge",
+            "Stream bitrate over the last refresh.",
+            self.stream_kbps,
+        );
+        metric(
+            "obs_stream_skipped_frames_total",
+            "counter",
+            "Frames dropped by the stream output.",
+            self.stream_skipped_frames as f64,
+        );
+        metric(
+            "obs_stream_frames_total",
+            "counter",
+            "Frames sent by the stream output.",
+            self.stream_total_frames as f64,
+        );
+        metric(
+            "obs_render_skipped_frames_total",
+            "counter",
+            "Frames skipped because rendering was too slow.",
+            self.render_skipped_frames as f64,
+        );
+        metric(
+            "obs_render_frames_total",
+            "counter",
+            "Frames rendered.",
+            self.render_total_frames as f64,
+        );
+        metric(
+            "obs_output_skipped_frames_total",
+            "counter",
+            "Frames skipped because encoding was too slow.",
+            self.output_skipped_frames as f64,
+        );
+        metric(
+            "obs_output_frames_total",
+            "counter",
+            "Frames encoded.",
+            self.output_total_frames as f64,
+        );
+        metric(
+            "obs_cpu_usage_percent",
+            "gauge",
+            "CPU used by OBS.",
+            self.cpu_usage,
+        );
+        metric(
+            "obs_memory_usage_megabytes",
+            "gauge",
+            "Memory used by OBS.",
+            self.memory_usage,
+        );
+        metric(
+            "obs_active_fps",
+            "gauge",
+            "Frames per second OBS is rendering.",
+            self.active_fps,
+        );
+        metric(
+            "rec_actions_total",
+            "counter",
+            "Actions REC has handled.",
+            self.actions as f64,
+        );
+        metric(
+            "rec_queued_actions",
+            "gauge",
+            "Actions waiting for OBS to come back.",
+            self.queued_actions as f64,
+        );
+        metric(
+            "rec_reconnects_total",
+            "counter",
+            "Times REC reconnected to OBS after losing it.",
+            self.reconnects as f64,
+        );
+        out
+    }
+}
+
+// Serves whatever the worker last put in the channel; the worker stops it by
+// aborting the task.
+pub async fn serve(listener: TcpListener, metrics_rx: watch::Receiver<Metrics>) {
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                tracing::warn!("failed to accept metrics client: {}", err);
+                continue;
+            }
+        };
+        let metrics_rx = metrics_rx.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, metrics_rx).await {
+                tracing::debug!(%addr, "metrics request failed: {:#}", err);
+            }
+        });
+    }
+}
+
+// Just enough HTTP for a scraper: one GET, one response, then close.
+async fn handle_connection(
+    mut stream: TcpStream,
+    metrics_rx: watch::Receiver<Metrics>,
+) -> Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 || request.len() > 8192 {
+            anyhow::bail!("incomplete request");
+        }
+        request.extend_from_slice(&buf[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+    let (status, body) = if path == "/metrics" {
+        ("200 OK", metrics_rx.borrow().render())
+    } else {
+        ("404 Not Found", String::new())
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
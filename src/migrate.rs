@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+// Bumped whenever the saved state changes in a way old files can't simply be
+// read as; each bump adds a step below.
+pub const STATE_VERSION: u32 = 2;
+
+type Step = fn(&mut Map<String, Value>);
+
+// Files from before versioning read as they are.
+fn unversioned(_: &mut Map<String, Value>) {}
+
+// Version 1 had no pairing switch; tokens were checked whenever a device was
+// paired. Those files get the switch on if they had one.
+fn pairing_switch(fields: &mut Map<String, Value>) {
+    let Some(grants) = fields.remove("grants") else {
+        return;
+    };
+    let enabled = grants.as_array().is_some_and(|grants| !grants.is_empty());
+    fields.insert(
+        "pairing".to_string(),
+        serde_json::json!({ "enabled": enabled, "grants": grants }),
+    );
+}
+
+// Step i takes a file from version i to i + 1, with the message telling the
+// operator what it changed, if anything worth telling.
+const STEPS: [(Step, Option<&'static str>); STATE_VERSION as usize] =
+    [(unversioned, None), (pairing_switch, None)];
+
+// What happened to a file on its way in, for the notice shown at startup.
+pub struct Migration {
+    pub from: u32,
+    pub backup: Option<PathBuf>,
+    // Message ids of the steps that ran.
+    pub changes: Vec<&'static str>,
+    // Settings that couldn't be read and were left at their defaults. They're
+    // still in the backup.
+    pub dropped: Vec<String>,
+    // The file wasn't JSON at all, so everything started from the defaults.
+    pub unreadable: bool,
+}
+
+impl Migration {
+    // Nothing the operator needs to hear about.
+    pub fn is_quiet(&self) -> bool {
+        self.changes.is_empty()
+            && self.dropped.is_empty()
+            && !self.unreadable
+            && self.from <= STATE_VERSION
+    }
+}
+
+// Copies the file aside before anything is written over it.
+fn back_up(path: &Path, from: u32) -> Result<PathBuf> {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("state");
+    let backup = path.with_file_name(format!(
+        "{}-v{}-{}.json",
+        stem,
+        from,
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::copy(path, &backup)
+        .with_context(|| format!("failed to back up {}", path.display()))?;
+    Ok(backup)
+}
+
+// Keeps every field that reads and, inside lists, every entry that does, so
+// one button from a newer or older REC can't take the whole grid with it.
+fn salvage<T: Serialize + DeserializeOwned + Default>(
+    fields: Map<String, Value>,
+) -> (T, Vec<String>) {
+    let mut dropped = Vec::new();
+    let Ok(Value::Object(mut state)) = serde_json::to_value(T::default()) else {
+        return (
+            T::default(),
+            fields.into_iter().map(|(key, _)| key).collect(),
+        );
+    };
+    let reads = |state: &Map<String, Value>| {
+        serde_json::from_value::<T>(Value::Object(state.clone())).is_ok()
+    };
+    for (key, field) in fields {
+        let old = state.insert(key.clone(), field.clone());
+        if reads(&state) {
+            continue;
+        }
+        let Value::Array(items) = field else {
+            restore(&mut state, &key, old);
+            dropped.push(key);
+            continue;
+        };
+        let mut kept = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            kept.push(item);
+            state.insert(key.clone(), Value::Array(kept.clone()));
+            if !reads(&state) {
+                kept.pop();
+                dropped.push(format!("{} #{}", key, index + 1));
+            }
+        }
+        state.insert(key, Value::Array(kept));
+    }
+    let state = serde_json::from_value(Value::Object(state)).unwrap_or_default();
+    (state, dropped)
+}
+
+fn restore(state: &mut Map<String, Value>, key: &str, old: Option<Value>) {
+    match old {
+        Some(old) => state.insert(key.to_string(), old),
+        None => state.remove(key),
+    };
+}
+
+// Brings a saved file up to the current version and reads it, backing it up
+// first whenever it has to be changed or couldn't be read in full.
+pub fn load<T: Serialize + DeserializeOwned + Default>(
+    path: &Path,
+    contents: &str,
+) -> (T, Option<Migration>) {
+    let (mut fields, unreadable) = match serde_json::from_str(contents) {
+        Ok(Value::Object(fields)) => (fields, false),
+        _ => (Map::new(), true),
+    };
+    let from = fields
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version as u32);
+    let mut changes = Vec::new();
+    for (step, message) in STEPS.iter().skip(from as usize) {
+        step(&mut fields);
+        changes.extend(*message);
+    }
+    fields.insert("version".to_string(), STATE_VERSION.into());
+
+    let (state, dropped) = match serde_json::from_value(Value::Object(fields.clone())) {
+        Ok(state) => (state, Vec::new()),
+        Err(err) => {
+            tracing::warn!("failed to read all of {}: {}", path.display(), err);
+            salvage(fields)
+        }
+    };
+    if unreadable {
+        tracing::warn!("{} isn't JSON, starting from defaults", path.display());
+    }
+    if from == STATE_VERSION && dropped.is_empty() && !unreadable {
+        return (state, None);
+    }
+    tracing::info!(from, to = STATE_VERSION, "migrating {}", path.display());
+    let backup = back_up(path, from)
+        .map_err(|err| tracing::error!("{:#}", err))
+        .ok();
+    let migration = Migration {
+        from,
+        backup,
+        changes,
+        dropped,
+        unreadable,
+    };
+    (state, Some(migration))
+}
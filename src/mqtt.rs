@@ -0,0 +1,168 @@
+use crate::{
+    obs_worker::{self, Action, Aliases},
+    state::ObsState,
+};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub prefix: String,
+    pub credentials: Option<(String, String)>,
+}
+
+// Like the relay, the worker stops this by aborting the task.
+pub async fn serve(
+    config: MqttConfig,
+    mut state_rx: watch::Receiver<ObsState>,
+    aliases: Aliases,
+    action_tx: mpsc::Sender<Action>,
+) {
+    let mut options = MqttOptions::new("rec", &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(5));
+    if let Some((username, password)) = &config.credentials {
+        options.set_credentials(username, password);
+    }
+    let (client, mut eventloop) = AsyncClient::new(options, 32);
+    let command_prefix = format!("{}/cmd/", config.prefix);
+    let mut last_state = ObsState::default();
+
+    loop {
+        tokio::select! {
+            notification = eventloop.poll() => match notification {
+                // Subscriptions and retained state are lost with a clean session, so
+                // both are sent again on every (re)connect.
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    tracing::info!(host = config.host, "connected to mqtt broker");
+                    if let Err(err) = client
+                        .subscribe(format!("{}#", command_prefix), QoS::AtLeastOnce)
+                        .await
+                    {
+                        tracing::warn!("failed to subscribe to mqtt commands: {}", err);
+                    }
+                    last_state = ObsState::default();
+                    let state = state_rx.borrow().clone();
+                    publish(&client, &config.prefix, &last_state, &state, &aliases).await;
+                    last_state = state;
+                }
+                Ok(Event::Incoming(Packet::Publish(message))) => {
+                    let Some(command) = message.topic.strip_prefix(&command_prefix) else {
+                        continue;
+                    };
+                    let payload = String::from_utf8_lossy(&message.payload);
+                    match to_action(command, payload.trim(), &last_state, &aliases) {
+                        Some(action) => {
+                            if action_tx.send(action).await.is_err() {
+                                return;
+                            }
+                        }
+                        None => tracing::debug!(topic = message.topic, "unhandled mqtt command"),
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::warn!("mqtt connection failed: {}", err);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            },
+            changed = state_rx.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                let state = state_rx.borrow_and_update().clone();
+                publish(&client, &config.prefix, &last_state, &state, &aliases).await;
+                last_state = state;
+            }
+        }
+    }
+}
+
+// Public for the tests; the topic is what follows `<prefix>/cmd/`.
+pub fn to_action(
+    command: &str,
+    payload: &str,
+    state: &ObsState,
+    aliases: &Aliases,
+) -> Option<Action> {
+    if let Some(input) = command.strip_prefix("mute/") {
+        // The state is keyed by input name, so an alias has to be looked up
+        // before a toggle can tell which way to go.
+        let input = aliases
+            .read()
+            .unwrap()
+            .get(input)
+            .cloned()
+            .unwrap_or_else(|| input.to_string());
+        let muted = match payload {
+            "toggle" => !state.muted.get(&input).copied().unwrap_or(false),
+            payload => parse_bool(payload)?,
+        };
+        return Some(Action::SetMute(input, muted));
+    }
+    if let Some(input) = command.strip_prefix("volume/") {
+        return Some(Action::SetVolume(input.to_string(), payload.parse().ok()?));
+    }
+    match command {
+        "scene" => Some(Action::SetScene(payload.to_string())),
+        "stream" => Some(if parse_bool(payload)? {
+            Action::StartStream
+        } else {
+            Action::StopStream
+        }),
+        "record" => Some(if parse_bool(payload)? {
+            Action::StartRecord
+        } else {
+            Action::StopRecord
+        }),
+        _ => None,
+    }
+}
+
+fn parse_bool(payload: &str) -> Option<bool> {
+    match payload.to_ascii_lowercase().as_str() {
+        "true" | "on" | "1" | "start" => Some(true),
+        "false" | "off" | "0" | "stop" => Some(false),
+        _ => None,
+    }
+}
+
+async fn publish(
+    client: &AsyncClient,
+    prefix: &str,
+    last: &ObsState,
+    state: &ObsState,
+    aliases: &Aliases,
+) {
+    let mut messages = Vec::new();
+    if state.streaming != last.streaming {
+        messages.push(("streaming".to_string(), state.streaming.to_string()));
+    }
+    if state.recording != last.recording {
+        messages.push(("recording".to_string(), state.recording.to_string()));
+    }
+    if state.current_scene != last.current_scene {
+        let scene = state.current_scene.clone().unwrap_or_default();
+        messages.push(("scene".to_string(), scene));
+    }
+    for (input, muted) in &state.muted {
+        if last.muted.get(input) != Some(muted) {
+            for name in obs_worker::names_for_input(aliases, input) {
+                messages.push((format!("muted/{}", name), muted.to_string()));
+            }
+        }
+    }
+
+    for (topic, payload) in messages {
+        let topic = format!("{}/state/{}", prefix, topic);
+        if let Err(err) = client
+            .publish(&topic, QoS::AtLeastOnce, true, payload)
+            .await
+        {
+            tracing::warn!(topic, "failed to publish mqtt state: {}", err);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::obs_worker::Action;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// The `[now_playing]` table of rec.toml.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NowPlayingConfig {
+    // The OBS text source to write into.
+    pub input: String,
+    // `{title}`, `{artist}` and `{album}` are filled in.
+    #[serde(default = "default_format")]
+    pub format: String,
+    // Shown while nothing is playing.
+    #[serde(default)]
+    pub idle: String,
+}
+
+fn default_format() -> String {
+    "{artist} - {title}".to_string()
+}
+
+pub struct Track {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+}
+
+impl NowPlayingConfig {
+    fn text(&self, track: Option<&Track>) -> String {
+        match track {
+            Some(track) => self
+                .format
+                .replace("{title}", &track.title)
+                .replace("{artist}", &track.artist)
+                .replace("{album}", &track.album),
+            None => self.idle.clone(),
+        }
+    }
+}
+
+// Like the chat commands, this goes through the worker's own action queue;
+// the worker stops it by aborting the task.
+pub async fn serve(config: NowPlayingConfig, action_tx: mpsc::Sender<Action>) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    let mut shown = None;
+    loop {
+        interval.tick().await;
+        let track = match tokio::task::spawn_blocking(current_track).await {
+            Ok(Ok(track)) => track,
+            Ok(Err(err)) => {
+                tracing::debug!("no media player: {:#}", err);
+                None
+            }
+            Err(_) => return,
+        };
+        let text = config.text(track.as_ref());
+        if shown.as_ref() == Some(&text) {
+            continue;
+        }
+        tracing::debug!(text, "now playing changed");
+        shown = Some(text.clone());
+        if action_tx
+            .send(Action::SetText(config.input.clone(), text))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+// Whichever MPRIS player is playing, or was most recently.
+#[cfg(target_os = "linux")]
+fn current_track() -> Result<Option<Track>> {
+    use anyhow::Context;
+    let player = mpris::PlayerFinder::new()
+        .context("failed to connect to d-bus")?
+        .find_active()
+        .context("no mpris player")?;
+    if player.get_playback_status()? != mpris::PlaybackStatus::Playing {
+        return Ok(None);
+    }
+    let metadata = player.get_metadata()?;
+    Ok(Some(Track {
+        title: metadata.title().unwrap_or_default().to_string(),
+        artist: metadata.artists().unwrap_or_default().join(", "),
+        album: metadata.album_name().unwrap_or_default().to_string(),
+    }))
+}
+
+#[cfg(target_os = "windows")]
+fn current_track() -> Result<Option<Track>> {
+    use windows::Media::Control::{
+        GlobalSystemMediaTransportControlsSessionManager as SessionManager,
+        GlobalSystemMediaTransportControlsSessionPlaybackStatus as PlaybackStatus,
+    };
+    let manager = SessionManager::RequestAsync()?.get()?;
+    let Ok(session) = manager.GetCurrentSession() else {
+        return Ok(None);
+    };
+    if session.GetPlaybackInfo()?.PlaybackStatus()? != PlaybackStatus::Playing {
+        return Ok(None);
+    }
+    let properties = session.TryGetMediaPropertiesAsync()?.get()?;
+    Ok(Some(Track {
+        title: properties.Title()?.to_string(),
+        artist: properties.Artist()?.to_string(),
+        album: properties.AlbumTitle()?.to_string(),
+    }))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn current_track() -> Result<Option<Track>> {
+    anyhow::bail!("now playing is only supported on linux and windows")
+}
@@ -0,0 +1,2680 @@
+use crate::{
+    audio::{self, AudioPreset, Ducking, DuckingPair, InputPreset, SceneCue},
+    chat::{self, ChatConfig},
+    features::{Feature, Flags},
+    journal::{self, Journal, JournalEntry, Recovery},
+    layout::Panel,
+    lighting::{self, LightingConfig},
+    lights::{self, KeyLight, LightChange, Lights},
+    metrics::{self, Metrics},
+    mqtt::{self, MqttConfig},
+    now_playing::{self, NowPlayingConfig},
+    osc,
+    pairing::{self, Pairing},
+    raw_request, relay, remote, scripting,
+    session::{self, SessionMessage},
+    source_record::{self, IsoRecorder, RecordMode},
+    state::{Condition, ObsState},
+    tally::{self, TallyConfig},
+    webhooks::{self, Details, Webhook, WebhookEvent},
+};
+use anyhow::{Context, Result};
+use futures_util::{Stream, StreamExt};
+use obws::{
+    common::MediaAction,
+    events::{Event, OutputState},
+    requests::{
+        config::SetProfileParameter,
+        filters,
+        general::CallVendorRequest,
+        inputs::{Create, SetSettings, Volume},
+        scene_items::{Id, SetEnabled},
+        sources::TakeScreenshot,
+    },
+    responses::{inputs::Input, outputs::Output},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::{IpAddr, SocketAddr},
+    path::Path,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread,
+    time::Duration,
+};
+
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+const OFFLINE_QUEUE_CAPACITY: usize = 32;
+// How often stream stats are checked for dropped frames, and the share of
+// frames dropped in that time worth alerting about.
+const STATS_INTERVAL: Duration = Duration::from_secs(30);
+const DROPPED_FRAMES_ALERT: f64 = 5.0;
+// How often the metrics exporter's numbers are refreshed while it runs.
+const METRICS_INTERVAL: Duration = Duration::from_secs(5);
+// Program previews are scaled down to this width before they're sent over.
+const PREVIEW_WIDTH: u32 = 480;
+// How long an instant replay waits for OBS to write the file, and the longest
+// it lets the replay play before going back if OBS never says it ended.
+const REPLAY_SAVE_TIMEOUT: Duration = Duration::from_secs(10);
+const REPLAY_LONGEST: Duration = Duration::from_secs(120);
+// How often a scene cue's fade moves the volumes along.
+const FADE_STEP: Duration = Duration::from_millis(50);
+// Long enough for a capture device to be released before it's reopened.
+const REACTIVATE_PAUSE: Duration = Duration::from_millis(500);
+// How long a gate calibration listens to the mic, and how far above the noise
+// floor it puts the gate's close and open thresholds.
+const CALIBRATE_DURATION: Duration = Duration::from_secs(3);
+const GATE_CLOSE_MARGIN: f64 = 4.0;
+const GATE_OPEN_MARGIN: f64 = 10.0;
+const NOISE_GATE_KIND: &str = "noise_gate_filter";
+
+// Name, kind and settings of each filter in the voice chain, in the order the
+// audio goes through them.
+fn voice_chain() -> [(&'static str, &'static str, serde_json::Value); 4] {
+    [
+        (
+            "Noise Suppression",
+            "noise_suppress_filter_v2",
+            json!({ "method": "rnnoise" }),
+        ),
+        (
+            "Noise Gate",
+            NOISE_GATE_KIND,
+            json!({
+                "open_threshold": -26.0,
+                "close_threshold": -32.0,
+                "attack_time": 25,
+                "hold_time": 200,
+                "release_time": 150,
+            }),
+        ),
+        (
+            "Compressor",
+            "compressor_filter",
+            json!({
+                "ratio": 4.0,
+                "threshold": -18.0,
+                "attack_time": 6,
+                "release_time": 60,
+                "output_gain": 0.0,
+            }),
+        ),
+        (
+            "Limiter",
+            "limiter_filter",
+            json!({ "threshold": -6.0, "release_time": 60 }),
+        ),
+    ]
+}
+
+// Called after every message sent to the UI, so it can wake up and repaint.
+pub type Notifier = Arc<dyn Fn() + Send + Sync>;
+
+// Alternative names for inputs, like "mic", so remote controllers don't have to
+// know the OBS input names.
+pub type Aliases = Arc<RwLock<HashMap<String, String>>>;
+
+// The input's own name followed by every alias pointing at it.
+pub fn names_for_input(aliases: &Aliases, input: &str) -> Vec<String> {
+    let aliases = aliases.read().unwrap();
+    std::iter::once(input.to_string())
+        .chain(
+            aliases
+                .iter()
+                .filter(|(_, target)| target.as_str() == input)
+                .map(|(alias, _)| alias.clone()),
+        )
+        .collect()
+}
+
+pub fn spawn(
+    notify: Notifier,
+) -> (
+    tokio::sync::mpsc::Sender<Action>,
+    tokio::sync::mpsc::Receiver<ObsInfo>,
+) {
+    let (action_tx, action_rx) = tokio::sync::mpsc::channel::<Action>(10);
+    let (obs_info_tx, obs_info_rx) = tokio::sync::mpsc::channel::<ObsInfo>(10);
+    let worker_tx = action_tx.clone();
+    thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build runtime");
+        rt.block_on(async {
+            let mut worker = Worker::new(obs_info_tx, worker_tx, notify);
+            worker.open_journal().await;
+            worker.discover_lights();
+            worker.run(action_rx).await;
+        });
+    });
+    (action_tx, obs_info_rx)
+}
+
+pub struct Worker {
+    obs_client: Option<Client>,
+    events: Option<Pin<Box<dyn Stream<Item = Event>>>>,
+    obs_state: ObsState,
+    obs_info_tx: tokio::sync::mpsc::Sender<ObsInfo>,
+    action_tx: tokio::sync::mpsc::Sender<Action>,
+    notify: Notifier,
+    login: Option<(IpAddr, u16, String)>,
+    // Kept open for the session, for the request batches obws can't send.
+    batches: Option<raw_request::Batches>,
+    queue_offline: bool,
+    offline_queue: VecDeque<Action>,
+    aliases: Aliases,
+    relay: Option<Service>,
+    osc: Option<Service>,
+    mqtt: Option<Service>,
+    remote: Option<Service>,
+    // Paired devices, checked by the relay and the web remote.
+    pairing: pairing::Shared,
+    chat: Option<tokio::task::JoinHandle<()>>,
+    now_playing: Option<tokio::task::JoinHandle<()>>,
+    // Time between preview frames and when the next is due, while the preview
+    // runs.
+    preview: Option<(Duration, tokio::time::Instant)>,
+    // Set while a preview frame is being fetched or decoded, so a slow OBS
+    // drops frames instead of queueing them.
+    preview_busy: Arc<AtomicBool>,
+    // Set while rehearsing: nothing goes live and nothing is announced. The
+    // chat task checks it too.
+    rehearsal: Arc<AtomicBool>,
+    // Shared with the scripts running now; set to stop them all.
+    scripts_cancel: Arc<AtomicBool>,
+    features: Flags,
+    tally: Option<Service>,
+    lighting: Option<Service>,
+    lights: Lights,
+    journal: Option<Journal>,
+    next_macro_id: u64,
+    webhooks: Vec<Webhook>,
+    audio_presets: Vec<AudioPreset>,
+    ducking: Vec<DuckingPair>,
+    // Slide each slideshow was last moved to from here; OBS doesn't report it.
+    slides: HashMap<String, usize>,
+    // Skipped and total frames at the last stats check.
+    frames: Option<(u32, u32)>,
+    metrics: Metrics,
+    metrics_server: Option<(
+        tokio::sync::watch::Sender<Metrics>,
+        tokio::task::JoinHandle<()>,
+    )>,
+    // Session messages going out to every relay client.
+    session_tx: tokio::sync::broadcast::Sender<SessionMessage>,
+    // Who controls which panel, while this instance hosts the session.
+    owners: BTreeMap<Panel, String>,
+    // Set while joined to another instance's session, which then keeps it.
+    peer: Option<(
+        tokio::sync::mpsc::Sender<SessionMessage>,
+        tokio::task::JoinHandle<()>,
+    )>,
+    instant_replay: Option<InstantReplay>,
+}
+
+// An instant replay under way. It moves on as OBS reports the replay saved
+// and played through, or gives up at the deadline.
+struct InstantReplay {
+    media: String,
+    scene: String,
+    // Where to go back to afterwards.
+    previous: Option<String>,
+    playing: bool,
+    deadline: tokio::time::Instant,
+}
+
+// A background task that follows the state, like the relay or OSC servers.
+struct Service {
+    state_tx: tokio::sync::watch::Sender<ObsState>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Worker {
+    pub fn new(
+        obs_info_tx: tokio::sync::mpsc::Sender<ObsInfo>,
+        action_tx: tokio::sync::mpsc::Sender<Action>,
+        notify: Notifier,
+    ) -> Self {
+        Self {
+            obs_client: None,
+            events: None,
+            obs_state: ObsState::default(),
+            obs_info_tx,
+            action_tx,
+            notify,
+            login: None,
+            batches: None,
+            queue_offline: false,
+            offline_queue: VecDeque::new(),
+            aliases: Aliases::default(),
+            relay: None,
+            osc: None,
+            mqtt: None,
+            remote: None,
+            pairing: pairing::Shared::default(),
+            chat: None,
+            now_playing: None,
+            preview: None,
+            preview_busy: Arc::default(),
+            rehearsal: Arc::default(),
+            scripts_cancel: Arc::default(),
+            features: Flags::default(),
+            tally: None,
+            lighting: None,
+            lights: Lights::default(),
+            journal: None,
+            next_macro_id: 0,
+            webhooks: Vec::new(),
+            audio_presets: audio::presets(Vec::new()),
+            ducking: Vec::new(),
+            slides: HashMap::new(),
+            frames: None,
+            metrics: Metrics::default(),
+            metrics_server: None,
+            session_tx: tokio::sync::broadcast::channel(64).0,
+            owners: BTreeMap::new(),
+            peer: None,
+            instant_replay: None,
+        }
+    }
+
+    pub async fn open_journal(&mut self) {
+        match Journal::open() {
+            Ok((journal, recovery)) => {
+                self.journal = Some(journal);
+                if let Some(recovery) = recovery {
+                    tracing::warn!("previous session did not shut down cleanly");
+                    self.send(ObsInfo::Recovery(recovery)).await;
+                }
+            }
+            Err(err) => tracing::error!("journal unavailable: {:#}", err),
+        }
+    }
+
+    pub fn discover_lights(&self) {
+        let obs_info_tx = self.obs_info_tx.clone();
+        let notify = self.notify.clone();
+        let result = lights::discover(self.lights.clone(), move |light| {
+            let info = match light {
+                Ok(light) => ObsInfo::Light(light),
+                Err(err) => ObsInfo::Error(format!("{:#}", err)),
+            };
+            let _ = obs_info_tx.blocking_send(info);
+            notify();
+        });
+        if let Err(err) = result {
+            tracing::warn!("key light discovery unavailable: {:#}", err);
+        }
+    }
+
+    fn set_key_lights(&self, targets: Vec<(String, SocketAddr)>, change: LightChange) {
+        let obs_info_tx = self.obs_info_tx.clone();
+        let notify = self.notify.clone();
+        tokio::task::spawn_blocking(move || {
+            for (name, addr) in targets {
+                let info = match lights::set(addr, &change) {
+                    Ok(state) => ObsInfo::Light(KeyLight { name, ..state }),
+                    Err(err) => ObsInfo::Error(format!("{:#}", err)),
+                };
+                let _ = obs_info_tx.blocking_send(info);
+                notify();
+            }
+        });
+    }
+
+    // Counted here rather than asked for, so a slide moved with OBS's own
+    // hotkeys isn't seen until the next press or restart.
+    async fn move_slide(
+        &mut self,
+        input: &str,
+        command: MediaCommand,
+    ) -> Result<Option<(usize, usize)>> {
+        let Some(obs_client) = &self.obs_client else {
+            return Ok(None);
+        };
+        if matches!(command, MediaCommand::Play | MediaCommand::Pause) {
+            return Ok(None);
+        }
+        let settings = obs_client
+            .inputs()
+            .settings::<serde_json::Value>(input)
+            .await
+            .with_context(|| format!("failed to get settings of {}", input))?
+            .settings;
+        // Only slideshows have a file list; other media has no slides.
+        let Some(count) = settings["files"].as_array().map(Vec::len) else {
+            return Ok(None);
+        };
+        let looping = settings["loop"].as_bool().unwrap_or(true);
+        let last = count.saturating_sub(1);
+        let slide = self.slides.entry(input.to_string()).or_default();
+        *slide = match command {
+            MediaCommand::Next if *slide < last => *slide + 1,
+            MediaCommand::Next if looping => 0,
+            MediaCommand::Previous if *slide > 0 => *slide - 1,
+            MediaCommand::Previous if looping => last,
+            MediaCommand::Restart | MediaCommand::Stop => 0,
+            _ => *slide,
+        };
+        Ok(Some((*slide, count)))
+    }
+
+    pub async fn run(mut self, mut action_rx: tokio::sync::mpsc::Receiver<Action>) {
+        let mut stats = tokio::time::interval(STATS_INTERVAL);
+        let mut metrics = tokio::time::interval(METRICS_INTERVAL);
+        loop {
+            let replay_deadline = self.instant_replay.as_ref().map(|replay| replay.deadline);
+            let preview_deadline = self.preview.map(|(_, next)| next);
+            tokio::select! {
+                action = action_rx.recv() => match action {
+                    Some(action) => {
+                        if let Err(err) = self.handle_action(action).await {
+                            self.report(err).await;
+                        }
+                    }
+                    None => break,
+                },
+                event = self.next_event() => match event {
+                    Some(event) => {
+                        if let Err(err) = self.handle_event(event).await {
+                            self.report(err).await;
+                        }
+                    }
+                    None => self.connection_lost().await,
+                },
+                _ = tokio::time::sleep(RECONNECT_INTERVAL),
+                    if self.obs_client.is_none() && self.login.is_some() =>
+                {
+                    self.reconnect().await;
+                }
+                _ = stats.tick(), if self.obs_client.is_some() && self.obs_state.streaming => {
+                    if let Err(err) = self.check_dropped_frames().await {
+                        self.report(err).await;
+                    }
+                }
+                _ = tokio::time::sleep_until(replay_deadline.unwrap_or_else(tokio::time::Instant::now)),
+                    if replay_deadline.is_some() =>
+                {
+                    if let Err(err) = self.instant_replay_timed_out().await {
+                        self.report(err).await;
+                    }
+                }
+                _ = tokio::time::sleep_until(preview_deadline.unwrap_or_else(tokio::time::Instant::now)),
+                    if preview_deadline.is_some() =>
+                {
+                    if let Err(err) = self.capture_preview().await {
+                        self.report(err).await;
+                    }
+                }
+                _ = metrics.tick(), if self.metrics_server.is_some() => {
+                    if let Err(err) = self.refresh_metrics().await {
+                        tracing::debug!("failed to refresh metrics: {:#}", err);
+                    }
+                }
+            }
+
+            if self.obs_client.is_some() && !self.offline_queue.is_empty() {
+                self.replay().await;
+            }
+        }
+    }
+
+    pub async fn next_event(&mut self) -> Option<Event> {
+        match &mut self.events {
+            Some(events) => events.next().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    pub async fn handle_event(&mut self, event: Event) -> Result<()> {
+        let details = format!("{:?}", event);
+        let kind = details
+            .split([' ', '(', '{'])
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        self.send(ObsInfo::Event(kind, details)).await;
+        let infos = match event {
+            Event::CurrentProgramSceneChanged { name } => vec![ObsInfo::SceneChanged(name)],
+            Event::CurrentPreviewSceneChanged { name } => {
+                vec![ObsInfo::PreviewSceneChanged(Some(name))]
+            }
+            Event::StudioModeStateChanged { enabled: false } => {
+                vec![ObsInfo::PreviewSceneChanged(None)]
+            }
+            Event::SceneListChanged { scenes } => vec![ObsInfo::SceneList(
+                scenes.into_iter().map(|scene| scene.name).collect(),
+            )],
+            Event::InputCreated { name, .. } => {
+                let Some(obs_client) = &self.obs_client else {
+                    return Ok(());
+                };
+                let mut infos = vec![ObsInfo::InputInfo(
+                    obs_client
+                        .inputs()
+                        .list(None)
+                        .await
+                        .context("failed to get input info")?,
+                )];
+                if let Ok(muted) = obs_client.inputs().muted(&name).await {
+                    infos.push(ObsInfo::MuteChanged(name.clone(), muted));
+                }
+                if let Ok(volume) = obs_client.inputs().volume(&name).await {
+                    infos.push(ObsInfo::VolumeChanged(name.clone(), volume.mul * 100.0));
+                }
+                infos.push(ObsInfo::Notice(Notice::InputAdded(name)));
+                infos
+            }
+            Event::InputRemoved { name } => vec![ObsInfo::InputRemoved(name)],
+            Event::InputNameChanged { old_name, new_name } => {
+                vec![ObsInfo::InputRenamed(old_name, new_name)]
+            }
+            Event::SourceFilterEnableStateChanged {
+                source,
+                filter,
+                enabled,
+            } => vec![ObsInfo::FilterChanged(source, filter, enabled)],
+            Event::VendorEvent {
+                vendor_name,
+                event_type,
+                event_data,
+            } => {
+                tracing::debug!(vendor_name, event_type, data = %event_data, "vendor event");
+                vec![ObsInfo::VendorEvent(vendor_name, event_type, event_data)]
+            }
+            Event::InputMuteStateChanged { name, muted } => vec![ObsInfo::MuteChanged(name, muted)],
+            Event::InputVolumeChanged { name, mul, .. } => {
+                vec![ObsInfo::VolumeChanged(name, (mul * 100.0) as f32)]
+            }
+            Event::StreamStateChanged { active, state } => {
+                let mut infos = vec![ObsInfo::StreamState(active)];
+                match state {
+                    OutputState::Started => {
+                        self.frames = None;
+                        self.fire(WebhookEvent::StreamStarted, Details::default());
+                    }
+                    OutputState::Stopped => {
+                        self.fire(WebhookEvent::StreamStopped, Details::default())
+                    }
+                    OutputState::Reconnecting => {
+                        infos.push(ObsInfo::Notice(Notice::StreamReconnecting))
+                    }
+                    OutputState::Reconnected => {
+                        infos.push(ObsInfo::Notice(Notice::StreamReconnected))
+                    }
+                    _ => {}
+                }
+                infos
+            }
+            Event::RecordStateChanged {
+                active,
+                state,
+                path,
+            } => {
+                let path = path.map(|path| Path::new(&path).display().to_string());
+                let mut infos = vec![ObsInfo::RecordState(active)];
+                match state {
+                    OutputState::Started => {
+                        self.fire(
+                            WebhookEvent::RecordingStarted,
+                            Details {
+                                path: path.clone(),
+                                dropped: None,
+                            },
+                        );
+                        infos.push(ObsInfo::Notice(Notice::RecordingStarted(path)))
+                    }
+                    OutputState::Stopped => {
+                        self.fire(
+                            WebhookEvent::RecordingStopped,
+                            Details {
+                                path: path.clone(),
+                                dropped: None,
+                            },
+                        );
+                        infos.push(ObsInfo::Notice(Notice::RecordingStopped(path)))
+                    }
+                    _ => {}
+                }
+                infos
+            }
+            Event::ReplayBufferSaved { path } => {
+                let path = Path::new(&path).display().to_string();
+                self.play_instant_replay(&path).await?;
+                vec![ObsInfo::Notice(Notice::ReplaySaved(path))]
+            }
+            Event::MediaInputPlaybackEnded { name } => {
+                if self
+                    .instant_replay
+                    .as_ref()
+                    .is_some_and(|replay| replay.playing && replay.media == name)
+                {
+                    self.end_instant_replay().await?;
+                }
+                return Ok(());
+            }
+            Event::ExitStarted => {
+                self.connection_lost().await;
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+
+        for info in infos {
+            self.obs_state.apply(&info);
+            self.send(info).await;
+        }
+        Ok(())
+    }
+
+    async fn send(&self, info: ObsInfo) {
+        // Only fails once the UI has shut down, at which point nobody is listening.
+        let _ = self.obs_info_tx.send(info).await;
+        (self.notify)();
+        for service in self
+            .relay
+            .iter()
+            .chain(&self.osc)
+            .chain(&self.mqtt)
+            .chain(&self.remote)
+            .chain(&self.tally)
+            .chain(&self.lighting)
+        {
+            service.state_tx.send_replace(self.obs_state.clone());
+        }
+    }
+
+    fn journal(&mut self, entry: JournalEntry) {
+        if let Some(journal) = &mut self.journal {
+            journal.write(entry);
+        }
+    }
+
+    async fn pending_changed(&mut self) {
+        self.journal(JournalEntry::PendingActions(
+            self.offline_queue.iter().cloned().collect(),
+        ));
+        self.send(ObsInfo::PendingActions(self.offline_queue.len()))
+            .await;
+    }
+
+    fn fire(&self, event: WebhookEvent, details: Details) {
+        if self.rehearsal.load(Ordering::Relaxed) {
+            return;
+        }
+        let hooks: Vec<_> = self
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.events.contains(&event))
+            .cloned()
+            .collect();
+        if hooks.is_empty() {
+            return;
+        }
+        let obs_info_tx = self.obs_info_tx.clone();
+        let notify = self.notify.clone();
+        tokio::task::spawn_blocking(move || {
+            for webhook in hooks {
+                if let Err(err) = webhooks::post(&webhook, event, &details) {
+                    tracing::warn!("{:#}", err);
+                    let _ = obs_info_tx.blocking_send(ObsInfo::Error(format!("{:#}", err)));
+                    notify();
+                }
+            }
+        });
+    }
+
+    // Compares against the previous check, so an old burst of drops doesn't
+    // keep alerting for the rest of the stream.
+    async fn check_dropped_frames(&mut self) -> Result<()> {
+        let Some(obs_client) = &self.obs_client else {
+            return Ok(());
+        };
+        let status = obs_client
+            .streaming()
+            .status()
+            .await
+            .context("failed to get stream status")?;
+        let current = (status.skipped_frames, status.total_frames);
+        let Some((skipped, total)) = self.frames.replace(current) else {
+            return Ok(());
+        };
+        let total = current.1.saturating_sub(total);
+        if total == 0 {
+            return Ok(());
+        }
+        let dropped = current.0.saturating_sub(skipped) as f64 / total as f64 * 100.0;
+        if dropped >= DROPPED_FRAMES_ALERT {
+            tracing::warn!(dropped, "dropping frames");
+            self.fire(
+                WebhookEvent::DroppedFrames,
+                Details {
+                    path: None,
+                    dropped: Some(dropped),
+                },
+            );
+            self.send(ObsInfo::Notice(Notice::DroppedFrames(dropped)))
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn refresh_metrics(&mut self) -> Result<()> {
+        let Some((metrics_tx, _)) = &self.metrics_server else {
+            return Ok(());
+        };
+        let metrics = &mut self.metrics;
+        metrics.connected = self.obs_client.is_some();
+        metrics.streaming = self.obs_state.streaming;
+        metrics.recording = self.obs_state.recording;
+        metrics.queued_actions = self.offline_queue.len();
+        if let Some(obs_client) = &self.obs_client {
+            let stats = obs_client
+                .general()
+                .stats()
+                .await
+                .context("failed to get stats")?;
+            metrics.cpu_usage = stats.cpu_usage;
+            metrics.memory_usage = stats.memory_usage;
+            metrics.active_fps = stats.active_fps;
+            metrics.render_skipped_frames = stats.render_skipped_frames;
+            metrics.render_total_frames = stats.render_total_frames;
+            metrics.output_skipped_frames = stats.output_skipped_frames;
+            metrics.output_total_frames = stats.output_total_frames;
+            let stream = obs_client
+                .streaming()
+                .status()
+                .await
+                .context("failed to get stream status")?;
+            // A stream's first sample has nothing before it to measure from,
+            // and neither has one after OBS's count went back to zero.
+            metrics.stream_kbps = match stream.bytes.checked_sub(metrics.stream_bytes) {
+                Some(sent) if metrics.stream_bytes > 0 => {
+                    sent as f64 * 8.0 / 1000.0 / METRICS_INTERVAL.as_secs_f64()
+                }
+                _ => 0.0,
+            };
+            metrics.stream_bytes = stream.bytes;
+            metrics.stream_skipped_frames = stream.skipped_frames;
+            metrics.stream_total_frames = stream.total_frames;
+        }
+        metrics_tx.send_replace(metrics.clone());
+        Ok(())
+    }
+
+    pub async fn report(&self, err: anyhow::Error) {
+        tracing::error!("{:#}", err);
+        self.send(ObsInfo::Error(format!("{:#}", err))).await;
+    }
+
+    pub async fn disconnect(&mut self) {
+        tracing::info!("disconnected from obs");
+        self.obs_client = None;
+        self.events = None;
+        self.batches = None;
+        self.instant_replay = None;
+        self.obs_state = ObsState::default();
+        self.login = None;
+        self.offline_queue.clear();
+        self.pending_changed().await;
+        self.send(ObsInfo::Disconnected).await;
+    }
+
+    pub async fn connection_lost(&mut self) {
+        tracing::warn!("lost connection to obs");
+        self.obs_client = None;
+        self.events = None;
+        self.batches = None;
+        self.instant_replay = None;
+        self.send(ObsInfo::ConnectionLost).await;
+    }
+
+    async fn reconnect(&mut self) {
+        let Some((addr, port, pass)) = self.login.clone() else {
+            return;
+        };
+        // Failures are expected while OBS is down, so they are not reported.
+        if self.log_in(addr, port, pass).await.is_ok() {
+            self.metrics.reconnects += 1;
+        }
+    }
+
+    async fn enqueue(&mut self, action: Action) {
+        tracing::debug!("queueing action while offline");
+        self.offline_queue
+            .retain(|queued| !action.supersedes(queued));
+        if self.offline_queue.len() == OFFLINE_QUEUE_CAPACITY {
+            self.offline_queue.pop_front();
+        }
+        self.offline_queue.push_back(action);
+        self.pending_changed().await;
+    }
+
+    async fn replay(&mut self) {
+        while let Some(action) = self.offline_queue.pop_front() {
+            if let Err(err) = self.execute_action(action).await {
+                self.report(err).await;
+            }
+        }
+        self.pending_changed().await;
+    }
+
+    pub async fn handle_action(&mut self, action: Action) -> Result<()> {
+        self.metrics.actions += 1;
+        let Action::RunMacro(obs_macro) = &action else {
+            return self.run_steps(action).await;
+        };
+        let id = self.next_macro_id;
+        self.next_macro_id += 1;
+        self.journal(JournalEntry::MacroStarted(id, obs_macro.clone()));
+        let result = self.run_steps(action).await;
+        self.journal(JournalEntry::MacroFinished(id));
+        result
+    }
+
+    async fn run_steps(&mut self, action: Action) -> Result<()> {
+        let mut pending = vec![MacroStep::Do(action)];
+        while let Some(step) = pending.pop() {
+            match step {
+                MacroStep::Do(Action::RunMacro(obs_macro)) => {
+                    pending.extend(obs_macro.steps.into_iter().rev());
+                }
+                MacroStep::Do(action) if action.batch_request().is_some() => {
+                    let mut actions = vec![action];
+                    while let Some(MacroStep::Do(next)) = pending.last() {
+                        if next.batch_request().is_none() {
+                            break;
+                        }
+                        if let Some(MacroStep::Do(next)) = pending.pop() {
+                            actions.push(next);
+                        }
+                    }
+                    self.execute_batch(actions).await?;
+                }
+                MacroStep::Do(action) => {
+                    self.execute_action(action).await?;
+                }
+                MacroStep::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let branch = if self.obs_state.check(&condition) {
+                        then
+                    } else {
+                        otherwise
+                    };
+                    pending.extend(branch.into_iter().rev());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Steps in a row that are each a single request go to OBS as one batch.
+    // Like running them one by one, the macro stops at the first that fails.
+    async fn execute_batch(&mut self, actions: Vec<Action>) -> Result<()> {
+        let mut admitted = Vec::new();
+        for action in actions {
+            admitted.extend(self.admit(action).await);
+        }
+        if admitted.len() < 2 {
+            for action in admitted {
+                self.run_action(action).await?;
+            }
+            return Ok(());
+        }
+        let requests = admitted.iter().filter_map(Action::batch_request).collect();
+        let results = self.send_batch(requests, true).await?;
+        for (action, result) in admitted.into_iter().zip(results) {
+            result?;
+            match action {
+                Action::SetMute(name, muted) => {
+                    self.obs_state.muted.insert(name, muted);
+                }
+                Action::SetVolume(name, volume) => {
+                    self.obs_state.volumes.insert(name, volume);
+                }
+                Action::SetScene(name) => self.obs_state.current_scene = Some(name),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // Sent over the session's batch connection, logged in the same way obws
+    // is. A connection that has gone stale is replaced and the batch sent
+    // again, which is harmless since every batched request sets or reads a
+    // state.
+    async fn send_batch(
+        &mut self,
+        requests: Vec<raw_request::Request>,
+        halt_on_failure: bool,
+    ) -> Result<Vec<Result<serde_json::Value>>> {
+        if let Some(batches) = &mut self.batches {
+            match batches.send(requests.clone(), halt_on_failure).await {
+                Ok(results) => return Ok(results),
+                Err(err) => tracing::debug!("batch connection failed, reopening: {:#}", err),
+            }
+        }
+        self.batches = None;
+        let Some((addr, port, pass)) = &self.login else {
+            anyhow::bail!("not connected to obs");
+        };
+        let batches = raw_request::Batches::open(*addr, *port, pass).await?;
+        let batches = self.batches.insert(batches);
+        let results = batches.send(requests, halt_on_failure).await;
+        if results.is_err() {
+            self.batches = None;
+        }
+        results
+    }
+
+    async fn execute_action(&mut self, action: Action) -> Result<()> {
+        match self.admit(action).await {
+            Some(action) => self.run_action(action).await,
+            None => Ok(()),
+        }
+    }
+
+    // The action with its aliases resolved, if it may run now. One held back
+    // has already been queued or reported.
+    async fn admit(&mut self, action: Action) -> Option<Action> {
+        let action = self.resolve_aliases(action);
+        if self.obs_client.is_none() && action.is_replayable() {
+            if self.queue_offline {
+                self.enqueue(action).await;
+            }
+            return None;
+        }
+        if self.rehearsal.load(Ordering::Relaxed)
+            && matches!(action, Action::StartStream | Action::StartRecord)
+        {
+            tracing::info!("rehearsing, not going live");
+            self.send(ObsInfo::Notice(Notice::RehearsalBlocked)).await;
+            return None;
+        }
+        // Macros, chat and the relay can all carry these, not only the UI.
+        if let Some(feature) = Feature::of(&action).filter(|f| !self.features.enabled(*f)) {
+            self.send(ObsInfo::Notice(Notice::FeatureDisabled(feature)))
+                .await;
+            return None;
+        }
+        Some(action)
+    }
+
+    async fn run_action(&mut self, action: Action) -> Result<()> {
+        // Recorded only once it has taken effect, so a failed login or bind isn't
+        // offered for resuming.
+        let control = journal::is_control(&action).then(|| action.clone());
+        match action {
+            Action::SetMute(name, val) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .inputs()
+                        .set_muted(&name, val)
+                        .await
+                        .context("failed to mute")?;
+                    self.obs_state.muted.insert(name, val);
+                }
+            }
+            Action::SetVolume(name, value) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let volume = Volume::Mul(value / 100.0);
+                    obs_client
+                        .inputs()
+                        .set_volume(&name, volume)
+                        .await
+                        .with_context(|| format!("failed to set volume for device {}", name))?;
+                    self.obs_state.volumes.insert(name, value);
+                }
+            }
+            Action::SetScene(name) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .scenes()
+                        .set_current_program_scene(&name)
+                        .await
+                        .context("failed to set scene")?;
+                    self.obs_state.current_scene = Some(name);
+                }
+            }
+            Action::SetPreviewScene(scene) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .scenes()
+                        .set_current_preview_scene(&scene)
+                        .await
+                        .with_context(|| format!("failed to preview {}", scene))?;
+                }
+            }
+            Action::SceneCue(cue) => self.run_scene_cue(cue).await?,
+            Action::InstantReplay(media, scene) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let active = obs_client
+                        .replay_buffer()
+                        .status()
+                        .await
+                        .context("failed to check the replay buffer")?;
+                    if !active {
+                        anyhow::bail!("the replay buffer isn't running");
+                    }
+                    obs_client
+                        .replay_buffer()
+                        .save()
+                        .await
+                        .context("failed to save the replay buffer")?;
+                    self.instant_replay = Some(InstantReplay {
+                        media,
+                        scene,
+                        previous: self.obs_state.current_scene.clone(),
+                        playing: false,
+                        deadline: tokio::time::Instant::now() + REPLAY_SAVE_TIMEOUT,
+                    });
+                }
+            }
+            Action::StartStream => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .streaming()
+                        .start()
+                        .await
+                        .context("failed to start stream")?;
+                    self.obs_state.streaming = true;
+                }
+            }
+            Action::StopStream => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .streaming()
+                        .stop()
+                        .await
+                        .context("failed to stop stream")?;
+                    self.obs_state.streaming = false;
+                }
+            }
+            Action::StartRecord => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .recording()
+                        .start()
+                        .await
+                        .context("failed to start recording")?;
+                    self.obs_state.recording = true;
+                }
+            }
+            Action::StopRecord => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .recording()
+                        .stop()
+                        .await
+                        .context("failed to stop recording")?;
+                    self.obs_state.recording = false;
+                }
+            }
+            Action::LogIn(addr, port, pass) => {
+                if let Err(err) = self.log_in(addr, port, pass).await {
+                    self.disconnect().await;
+                    return Err(err);
+                }
+            }
+            Action::LogOut => self.disconnect().await,
+            Action::SetOfflineQueue(enabled) => {
+                self.queue_offline = enabled;
+                if !enabled {
+                    self.offline_queue.clear();
+                    self.pending_changed().await;
+                }
+            }
+            Action::RunScript(source) => {
+                let obs_state = self.obs_state.clone();
+                let script_tx = self.action_tx.clone();
+                let obs_info_tx = self.obs_info_tx.clone();
+                let notify = self.notify.clone();
+                let cancel = self.scripts_cancel.clone();
+                tokio::task::spawn_blocking(move || {
+                    if let Err(err) = scripting::run_script(&source, obs_state, script_tx, cancel) {
+                        tracing::error!("script failed: {}", err);
+                        let _ = obs_info_tx
+                            .blocking_send(ObsInfo::Error(format!("script failed: {}", err)));
+                        notify();
+                    }
+                });
+            }
+            // Scripts started after this get a flag of their own.
+            Action::StopScripts => {
+                std::mem::take(&mut self.scripts_cancel).store(true, Ordering::Relaxed);
+            }
+            Action::StartRelay(port) => {
+                self.stop_relay();
+                let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+                    .await
+                    .with_context(|| format!("failed to start relay on port {}", port))?;
+                let (state_tx, state_rx) = tokio::sync::watch::channel(self.obs_state.clone());
+                let task = tokio::spawn(relay::serve(
+                    listener,
+                    state_rx,
+                    self.action_tx.clone(),
+                    self.session_tx.clone(),
+                    self.pairing.clone(),
+                ));
+                self.relay = Some(Service { state_tx, task });
+                tracing::info!(port, "relay server started");
+                self.send(ObsInfo::RelayRunning(true)).await;
+            }
+            Action::StopRelay => {
+                self.stop_relay();
+                self.owners.clear();
+                self.send(ObsInfo::RelayRunning(false)).await;
+            }
+            Action::Session(message) => match &self.peer {
+                Some((peer_tx, _)) => {
+                    // The session ended under us; carry on as host.
+                    if let Err(err) = peer_tx.send(message).await {
+                        self.peer = None;
+                        self.host_session(err.0).await;
+                    }
+                }
+                None => self.host_session(message).await,
+            },
+            Action::JoinSession(host, operator) => {
+                self.leave_session();
+                let (peer_tx, peer_rx) = tokio::sync::mpsc::channel(32);
+                let task = tokio::spawn(session::join(
+                    host,
+                    operator,
+                    peer_rx,
+                    self.obs_info_tx.clone(),
+                    self.notify.clone(),
+                ));
+                self.peer = Some((peer_tx, task));
+            }
+            Action::LeaveSession => {
+                self.leave_session();
+                self.send(ObsInfo::SessionJoined(false)).await;
+            }
+            Action::StartOsc(port, feedback) => {
+                self.stop_osc();
+                let socket = tokio::net::UdpSocket::bind(("0.0.0.0", port))
+                    .await
+                    .with_context(|| format!("failed to start osc on port {}", port))?;
+                let (state_tx, state_rx) = tokio::sync::watch::channel(self.obs_state.clone());
+                let task = tokio::spawn(osc::serve(
+                    socket,
+                    feedback,
+                    state_rx,
+                    self.aliases.clone(),
+                    self.action_tx.clone(),
+                ));
+                self.osc = Some(Service { state_tx, task });
+                tracing::info!(port, "osc listener started");
+                self.send(ObsInfo::OscRunning(true)).await;
+            }
+            Action::StopOsc => {
+                self.stop_osc();
+                self.send(ObsInfo::OscRunning(false)).await;
+            }
+            Action::StartMqtt(config) => {
+                self.stop_mqtt();
+                let (state_tx, state_rx) = tokio::sync::watch::channel(self.obs_state.clone());
+                let task = tokio::spawn(mqtt::serve(
+                    config,
+                    state_rx,
+                    self.aliases.clone(),
+                    self.action_tx.clone(),
+                ));
+                self.mqtt = Some(Service { state_tx, task });
+                tracing::info!("mqtt bridge started");
+                self.send(ObsInfo::MqttRunning(true)).await;
+            }
+            Action::StopMqtt => {
+                self.stop_mqtt();
+                self.send(ObsInfo::MqttRunning(false)).await;
+            }
+            Action::StartRemote(port) => {
+                self.stop_remote();
+                let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+                    .await
+                    .with_context(|| format!("failed to start the web remote on port {}", port))?;
+                let (state_tx, state_rx) = tokio::sync::watch::channel(self.obs_state.clone());
+                let task = tokio::spawn(remote::serve(
+                    listener,
+                    state_rx,
+                    self.aliases.clone(),
+                    self.action_tx.clone(),
+                    self.pairing.clone(),
+                ));
+                self.remote = Some(Service { state_tx, task });
+                tracing::info!(port, "web remote started");
+                self.send(ObsInfo::RemoteRunning(true)).await;
+            }
+            Action::StopRemote => {
+                self.stop_remote();
+                self.send(ObsInfo::RemoteRunning(false)).await;
+            }
+            Action::SetPairing(pairing) => *self.pairing.write().unwrap() = pairing,
+            Action::ForgetRecovery => {
+                if let Some(journal) = &mut self.journal {
+                    journal.forget_previous();
+                }
+            }
+            Action::StartChat(config) => {
+                self.stop_chat();
+                self.chat = Some(tokio::spawn(chat::serve(
+                    config,
+                    self.action_tx.clone(),
+                    self.rehearsal.clone(),
+                )));
+            }
+            Action::StopChat => self.stop_chat(),
+            Action::Intermission(started) => {
+                let event = if started {
+                    WebhookEvent::IntermissionStarted
+                } else {
+                    WebhookEvent::IntermissionEnded
+                };
+                self.fire(event, Details::default());
+            }
+            Action::SetFeatures(features) => {
+                if !features.enabled(Feature::Relay) && self.relay.is_some() {
+                    self.stop_relay();
+                    self.owners.clear();
+                    self.send(ObsInfo::RelayRunning(false)).await;
+                }
+                self.features = features;
+            }
+            Action::SetRehearsal(rehearsal) => {
+                self.rehearsal.store(rehearsal, Ordering::Relaxed);
+                tracing::info!(rehearsal, "rehearsal mode changed");
+                self.send(ObsInfo::Rehearsal(rehearsal)).await;
+            }
+            Action::StartNowPlaying(config) => {
+                self.stop_now_playing();
+                self.now_playing = Some(tokio::spawn(now_playing::serve(
+                    config,
+                    self.action_tx.clone(),
+                )));
+            }
+            Action::StopNowPlaying => self.stop_now_playing(),
+            Action::StartPreview(fps) => {
+                let period = Duration::from_secs(1) / fps.clamp(1, 10);
+                self.preview = Some((period, tokio::time::Instant::now()));
+            }
+            Action::StopPreview => self.preview = None,
+            Action::SetText(input, text) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .inputs()
+                        .set_settings(SetSettings {
+                            input: &input,
+                            settings: &serde_json::json!({ "text": text }),
+                            overlay: Some(true),
+                        })
+                        .await
+                        .with_context(|| format!("failed to set text of {}", input))?;
+                }
+            }
+            // HTTP to the lights is blocking and can be slow, so it runs off
+            // the worker.
+            Action::SetKeyLight(name, change) => {
+                let addr = self.lights.read().unwrap().get(&name).copied();
+                let addr = addr.with_context(|| format!("no key light named {}", name))?;
+                self.set_key_lights(vec![(name, addr)], change);
+            }
+            Action::SetKeyLights(change) => {
+                let targets = self
+                    .lights
+                    .read()
+                    .unwrap()
+                    .iter()
+                    .map(|(name, addr)| (name.clone(), *addr))
+                    .collect();
+                self.set_key_lights(targets, change);
+            }
+            Action::SetInputSettings(input, settings) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .inputs()
+                        .set_settings(SetSettings {
+                            input: &input,
+                            settings: &settings,
+                            overlay: Some(true),
+                        })
+                        .await
+                        .with_context(|| format!("failed to change settings of {}", input))?;
+                }
+            }
+            Action::PressInputButton(input, button) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .inputs()
+                        .press_properties_button(&input, &button)
+                        .await
+                        .with_context(|| format!("failed to press {} on {}", button, input))?;
+                }
+            }
+            // Pointing the input at no device and back makes OBS reopen it,
+            // which brings back a webcam stuck on a black frame.
+            Action::ReactivateInput(input, property) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let settings = obs_client
+                        .inputs()
+                        .settings::<serde_json::Value>(&input)
+                        .await
+                        .with_context(|| format!("failed to get settings of {}", input))?
+                        .settings;
+                    let device = settings[&property].clone();
+                    for (value, pause) in [(serde_json::json!(""), true), (device, false)] {
+                        let mut change = serde_json::Map::new();
+                        change.insert(property.clone(), value);
+                        obs_client
+                            .inputs()
+                            .set_settings(SetSettings {
+                                input: &input,
+                                settings: &serde_json::Value::Object(change),
+                                overlay: Some(true),
+                            })
+                            .await
+                            .with_context(|| format!("failed to reactivate {}", input))?;
+                        if pause {
+                            tokio::time::sleep(REACTIVATE_PAUSE).await;
+                        }
+                    }
+                }
+            }
+            Action::SetFilterEnabled(source, filter, enabled) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .filters()
+                        .set_enabled(filters::SetEnabled {
+                            source: &source,
+                            filter: &filter,
+                            enabled,
+                        })
+                        .await
+                        .with_context(|| format!("failed to switch {} on {}", filter, source))?;
+                }
+            }
+            Action::ToggleFilter(source, filter) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let enabled = obs_client
+                        .filters()
+                        .get(&source, &filter)
+                        .await
+                        .with_context(|| format!("no filter {} on {}", filter, source))?
+                        .enabled;
+                    obs_client
+                        .filters()
+                        .set_enabled(filters::SetEnabled {
+                            source: &source,
+                            filter: &filter,
+                            enabled: !enabled,
+                        })
+                        .await
+                        .with_context(|| format!("failed to switch {} on {}", filter, source))?;
+                }
+            }
+            Action::SetProfileParameter(category, name, value) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .config()
+                        .set_profile_parameter(SetProfileParameter {
+                            category: &category,
+                            name: &name,
+                            value: value.as_deref(),
+                        })
+                        .await
+                        .with_context(|| format!("failed to set {}/{}", category, name))?;
+                }
+            }
+            Action::VendorRequest(vendor, request_type, data) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let response = obs_client
+                        .general()
+                        .call_vendor_request::<_, serde_json::Value>(CallVendorRequest {
+                            vendor_name: &vendor,
+                            request_type: &request_type,
+                            request_data: &data,
+                        })
+                        .await
+                        .with_context(|| format!("{} request {} failed", vendor, request_type))?;
+                    tracing::info!(
+                        vendor,
+                        request_type,
+                        response = %response.response_data,
+                        "vendor request done"
+                    );
+                }
+            }
+            Action::TriggerHotkey(name) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .hotkeys()
+                        .trigger_by_name(&name)
+                        .await
+                        .with_context(|| format!("failed to trigger hotkey {}", name))?;
+                }
+            }
+            Action::CreateInput(scene, input, kind) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .inputs()
+                        .create(Create::<()> {
+                            scene: &scene,
+                            input: &input,
+                            kind: &kind,
+                            settings: None,
+                            enabled: Some(true),
+                        })
+                        .await
+                        .with_context(|| format!("failed to create {}", input))?;
+                }
+            }
+            // Listens on a connection of its own, since the main one isn't
+            // subscribed to meters, and comes back with the thresholds.
+            Action::CalibrateGate(input) => {
+                let Some((addr, port, pass)) = self.login.clone() else {
+                    anyhow::bail!("not connected");
+                };
+                let action_tx = self.action_tx.clone();
+                let obs_info_tx = self.obs_info_tx.clone();
+                let notify = self.notify.clone();
+                tokio::spawn(async move {
+                    let result =
+                        raw_request::meter_levels(addr, port, &pass, &input, CALIBRATE_DURATION)
+                            .await
+                            .and_then(|levels| {
+                                noise_floor(levels)
+                                    .with_context(|| format!("no meter readings from {}", input))
+                            });
+                    match result {
+                        Ok(floor) => {
+                            let _ = action_tx
+                                .send(Action::SetGateThresholds(
+                                    input,
+                                    (floor + GATE_OPEN_MARGIN).min(0.0),
+                                    (floor + GATE_CLOSE_MARGIN).min(0.0),
+                                ))
+                                .await;
+                        }
+                        Err(err) => {
+                            let _ = obs_info_tx
+                                .send(ObsInfo::Error(format!(
+                                    "{:#}",
+                                    err.context("calibration failed")
+                                )))
+                                .await;
+                            notify();
+                        }
+                    }
+                });
+            }
+            Action::SetGateThresholds(input, open, close) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let gate = obs_client
+                        .filters()
+                        .list(&input)
+                        .await
+                        .with_context(|| format!("failed to list filters of {}", input))?
+                        .into_iter()
+                        .find(|filter| filter.kind == NOISE_GATE_KIND);
+                    let settings = serde_json::json!({
+                        "open_threshold": open,
+                        "close_threshold": close,
+                    });
+                    match gate {
+                        Some(gate) => obs_client
+                            .filters()
+                            .set_settings(filters::SetSettings {
+                                source: &input,
+                                filter: &gate.name,
+                                settings: &settings,
+                                overlay: Some(true),
+                            })
+                            .await
+                            .with_context(|| format!("failed to set the gate on {}", input))?,
+                        None => obs_client
+                            .filters()
+                            .create(filters::Create {
+                                source: &input,
+                                filter: "Noise Gate",
+                                kind: NOISE_GATE_KIND,
+                                settings: Some(&settings),
+                            })
+                            .await
+                            .with_context(|| format!("failed to add a gate to {}", input))?,
+                    }
+                    self.send(ObsInfo::Notice(Notice::GateCalibrated(input, open, close)))
+                        .await;
+                }
+            }
+            // Filters the input already has are left as they are, so running
+            // this twice, or on a tuned mic, doesn't stack a second chain.
+            Action::AddVoiceChain(input) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let existing: Vec<String> = obs_client
+                        .filters()
+                        .list(&input)
+                        .await
+                        .with_context(|| format!("failed to list filters of {}", input))?
+                        .into_iter()
+                        .map(|filter| filter.kind)
+                        .collect();
+                    let mut added = 0;
+                    for (name, kind, settings) in voice_chain() {
+                        if existing.iter().any(|existing| existing == kind) {
+                            continue;
+                        }
+                        obs_client
+                            .filters()
+                            .create(filters::Create {
+                                source: &input,
+                                filter: name,
+                                kind,
+                                settings: Some(&settings),
+                            })
+                            .await
+                            .with_context(|| format!("failed to add {} to {}", name, input))?;
+                        added += 1;
+                    }
+                    self.send(ObsInfo::Notice(Notice::VoiceChainAdded(input, added)))
+                        .await;
+                }
+            }
+            Action::Media(input, command) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .media_inputs()
+                        .trigger_action(&input, command.into())
+                        .await
+                        .with_context(|| format!("failed to control {}", input))?;
+                }
+                if let Some(slide) = self.move_slide(&input, command).await? {
+                    self.send(ObsInfo::Slide(input, slide)).await;
+                }
+            }
+            Action::ListIsoRecorders => {
+                if let Some(obs_client) = &self.obs_client {
+                    let inputs = obs_client
+                        .inputs()
+                        .list(None)
+                        .await
+                        .context("failed to list inputs")?;
+                    let sources: Vec<String> = inputs
+                        .into_iter()
+                        .map(|input| input.name)
+                        .chain(self.obs_state.scenes.iter().cloned())
+                        .collect();
+                    let filters = futures_util::future::join_all(
+                        sources
+                            .iter()
+                            .map(|source| obs_client.filters().list(source)),
+                    )
+                    .await;
+                    // Sources whose filters can't be listed just don't count.
+                    let recorders = sources
+                        .into_iter()
+                        .zip(filters)
+                        .filter_map(|(source, filters)| Some((source, filters.ok()?)))
+                        .flat_map(|(source, filters)| {
+                            filters
+                                .into_iter()
+                                .filter(|filter| filter.kind == source_record::FILTER_KIND)
+                                .map(move |filter| IsoRecorder {
+                                    source: source.clone(),
+                                    mode: RecordMode::from_settings(&filter.settings),
+                                    filter: filter.name,
+                                    enabled: filter.enabled,
+                                })
+                        })
+                        .collect();
+                    self.send(ObsInfo::IsoRecorders(recorders)).await;
+                }
+            }
+            Action::OpenInteract(input) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .ui()
+                        .open_input_interact_dialog(&input)
+                        .await
+                        .context("failed to open interact window")?;
+                }
+            }
+            Action::SetSceneItemEnabled(scene, source, enabled) => {
+                if let Some(obs_client) = &self.obs_client {
+                    let item_id = obs_client
+                        .scene_items()
+                        .id(Id {
+                            scene: &scene,
+                            source: &source,
+                            search_offset: None,
+                        })
+                        .await
+                        .with_context(|| format!("no source {} in scene {}", source, scene))?;
+                    obs_client
+                        .scene_items()
+                        .set_enabled(SetEnabled {
+                            scene: &scene,
+                            item_id,
+                            enabled,
+                        })
+                        .await
+                        .context("failed to show or hide source")?;
+                    self.send(ObsInfo::SceneItemChanged(scene, source, enabled))
+                        .await;
+                }
+            }
+            Action::StartTally(config) => {
+                self.stop_tally();
+                let sockets = tally::Sockets::bind(&config).await?;
+                let (state_tx, state_rx) = tokio::sync::watch::channel(self.obs_state.clone());
+                let task = tokio::spawn(tally::serve(config, sockets, state_rx));
+                self.tally = Some(Service { state_tx, task });
+                tracing::info!("tally output started");
+            }
+            Action::StopTally => self.stop_tally(),
+            Action::StartMetrics(port) => {
+                self.stop_metrics();
+                let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+                    .await
+                    .with_context(|| format!("failed to start metrics on port {}", port))?;
+                let (metrics_tx, metrics_rx) = tokio::sync::watch::channel(self.metrics.clone());
+                let task = tokio::spawn(metrics::serve(listener, metrics_rx));
+                self.metrics_server = Some((metrics_tx, task));
+                tracing::info!(port, "metrics exporter started");
+            }
+            Action::StopMetrics => self.stop_metrics(),
+            Action::SetWebhooks(webhooks) => self.webhooks = webhooks,
+            Action::SetAudioPresets(presets) => self.audio_presets = presets,
+            Action::ApplyAudioPreset(name) => {
+                let preset = self
+                    .audio_presets
+                    .iter()
+                    .find(|preset| preset.name == name)
+                    .cloned()
+                    .with_context(|| format!("no audio preset {}", name))?;
+                self.apply_audio_preset(preset).await?;
+            }
+            Action::SetDuckingPairs(pairs) => self.ducking = pairs,
+            Action::SetDucking(pair) => {
+                self.set_ducking(&pair.ducked, Some((pair.trigger.clone(), pair.ducking)))
+                    .await?;
+                self.ducking.retain(|other| other.ducked != pair.ducked);
+                self.ducking.push(pair);
+            }
+            Action::StopDucking(ducked) => {
+                self.set_ducking(&ducked, None).await?;
+                self.ducking.retain(|pair| pair.ducked != ducked);
+            }
+            Action::SetLighting(config) => {
+                if let Some(lighting) = self.lighting.take() {
+                    lighting.task.abort();
+                }
+                if !config.mappings.is_empty() {
+                    let (state_tx, state_rx) = tokio::sync::watch::channel(self.obs_state.clone());
+                    let task = tokio::spawn(lighting::serve(config, state_rx));
+                    self.lighting = Some(Service { state_tx, task });
+                }
+            }
+            Action::PairHue(bridge) => {
+                let obs_info_tx = self.obs_info_tx.clone();
+                let notify = self.notify.clone();
+                tokio::task::spawn_blocking(move || {
+                    let info = match lighting::pair_hue(&bridge) {
+                        Ok(username) => ObsInfo::HuePaired(username),
+                        Err(err) => ObsInfo::Error(format!("{:#}", err)),
+                    };
+                    let _ = obs_info_tx.blocking_send(info);
+                    notify();
+                });
+            }
+            Action::SetAlias(alias, input) => {
+                let mut aliases = self.aliases.write().unwrap();
+                match input {
+                    Some(input) => aliases.insert(alias, input),
+                    None => aliases.remove(&alias),
+                };
+            }
+            Action::SetStreamService(server, key) => {
+                if let Some(obs_client) = &self.obs_client {
+                    obs_client
+                        .config()
+                        .set_stream_service_settings(
+                            "rtmp_custom",
+                            &serde_json::json!({ "server": server, "key": key }),
+                        )
+                        .await
+                        .context("failed to set stream service")?;
+                }
+            }
+            Action::AddMarker(description) => {
+                let mut recording = None;
+                if let Some(obs_client) = &self.obs_client {
+                    let status = obs_client
+                        .recording()
+                        .status()
+                        .await
+                        .context("failed to get record status")?;
+                    recording = status.active.then(|| {
+                        Duration::from_secs(status.duration.whole_seconds().max(0) as u64)
+                    });
+                }
+                tracing::info!(description, ?recording, "marker added");
+                self.send(ObsInfo::Marker(description, recording)).await;
+            }
+            Action::Query(id, query) => {
+                let response = self.query(query).await.map_err(|err| format!("{:#}", err));
+                self.send(ObsInfo::Response(id, response)).await;
+            }
+            Action::RunMacro(_) => unreachable!("macros are expanded by handle_action"),
+        }
+        if let Some(action) = control {
+            self.journal(JournalEntry::Control(action));
+        }
+        Ok(())
+    }
+
+    fn leave_session(&mut self) {
+        if let Some((_, task)) = self.peer.take() {
+            task.abort();
+        }
+    }
+
+    // Claims and releases change the owners everyone is sent; anything else
+    // is passed on as it came.
+    async fn host_session(&mut self, message: SessionMessage) {
+        let message = match message {
+            SessionMessage::Claim { panel, operator } => {
+                self.owners.insert(panel, operator);
+                None
+            }
+            SessionMessage::Release { panel, operator } => {
+                if self.owners.get(&panel) == Some(&operator) {
+                    self.owners.remove(&panel);
+                }
+                None
+            }
+            SessionMessage::Hello { operator } => {
+                tracing::info!(operator, "operator joined the session");
+                None
+            }
+            SessionMessage::Owners { .. } => return,
+            message => Some(message),
+        }
+        .unwrap_or_else(|| SessionMessage::Owners {
+            owners: self.owners.clone(),
+        });
+        // Fails only while nobody is connected to the relay.
+        let _ = self.session_tx.send(message.clone());
+        self.send(ObsInfo::Peer(message)).await;
+    }
+
+    fn stop_relay(&mut self) {
+        if let Some(relay) = self.relay.take() {
+            relay.task.abort();
+            tracing::info!("relay server stopped");
+        }
+    }
+
+    fn stop_osc(&mut self) {
+        if let Some(osc) = self.osc.take() {
+            osc.task.abort();
+            tracing::info!("osc listener stopped");
+        }
+    }
+
+    fn stop_mqtt(&mut self) {
+        if let Some(mqtt) = self.mqtt.take() {
+            mqtt.task.abort();
+            tracing::info!("mqtt bridge stopped");
+        }
+    }
+
+    fn stop_remote(&mut self) {
+        if let Some(remote) = self.remote.take() {
+            remote.task.abort();
+            tracing::info!("web remote stopped");
+        }
+    }
+
+    fn stop_metrics(&mut self) {
+        if let Some((_, task)) = self.metrics_server.take() {
+            task.abort();
+            tracing::info!("metrics exporter stopped");
+        }
+    }
+
+    fn stop_tally(&mut self) {
+        if let Some(tally) = self.tally.take() {
+            tally.task.abort();
+            tracing::info!("tally output stopped");
+        }
+    }
+
+    fn stop_chat(&mut self) {
+        if let Some(chat) = self.chat.take() {
+            chat.abort();
+            tracing::info!("twitch chat commands stopped");
+        }
+    }
+
+    // Takes the next preview frame. Fetched here but decoded off the worker,
+    // and skipped while the one before is still on its way.
+    async fn capture_preview(&mut self) -> Result<()> {
+        if let Some((period, next)) = &mut self.preview {
+            *next = tokio::time::Instant::now() + *period;
+        }
+        let (Some(obs_client), Some(scene)) = (&self.obs_client, &self.obs_state.current_scene)
+        else {
+            return Ok(());
+        };
+        if self.preview_busy.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let screenshot = obs_client
+            .sources()
+            .take_screenshot(TakeScreenshot {
+                source: scene,
+                format: "jpg",
+                width: Some(PREVIEW_WIDTH),
+                height: None,
+                compression_quality: Some(70),
+            })
+            .await;
+        let screenshot = match screenshot {
+            Ok(screenshot) => screenshot,
+            Err(err) => {
+                self.preview_busy.store(false, Ordering::Release);
+                return Err(err).context("failed to take screenshot");
+            }
+        };
+        let obs_info_tx = self.obs_info_tx.clone();
+        let notify = self.notify.clone();
+        let busy = self.preview_busy.clone();
+        tokio::task::spawn_blocking(move || {
+            match decode_screenshot(&screenshot) {
+                Ok(frame) => {
+                    let _ = obs_info_tx.blocking_send(ObsInfo::Preview(frame));
+                    notify();
+                }
+                Err(err) => tracing::debug!("bad preview frame: {:#}", err),
+            }
+            busy.store(false, Ordering::Release);
+        });
+        Ok(())
+    }
+
+    fn stop_now_playing(&mut self) {
+        if let Some(now_playing) = self.now_playing.take() {
+            now_playing.abort();
+            tracing::info!("now playing updates stopped");
+        }
+    }
+
+    fn resolve_aliases(&self, action: Action) -> Action {
+        let aliases = self.aliases.read().unwrap();
+        let resolve = |name: String| aliases.get(&name).cloned().unwrap_or(name);
+        match action {
+            Action::SetMute(name, muted) => Action::SetMute(resolve(name), muted),
+            Action::SetVolume(name, volume) => Action::SetVolume(resolve(name), volume),
+            Action::SetFilterEnabled(name, filter, enabled) => {
+                Action::SetFilterEnabled(resolve(name), filter, enabled)
+            }
+            Action::ToggleFilter(name, filter) => Action::ToggleFilter(resolve(name), filter),
+            Action::CalibrateGate(name) => Action::CalibrateGate(resolve(name)),
+            Action::AddVoiceChain(name) => Action::AddVoiceChain(resolve(name)),
+            Action::SetGateThresholds(name, open, close) => {
+                Action::SetGateThresholds(resolve(name), open, close)
+            }
+            action => action,
+        }
+    }
+
+    // Points the media source at the file just saved and cuts to it.
+    async fn play_instant_replay(&mut self, path: &str) -> Result<()> {
+        let (Some(obs_client), Some(replay)) = (&self.obs_client, &mut self.instant_replay) else {
+            return Ok(());
+        };
+        if replay.playing {
+            return Ok(());
+        }
+        let settings = serde_json::json!({ "is_local_file": true, "local_file": path });
+        obs_client
+            .inputs()
+            .set_settings(SetSettings {
+                input: &replay.media,
+                settings: &settings,
+                overlay: Some(true),
+            })
+            .await
+            .with_context(|| format!("failed to load the replay into {}", replay.media))?;
+        obs_client
+            .scenes()
+            .set_current_program_scene(&replay.scene)
+            .await
+            .with_context(|| format!("failed to switch to {}", replay.scene))?;
+        obs_client
+            .media_inputs()
+            .trigger_action(&replay.media, MediaAction::Restart)
+            .await
+            .with_context(|| format!("failed to play {}", replay.media))?;
+        tracing::info!(path, "playing instant replay");
+        replay.playing = true;
+        replay.deadline = tokio::time::Instant::now() + REPLAY_LONGEST;
+        Ok(())
+    }
+
+    // Goes back to where the show was, unless the operator has already moved
+    // on from the replay scene.
+    async fn end_instant_replay(&mut self) -> Result<()> {
+        let (Some(obs_client), Some(replay)) = (&self.obs_client, self.instant_replay.take())
+        else {
+            return Ok(());
+        };
+        let current = obs_client
+            .scenes()
+            .current_program_scene()
+            .await
+            .context("failed to get current scene")?;
+        if let (Some(previous), true) = (replay.previous, current == replay.scene) {
+            obs_client
+                .scenes()
+                .set_current_program_scene(&previous)
+                .await
+                .with_context(|| format!("failed to switch back to {}", previous))?;
+            self.obs_state.current_scene = Some(previous);
+        }
+        Ok(())
+    }
+
+    async fn instant_replay_timed_out(&mut self) -> Result<()> {
+        match &self.instant_replay {
+            Some(replay) if replay.playing => self.end_instant_replay().await,
+            _ => {
+                self.instant_replay = None;
+                anyhow::bail!("the replay wasn't saved in time")
+            }
+        }
+    }
+
+    // Runs in one go, so nothing else lands halfway through the fade; the
+    // volumes move in dB, the way a fader does. Each step is one batch, so
+    // every fader moves at the same moment, and the scene cuts together with
+    // the first.
+    async fn run_scene_cue(&mut self, cue: SceneCue) -> Result<()> {
+        if self.obs_client.is_none() {
+            return Ok(());
+        }
+        let levels: Vec<_> = cue
+            .audio
+            .into_iter()
+            .map(|(input, level)| {
+                let input = self
+                    .aliases
+                    .read()
+                    .unwrap()
+                    .get(&input)
+                    .cloned()
+                    .unwrap_or(input);
+                (input, level)
+            })
+            .collect();
+        let volumes = self
+            .send_batch(
+                levels
+                    .iter()
+                    .map(|(input, _)| ("GetInputVolume", json!({ "inputName": input })))
+                    .collect(),
+                false,
+            )
+            .await?;
+        let mut fades = Vec::new();
+        for ((input, level), volume) in levels.into_iter().zip(volumes) {
+            let from = volume
+                .with_context(|| format!("failed to get volume for device {}", input))?
+                ["inputVolumeDb"]
+                .as_f64()
+                .map_or(audio::MIN_DB, |db| db as f32)
+                .max(audio::MIN_DB);
+            fades.push((input, from, level.target(from)));
+        }
+        let steps = (cue.fade_ms / FADE_STEP.as_millis() as u64).max(1);
+        let mut interval = tokio::time::interval(FADE_STEP);
+        interval.tick().await;
+        for step in 1..=steps {
+            let mut requests = Vec::new();
+            if let Some(scene) = cue.scene.as_ref().filter(|_| step == 1) {
+                requests.push(("SetCurrentProgramScene", json!({ "sceneName": scene })));
+            }
+            // The last step lands right on the target.
+            let progress = step as f32 / steps as f32;
+            requests.extend(fades.iter().map(|(input, from, to)| {
+                (
+                    "SetInputVolume",
+                    json!({ "inputName": input, "inputVolumeDb": from + (to - from) * progress }),
+                )
+            }));
+            for result in self.send_batch(requests, true).await? {
+                result.context("failed to run the scene cue")?;
+            }
+            if step == 1 && cue.scene.is_some() {
+                self.obs_state.current_scene.clone_from(&cue.scene);
+            }
+            if step < steps {
+                interval.tick().await;
+            }
+        }
+        for (input, _, to) in fades {
+            let volume = 10f32.powf(to / 20.0) * 100.0;
+            self.obs_state.volumes.insert(input, volume);
+        }
+        Ok(())
+    }
+
+    async fn apply_audio_preset(&mut self, preset: AudioPreset) -> Result<()> {
+        if self.obs_client.is_none() {
+            return Ok(());
+        }
+        let aliases = self.aliases.clone();
+        let resolve = |name: &str| aliases.read().unwrap().get(name).cloned();
+        // Every input's settings in one batch, so the mix changes all at once.
+        let mut requests = Vec::new();
+        for (input, settings) in &preset.inputs {
+            let input = resolve(input).unwrap_or_else(|| input.clone());
+            if let Some(db) = settings.volume_db {
+                requests.push((
+                    "SetInputVolume",
+                    json!({ "inputName": input, "inputVolumeDb": db }),
+                ));
+            }
+            if let Some(monitor) = settings.monitor {
+                requests.push((
+                    "SetInputAudioMonitorType",
+                    json!({ "inputName": input, "monitorType": monitor.obs_name() }),
+                ));
+            }
+            if let Some(tracks) = &settings.tracks {
+                let tracks = InputPreset::track_map(tracks);
+                requests.push((
+                    "SetInputAudioTracks",
+                    json!({ "inputName": input, "inputAudioTracks": tracks }),
+                ));
+            }
+        }
+        if !requests.is_empty() {
+            for result in self.send_batch(requests, true).await? {
+                result.context("failed to apply the audio preset")?;
+            }
+        }
+
+        let (Some(mic), Some(desktop)) = (resolve("mic"), resolve("desktop")) else {
+            if preset.ducking {
+                anyhow::bail!("ducking needs both a mic and a desktop input chosen");
+            }
+            return Ok(());
+        };
+        // Tuned in the mixer if this pair has been set up there before.
+        let ducking = preset.ducking.then(|| {
+            let ducking = self
+                .ducking
+                .iter()
+                .find(|pair| pair.ducked == desktop && pair.trigger == mic)
+                .map_or_else(Ducking::default, |pair| pair.ducking);
+            (mic, ducking)
+        });
+        self.set_ducking(&desktop, ducking).await
+    }
+
+    // Adds, updates or (given none) removes the ducking compressor on an input.
+    async fn set_ducking(&self, ducked: &str, ducking: Option<(String, Ducking)>) -> Result<()> {
+        let Some(obs_client) = &self.obs_client else {
+            return Ok(());
+        };
+        let exists = obs_client
+            .filters()
+            .list(ducked)
+            .await
+            .with_context(|| format!("failed to list filters of {}", ducked))?
+            .into_iter()
+            .any(|filter| filter.name == audio::DUCKING_FILTER);
+        match (ducking, exists) {
+            (Some((trigger, ducking)), true) => obs_client
+                .filters()
+                .set_settings(filters::SetSettings {
+                    source: ducked,
+                    filter: audio::DUCKING_FILTER,
+                    settings: &ducking.settings(&trigger),
+                    overlay: Some(true),
+                })
+                .await
+                .with_context(|| format!("failed to set ducking on {}", ducked))?,
+            (Some((trigger, ducking)), false) => obs_client
+                .filters()
+                .create(filters::Create {
+                    source: ducked,
+                    filter: audio::DUCKING_FILTER,
+                    kind: "compressor_filter",
+                    settings: Some(&ducking.settings(&trigger)),
+                })
+                .await
+                .with_context(|| format!("failed to add ducking to {}", ducked))?,
+            (None, true) => obs_client
+                .filters()
+                .remove(ducked, audio::DUCKING_FILTER)
+                .await
+                .with_context(|| format!("failed to remove ducking from {}", ducked))?,
+            (None, false) => {}
+        }
+        Ok(())
+    }
+
+    async fn query(&self, query: Query) -> Result<Response> {
+        let Some(obs_client) = &self.obs_client else {
+            anyhow::bail!("not connected");
+        };
+        Ok(match query {
+            Query::InputSettings(name) => Response::InputSettings(
+                obs_client
+                    .inputs()
+                    .settings::<serde_json::Value>(&name)
+                    .await
+                    .with_context(|| format!("failed to get settings of {}", name))?
+                    .settings,
+            ),
+            Query::ListItems(name, property) => Response::ListItems(
+                obs_client
+                    .inputs()
+                    .properties_list_property_items(&name, &property)
+                    .await
+                    .with_context(|| format!("failed to list {} of {}", property, name))?
+                    .into_iter()
+                    .filter(|item| item.enabled)
+                    .map(|item| (item.name, item.value))
+                    .collect(),
+            ),
+            Query::FilterEnabled(source, filter) => Response::FilterEnabled(
+                obs_client
+                    .filters()
+                    .get(&source, &filter)
+                    .await
+                    .with_context(|| format!("no filter {} on {}", filter, source))?
+                    .enabled,
+            ),
+            Query::ProfileParameter(category, name) => {
+                let parameter = obs_client
+                    .config()
+                    .profile_parameter(&category, &name)
+                    .await
+                    .with_context(|| format!("failed to get {}/{}", category, name))?;
+                Response::ProfileParameter(parameter.value, parameter.default_value)
+            }
+            Query::Raw(request_type, data) => {
+                let Some((addr, port, pass)) = &self.login else {
+                    anyhow::bail!("not connected");
+                };
+                Response::Raw(raw_request::send(*addr, *port, pass, &request_type, data).await?)
+            }
+            Query::Hotkeys => Response::Hotkeys(
+                obs_client
+                    .hotkeys()
+                    .list()
+                    .await
+                    .context("failed to list hotkeys")?,
+            ),
+            Query::Volume(name) => Response::Volume(
+                obs_client
+                    .inputs()
+                    .volume(&name)
+                    .await
+                    .with_context(|| format!("failed to get volume for device {}", name))?
+                    .mul
+                    * 100.0,
+            ),
+            Query::Muted(name) => Response::Muted(
+                obs_client
+                    .inputs()
+                    .muted(&name)
+                    .await
+                    .with_context(|| format!("failed to get mute state for device {}", name))?,
+            ),
+            Query::CurrentScene => Response::CurrentScene(
+                obs_client
+                    .scenes()
+                    .current_program_scene()
+                    .await
+                    .context("failed to get current scene")?,
+            ),
+            Query::Streaming => Response::Streaming(
+                obs_client
+                    .streaming()
+                    .status()
+                    .await
+                    .context("failed to get stream status")?
+                    .active,
+            ),
+            Query::Recording => Response::Recording(
+                obs_client
+                    .recording()
+                    .status()
+                    .await
+                    .context("failed to get record status")?
+                    .active,
+            ),
+        })
+    }
+
+    async fn log_in(&mut self, addr: IpAddr, port: u16, pass: String) -> Result<()> {
+        let client = Client::connect(addr.to_string(), port, Some(pass.clone()))
+            .await
+            .context("failed to connect to obs")?;
+        // These come back as obws's own types, and obws matches responses to
+        // requests, so sending them all at once costs a single round trip.
+        let (input_info, output_info, scenes, version, stream_status, record_status) = tokio::try_join!(
+            async {
+                client
+                    .inputs()
+                    .list(None)
+                    .await
+                    .context("failed to get input info")
+            },
+            async {
+                client
+                    .outputs()
+                    .list()
+                    .await
+                    .context("failed to get output info")
+            },
+            async {
+                client
+                    .scenes()
+                    .list()
+                    .await
+                    .context("failed to get scene list")
+            },
+            async {
+                client
+                    .general()
+                    .version()
+                    .await
+                    .context("failed to get obs version")
+            },
+            async {
+                client
+                    .streaming()
+                    .status()
+                    .await
+                    .context("failed to get stream status")
+            },
+            async {
+                client
+                    .recording()
+                    .status()
+                    .await
+                    .context("failed to get record status")
+            },
+        )?;
+
+        self.obs_state = ObsState {
+            streaming: stream_status.active,
+            recording: record_status.active,
+            current_scene: scenes.current_program_scene_name,
+            preview_scene: scenes.current_preview_scene_name,
+            scenes: scenes.scenes.into_iter().map(|scene| scene.name).collect(),
+            muted: HashMap::new(),
+            volumes: HashMap::new(),
+            filters: HashMap::new(),
+            scene_items: HashMap::new(),
+        };
+        // Two requests for every input, so they go as one batch.
+        let requests: Vec<_> = input_info
+            .iter()
+            .flat_map(|input| {
+                [
+                    ("GetInputMute", json!({ "inputName": input.name })),
+                    ("GetInputVolume", json!({ "inputName": input.name })),
+                ]
+            })
+            .collect();
+        if !requests.is_empty() {
+            let batches = self
+                .batches
+                .insert(raw_request::Batches::open(addr, port, &pass).await?);
+            let results = batches.send(requests, false).await?;
+            for (input, results) in input_info.iter().zip(results.chunks(2)) {
+                // Inputs without audio have no mute state or volume.
+                let [muted, volume] = results else {
+                    continue;
+                };
+                if let Some(muted) = muted
+                    .as_ref()
+                    .ok()
+                    .and_then(|data| data["inputMuted"].as_bool())
+                {
+                    self.obs_state.muted.insert(input.name.clone(), muted);
+                }
+                if let Some(mul) = volume
+                    .as_ref()
+                    .ok()
+                    .and_then(|data| data["inputVolumeMul"].as_f64())
+                {
+                    self.obs_state
+                        .volumes
+                        .insert(input.name.clone(), mul as f32 * 100.0);
+                }
+            }
+        }
+        self.events = Some(Box::pin(
+            client.events().context("failed to subscribe to events")?,
+        ));
+        self.obs_client = Some(client);
+        self.login = Some((addr, port, pass));
+        tracing::info!(%addr, port, "connected to obs");
+
+        self.send(ObsInfo::InputInfo(input_info)).await;
+        self.send(ObsInfo::OutputInfo(output_info)).await;
+        self.send(ObsInfo::State(self.obs_state.clone())).await;
+        self.send(ObsInfo::Session(SessionInfo {
+            obs_version: version.obs_version.to_string(),
+            platform: version.platform,
+            stream_elapsed: Duration::from_secs(
+                stream_status.duration.whole_seconds().max(0) as u64
+            ),
+            record_elapsed: Duration::from_secs(
+                record_status.duration.whole_seconds().max(0) as u64
+            ),
+        }))
+        .await;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Action {
+    LogIn(IpAddr, u16, String),
+    LogOut,
+    SetOfflineQueue(bool),
+    SetMute(String, bool),
+    SetVolume(String, f32),
+    SetScene(String),
+    // The scene waiting in preview while OBS is in studio mode.
+    SetPreviewScene(String),
+    // A scene change and an audio fade together, done before anything else
+    // the worker is asked.
+    SceneCue(SceneCue),
+    // Saves the replay buffer and plays it in a media source (first) on a
+    // scene of its own (second), then goes back to the scene before.
+    InstantReplay(String, String),
+    StartStream,
+    StopStream,
+    StartRecord,
+    StopRecord,
+    RunMacro(Macro),
+    RunScript(String),
+    StopScripts,
+    Query(u64, Query),
+    StartRelay(u16),
+    StopRelay,
+    // From this instance's UI or an operator connected to its relay.
+    Session(SessionMessage),
+    // Another instance's relay address, and the name to join it under.
+    JoinSession(String, String),
+    LeaveSession,
+    StartOsc(u16, Option<SocketAddr>),
+    StopOsc,
+    StartMqtt(MqttConfig),
+    StopMqtt,
+    // The page for phones, on this port.
+    StartRemote(u16),
+    StopRemote,
+    // Replaces the paired devices; anything no longer listed is revoked.
+    SetPairing(Pairing),
+    // The resume prompt was answered, so the journal can drop the previous run.
+    ForgetRecovery,
+    StartChat(ChatConfig),
+    StopChat,
+    // Which experimental subsystems may run.
+    SetFeatures(Flags),
+    // Fires the intermission webhooks, for its start or its end.
+    Intermission(bool),
+    // Lets scenes and audio change but keeps the stream and recording from
+    // starting, webhooks from firing and chat commands from running.
+    SetRehearsal(bool),
+    StartNowPlaying(NowPlayingConfig),
+    StopNowPlaying,
+    // Frames per second of program preview to send back.
+    StartPreview(u32),
+    StopPreview,
+    // Input name and new text for a text source.
+    SetText(String, String),
+    // Settings merged over what the input already has.
+    SetInputSettings(String, serde_json::Value),
+    // Input and the id of a button in its properties.
+    PressInputButton(String, String),
+    // Input and the property holding its device.
+    ReactivateInput(String, String),
+    // Source, filter on it, and whether it applies.
+    SetFilterEnabled(String, String, bool),
+    ToggleFilter(String, String),
+    // Category, name, and the value to store; none restores the default.
+    SetProfileParameter(String, String, Option<String>),
+    // Vendor (a plugin's registered name), request type and its data.
+    VendorRequest(String, String, serde_json::Value),
+    // An OBS hotkey by its internal name, as if its keys were pressed.
+    TriggerHotkey(String),
+    // Scene to add it to, input name and kind.
+    CreateInput(String, String, String),
+    // Media or slideshow input and what to do with it.
+    Media(String, MediaCommand),
+    // Listens to a mic kept silent and sets its noise gate from the floor.
+    CalibrateGate(String),
+    // Input, open and close thresholds in dB; adds a noise gate if it has none.
+    SetGateThresholds(String, f64, f64),
+    // Adds whatever the input is missing of the voice chain.
+    AddVoiceChain(String),
+    // Replaces the presets ApplyAudioPreset picks from.
+    SetAudioPresets(Vec<AudioPreset>),
+    // An audio preset by name.
+    ApplyAudioPreset(String),
+    // Replaces the ducking settings presets use, without touching OBS.
+    SetDuckingPairs(Vec<DuckingPair>),
+    // Ducks one input under another, replacing how it ducked before.
+    SetDucking(DuckingPair),
+    // Stops ducking an input.
+    StopDucking(String),
+    // Every Source Record filter on an input or scene, sent back as
+    // IsoRecorders. They're armed and disarmed with SetFilterEnabled.
+    ListIsoRecorders,
+    // Opens OBS's own interact window for a browser source.
+    OpenInteract(String),
+    // Scene, source in it, and whether it shows.
+    SetSceneItemEnabled(String, String, bool),
+    StartTally(TallyConfig),
+    StopTally,
+    // Port for the Prometheus `/metrics` endpoint.
+    StartMetrics(u16),
+    StopMetrics,
+    SetWebhooks(Vec<Webhook>),
+    // Replaces the lighting mappings; none turns them off.
+    SetLighting(LightingConfig),
+    // Address of a Hue bridge whose link button was just pressed.
+    PairHue(String),
+    // A Key Light by name.
+    SetKeyLight(String, LightChange),
+    // Every key light found so far.
+    SetKeyLights(LightChange),
+    SetAlias(String, Option<String>),
+    // Server and stream key for a custom RTMP service.
+    SetStreamService(String, String),
+    // A highlight marker with an optional description.
+    AddMarker(String),
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum MediaCommand {
+    Play,
+    Pause,
+    Restart,
+    Stop,
+    Next,
+    Previous,
+}
+
+impl From<MediaCommand> for MediaAction {
+    fn from(command: MediaCommand) -> Self {
+        match command {
+            MediaCommand::Play => MediaAction::Play,
+            MediaCommand::Pause => MediaAction::Pause,
+            MediaCommand::Restart => MediaAction::Restart,
+            MediaCommand::Stop => MediaAction::Stop,
+            MediaCommand::Next => MediaAction::Next,
+            MediaCommand::Previous => MediaAction::Previous,
+        }
+    }
+}
+
+impl Action {
+    // The one request this sends to OBS, for those that are no more than that.
+    fn batch_request(&self) -> Option<raw_request::Request> {
+        Some(match self {
+            Action::SetMute(name, muted) => (
+                "SetInputMute",
+                json!({ "inputName": name, "inputMuted": muted }),
+            ),
+            Action::SetVolume(name, volume) => (
+                "SetInputVolume",
+                json!({ "inputName": name, "inputVolumeMul": volume / 100.0 }),
+            ),
+            Action::SetScene(name) => ("SetCurrentProgramScene", json!({ "sceneName": name })),
+            Action::SetFilterEnabled(source, filter, enabled) => (
+                "SetSourceFilterEnabled",
+                json!({ "sourceName": source, "filterName": filter, "filterEnabled": enabled }),
+            ),
+            _ => return None,
+        })
+    }
+
+    // Actions that describe a target state rather than a transition, so running
+    // them late after a reconnect is still what the user asked for.
+    fn is_replayable(&self) -> bool {
+        matches!(
+            self,
+            Action::SetMute(..)
+                | Action::SetVolume(..)
+                | Action::SetScene(_)
+                | Action::SetFilterEnabled(..)
+        )
+    }
+
+    fn supersedes(&self, other: &Action) -> bool {
+        match (self, other) {
+            (Action::SetMute(name, _), Action::SetMute(other, _))
+            | (Action::SetVolume(name, _), Action::SetVolume(other, _)) => name == other,
+            (Action::SetScene(_), Action::SetScene(_)) => true,
+            (
+                Action::SetFilterEnabled(source, filter, _),
+                Action::SetFilterEnabled(other_source, other_filter, _),
+            ) => source == other_source && filter == other_filter,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Query {
+    InputSettings(String),
+    // Input and property name.
+    ListItems(String, String),
+    // Source and filter name.
+    FilterEnabled(String, String),
+    Hotkeys,
+    // Any request type with its data, for trying out what REC doesn't model.
+    Raw(String, serde_json::Value),
+    // Category and name.
+    ProfileParameter(String, String),
+    Volume(String),
+    Muted(String),
+    CurrentScene,
+    Streaming,
+    Recording,
+}
+
+pub enum Response {
+    InputSettings(serde_json::Value),
+    // Display names and values.
+    ListItems(Vec<(String, serde_json::Value)>),
+    FilterEnabled(bool),
+    Hotkeys(Vec<String>),
+    Raw(serde_json::Value),
+    // Value and default value.
+    ProfileParameter(Option<String>, Option<String>),
+    Volume(f32),
+    Muted(bool),
+    CurrentScene(String),
+    Streaming(bool),
+    Recording(bool),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Macro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+impl Macro {
+    // Expects a scene and a media source in it both called "Replay".
+    pub fn instant_replay() -> Self {
+        Self {
+            name: "Instant Replay".to_string(),
+            steps: vec![MacroStep::Do(Action::InstantReplay(
+                "Replay".to_string(),
+                "Replay".to_string(),
+            ))],
+        }
+    }
+
+    pub fn start_show() -> Self {
+        Self {
+            name: "Start Show".to_string(),
+            steps: vec![
+                MacroStep::If {
+                    condition: Condition::Streaming,
+                    then: vec![],
+                    otherwise: vec![MacroStep::Do(Action::StartStream)],
+                },
+                MacroStep::If {
+                    condition: Condition::Recording,
+                    then: vec![],
+                    otherwise: vec![MacroStep::Do(Action::StartRecord)],
+                },
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum MacroStep {
+    Do(Action),
+    If {
+        condition: Condition,
+        then: Vec<MacroStep>,
+        otherwise: Vec<MacroStep>,
+    },
+}
+
+// A decoded program screenshot.
+pub struct PreviewFrame {
+    pub size: [usize; 2],
+    pub rgba: Vec<u8>,
+}
+
+// OBS hands screenshots over as a data URL.
+fn decode_screenshot(screenshot: &str) -> Result<PreviewFrame> {
+    use base64::Engine;
+    let (_, data) = screenshot
+        .split_once("base64,")
+        .context("screenshot isn't a data url")?;
+    let bytes = base64::engine::general_purpose::STANDARD.decode(data)?;
+    let image = image::load_from_memory(&bytes)?.to_rgba8();
+    Ok(PreviewFrame {
+        size: [image.width() as usize, image.height() as usize],
+        rgba: image.into_raw(),
+    })
+}
+
+// The top of the quiet readings in dB, leaving out the loudest few in case of
+// a cough or a click.
+fn noise_floor(levels: Vec<f64>) -> Option<f64> {
+    let mut levels: Vec<f64> = levels
+        .into_iter()
+        .map(|level| 20.0 * level.max(1e-5).log10())
+        .collect();
+    levels.sort_by(f64::total_cmp);
+    let index = (levels.len() * 95 / 100).min(levels.len().checked_sub(1)?);
+    Some(levels[index])
+}
+
+// What the status bar needs to know about OBS beyond its state.
+pub struct SessionInfo {
+    pub obs_version: String,
+    // "windows", "macos" or "linux", where OBS runs rather than REC.
+    pub platform: String,
+    pub stream_elapsed: Duration,
+    pub record_elapsed: Duration,
+}
+
+// Events worth telling the operator about that don't change any state.
+pub enum Notice {
+    RecordingStarted(Option<String>),
+    RecordingStopped(Option<String>),
+    ReplaySaved(String),
+    StreamReconnecting,
+    StreamReconnected,
+    InputAdded(String),
+    // Percentage of frames dropped since the last check.
+    DroppedFrames(f64),
+    // Input and the open and close thresholds it was given, in dB.
+    GateCalibrated(String, f64, f64),
+    // Input and how many of the chain's filters it was missing.
+    VoiceChainAdded(String, usize),
+    // A stream or recording start refused while rehearsing.
+    RehearsalBlocked,
+    // An action refused because its feature is switched off.
+    FeatureDisabled(Feature),
+}
+
+pub enum ObsInfo {
+    InputInfo(Vec<Input>),
+    OutputInfo(Vec<Output>),
+    State(ObsState),
+    Session(SessionInfo),
+    SceneChanged(String),
+    PreviewSceneChanged(Option<String>),
+    SceneList(Vec<String>),
+    InputRemoved(String),
+    InputRenamed(String, String),
+    MuteChanged(String, bool),
+    // Source, filter and whether it's enabled.
+    FilterChanged(String, String, bool),
+    // Scene, source and whether it shows.
+    SceneItemChanged(String, String, bool),
+    // Every OBS event, by name and in full, for the event log.
+    Event(String, String),
+    // Vendor, event type and data, from a plugin.
+    VendorEvent(String, String, serde_json::Value),
+    VolumeChanged(String, f32),
+    StreamState(bool),
+    RecordState(bool),
+    Notice(Notice),
+    // Description and where the recording was, if one was running.
+    Marker(String, Option<Duration>),
+    Light(KeyLight),
+    Preview(PreviewFrame),
+    // Slideshow, and the slide shown with the number of slides.
+    Slide(String, (usize, usize)),
+    // Username handed out by a Hue bridge.
+    HuePaired(String),
+    Disconnected,
+    ConnectionLost,
+    PendingActions(usize),
+    RelayRunning(bool),
+    // The shared session as the host last described it.
+    Peer(SessionMessage),
+    SessionJoined(bool),
+    OscRunning(bool),
+    MqttRunning(bool),
+    RemoteRunning(bool),
+    Rehearsal(bool),
+    IsoRecorders(Vec<IsoRecorder>),
+    Recovery(Recovery),
+    Error(String),
+    Response(u64, Result<Response, String>),
+}
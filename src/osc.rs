@@ -0,0 +1,186 @@
+use crate::{
+    obs_worker::{self, Action, Aliases},
+    state::ObsState,
+};
+use rosc::{OscMessage, OscPacket, OscType};
+use std::net::SocketAddr;
+use tokio::{
+    net::UdpSocket,
+    sync::{mpsc, watch},
+};
+
+// Like the relay, the worker stops this by aborting the task.
+pub async fn serve(
+    socket: UdpSocket,
+    feedback: Option<SocketAddr>,
+    mut state_rx: watch::Receiver<ObsState>,
+    aliases: Aliases,
+    action_tx: mpsc::Sender<Action>,
+) {
+    let mut buf = vec![0; rosc::decoder::MTU];
+    let mut last_state = ObsState::default();
+    loop {
+        tokio::select! {
+            received = socket.recv_from(&mut buf) => {
+                let len = match received {
+                    Ok((len, _)) => len,
+                    Err(err) => {
+                        tracing::warn!("failed to receive osc packet: {}", err);
+                        continue;
+                    }
+                };
+                let packet = match rosc::decoder::decode_udp(&buf[..len]) {
+                    Ok((_, packet)) => packet,
+                    Err(err) => {
+                        tracing::warn!("invalid osc packet: {:?}", err);
+                        continue;
+                    }
+                };
+                for message in flatten(packet) {
+                    match to_action(&message) {
+                        Some(action) => {
+                            if action_tx.send(action).await.is_err() {
+                                return;
+                            }
+                        }
+                        None => tracing::debug!(addr = message.addr, "unhandled osc message"),
+                    }
+                }
+            }
+            changed = state_rx.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                let state = state_rx.borrow_and_update().clone();
+                if let Some(target) = feedback {
+                    for message in feedback_messages(&last_state, &state, &aliases) {
+                        send(&socket, target, message).await;
+                    }
+                }
+                last_state = state;
+            }
+        }
+    }
+}
+
+fn flatten(packet: OscPacket) -> Vec<OscMessage> {
+    match packet {
+        OscPacket::Message(message) => vec![message],
+        OscPacket::Bundle(bundle) => bundle.content.into_iter().flat_map(flatten).collect(),
+    }
+}
+
+fn to_action(message: &OscMessage) -> Option<Action> {
+    let arg = message.args.first();
+    if let Some(input) = message.addr.strip_prefix("/rec/volume/") {
+        return Some(Action::SetVolume(input.to_string(), as_f32(arg)? * 100.0));
+    }
+    if let Some(input) = message.addr.strip_prefix("/rec/mute/") {
+        return Some(Action::SetMute(input.to_string(), as_bool(arg)?));
+    }
+    if let Some(scene) = message.addr.strip_prefix("/rec/scene/") {
+        // Buttons send 1 on press and 0 on release; only the press switches.
+        return as_bool(arg)
+            .unwrap_or(true)
+            .then(|| Action::SetScene(scene.to_string()));
+    }
+    match message.addr.as_str() {
+        "/rec/scene" => match arg {
+            Some(OscType::String(scene)) => Some(Action::SetScene(scene.clone())),
+            _ => None,
+        },
+        "/rec/stream" => Some(if as_bool(arg)? {
+            Action::StartStream
+        } else {
+            Action::StopStream
+        }),
+        "/rec/record" => Some(if as_bool(arg)? {
+            Action::StartRecord
+        } else {
+            Action::StopRecord
+        }),
+        _ => None,
+    }
+}
+
+fn as_f32(arg: Option<&OscType>) -> Option<f32> {
+    match arg? {
+        OscType::Float(value) => Some(*value),
+        OscType::Double(value) => Some(*value as f32),
+        OscType::Int(value) => Some(*value as f32),
+        _ => None,
+    }
+}
+
+fn as_bool(arg: Option<&OscType>) -> Option<bool> {
+    match arg? {
+        OscType::Bool(value) => Some(*value),
+        OscType::Int(value) => Some(*value != 0),
+        OscType::Float(value) => Some(*value >= 0.5),
+        OscType::Double(value) => Some(*value >= 0.5),
+        _ => None,
+    }
+}
+
+fn feedback_messages(last: &ObsState, state: &ObsState, aliases: &Aliases) -> Vec<OscMessage> {
+    let addresses = |prefix: &str, input: &str| {
+        obs_worker::names_for_input(aliases, input)
+            .into_iter()
+            .map(|name| format!("{}{}", prefix, name))
+            .collect::<Vec<_>>()
+    };
+
+    let mut messages = Vec::new();
+    for (input, volume) in &state.volumes {
+        if last.volumes.get(input) != Some(volume) {
+            for addr in addresses("/rec/volume/", input) {
+                messages.push(OscMessage {
+                    addr,
+                    args: vec![OscType::Float(volume / 100.0)],
+                });
+            }
+        }
+    }
+    for (input, muted) in &state.muted {
+        if last.muted.get(input) != Some(muted) {
+            for addr in addresses("/rec/mute/", input) {
+                messages.push(OscMessage {
+                    addr,
+                    args: vec![OscType::Int(*muted as i32)],
+                });
+            }
+        }
+    }
+    if state.current_scene != last.current_scene {
+        if let Some(scene) = &state.current_scene {
+            messages.push(OscMessage {
+                addr: "/rec/scene".to_string(),
+                args: vec![OscType::String(scene.clone())],
+            });
+        }
+    }
+    if state.streaming != last.streaming {
+        messages.push(OscMessage {
+            addr: "/rec/stream".to_string(),
+            args: vec![OscType::Int(state.streaming as i32)],
+        });
+    }
+    if state.recording != last.recording {
+        messages.push(OscMessage {
+            addr: "/rec/record".to_string(),
+            args: vec![OscType::Int(state.recording as i32)],
+        });
+    }
+    messages
+}
+
+async fn send(socket: &UdpSocket, target: SocketAddr, message: OscMessage) {
+    match rosc::encoder::encode(&OscPacket::Message(message)) {
+        Ok(packet) => {
+            if let Err(err) = socket.send_to(&packet, target).await {
+                tracing::warn!(%target, "failed to send osc feedback: {}", err);
+            }
+        }
+        Err(err) => tracing::warn!("failed to encode osc message: {:?}", err),
+    }
+}
@@ -0,0 +1,115 @@
+use crate::obs_worker::Action;
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{IpAddr, Ipv4Addr, UdpSocket},
+    sync::{Arc, RwLock},
+};
+
+// What a paired device may do over the relay or the web remote.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    // Sees the state, changes nothing.
+    Viewer,
+    // Mutes, levels and scenes, but never starts or ends the stream or the
+    // recording.
+    Operator,
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::Viewer, Role::Operator, Role::Admin];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Role::Viewer => "pairing-viewer",
+            Role::Operator => "pairing-operator",
+            Role::Admin => "pairing-admin",
+        }
+    }
+
+    pub fn allows(self, action: &Action) -> bool {
+        match self {
+            Role::Viewer => false,
+            Role::Operator => matches!(
+                action,
+                Action::SetMute(..)
+                    | Action::SetVolume(..)
+                    | Action::SetScene(_)
+                    | Action::Session(_)
+            ),
+            Role::Admin => true,
+        }
+    }
+}
+
+// A token handed to one device. Revoking deletes it.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grant {
+    // Who it was given to, so it can be told apart when revoking.
+    pub name: String,
+    pub role: Role,
+    pub token: String,
+}
+
+// Whether tokens are checked at all, and which ones are good. Kept apart so
+// revoking the last device doesn't open everything up again.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Pairing {
+    pub enabled: bool,
+    pub grants: Vec<Grant>,
+}
+
+// Shared by the worker with the relay and the web remote, so a revoked token
+// stops working mid-connection.
+pub type Shared = Arc<RwLock<Pairing>>;
+
+// The relay and the web remote listen on every interface, so until pairing
+// is turned on anyone on the network may watch but not touch. Once it is, a
+// request needs a token that's still granted.
+pub fn role(pairing: &Shared, token: Option<&str>) -> Option<Role> {
+    let pairing = pairing.read().unwrap();
+    if !pairing.enabled {
+        return Some(Role::Viewer);
+    }
+    let token = token?;
+    pairing
+        .grants
+        .iter()
+        .find(|grant| same_token(&grant.token, token))
+        .map(|grant| grant.role)
+}
+
+// Looks at every byte whatever the first difference, so the time taken says
+// nothing about how much of a guess was right.
+pub fn same_token(expected: &str, given: &str) -> bool {
+    let (expected, given) = (expected.as_bytes(), given.as_bytes());
+    expected.len() == given.len()
+        && expected
+            .iter()
+            .zip(given)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+// The token from a `?token=` in a request target.
+pub fn token_in(target: &str) -> Option<&str> {
+    let (_, query) = target.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == "token")
+        .map(|(_, token)| token)
+}
+
+// The address other devices on the network reach this one at, for links.
+// Connecting a UDP socket sends nothing; it only picks the outgoing interface.
+pub fn link_host() -> String {
+    let address = || -> Option<IpAddr> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+        socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9)).ok()?;
+        Some(socket.local_addr().ok()?.ip())
+    };
+    address().map_or_else(|| "localhost".to_string(), |address| address.to_string())
+}
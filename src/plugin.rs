@@ -0,0 +1,103 @@
+use crate::{
+    obs_worker::{Action, Macro, ObsInfo},
+    state::ObsState,
+};
+use anyhow::{Context, Result};
+use std::{
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+pub trait Plugin {
+    fn name(&self) -> &str;
+
+    // Shown as extra buttons in the grid.
+    fn macros(&self) -> Vec<Macro> {
+        Vec::new()
+    }
+
+    fn show(&mut self, _ui: &mut egui::Ui, _state: &ObsState) -> Vec<Action> {
+        Vec::new()
+    }
+
+    fn on_event(&mut self, _info: &ObsInfo, _state: &ObsState) -> Vec<Action> {
+        Vec::new()
+    }
+}
+
+// The trait object crosses the library boundary as is, so plugins have to be
+// built with the same compiler and rec version as the app.
+pub type CreatePlugin = unsafe fn() -> Box<dyn Plugin>;
+
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub fn rec_create_plugin() -> Box<dyn $crate::plugin::Plugin> {
+            Box::new($constructor)
+        }
+    };
+}
+
+pub struct LoadedPlugin {
+    // Declared first so it is dropped before the library its code lives in.
+    plugin: Box<dyn Plugin>,
+    _library: libloading::Library,
+}
+
+impl Deref for LoadedPlugin {
+    type Target = dyn Plugin;
+
+    fn deref(&self) -> &Self::Target {
+        self.plugin.as_ref()
+    }
+}
+
+impl DerefMut for LoadedPlugin {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.plugin.as_mut()
+    }
+}
+
+pub fn load(path: &Path) -> Result<LoadedPlugin> {
+    unsafe {
+        let library = libloading::Library::new(path).context("failed to open library")?;
+        let plugin = {
+            let create = library
+                .get::<CreatePlugin>(b"rec_create_plugin")
+                .context("missing rec_create_plugin")?;
+            create()
+        };
+        Ok(LoadedPlugin {
+            plugin,
+            _library: library,
+        })
+    }
+}
+
+pub fn load_all() -> Vec<LoadedPlugin> {
+    let Some(dirs) = directories::ProjectDirs::from("", "", "rec") else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dirs.data_dir().join("plugins")) else {
+        return Vec::new();
+    };
+
+    let mut plugins = Vec::new();
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.extension().and_then(|ext| ext.to_str()) != Some(std::env::consts::DLL_EXTENSION) {
+            continue;
+        }
+        match load(&path) {
+            Ok(plugin) => {
+                tracing::info!(path = %path.display(), "loaded plugin {}", plugin.name());
+                plugins.push(plugin);
+            }
+            Err(err) => tracing::error!("failed to load plugin {}: {:#}", path.display(), err),
+        }
+    }
+    plugins
+}
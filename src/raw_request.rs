@@ -0,0 +1,211 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::{net::IpAddr, time::Duration};
+use tokio_tungstenite::tungstenite::Message;
+
+// The InputVolumeMeters bit of the event subscription mask; obws leaves these
+// out because they arrive many times a second.
+const INPUT_VOLUME_METERS: u32 = 1 << 16;
+
+// obws only sends the requests it models, so anything else goes over a short
+// connection of its own, speaking the obs-websocket 5 protocol by hand.
+pub async fn send(
+    addr: IpAddr,
+    port: u16,
+    password: &str,
+    request_type: &str,
+    data: Value,
+) -> Result<Value> {
+    let mut stream = connect(addr, port, password, 0).await?;
+    send_message(
+        &mut stream,
+        6,
+        json!({
+            "requestType": request_type,
+            "requestId": "rec",
+            "requestData": data,
+        }),
+    )
+    .await?;
+    let response = receive(&mut stream, 7).await?;
+    let _ = stream.close(None).await;
+    response_data(request_type, response)
+}
+
+fn response_data(request_type: &str, mut response: Value) -> Result<Value> {
+    let status = &response["requestStatus"];
+    if status["result"] != json!(true) {
+        anyhow::bail!(
+            "{} failed ({}): {}",
+            request_type,
+            status["code"],
+            status["comment"].as_str().unwrap_or_default()
+        );
+    }
+    Ok(response
+        .get_mut("responseData")
+        .map(Value::take)
+        .unwrap_or(Value::Null))
+}
+
+// A request type and its data.
+pub type Request = (&'static str, Value);
+
+// Kept open for request batches, which obws can't send either. OBS runs a
+// batch's requests back to back with nothing else in between, and answers
+// them all in one message.
+pub struct Batches {
+    stream: Stream,
+}
+
+impl Batches {
+    pub async fn open(addr: IpAddr, port: u16, password: &str) -> Result<Self> {
+        Ok(Self {
+            stream: connect(addr, port, password, 0).await?,
+        })
+    }
+
+    // A result for each request, in order. With `halt_on_failure` OBS stops at
+    // the first request that fails, and the ones after it get no result.
+    pub async fn send(
+        &mut self,
+        requests: Vec<Request>,
+        halt_on_failure: bool,
+    ) -> Result<Vec<Result<Value>>> {
+        let types: Vec<_> = requests
+            .iter()
+            .map(|(request_type, _)| *request_type)
+            .collect();
+        let requests: Vec<_> = requests
+            .into_iter()
+            .enumerate()
+            .map(|(index, (request_type, data))| {
+                json!({
+                    "requestType": request_type,
+                    "requestId": index.to_string(),
+                    "requestData": data,
+                })
+            })
+            .collect();
+        send_message(
+            &mut self.stream,
+            8,
+            json!({
+                "requestId": "rec",
+                "haltOnFailure": halt_on_failure,
+                // SerialRealtime: in order, as soon as each can run.
+                "executionType": 0,
+                "requests": requests,
+            }),
+        )
+        .await?;
+        let mut response = receive(&mut self.stream, 9).await?;
+        let results = match response["results"].take() {
+            Value::Array(results) => results,
+            _ => anyhow::bail!("malformed batch response from obs"),
+        };
+        Ok(types
+            .into_iter()
+            .zip(results)
+            .map(|(request_type, result)| response_data(request_type, result))
+            .collect())
+    }
+}
+
+// Loudest magnitude across the input's channels, as a multiplier, for every
+// meter update OBS sends over the given time.
+pub async fn meter_levels(
+    addr: IpAddr,
+    port: u16,
+    password: &str,
+    input: &str,
+    duration: Duration,
+) -> Result<Vec<f64>> {
+    let mut stream = connect(addr, port, password, INPUT_VOLUME_METERS).await?;
+    let mut levels = Vec::new();
+    let deadline = tokio::time::Instant::now() + duration;
+    while let Ok(event) = tokio::time::timeout_at(deadline, receive(&mut stream, 5)).await {
+        let event = event?;
+        if event["eventType"] != "InputVolumeMeters" {
+            continue;
+        }
+        let inputs = event["eventData"]["inputs"]
+            .as_array()
+            .into_iter()
+            .flatten();
+        for meter in inputs.filter(|meter| meter["inputName"] == input) {
+            let level = meter["inputLevelsMul"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|channel| channel[0].as_f64())
+                .fold(0.0, f64::max);
+            levels.push(level);
+        }
+    }
+    let _ = stream.close(None).await;
+    Ok(levels)
+}
+
+type Stream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+async fn connect(addr: IpAddr, port: u16, password: &str, subscriptions: u32) -> Result<Stream> {
+    let url = format!("ws://{}", std::net::SocketAddr::new(addr, port));
+    let (mut stream, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .context("failed to connect to obs")?;
+
+    let hello = receive(&mut stream, 0).await?;
+    let mut identify = json!({ "rpcVersion": 1, "eventSubscriptions": subscriptions });
+    if let Some(auth) = hello.get("authentication") {
+        let (Some(challenge), Some(salt)) = (auth["challenge"].as_str(), auth["salt"].as_str())
+        else {
+            anyhow::bail!("malformed hello from obs");
+        };
+        identify["authentication"] = json!(authentication(password, salt, challenge));
+    }
+    send_message(&mut stream, 1, identify).await?;
+    receive(&mut stream, 2).await?;
+    Ok(stream)
+}
+
+async fn send_message(stream: &mut Stream, op: u8, data: Value) -> Result<()> {
+    let message = json!({ "op": op, "d": data });
+    stream.send(Message::Text(message.to_string())).await?;
+    Ok(())
+}
+
+// The data of the next message with the given op code; events and anything
+// else in between are skipped.
+async fn receive(stream: &mut Stream, op: u8) -> Result<Value> {
+    while let Some(message) = stream.next().await {
+        match message? {
+            Message::Text(text) => {
+                let mut message: Value = serde_json::from_str(&text)?;
+                if message["op"] == json!(op) {
+                    return Ok(message["d"].take());
+                }
+            }
+            Message::Close(frame) => {
+                anyhow::bail!(
+                    "obs closed the connection: {}",
+                    frame
+                        .map(|frame| frame.reason.into_owned())
+                        .unwrap_or_default()
+                )
+            }
+            _ => {}
+        }
+    }
+    anyhow::bail!("obs closed the connection")
+}
+
+fn authentication(password: &str, salt: &str, challenge: &str) -> String {
+    let base64 = base64::engine::general_purpose::STANDARD;
+    let secret = base64.encode(Sha256::digest(format!("{}{}", password, salt)));
+    base64.encode(Sha256::digest(format!("{}{}", secret, challenge)))
+}
@@ -0,0 +1,159 @@
+use crate::{obs_worker::Action, pairing, session::SessionMessage, state::ObsState};
+use anyhow::{bail, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, mpsc, watch},
+};
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    Message,
+};
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelayCommand {
+    SetMute { input: String, muted: bool },
+    SetVolume { input: String, volume: f32 },
+    SetScene { scene: String },
+    StartStream,
+    StopStream,
+    StartRecord,
+    StopRecord,
+}
+
+impl From<RelayCommand> for Action {
+    fn from(command: RelayCommand) -> Self {
+        match command {
+            RelayCommand::SetMute { input, muted } => Action::SetMute(input, muted),
+            RelayCommand::SetVolume { input, volume } => Action::SetVolume(input, volume),
+            RelayCommand::SetScene { scene } => Action::SetScene(scene),
+            RelayCommand::StartStream => Action::StartStream,
+            RelayCommand::StopStream => Action::StopStream,
+            RelayCommand::StartRecord => Action::StartRecord,
+            RelayCommand::StopRecord => Action::StopRecord,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RelayMessage<'a> {
+    State(&'a ObsState),
+    Error { message: String },
+}
+
+// The worker stops the relay by aborting this task and dropping the state sender,
+// which also ends every client connection.
+pub async fn serve(
+    listener: TcpListener,
+    state_rx: watch::Receiver<ObsState>,
+    action_tx: mpsc::Sender<Action>,
+    session_tx: broadcast::Sender<SessionMessage>,
+    pairing: pairing::Shared,
+) {
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                tracing::warn!("failed to accept relay client: {}", err);
+                continue;
+            }
+        };
+        tracing::info!(%addr, "relay client connected");
+        let state_rx = state_rx.clone();
+        let action_tx = action_tx.clone();
+        let session_rx = session_tx.subscribe();
+        let pairing = pairing.clone();
+        tokio::spawn(async move {
+            if let Err(err) =
+                handle_connection(stream, state_rx, action_tx, session_rx, pairing).await
+            {
+                tracing::warn!(%addr, "relay client failed: {:#}", err);
+            }
+            tracing::info!(%addr, "relay client disconnected");
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    mut state_rx: watch::Receiver<ObsState>,
+    action_tx: mpsc::Sender<Action>,
+    mut session_rx: broadcast::Receiver<SessionMessage>,
+    pairing: pairing::Shared,
+) -> Result<()> {
+    // Clients pair by connecting to ws://host:port/?token=...
+    let mut token = None;
+    let stream = tokio_tungstenite::accept_hdr_async(
+        stream,
+        |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+            token = pairing::token_in(&request.uri().to_string()).map(str::to_string);
+            Ok(response)
+        },
+    )
+    .await?;
+    if pairing::role(&pairing, token.as_deref()).is_none() {
+        bail!("unknown pairing token");
+    }
+    let (mut sink, mut source) = stream.split();
+
+    let state = serde_json::to_string(&RelayMessage::State(&state_rx.borrow_and_update()))?;
+    sink.send(Message::Text(state)).await?;
+
+    loop {
+        tokio::select! {
+            changed = state_rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let state =
+                    serde_json::to_string(&RelayMessage::State(&state_rx.borrow_and_update()))?;
+                sink.send(Message::Text(state)).await?;
+            }
+            message = session_rx.recv() => match message {
+                Ok(message) => sink.send(Message::Text(serde_json::to_string(&message)?)).await?,
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = source.next() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<RelayCommand>(&text)
+                    .map(Action::from)
+                    .or_else(|err| {
+                        // Other REC instances sharing the session.
+                        serde_json::from_str::<SessionMessage>(&text)
+                            .map(Action::Session)
+                            .map_err(|_| err)
+                    }) {
+                    // Checked on every message, so revoking takes effect at once.
+                    Ok(action) => match pairing::role(&pairing, token.as_deref()) {
+                        Some(role) if role.allows(&action) => {
+                            if action_tx.send(action).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(_) => {
+                            let error = RelayMessage::Error {
+                                message: "not allowed for this pairing".to_string(),
+                            };
+                            sink.send(Message::Text(serde_json::to_string(&error)?)).await?;
+                        }
+                        None => break,
+                    },
+                    Err(err) => {
+                        let error = RelayMessage::Error {
+                            message: err.to_string(),
+                        };
+                        sink.send(Message::Text(serde_json::to_string(&error)?)).await?;
+                    }
+                },
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err.into()),
+            },
+        }
+    }
+    sink.close().await?;
+    Ok(())
+}
@@ -0,0 +1,303 @@
+use crate::{
+    i18n::{I18n, LANGUAGES},
+    obs_worker::{self, Action, Aliases},
+    pairing::{self, Role},
+    state::ObsState,
+};
+use anyhow::{bail, Result};
+use serde::Serialize;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, watch},
+};
+
+const PAGE: &str = include_str!("remote.html");
+// Headers or body; the page never sends anything near this.
+const MAX_REQUEST: usize = 8192;
+
+// What the page polls for.
+#[derive(Serialize)]
+struct Status {
+    // So the page only offers what the token allows.
+    role: Role,
+    streaming: bool,
+    recording: bool,
+    scene: Option<String>,
+    scenes: Vec<String>,
+    inputs: Vec<Input>,
+}
+
+#[derive(Serialize)]
+struct Input {
+    name: String,
+    // The alias if there is one, so "mic" shows instead of the OBS input
+    // name. Aliased inputs are listed first.
+    label: String,
+    muted: bool,
+}
+
+fn status(state: &ObsState, aliases: &Aliases, role: Role) -> Status {
+    let mut inputs: Vec<Input> = state
+        .muted
+        .iter()
+        .map(|(name, muted)| {
+            let names = obs_worker::names_for_input(aliases, name);
+            Input {
+                name: name.clone(),
+                label: names.last().cloned().unwrap_or_else(|| name.clone()),
+                muted: *muted,
+            }
+        })
+        .collect();
+    inputs.sort_by(|a, b| (a.label == a.name, &a.label).cmp(&(b.label == b.name, &b.label)));
+    Status {
+        role,
+        streaming: state.streaming,
+        recording: state.recording,
+        scene: state.current_scene.clone(),
+        scenes: state.scenes.clone(),
+        inputs,
+    }
+}
+
+// A page for a phone on the same network. Like the OSC listener, the worker
+// stops this by aborting the task.
+pub async fn serve(
+    listener: TcpListener,
+    state_rx: watch::Receiver<ObsState>,
+    aliases: Aliases,
+    action_tx: mpsc::Sender<Action>,
+    pairing: pairing::Shared,
+) {
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                tracing::warn!("failed to accept remote client: {}", err);
+                continue;
+            }
+        };
+        let (state_rx, aliases, action_tx, pairing) = (
+            state_rx.clone(),
+            aliases.clone(),
+            action_tx.clone(),
+            pairing.clone(),
+        );
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, state_rx, aliases, action_tx, pairing).await
+            {
+                tracing::debug!(%addr, "remote request failed: {:#}", err);
+            }
+        });
+    }
+}
+
+// One request per connection, like the metrics exporter. Changes are POSTs
+// with a form body, and are only taken from the page this serves.
+async fn handle_connection(
+    mut stream: TcpStream,
+    state_rx: watch::Receiver<ObsState>,
+    aliases: Aliases,
+    action_tx: mpsc::Sender<Action>,
+    pairing: pairing::Shared,
+) -> Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    let head_len = loop {
+        if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        let read = stream.read(&mut buf).await?;
+        if read == 0 || request.len() > MAX_REQUEST {
+            bail!("incomplete request");
+        }
+        request.extend_from_slice(&buf[..read]);
+    };
+    let head = String::from_utf8_lossy(&request[..head_len]).into_owned();
+    let body_len: usize = header(&head, "Content-Length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    if body_len > MAX_REQUEST {
+        bail!("request body too large");
+    }
+    while request.len() < head_len + body_len {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            bail!("incomplete request body");
+        }
+        request.extend_from_slice(&buf[..read]);
+    }
+    let form = String::from_utf8_lossy(&request[head_len..head_len + body_len]).into_owned();
+
+    let mut words = head.split_whitespace();
+    let method = words.next().unwrap_or_default();
+    let target = words.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    // Reads come with the token in the query; changes carry everything in the
+    // body, so nothing that acts ends up in a URL.
+    let params = if method == "POST" {
+        form.as_str()
+    } else {
+        query
+    };
+    let param = |key: &str| {
+        params
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| decode(value))
+    };
+    let flag = |key: &str| param(key).map(|value| value == "true");
+    // The page itself is the same for everyone; it passes its link's token on.
+    let role = pairing::role(&pairing, param("token").as_deref());
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/") => (
+            "200 OK",
+            "text/html; charset=utf-8",
+            page(header(&head, "Accept-Language").unwrap_or_default()),
+        ),
+        ("GET", "/state") => match role {
+            Some(role) => {
+                // The state is copied out before anything is awaited.
+                let body = serde_json::to_string(&status(&state_rx.borrow(), &aliases, role))?;
+                ("200 OK", "application/json", body)
+            }
+            None => ("403 Forbidden", "text/plain", String::new()),
+        },
+        // Another site open on the phone could otherwise post here too.
+        ("POST", _) if !same_origin(&head) => ("403 Forbidden", "text/plain", String::new()),
+        ("POST", _) => {
+            let action = match path {
+                "/mute" => param("input")
+                    .zip(flag("muted"))
+                    .map(|(input, muted)| Action::SetMute(input, muted)),
+                "/scene" => param("name").map(Action::SetScene),
+                "/record" => flag("active").map(|active| {
+                    if active {
+                        Action::StartRecord
+                    } else {
+                        Action::StopRecord
+                    }
+                }),
+                "/stream" => flag("active").map(|active| {
+                    if active {
+                        Action::StartStream
+                    } else {
+                        Action::StopStream
+                    }
+                }),
+                _ => None,
+            };
+            match action {
+                Some(action) if !role.is_some_and(|role| role.allows(&action)) => {
+                    ("403 Forbidden", "text/plain", String::new())
+                }
+                Some(action) => {
+                    action_tx.send(action).await?;
+                    ("204 No Content", "text/plain", String::new())
+                }
+                None => ("400 Bad Request", "text/plain", String::new()),
+            }
+        }
+        _ => ("404 Not Found", "text/plain", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+// Browsers send the Origin of the page a POST comes from; older ones only the
+// Referer. Either has to be this server as the phone reached it.
+fn same_origin(head: &str) -> bool {
+    let Some(host) = header(head, "Host") else {
+        return false;
+    };
+    let origin = format!("http://{}", host);
+    match (header(head, "Origin"), header(head, "Referer")) {
+        (Some(sent), _) => sent == origin,
+        (None, Some(referer)) => referer
+            .strip_prefix(&origin)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')),
+        (None, None) => false,
+    }
+}
+
+// The page in the phone's language if REC has it. Every `{{message-id}}` in
+// it is filled in from the translations.
+fn page(accept_language: &str) -> String {
+    let code = accept_language
+        .split(',')
+        .filter_map(|tag| tag.split(';').next())
+        .find_map(|tag| {
+            LANGUAGES
+                .iter()
+                .find(|language| tag.trim().starts_with(language.code))
+        })
+        .map_or(LANGUAGES[0].code, |language| language.code);
+    let i18n = I18n::new(code);
+    let mut page = String::with_capacity(PAGE.len());
+    let mut rest = PAGE;
+    while let Some((before, after)) = rest.split_once("{{") {
+        let Some((id, after)) = after.split_once("}}") else {
+            break;
+        };
+        page.push_str(before);
+        match id {
+            "lang" => page.push_str(code),
+            id => page.push_str(&escape(&i18n.tr(id))),
+        }
+        rest = after;
+    }
+    page.push_str(rest);
+    page
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// Percent-decoding, as encodeURIComponent writes it.
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
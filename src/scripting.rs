@@ -0,0 +1,112 @@
+use crate::{obs_worker::Action, state::ObsState};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+// Enough for any button, and a runaway loop ends in well under a second.
+const MAX_OPERATIONS: u64 = 1_000_000;
+// How often a sleeping script looks whether it was stopped.
+const SLEEP_SLICE: Duration = Duration::from_millis(50);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Scripts see a snapshot of the state taken when they were started, and their
+// actions go through the same channel as the UI's. Setting `cancel` stops the
+// script at its next operation, or while it sleeps.
+pub fn run_script(
+    source: &str,
+    obs_state: ObsState,
+    action_tx: tokio::sync::mpsc::Sender<Action>,
+    cancel: Arc<AtomicBool>,
+) -> Result<(), Box<rhai::EvalAltResult>> {
+    let obs_state = Arc::new(obs_state);
+    let mut engine = rhai::Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    let stopped = cancel.clone();
+    engine.on_progress(move |_| {
+        stopped
+            .load(Ordering::Relaxed)
+            .then(|| "script stopped".into())
+    });
+
+    let send = move |action: Action| {
+        action_tx
+            .blocking_send(action)
+            .map_err(|_| Box::<rhai::EvalAltResult>::from("action channel closed"))
+    };
+    let tx = send.clone();
+    engine.register_fn("set_volume", move |name: &str, value: f64| {
+        tx(Action::SetVolume(name.to_string(), value as f32))
+    });
+    let tx = send.clone();
+    engine.register_fn("set_mute", move |name: &str, muted: bool| {
+        tx(Action::SetMute(name.to_string(), muted))
+    });
+    let tx = send.clone();
+    engine.register_fn("set_scene", move |name: &str| {
+        tx(Action::SetScene(name.to_string()))
+    });
+    let tx = send.clone();
+    engine.register_fn("start_stream", move || tx(Action::StartStream));
+    let tx = send.clone();
+    engine.register_fn("stop_stream", move || tx(Action::StopStream));
+    let tx = send.clone();
+    engine.register_fn("start_record", move || tx(Action::StartRecord));
+    let tx = send;
+    engine.register_fn("stop_record", move || tx(Action::StopRecord));
+
+    let state = obs_state.clone();
+    engine.register_fn("streaming", move || state.streaming);
+    let state = obs_state.clone();
+    engine.register_fn("recording", move || state.recording);
+    let state = obs_state.clone();
+    engine.register_fn("scene", move || {
+        state.current_scene.clone().unwrap_or_default()
+    });
+    let state = obs_state.clone();
+    engine.register_fn("muted", move |name: &str| {
+        state.muted.get(name).copied().unwrap_or(false)
+    });
+    let state = obs_state;
+    engine.register_fn("volume", move |name: &str| {
+        state.volumes.get(name).copied().unwrap_or(0.0) as f64
+    });
+
+    engine.register_fn("sleep", move |ms: i64| {
+        let until = Instant::now() + Duration::from_millis(ms.max(0) as u64);
+        while !cancel.load(Ordering::Relaxed) {
+            let left = until.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            thread::sleep(left.min(SLEEP_SLICE));
+        }
+    });
+    // ureq also caps a response it reads into a string at 10 MB.
+    let http = ureq::AgentBuilder::new().timeout(HTTP_TIMEOUT).build();
+    let agent = http.clone();
+    engine.register_fn(
+        "http_get",
+        move |url: &str| -> Result<String, Box<rhai::EvalAltResult>> {
+            let response = agent.get(url).call().map_err(|err| err.to_string())?;
+            response.into_string().map_err(|err| err.to_string().into())
+        },
+    );
+    let agent = http;
+    engine.register_fn(
+        "http_post",
+        move |url: &str, body: &str| -> Result<String, Box<rhai::EvalAltResult>> {
+            let response = agent
+                .post(url)
+                .send_string(body)
+                .map_err(|err| err.to_string())?;
+            response.into_string().map_err(|err| err.to_string().into())
+        },
+    );
+
+    engine.run(source)
+}
@@ -0,0 +1,100 @@
+use crate::{
+    layout::Panel,
+    obs_worker::{Notifier, ObsInfo},
+};
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+// What operators sharing a show tell each other over the relay. The instance
+// running the relay keeps who controls which panel; the others join it.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SessionMessage {
+    Hello {
+        operator: String,
+    },
+    Claim {
+        panel: Panel,
+        operator: String,
+    },
+    Release {
+        panel: Panel,
+        operator: String,
+    },
+    // Sent by the host after every claim or release.
+    Owners {
+        owners: BTreeMap<Panel, String>,
+    },
+    Pressed {
+        operator: String,
+        button: String,
+    },
+    // A mixer channel picked for the "mic" or "desktop" alias.
+    Selected {
+        operator: String,
+        alias: String,
+        input: String,
+    },
+}
+
+// Connects to another instance's relay and passes session messages both ways
+// until either side goes away. Everything else the relay sends is ignored.
+pub async fn join(
+    host: String,
+    operator: String,
+    mut outgoing: mpsc::Receiver<SessionMessage>,
+    obs_info_tx: mpsc::Sender<ObsInfo>,
+    notify: Notifier,
+) {
+    if let Err(err) = run(&host, operator, &mut outgoing, &obs_info_tx, &notify).await {
+        tracing::warn!(host, "shared session ended: {:#}", err);
+        let _ = obs_info_tx.send(ObsInfo::Error(format!("{:#}", err))).await;
+    }
+    let _ = obs_info_tx.send(ObsInfo::SessionJoined(false)).await;
+    notify();
+}
+
+async fn run(
+    host: &str,
+    operator: String,
+    outgoing: &mut mpsc::Receiver<SessionMessage>,
+    obs_info_tx: &mpsc::Sender<ObsInfo>,
+    notify: &Notifier,
+) -> Result<()> {
+    let (stream, _) = tokio_tungstenite::connect_async(format!("ws://{}", host))
+        .await
+        .with_context(|| format!("failed to join the session at {}", host))?;
+    let (mut sink, mut source) = stream.split();
+    let _ = obs_info_tx.send(ObsInfo::SessionJoined(true)).await;
+    notify();
+    let hello = SessionMessage::Hello { operator };
+    sink.send(Message::Text(serde_json::to_string(&hello)?))
+        .await?;
+    loop {
+        tokio::select! {
+            message = outgoing.recv() => match message {
+                Some(message) => sink.send(Message::Text(serde_json::to_string(&message)?)).await?,
+                None => break,
+            },
+            message = source.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Ok(message) = serde_json::from_str::<SessionMessage>(&text) {
+                        if obs_info_tx.send(ObsInfo::Peer(message)).await.is_err() {
+                            break;
+                        }
+                        notify();
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => anyhow::bail!("the host left the session"),
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err.into()),
+            },
+        }
+    }
+    sink.close().await?;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+// The Source Record plugin's filter, which records the source it's on to a
+// file of its own (an ISO recording) while it's enabled.
+pub const FILTER_KIND: &str = "source_record_filter";
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RecordMode {
+    Never,
+    Always,
+    Streaming,
+    Recording,
+    StreamingOrRecording,
+    VirtualCamera,
+}
+
+impl RecordMode {
+    // OBS leaves settings at their default out, and the plugin's default is to
+    // record along with OBS.
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        match settings["record_mode"].as_i64() {
+            Some(0) => RecordMode::Never,
+            Some(1) => RecordMode::Always,
+            Some(2) => RecordMode::Streaming,
+            Some(4) => RecordMode::StreamingOrRecording,
+            Some(5) => RecordMode::VirtualCamera,
+            _ => RecordMode::Recording,
+        }
+    }
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            RecordMode::Never => "iso-mode-never",
+            RecordMode::Always => "iso-mode-always",
+            RecordMode::Streaming => "iso-mode-streaming",
+            RecordMode::Recording => "iso-mode-recording",
+            RecordMode::StreamingOrRecording => "iso-mode-streaming-or-recording",
+            RecordMode::VirtualCamera => "iso-mode-virtual-camera",
+        }
+    }
+
+    // Whether an enabled filter is writing a file right now. The virtual
+    // camera isn't followed, so it never counts.
+    pub fn active(self, streaming: bool, recording: bool) -> bool {
+        match self {
+            RecordMode::Always => true,
+            RecordMode::Streaming => streaming,
+            RecordMode::Recording => recording,
+            RecordMode::StreamingOrRecording => streaming || recording,
+            RecordMode::Never | RecordMode::VirtualCamera => false,
+        }
+    }
+}
+
+// A Source Record filter as it was when listed. Whether it's enabled is kept
+// up to date in the state from then on.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IsoRecorder {
+    pub source: String,
+    pub filter: String,
+    pub enabled: bool,
+    pub mode: RecordMode,
+}
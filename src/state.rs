@@ -0,0 +1,134 @@
+use crate::obs_worker::ObsInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Condition {
+    Streaming,
+    Recording,
+    SceneIs(String),
+    InputMuted(String),
+    // Source and filter name.
+    FilterEnabled(String, String),
+    Not(Box<Condition>),
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ObsState {
+    pub streaming: bool,
+    pub recording: bool,
+    pub current_scene: Option<String>,
+    // Only set in studio mode.
+    pub preview_scene: Option<String>,
+    pub scenes: Vec<String>,
+    pub muted: HashMap<String, bool>,
+    pub volumes: HashMap<String, f32>,
+    // Filters by source, as far as they've been seen.
+    pub filters: HashMap<String, HashMap<String, bool>>,
+    // Whether a source shows in a scene, by scene and then source, as far as
+    // REC has changed it.
+    pub scene_items: HashMap<String, HashMap<String, bool>>,
+}
+
+impl ObsState {
+    pub fn check(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Streaming => self.streaming,
+            Condition::Recording => self.recording,
+            Condition::SceneIs(name) => self.current_scene.as_ref() == Some(name),
+            Condition::InputMuted(name) => self.muted.get(name).copied().unwrap_or(false),
+            Condition::FilterEnabled(source, filter) => self
+                .filters
+                .get(source)
+                .and_then(|filters| filters.get(filter))
+                .copied()
+                .unwrap_or(false),
+            Condition::Not(condition) => !self.check(condition),
+        }
+    }
+
+    pub fn apply(&mut self, info: &ObsInfo) {
+        match info {
+            ObsInfo::State(state) => *self = state.clone(),
+            ObsInfo::SceneChanged(name) => self.current_scene = Some(name.clone()),
+            ObsInfo::PreviewSceneChanged(name) => self.preview_scene = name.clone(),
+            ObsInfo::SceneList(scenes) => self.scenes = scenes.clone(),
+            ObsInfo::InputRemoved(name) => {
+                self.muted.remove(name);
+                self.volumes.remove(name);
+                self.filters.remove(name);
+                for items in self.scene_items.values_mut() {
+                    items.remove(name);
+                }
+            }
+            ObsInfo::InputRenamed(old_name, new_name) => {
+                if let Some(muted) = self.muted.remove(old_name) {
+                    self.muted.insert(new_name.clone(), muted);
+                }
+                if let Some(volume) = self.volumes.remove(old_name) {
+                    self.volumes.insert(new_name.clone(), volume);
+                }
+                if let Some(filters) = self.filters.remove(old_name) {
+                    self.filters.insert(new_name.clone(), filters);
+                }
+                for items in self.scene_items.values_mut() {
+                    if let Some(enabled) = items.remove(old_name) {
+                        items.insert(new_name.clone(), enabled);
+                    }
+                }
+            }
+            ObsInfo::MuteChanged(name, muted) => {
+                self.muted.insert(name.clone(), *muted);
+            }
+            ObsInfo::FilterChanged(source, filter, enabled) => {
+                self.filters
+                    .entry(source.clone())
+                    .or_default()
+                    .insert(filter.clone(), *enabled);
+            }
+            ObsInfo::SceneItemChanged(scene, source, enabled) => {
+                self.scene_items
+                    .entry(scene.clone())
+                    .or_default()
+                    .insert(source.clone(), *enabled);
+            }
+            ObsInfo::VolumeChanged(name, volume) => {
+                self.volumes.insert(name.clone(), *volume);
+            }
+            ObsInfo::IsoRecorders(recorders) => {
+                for recorder in recorders {
+                    self.filters
+                        .entry(recorder.source.clone())
+                        .or_default()
+                        .insert(recorder.filter.clone(), recorder.enabled);
+                }
+            }
+            ObsInfo::StreamState(active) => self.streaming = *active,
+            ObsInfo::RecordState(active) => self.recording = *active,
+            ObsInfo::Disconnected => *self = ObsState::default(),
+            ObsInfo::InputInfo(_)
+            | ObsInfo::OutputInfo(_)
+            | ObsInfo::Session(_)
+            | ObsInfo::Notice(_)
+            | ObsInfo::Marker(..)
+            | ObsInfo::Light(_)
+            | ObsInfo::HuePaired(_)
+            | ObsInfo::Slide(..)
+            | ObsInfo::VendorEvent(..)
+            | ObsInfo::Event(..)
+            | ObsInfo::Preview(_)
+            | ObsInfo::ConnectionLost
+            | ObsInfo::PendingActions(_)
+            | ObsInfo::RelayRunning(_)
+            | ObsInfo::Peer(_)
+            | ObsInfo::SessionJoined(_)
+            | ObsInfo::OscRunning(_)
+            | ObsInfo::MqttRunning(_)
+            | ObsInfo::RemoteRunning(_)
+            | ObsInfo::Rehearsal(_)
+            | ObsInfo::Recovery(_)
+            | ObsInfo::Error(_)
+            | ObsInfo::Response(..) => {}
+        }
+    }
+}
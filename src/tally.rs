@@ -0,0 +1,211 @@
+use crate::state::ObsState;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, net::SocketAddr, time::Duration};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream, UdpSocket},
+    sync::{broadcast, watch},
+};
+
+// UDP is lossy, so the whole state is repeated this often even without changes.
+const KEEPALIVE: Duration = Duration::from_secs(1);
+
+// The `[tally]` table of rec.toml.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TallyConfig {
+    // Camera id to the scene that shows it.
+    pub cameras: BTreeMap<String, String>,
+    // Where UDP datagrams go, usually a broadcast address.
+    pub udp_targets: Vec<SocketAddr>,
+    // Lights can also connect over TCP and keep the connection open.
+    pub tcp_port: Option<u16>,
+    // Camera id to sysfs GPIO pins, for lights wired to the machine itself.
+    pub gpio: BTreeMap<String, GpioPins>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GpioPins {
+    pub program: Option<u32>,
+    pub preview: Option<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tally {
+    Program,
+    Preview,
+    Off,
+}
+
+impl Tally {
+    fn name(self) -> &'static str {
+        match self {
+            Tally::Program => "program",
+            Tally::Preview => "preview",
+            Tally::Off => "off",
+        }
+    }
+}
+
+pub struct Sockets {
+    udp: Option<UdpSocket>,
+    tcp: Option<TcpListener>,
+}
+
+impl Sockets {
+    pub async fn bind(config: &TallyConfig) -> Result<Self> {
+        let udp = if config.udp_targets.is_empty() {
+            None
+        } else {
+            let socket = UdpSocket::bind(("0.0.0.0", 0))
+                .await
+                .context("failed to open tally udp socket")?;
+            socket
+                .set_broadcast(true)
+                .context("failed to enable udp broadcast")?;
+            Some(socket)
+        };
+        let tcp = match config.tcp_port {
+            Some(port) => Some(
+                TcpListener::bind(("0.0.0.0", port))
+                    .await
+                    .with_context(|| format!("failed to start tally server on port {}", port))?,
+            ),
+            None => None,
+        };
+        Ok(Self { udp, tcp })
+    }
+}
+
+// One line per camera, e.g. "1:program\n2:preview\n3:off\n", which is easy
+// to match on a microcontroller. Like the relay, the worker stops this by
+// aborting the task.
+pub async fn serve(config: TallyConfig, sockets: Sockets, mut state_rx: watch::Receiver<ObsState>) {
+    let (lines_tx, _) = broadcast::channel::<String>(16);
+    let mut tallies = compute(&config, &state_rx.borrow());
+    let mut message = format_message(&tallies);
+    set_gpio(&config, &tallies);
+    let mut keepalive = tokio::time::interval(KEEPALIVE);
+
+    loop {
+        tokio::select! {
+            changed = state_rx.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                let next = compute(&config, &state_rx.borrow_and_update());
+                if next == tallies {
+                    continue;
+                }
+                tallies = next;
+                message = format_message(&tallies);
+                tracing::debug!(message, "tally changed");
+                set_gpio(&config, &tallies);
+                send_udp(&sockets, &config, &message).await;
+                // Only fails when no light is connected.
+                let _ = lines_tx.send(message.clone());
+            }
+            _ = keepalive.tick() => send_udp(&sockets, &config, &message).await,
+            accepted = accept(&sockets.tcp) => match accepted {
+                Ok((stream, addr)) => {
+                    tracing::info!(%addr, "tally light connected");
+                    tokio::spawn(client(stream, message.clone(), lines_tx.subscribe()));
+                }
+                Err(err) => tracing::warn!("failed to accept tally connection: {}", err),
+            },
+        }
+    }
+}
+
+async fn accept(listener: &Option<TcpListener>) -> std::io::Result<(TcpStream, SocketAddr)> {
+    match listener {
+        Some(listener) => listener.accept().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn client(mut stream: TcpStream, current: String, mut lines_rx: broadcast::Receiver<String>) {
+    let mut message = current;
+    loop {
+        if stream.write_all(message.as_bytes()).await.is_err() {
+            return;
+        }
+        message = match lines_rx.recv().await {
+            Ok(message) => message,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+    }
+}
+
+fn compute(config: &TallyConfig, state: &ObsState) -> Vec<(String, Tally)> {
+    config
+        .cameras
+        .iter()
+        .map(|(camera, scene)| {
+            let tally = if state.current_scene.as_ref() == Some(scene) {
+                Tally::Program
+            } else if state.preview_scene.as_ref() == Some(scene) {
+                Tally::Preview
+            } else {
+                Tally::Off
+            };
+            (camera.clone(), tally)
+        })
+        .collect()
+}
+
+fn format_message(tallies: &[(String, Tally)]) -> String {
+    tallies
+        .iter()
+        .map(|(camera, tally)| format!("{}:{}\n", camera, tally.name()))
+        .collect()
+}
+
+async fn send_udp(sockets: &Sockets, config: &TallyConfig, message: &str) {
+    let Some(socket) = &sockets.udp else {
+        return;
+    };
+    for target in &config.udp_targets {
+        if let Err(err) = socket.send_to(message.as_bytes(), target).await {
+            tracing::warn!(%target, "failed to send tally: {}", err);
+        }
+    }
+}
+
+fn set_gpio(config: &TallyConfig, tallies: &[(String, Tally)]) {
+    for (camera, tally) in tallies {
+        let Some(pins) = config.gpio.get(camera) else {
+            continue;
+        };
+        for (pin, on) in [
+            (pins.program, *tally == Tally::Program),
+            (pins.preview, *tally == Tally::Preview),
+        ] {
+            if let Some(pin) = pin {
+                if let Err(err) = set_pin(pin, on) {
+                    tracing::warn!(pin, "failed to set tally gpio: {:#}", err);
+                }
+            }
+        }
+    }
+}
+
+// The sysfs interface is deprecated but present on every Raspberry Pi image,
+// and needs nothing beyond file writes.
+#[cfg(target_os = "linux")]
+fn set_pin(pin: u32, on: bool) -> Result<()> {
+    let dir = std::path::PathBuf::from(format!("/sys/class/gpio/gpio{}", pin));
+    if !dir.exists() {
+        std::fs::write("/sys/class/gpio/export", pin.to_string())
+            .context("failed to export pin")?;
+        std::fs::write(dir.join("direction"), "out").context("failed to set direction")?;
+    }
+    std::fs::write(dir.join("value"), if on { "1" } else { "0" }).context("failed to write pin")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_pin(_pin: u32, _on: bool) -> Result<()> {
+    anyhow::bail!("gpio is only supported on linux")
+}
@@ -0,0 +1,235 @@
+use crate::{
+    audio::{AudioPreset, DuckingPair},
+    config::Cue,
+    obs_worker::{Action, Macro, MacroStep},
+    state::Condition,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+// Follow whichever input is picked for them, so they're never remapped.
+const ALIASES: [&str; 2] = ["mic", "desktop"];
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Scene,
+    // An input, or a scene used as a source (filters, scene items).
+    Source,
+}
+
+// Everything that makes up a show but not the night it runs on, to start the
+// next event from. Scenes and inputs are kept by name and mapped onto the
+// scene collection it's opened against.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShowTemplate {
+    pub name: String,
+    pub macros: Vec<Macro>,
+    pub rundown: Vec<Cue>,
+    pub audio_presets: Vec<AudioPreset>,
+    pub ducking: Vec<DuckingPair>,
+    // Address and port of OBS; the password is never written.
+    pub connection: Option<(String, String)>,
+}
+
+// Scenes and sources a template uses that the scene collection doesn't have.
+#[derive(Default)]
+pub struct Missing {
+    pub scenes: BTreeSet<String>,
+    pub sources: BTreeSet<String>,
+}
+
+impl Missing {
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty() && self.sources.is_empty()
+    }
+}
+
+fn dir() -> Result<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "rec").context("no home directory")?;
+    Ok(dirs.data_dir().join("shows"))
+}
+
+fn path(name: &str) -> Result<PathBuf> {
+    let file = name.replace(['/', '\\'], "-");
+    Ok(dir()?.join(format!("{}.json", file)))
+}
+
+// Names of the saved templates, sorted.
+pub fn list() -> Vec<String> {
+    let Ok(entries) = dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+impl ShowTemplate {
+    pub fn save(&self) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir()?).context("failed to create show template directory")?;
+        let path = path(&self.name)?;
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn load(name: &str) -> Result<Self> {
+        let path = path(name)?;
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("invalid show template {}", name))
+    }
+
+    pub fn missing(&mut self, scenes: &[String], inputs: &[String]) -> Missing {
+        let mut missing = Missing::default();
+        self.visit(&mut |kind, name| match kind {
+            Kind::Scene if !scenes.contains(name) => {
+                missing.scenes.insert(name.clone());
+            }
+            Kind::Source if !inputs.contains(name) && !scenes.contains(name) => {
+                missing.sources.insert(name.clone());
+            }
+            _ => {}
+        });
+        missing
+    }
+
+    // Renames scenes and sources throughout; names not in the maps stay.
+    pub fn remap(&mut self, scenes: &BTreeMap<String, String>, sources: &BTreeMap<String, String>) {
+        self.visit(&mut |kind, name| {
+            let map = match kind {
+                Kind::Scene => scenes,
+                Kind::Source => sources,
+            };
+            if let Some(new) = map.get(name.as_str()) {
+                *name = new.clone();
+            }
+        });
+    }
+
+    fn visit(&mut self, visit: &mut dyn FnMut(Kind, &mut String)) {
+        // Aliases are skipped here once rather than by every visitor.
+        let mut visit = |kind, name: &mut String| {
+            if !ALIASES.contains(&name.as_str()) {
+                visit(kind, name);
+            }
+        };
+        for obs_macro in &mut self.macros {
+            visit_steps(&mut obs_macro.steps, &mut visit);
+        }
+        for cue in &mut self.rundown {
+            if let Some(scene) = &mut cue.scene {
+                visit(Kind::Scene, scene);
+            }
+            cue.audio = visit_keys(std::mem::take(&mut cue.audio), &mut visit);
+            for (input, _) in &mut cue.texts {
+                visit(Kind::Source, input);
+            }
+            for input in &mut cue.media {
+                visit(Kind::Source, input);
+            }
+        }
+        for preset in &mut self.audio_presets {
+            preset.inputs = visit_keys(std::mem::take(&mut preset.inputs), &mut visit);
+        }
+        for pair in &mut self.ducking {
+            visit(Kind::Source, &mut pair.ducked);
+            visit(Kind::Source, &mut pair.trigger);
+        }
+    }
+}
+
+fn visit_keys<T>(
+    map: BTreeMap<String, T>,
+    visit: &mut dyn FnMut(Kind, &mut String),
+) -> BTreeMap<String, T> {
+    map.into_iter()
+        .map(|(mut input, value)| {
+            visit(Kind::Source, &mut input);
+            (input, value)
+        })
+        .collect()
+}
+
+fn visit_steps(steps: &mut [MacroStep], visit: &mut dyn FnMut(Kind, &mut String)) {
+    for step in steps {
+        match step {
+            MacroStep::Do(action) => visit_action(action, visit),
+            MacroStep::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                visit_condition(condition, visit);
+                visit_steps(then, visit);
+                visit_steps(otherwise, visit);
+            }
+        }
+    }
+}
+
+fn visit_condition(condition: &mut Condition, visit: &mut dyn FnMut(Kind, &mut String)) {
+    match condition {
+        Condition::SceneIs(scene) => visit(Kind::Scene, scene),
+        Condition::InputMuted(source) | Condition::FilterEnabled(source, _) => {
+            visit(Kind::Source, source)
+        }
+        Condition::Not(condition) => visit_condition(condition, visit),
+        Condition::Streaming | Condition::Recording => {}
+    }
+}
+
+fn visit_action(action: &mut Action, visit: &mut dyn FnMut(Kind, &mut String)) {
+    match action {
+        Action::SetScene(scene)
+        | Action::SetPreviewScene(scene)
+        | Action::CreateInput(scene, ..) => visit(Kind::Scene, scene),
+        Action::SetMute(source, _)
+        | Action::SetVolume(source, _)
+        | Action::SetText(source, _)
+        | Action::SetInputSettings(source, _)
+        | Action::PressInputButton(source, _)
+        | Action::ReactivateInput(source, _)
+        | Action::SetFilterEnabled(source, ..)
+        | Action::ToggleFilter(source, _)
+        | Action::Media(source, _)
+        | Action::CalibrateGate(source)
+        | Action::SetGateThresholds(source, ..)
+        | Action::AddVoiceChain(source)
+        | Action::StopDucking(source)
+        | Action::OpenInteract(source) => visit(Kind::Source, source),
+        Action::InstantReplay(source, scene) => {
+            visit(Kind::Source, source);
+            visit(Kind::Scene, scene);
+        }
+        Action::SetSceneItemEnabled(scene, source, _) => {
+            visit(Kind::Scene, scene);
+            visit(Kind::Source, source);
+        }
+        Action::SetDucking(pair) => {
+            visit(Kind::Source, &mut pair.ducked);
+            visit(Kind::Source, &mut pair.trigger);
+        }
+        Action::SceneCue(cue) => {
+            if let Some(scene) = &mut cue.scene {
+                visit(Kind::Scene, scene);
+            }
+            cue.audio = visit_keys(std::mem::take(&mut cue.audio), visit);
+        }
+        Action::RunMacro(obs_macro) => visit_steps(&mut obs_macro.steps, visit),
+        _ => {}
+    }
+}
@@ -0,0 +1,133 @@
+use egui::{vec2, Color32, Stroke, Style, Visuals};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ThemeKind {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    Custom,
+}
+
+impl ThemeKind {
+    pub const ALL: [ThemeKind; 4] = [
+        ThemeKind::Dark,
+        ThemeKind::Light,
+        ThemeKind::HighContrast,
+        ThemeKind::Custom,
+    ];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            ThemeKind::Dark => "theme-dark",
+            ThemeKind::Light => "theme-light",
+            ThemeKind::HighContrast => "theme-high-contrast",
+            ThemeKind::Custom => "theme-custom",
+        }
+    }
+}
+
+// The custom colors are kept while another theme is selected so switching back
+// doesn't lose them.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub kind: ThemeKind,
+    pub accent: [u8; 3],
+    pub background: [u8; 3],
+    pub slider: [u8; 3],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            kind: ThemeKind::Dark,
+            accent: [0, 92, 128],
+            background: [27, 27, 27],
+            slider: [0, 160, 90],
+        }
+    }
+}
+
+fn rgb([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+impl Theme {
+    pub fn visuals(&self) -> Visuals {
+        match self.kind {
+            ThemeKind::Dark => Visuals::dark(),
+            ThemeKind::Light => Visuals::light(),
+            ThemeKind::HighContrast => high_contrast(),
+            ThemeKind::Custom => {
+                let [r, g, b] = self.background;
+                let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+                let mut visuals = if luminance < 128.0 {
+                    Visuals::dark()
+                } else {
+                    Visuals::light()
+                };
+                let accent = rgb(self.accent);
+                let background = rgb(self.background);
+                visuals.selection.bg_fill = accent;
+                visuals.hyperlink_color = accent;
+                visuals.widgets.active.bg_fill = accent;
+                visuals.panel_fill = background;
+                visuals.window_fill = background;
+                visuals
+            }
+        }
+    }
+
+    // Used for the filled part of the mixer sliders.
+    pub fn slider_color(&self) -> Color32 {
+        match self.kind {
+            ThemeKind::Custom => rgb(self.slider),
+            ThemeKind::HighContrast => Color32::BLACK,
+            _ => self.visuals().selection.bg_fill,
+        }
+    }
+}
+
+// Finger-sized targets for small touchscreens. Sliders get thicker with
+// `interact_size` and egui already jumps them to wherever they are touched.
+pub fn touch_style(style: &mut Style) {
+    style.spacing.interact_size = vec2(64.0, 48.0);
+    style.spacing.button_padding = vec2(16.0, 12.0);
+    style.spacing.item_spacing = vec2(12.0, 12.0);
+    style.spacing.slider_width = 240.0;
+    style.spacing.icon_width = 28.0;
+    style.spacing.icon_width_inner = 16.0;
+    style.spacing.icon_spacing = 8.0;
+    for font in style.text_styles.values_mut() {
+        font.size *= 1.3;
+    }
+}
+
+// Black on white with thick outlines, readable under stage lighting.
+fn high_contrast() -> Visuals {
+    let mut visuals = Visuals::light();
+    visuals.override_text_color = Some(Color32::BLACK);
+    visuals.panel_fill = Color32::WHITE;
+    visuals.window_fill = Color32::WHITE;
+    visuals.extreme_bg_color = Color32::WHITE;
+    visuals.selection.bg_fill = Color32::from_rgb(255, 210, 0);
+    visuals.selection.stroke = Stroke::new(2.0, Color32::BLACK);
+    visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.5, Color32::BLACK);
+    visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.5, Color32::BLACK);
+    for widget in [
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        widget.bg_stroke = Stroke::new(2.0, Color32::BLACK);
+        widget.fg_stroke = Stroke::new(2.0, Color32::BLACK);
+    }
+    visuals.widgets.inactive.bg_fill = Color32::from_gray(230);
+    visuals.widgets.inactive.weak_bg_fill = Color32::from_gray(230);
+    visuals.widgets.hovered.bg_fill = Color32::from_rgb(255, 230, 120);
+    visuals.widgets.hovered.weak_bg_fill = Color32::from_rgb(255, 230, 120);
+    visuals
+}
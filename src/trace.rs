@@ -0,0 +1,217 @@
+use crate::{
+    journal,
+    obs_worker::{Action, ObsInfo},
+    source_record::IsoRecorder,
+    state::ObsState,
+};
+use anyhow::{Context, Result};
+use chrono::Local;
+use obws::responses::inputs::Input;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+// The part of what the worker reports that the UI's state is built from.
+// Preview frames, lights and query responses are left out: they're large,
+// tied to this machine, or only make sense next to a live OBS.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum TracedInfo {
+    InputInfo(Vec<Input>),
+    State(ObsState),
+    SceneChanged(String),
+    PreviewSceneChanged(Option<String>),
+    SceneList(Vec<String>),
+    InputRemoved(String),
+    InputRenamed(String, String),
+    MuteChanged(String, bool),
+    FilterChanged(String, String, bool),
+    SceneItemChanged(String, String, bool),
+    Event(String, String),
+    VendorEvent(String, String, serde_json::Value),
+    VolumeChanged(String, f32),
+    StreamState(bool),
+    RecordState(bool),
+    Disconnected,
+    ConnectionLost,
+    PendingActions(usize),
+    Rehearsal(bool),
+    IsoRecorders(Vec<IsoRecorder>),
+    Error(String),
+}
+
+impl TracedInfo {
+    fn from_info(info: &ObsInfo) -> Option<Self> {
+        Some(match info {
+            ObsInfo::InputInfo(inputs) => TracedInfo::InputInfo(inputs.clone()),
+            ObsInfo::State(state) => TracedInfo::State(state.clone()),
+            ObsInfo::SceneChanged(scene) => TracedInfo::SceneChanged(scene.clone()),
+            ObsInfo::PreviewSceneChanged(scene) => TracedInfo::PreviewSceneChanged(scene.clone()),
+            ObsInfo::SceneList(scenes) => TracedInfo::SceneList(scenes.clone()),
+            ObsInfo::InputRemoved(input) => TracedInfo::InputRemoved(input.clone()),
+            ObsInfo::InputRenamed(old, new) => TracedInfo::InputRenamed(old.clone(), new.clone()),
+            ObsInfo::MuteChanged(input, muted) => TracedInfo::MuteChanged(input.clone(), *muted),
+            ObsInfo::FilterChanged(source, filter, enabled) => {
+                TracedInfo::FilterChanged(source.clone(), filter.clone(), *enabled)
+            }
+            ObsInfo::SceneItemChanged(scene, source, enabled) => {
+                TracedInfo::SceneItemChanged(scene.clone(), source.clone(), *enabled)
+            }
+            ObsInfo::Event(name, data) => TracedInfo::Event(name.clone(), data.clone()),
+            ObsInfo::VendorEvent(vendor, event, data) => {
+                TracedInfo::VendorEvent(vendor.clone(), event.clone(), data.clone())
+            }
+            ObsInfo::VolumeChanged(input, volume) => {
+                TracedInfo::VolumeChanged(input.clone(), *volume)
+            }
+            ObsInfo::StreamState(active) => TracedInfo::StreamState(*active),
+            ObsInfo::RecordState(active) => TracedInfo::RecordState(*active),
+            ObsInfo::Disconnected => TracedInfo::Disconnected,
+            ObsInfo::ConnectionLost => TracedInfo::ConnectionLost,
+            ObsInfo::PendingActions(count) => TracedInfo::PendingActions(*count),
+            ObsInfo::Rehearsal(rehearsal) => TracedInfo::Rehearsal(*rehearsal),
+            ObsInfo::IsoRecorders(recorders) => TracedInfo::IsoRecorders(recorders.clone()),
+            ObsInfo::Error(err) => TracedInfo::Error(err.clone()),
+            _ => return None,
+        })
+    }
+
+    pub fn into_info(self) -> ObsInfo {
+        match self {
+            TracedInfo::InputInfo(inputs) => ObsInfo::InputInfo(inputs),
+            TracedInfo::State(state) => ObsInfo::State(state),
+            TracedInfo::SceneChanged(scene) => ObsInfo::SceneChanged(scene),
+            TracedInfo::PreviewSceneChanged(scene) => ObsInfo::PreviewSceneChanged(scene),
+            TracedInfo::SceneList(scenes) => ObsInfo::SceneList(scenes),
+            TracedInfo::InputRemoved(input) => ObsInfo::InputRemoved(input),
+            TracedInfo::InputRenamed(old, new) => ObsInfo::InputRenamed(old, new),
+            TracedInfo::MuteChanged(input, muted) => ObsInfo::MuteChanged(input, muted),
+            TracedInfo::FilterChanged(source, filter, enabled) => {
+                ObsInfo::FilterChanged(source, filter, enabled)
+            }
+            TracedInfo::SceneItemChanged(scene, source, enabled) => {
+                ObsInfo::SceneItemChanged(scene, source, enabled)
+            }
+            TracedInfo::Event(name, data) => ObsInfo::Event(name, data),
+            TracedInfo::VendorEvent(vendor, event, data) => {
+                ObsInfo::VendorEvent(vendor, event, data)
+            }
+            TracedInfo::VolumeChanged(input, volume) => ObsInfo::VolumeChanged(input, volume),
+            TracedInfo::StreamState(active) => ObsInfo::StreamState(active),
+            TracedInfo::RecordState(active) => ObsInfo::RecordState(active),
+            TracedInfo::Disconnected => ObsInfo::Disconnected,
+            TracedInfo::ConnectionLost => ObsInfo::ConnectionLost,
+            TracedInfo::PendingActions(count) => ObsInfo::PendingActions(count),
+            TracedInfo::Rehearsal(rehearsal) => ObsInfo::Rehearsal(rehearsal),
+            TracedInfo::IsoRecorders(recorders) => ObsInfo::IsoRecorders(recorders),
+            TracedInfo::Error(err) => ObsInfo::Error(err),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Traced {
+    // Sent by the UI; shown on replay but never run.
+    Action(Action),
+    Info(TracedInfo),
+}
+
+// One line of a trace file.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TraceEntry {
+    // Since recording started.
+    pub at_ms: u64,
+    pub traced: Traced,
+}
+
+fn dir() -> Result<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "rec").context("no home directory")?;
+    Ok(dirs.data_dir().join("traces"))
+}
+
+// Newest first.
+pub fn list() -> Vec<PathBuf> {
+    let Ok(entries) = dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    paths.sort();
+    paths.reverse();
+    paths
+}
+
+pub fn load(path: &Path) -> Result<Vec<TraceEntry>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .map(|(number, line)| {
+            serde_json::from_str(&line?)
+                .with_context(|| format!("{} line {}", path.display(), number + 1))
+        })
+        .collect()
+}
+
+// Writes every action and report as it passes, passwords left out, so a bug
+// report can carry the session that led up to it.
+pub struct Recorder {
+    file: BufWriter<File>,
+    started: Instant,
+    pub path: PathBuf,
+}
+
+impl Recorder {
+    pub fn start() -> Result<Self> {
+        let dir = dir()?;
+        std::fs::create_dir_all(&dir).context("failed to create trace directory")?;
+        let path = dir.join(format!(
+            "trace-{}.jsonl",
+            Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        let file =
+            File::create(&path).with_context(|| format!("failed to create {}", path.display()))?;
+        tracing::info!(path = %path.display(), "trace recording started");
+        Ok(Self {
+            file: BufWriter::new(file),
+            started: Instant::now(),
+            path,
+        })
+    }
+
+    fn write(&mut self, traced: Traced) {
+        let entry = TraceEntry {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            traced,
+        };
+        let written = serde_json::to_string(&entry)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| Ok(writeln!(self.file, "{}", line)?));
+        if let Err(err) = written {
+            tracing::warn!("failed to write trace: {:#}", err);
+        }
+    }
+
+    pub fn action(&mut self, action: &Action) {
+        self.write(Traced::Action(journal::redact(action.clone())));
+    }
+
+    pub fn info(&mut self, info: &ObsInfo) {
+        if let Some(info) = TracedInfo::from_info(info) {
+            self.write(Traced::Info(info));
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(err) = self.file.flush() {
+            tracing::warn!("failed to finish trace: {}", err);
+        }
+    }
+}
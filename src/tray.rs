@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use std::sync::mpsc;
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    Icon, TrayIcon, TrayIconBuilder,
+};
+
+const ICON_SIZE: u32 = 32;
+
+pub enum TrayCommand {
+    ToggleMute,
+    ToggleRecord,
+    ToggleWindow,
+    Quit,
+}
+
+// Menu text comes in with the status so the tray follows the UI language.
+#[derive(Clone, PartialEq)]
+pub struct TrayStatus {
+    pub streaming: bool,
+    pub recording: bool,
+    pub tooltip: String,
+    pub mute_label: String,
+    pub record_label: String,
+    pub window_label: String,
+    pub quit_label: String,
+}
+
+// A filled circle, so no icon assets need to ship with the binary.
+fn status_icon(streaming: bool, recording: bool) -> Result<Icon> {
+    let [r, g, b] = match (streaming, recording) {
+        (true, _) => [220, 30, 30],
+        (false, true) => [240, 140, 0],
+        (false, false) => [128, 128, 128],
+    };
+    let center = ICON_SIZE as f32 / 2.0;
+    let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let distance =
+                ((x as f32 + 0.5 - center).powi(2) + (y as f32 + 0.5 - center).powi(2)).sqrt();
+            let alpha = if distance <= center - 1.0 { 255 } else { 0 };
+            rgba.extend_from_slice(&[r, g, b, alpha]);
+        }
+    }
+    Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).context("failed to build tray icon")
+}
+
+struct TrayItems {
+    icon: TrayIcon,
+    mute: MenuItem,
+    record: MenuItem,
+    window: MenuItem,
+    quit: MenuItem,
+}
+
+impl TrayItems {
+    // The labels stay empty until the first status arrives.
+    fn build() -> Result<Self> {
+        let mute = MenuItem::with_id("mute", "", true, None);
+        let record = MenuItem::with_id("record", "", true, None);
+        let window = MenuItem::with_id("window", "", true, None);
+        let quit = MenuItem::with_id("quit", "", true, None);
+        let menu = Menu::new();
+        menu.append_items(&[
+            &mute,
+            &record,
+            &PredefinedMenuItem::separator(),
+            &window,
+            &quit,
+        ])
+        .context("failed to build tray menu")?;
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("REC")
+            .with_icon(status_icon(false, false)?)
+            .build()
+            .context("failed to create tray icon")?;
+        Ok(Self {
+            icon,
+            mute,
+            record,
+            window,
+            quit,
+        })
+    }
+
+    fn apply(&self, status: &TrayStatus) {
+        let result = status_icon(status.streaming, status.recording).and_then(|icon| {
+            self.icon.set_icon(Some(icon))?;
+            self.icon.set_tooltip(Some(&status.tooltip))?;
+            Ok(())
+        });
+        if let Err(err) = result {
+            tracing::warn!("failed to update tray icon: {:#}", err);
+        }
+        self.mute.set_text(&status.mute_label);
+        self.record.set_text(&status.record_label);
+        self.window.set_text(&status.window_label);
+        self.quit.set_text(&status.quit_label);
+    }
+}
+
+pub struct Tray {
+    #[cfg(not(target_os = "linux"))]
+    items: TrayItems,
+    #[cfg(target_os = "linux")]
+    status_tx: mpsc::Sender<TrayStatus>,
+    command_rx: mpsc::Receiver<TrayCommand>,
+    last_status: Option<TrayStatus>,
+}
+
+impl Tray {
+    pub fn new(ctx: egui::Context) -> Result<Self> {
+        let (command_tx, command_rx) = mpsc::channel();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            let command = match event.id.0.as_str() {
+                "mute" => TrayCommand::ToggleMute,
+                "record" => TrayCommand::ToggleRecord,
+                "window" => TrayCommand::ToggleWindow,
+                "quit" => TrayCommand::Quit,
+                _ => return,
+            };
+            let _ = command_tx.send(command);
+            ctx.request_repaint();
+        }));
+
+        #[cfg(not(target_os = "linux"))]
+        let tray = Self {
+            items: TrayItems::build()?,
+            command_rx,
+            last_status: None,
+        };
+        #[cfg(target_os = "linux")]
+        let tray = Self {
+            status_tx: spawn_gtk()?,
+            command_rx,
+            last_status: None,
+        };
+        Ok(tray)
+    }
+
+    pub fn update(&mut self, status: TrayStatus) {
+        if self.last_status.as_ref() == Some(&status) {
+            return;
+        }
+        #[cfg(not(target_os = "linux"))]
+        self.items.apply(&status);
+        #[cfg(target_os = "linux")]
+        let _ = self.status_tx.send(status.clone());
+        self.last_status = Some(status);
+    }
+
+    pub fn commands(&self) -> Vec<TrayCommand> {
+        self.command_rx.try_iter().collect()
+    }
+}
+
+// On Linux the tray lives on a GTK main loop, which can't share the winit
+// thread, so it gets a thread of its own and is fed status updates.
+#[cfg(target_os = "linux")]
+fn spawn_gtk() -> Result<mpsc::Sender<TrayStatus>> {
+    let (status_tx, status_rx) = mpsc::channel::<TrayStatus>();
+    let (ready_tx, ready_rx) = mpsc::channel::<Result<()>>();
+    std::thread::spawn(move || {
+        let items = gtk::init()
+            .context("failed to initialize gtk")
+            .and_then(|()| TrayItems::build());
+        let items = match items {
+            Ok(items) => {
+                let _ = ready_tx.send(Ok(()));
+                items
+            }
+            Err(err) => {
+                let _ = ready_tx.send(Err(err));
+                return;
+            }
+        };
+        gtk::glib::timeout_add_local(std::time::Duration::from_millis(200), move || {
+            while let Ok(status) = status_rx.try_recv() {
+                items.apply(&status);
+            }
+            gtk::glib::ControlFlow::Continue
+        });
+        gtk::main();
+    });
+    ready_rx.recv().context("tray thread exited")??;
+    Ok(status_tx)
+}
@@ -0,0 +1,390 @@
+use crate::{chat::ChatCommand, obs_worker::Notifier};
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+// Accounts logged in before clips were supported need to log in again.
+const SCOPES: &str = "channel:manage:broadcast clips:edit chat:read user:write:chat";
+const DEVICE_URL: &str = "https://id.twitch.tv/oauth2/device";
+const TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+const VALIDATE_URL: &str = "https://id.twitch.tv/oauth2/validate";
+const HELIX_URL: &str = "https://api.twitch.tv/helix";
+
+// The `[twitch]` table of rec.toml. REC has no client id of its own, so the
+// operator registers an application with Twitch and puts its id here.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TwitchConfig {
+    pub client_id: Option<String>,
+    // Scene name to the title and category to switch to when it goes live.
+    pub scenes: BTreeMap<String, ChannelInfo>,
+    pub commands: Vec<ChatCommand>,
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelInfo {
+    #[serde(default)]
+    pub title: String,
+    // Category (game) name as Twitch spells it. Empty leaves it unchanged.
+    #[serde(default)]
+    pub category: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Auth {
+    pub client_id: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub user_id: String,
+    pub login: String,
+}
+
+pub enum Request {
+    LogIn(String),
+    Restore(Auth),
+    LogOut,
+    Update(ChannelInfo),
+    Search(String),
+    Marker(String),
+    Clip,
+    // A message in the channel's own chat, sent as the broadcaster.
+    Chat(String),
+}
+
+pub enum TwitchInfo {
+    DeviceCode {
+        user_code: String,
+        verification_uri: String,
+    },
+    LoggedIn(Auth),
+    LoggedOut,
+    Channel(ChannelInfo),
+    Categories(Vec<String>),
+    // Where the marker landed in the live stream.
+    Marker(Duration),
+    // Edit URL of a new clip.
+    Clip(String),
+    Error(String),
+}
+
+// Twitch calls are blocking, so they get their own thread rather than
+// holding up the OBS worker.
+pub fn spawn(notify: Notifier) -> (mpsc::Sender<Request>, mpsc::Receiver<TwitchInfo>) {
+    let (request_tx, request_rx) = mpsc::channel();
+    let (info_tx, info_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut client = Client {
+            auth: None,
+            info_tx,
+            notify,
+        };
+        while let Ok(request) = request_rx.recv() {
+            if let Err(err) = client.handle(request, &request_rx) {
+                tracing::warn!("twitch: {:#}", err);
+                client.send(TwitchInfo::Error(format!("{:#}", err)));
+            }
+        }
+    });
+    (request_tx, info_rx)
+}
+
+struct Client {
+    auth: Option<Auth>,
+    info_tx: mpsc::Sender<TwitchInfo>,
+    notify: Notifier,
+}
+
+#[derive(Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: u64,
+    interval: u64,
+}
+
+#[derive(Deserialize)]
+struct Token {
+    access_token: String,
+    refresh_token: String,
+}
+
+#[derive(Deserialize)]
+struct Validation {
+    login: String,
+    user_id: String,
+}
+
+#[derive(Deserialize)]
+struct Data<T> {
+    data: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct Channel {
+    title: String,
+    game_name: String,
+}
+
+#[derive(Deserialize)]
+struct Marker {
+    position_seconds: u64,
+}
+
+#[derive(Deserialize)]
+struct Clip {
+    edit_url: String,
+}
+
+#[derive(Deserialize)]
+struct Category {
+    id: String,
+    name: String,
+}
+
+impl Client {
+    fn send(&self, info: TwitchInfo) {
+        if self.info_tx.send(info).is_ok() {
+            (self.notify)();
+        }
+    }
+
+    fn handle(&mut self, request: Request, request_rx: &mpsc::Receiver<Request>) -> Result<()> {
+        match request {
+            Request::LogIn(client_id) => self.log_in(client_id, request_rx)?,
+            Request::Restore(auth) => {
+                self.auth = Some(auth);
+                self.fetch_channel()?;
+            }
+            Request::LogOut => {
+                self.auth = None;
+                self.send(TwitchInfo::LoggedOut);
+            }
+            Request::Update(info) => {
+                let user_id = self.auth()?.user_id.clone();
+                let mut body = serde_json::Map::new();
+                if !info.title.is_empty() {
+                    body.insert("title".into(), info.title.clone().into());
+                }
+                if !info.category.is_empty() {
+                    let query = [("name", info.category.as_str())];
+                    let categories: Data<Category> = self.helix_get("/games", &query)?;
+                    let Some(category) = categories.data.into_iter().next() else {
+                        bail!("no Twitch category named \"{}\"", info.category);
+                    };
+                    body.insert("game_id".into(), category.id.into());
+                }
+                let path = format!("/channels?broadcaster_id={}", user_id);
+                self.helix(|auth| {
+                    helix_request("PATCH", &path, auth)
+                        .set("Content-Type", "application/json")
+                        .send_string(&serde_json::Value::Object(body.clone()).to_string())
+                })?;
+                tracing::info!(
+                    title = info.title,
+                    category = info.category,
+                    "updated twitch channel"
+                );
+                self.fetch_channel()?;
+            }
+            Request::Search(query) => {
+                let query = [("query", query.as_str()), ("first", "10")];
+                let categories: Data<Category> = self.helix_get("/search/categories", &query)?;
+                self.send(TwitchInfo::Categories(
+                    categories
+                        .data
+                        .into_iter()
+                        .map(|category| category.name)
+                        .collect(),
+                ));
+            }
+            // Twitch only accepts markers while the channel is live.
+            Request::Marker(description) => {
+                let user_id = self.auth()?.user_id.clone();
+                let body = serde_json::json!({
+                    "user_id": user_id,
+                    "description": description,
+                });
+                let response = self.helix(|auth| {
+                    helix_request("POST", "/streams/markers", auth)
+                        .set("Content-Type", "application/json")
+                        .send_string(&body.to_string())
+                })?;
+                let markers: Data<Marker> = json(response)?;
+                if let Some(marker) = markers.data.into_iter().next() {
+                    self.send(TwitchInfo::Marker(Duration::from_secs(
+                        marker.position_seconds,
+                    )));
+                }
+            }
+            Request::Chat(message) => {
+                let user_id = self.auth()?.user_id.clone();
+                let body = serde_json::json!({
+                    "broadcaster_id": user_id,
+                    "sender_id": user_id,
+                    "message": message,
+                });
+                self.helix(|auth| {
+                    helix_request("POST", "/chat/messages", auth)
+                        .set("Content-Type", "application/json")
+                        .send_string(&body.to_string())
+                })?;
+            }
+            Request::Clip => {
+                let path = format!("/clips?broadcaster_id={}", self.auth()?.user_id);
+                let response = self.helix(|auth| helix_request("POST", &path, auth).call())?;
+                let clips: Data<Clip> = json(response)?;
+                let clip = clips
+                    .data
+                    .into_iter()
+                    .next()
+                    .context("Twitch made no clip")?;
+                tracing::info!(url = clip.edit_url, "created twitch clip");
+                self.send(TwitchInfo::Clip(clip.edit_url));
+            }
+        }
+        Ok(())
+    }
+
+    // The device flow: the operator enters a short code on twitch.tv/activate
+    // while this polls for the token. Logging out cancels the wait.
+    fn log_in(&mut self, client_id: String, request_rx: &mpsc::Receiver<Request>) -> Result<()> {
+        let code: DeviceCode = read(
+            ureq::post(DEVICE_URL)
+                .send_form(&[("client_id", client_id.as_str()), ("scopes", SCOPES)]),
+        )
+        .context("failed to start Twitch login")?;
+        self.send(TwitchInfo::DeviceCode {
+            user_code: code.user_code.clone(),
+            verification_uri: code.verification_uri.clone(),
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(code.expires_in);
+        let token = loop {
+            match request_rx.recv_timeout(Duration::from_secs(code.interval.max(1))) {
+                Ok(Request::LogOut) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    self.send(TwitchInfo::LoggedOut);
+                    return Ok(());
+                }
+                Ok(_) => tracing::debug!("ignoring twitch request while logging in"),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+            }
+            if Instant::now() > deadline {
+                bail!("the Twitch login code expired");
+            }
+            match ureq::post(TOKEN_URL).send_form(&[
+                ("client_id", client_id.as_str()),
+                ("scopes", SCOPES),
+                ("device_code", &code.device_code),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ]) {
+                Ok(response) => break json::<Token>(response)?,
+                // Still waiting for the operator.
+                Err(ureq::Error::Status(400, _)) => {}
+                Err(err) => return Err(err).context("failed to finish Twitch login"),
+            }
+        };
+
+        let validation: Validation = read(
+            ureq::get(VALIDATE_URL)
+                .set("Authorization", &format!("OAuth {}", token.access_token))
+                .call(),
+        )
+        .context("failed to validate Twitch token")?;
+        tracing::info!(login = validation.login, "logged in to twitch");
+        let auth = Auth {
+            client_id,
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            user_id: validation.user_id,
+            login: validation.login,
+        };
+        self.auth = Some(auth.clone());
+        self.send(TwitchInfo::LoggedIn(auth));
+        self.fetch_channel()
+    }
+
+    fn fetch_channel(&mut self) -> Result<()> {
+        let user_id = self.auth()?.user_id.clone();
+        let channels: Data<Channel> =
+            self.helix_get("/channels", &[("broadcaster_id", user_id.as_str())])?;
+        if let Some(channel) = channels.data.into_iter().next() {
+            self.send(TwitchInfo::Channel(ChannelInfo {
+                title: channel.title,
+                category: channel.game_name,
+            }));
+        }
+        Ok(())
+    }
+
+    fn auth(&self) -> Result<&Auth> {
+        self.auth.as_ref().context("not logged in to Twitch")
+    }
+
+    fn helix_get<T: DeserializeOwned>(&mut self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        let response = self.helix(|auth| {
+            query
+                .iter()
+                .fold(helix_request("GET", path, auth), |request, (key, value)| {
+                    request.query(key, value)
+                })
+                .call()
+        })?;
+        json(response)
+    }
+
+    // Access tokens expire after a few hours; a 401 refreshes once and retries.
+    fn helix(
+        &mut self,
+        call: impl Fn(&Auth) -> Result<ureq::Response, ureq::Error>,
+    ) -> Result<ureq::Response> {
+        match call(self.auth()?) {
+            Err(ureq::Error::Status(401, _)) => {}
+            result => return result.context("Twitch request failed"),
+        }
+        self.refresh()?;
+        call(self.auth()?).context("Twitch request failed")
+    }
+
+    fn refresh(&mut self) -> Result<()> {
+        let auth = self.auth()?;
+        let token: Token = match read(ureq::post(TOKEN_URL).send_form(&[
+            ("client_id", auth.client_id.as_str()),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", &auth.refresh_token),
+        ])) {
+            Ok(token) => token,
+            Err(err) => {
+                self.auth = None;
+                self.send(TwitchInfo::LoggedOut);
+                return Err(err.context("Twitch login expired, log in again"));
+            }
+        };
+        let auth = self.auth.as_mut().context("not logged in to Twitch")?;
+        auth.access_token = token.access_token;
+        auth.refresh_token = token.refresh_token;
+        let auth = auth.clone();
+        self.send(TwitchInfo::LoggedIn(auth));
+        Ok(())
+    }
+}
+
+fn helix_request(method: &str, path: &str, auth: &Auth) -> ureq::Request {
+    ureq::request(method, &format!("{}{}", HELIX_URL, path))
+        .set("Client-Id", &auth.client_id)
+        .set("Authorization", &format!("Bearer {}", auth.access_token))
+}
+
+fn read<T: DeserializeOwned>(response: Result<ureq::Response, ureq::Error>) -> Result<T> {
+    json(response?)
+}
+
+// ureq's own JSON support is behind a feature this crate doesn't enable.
+fn json<T: DeserializeOwned>(response: ureq::Response) -> Result<T> {
+    serde_json::from_reader(response.into_reader()).context("unexpected response from Twitch")
+}
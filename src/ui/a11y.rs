@@ -0,0 +1,37 @@
+use super::{channels, App};
+use crate::obs_worker::ObsInfo;
+use fluent::fluent_args;
+
+// For widgets whose visible text is an icon, a hint or nothing at all.
+pub(super) fn name(ui: &egui::Ui, response: &egui::Response, name: String) {
+    ui.ctx()
+        .accesskit_node_builder(response.id, |builder| builder.set_name(name));
+}
+
+// Mute buttons relabel themselves when pressed, which reads badly; screen
+// readers get a fixed name and the pressed state instead.
+pub(super) fn toggle(response: &egui::Response, name: &str, pressed: bool) {
+    response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Button, pressed, name));
+}
+
+impl App {
+    pub(super) fn announce_obs_info(&mut self, obs_info: &ObsInfo) {
+        let i18n = &self.i18n;
+        let announcement = match obs_info {
+            ObsInfo::MuteChanged(name, muted) => i18n.tr_args(
+                if *muted { "a11y-muted" } else { "a11y-unmuted" },
+                &fluent_args!["input" => channels::name(&self.channels, name)],
+            ),
+            ObsInfo::SceneChanged(name) => {
+                i18n.tr_args("a11y-scene", &fluent_args!["scene" => name.as_str()])
+            }
+            ObsInfo::StreamState(true) => i18n.tr("a11y-stream-started"),
+            ObsInfo::StreamState(false) => i18n.tr("a11y-stream-stopped"),
+            ObsInfo::RecordState(true) => i18n.tr("a11y-record-started"),
+            ObsInfo::RecordState(false) => i18n.tr("a11y-record-stopped"),
+            ObsInfo::ConnectionLost => i18n.tr("a11y-connection-lost"),
+            _ => return,
+        };
+        self.announcement = Some(announcement);
+    }
+}
@@ -0,0 +1,176 @@
+use super::{a11y, App};
+use crate::obs_worker::{Action, Query};
+use std::collections::HashMap;
+
+// Profile parameters worth having at hand, by category and name as they are
+// in the profile's basic.ini.
+const PARAMETERS: [(&str, &str, &str); 9] = [
+    ("Output", "DelayEnable", "advanced-delay-enable"),
+    ("Output", "DelaySec", "advanced-delay-seconds"),
+    ("Output", "DelayPreserve", "advanced-delay-preserve"),
+    ("Output", "Reconnect", "advanced-reconnect"),
+    ("Output", "RetryDelay", "advanced-retry-delay"),
+    ("Output", "MaxRetries", "advanced-max-retries"),
+    ("Output", "FilenameFormatting", "advanced-filename"),
+    ("SimpleOutput", "FilePath", "advanced-simple-path"),
+    ("AdvOut", "RecFilePath", "advanced-advanced-path"),
+];
+
+type Key = (String, String);
+
+#[derive(Default)]
+pub(super) struct Advanced {
+    // Value and default of each parameter fetched so far.
+    parameters: HashMap<Key, (Option<String>, Option<String>)>,
+    edits: HashMap<Key, String>,
+    // Parameters looked up by hand, shown after the curated ones.
+    extra: Vec<Key>,
+    requested: bool,
+    category: String,
+    name: String,
+}
+
+impl Advanced {
+    pub(super) fn loaded(
+        &mut self,
+        category: String,
+        name: String,
+        value: Option<String>,
+        default: Option<String>,
+    ) {
+        let key = (category, name);
+        self.edits
+            .insert(key.clone(), value.clone().unwrap_or_default());
+        self.parameters.insert(key, (value, default));
+    }
+}
+
+impl App {
+    fn request_parameters(&mut self) {
+        let keys: Vec<Key> = PARAMETERS
+            .iter()
+            .map(|(category, name, _)| (category.to_string(), name.to_string()))
+            .chain(self.advanced.extra.iter().cloned())
+            .collect();
+        for (category, name) in keys {
+            self.query(Query::ProfileParameter(category, name));
+        }
+    }
+
+    pub(super) fn show_advanced(&mut self, ui: &mut egui::Ui) {
+        if !self.connected {
+            self.advanced.requested = false;
+            ui.weak(self.i18n.tr("advanced-disconnected"));
+            return;
+        }
+        if !std::mem::replace(&mut self.advanced.requested, true) {
+            self.request_parameters();
+        }
+        ui.horizontal(|ui| {
+            ui.strong(self.i18n.tr("advanced-profile"));
+            if ui.button(self.i18n.tr("advanced-refresh")).clicked() {
+                self.advanced.requested = false;
+            }
+        });
+        ui.weak(self.i18n.tr("advanced-hint"));
+
+        let rows: Vec<(Key, String)> = PARAMETERS
+            .iter()
+            .map(|(category, name, id)| {
+                ((category.to_string(), name.to_string()), self.i18n.tr(id))
+            })
+            .chain(self.advanced.extra.iter().map(|(category, name)| {
+                (
+                    (category.clone(), name.clone()),
+                    format!("{}/{}", category, name),
+                )
+            }))
+            .collect();
+        let mut changes = Vec::new();
+        egui::Grid::new("profile_parameters")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                let advanced = &mut self.advanced;
+                for (key, text) in rows {
+                    let label = ui.label(text).on_hover_text(format!("{}/{}", key.0, key.1));
+                    let Some((value, default)) = advanced.parameters.get(&key) else {
+                        ui.spinner();
+                        ui.end_row();
+                        continue;
+                    };
+                    let edit = advanced.edits.entry(key.clone()).or_default();
+                    ui.add(
+                        egui::TextEdit::singleline(edit)
+                            .hint_text(default.clone().unwrap_or_default())
+                            .desired_width(200.0),
+                    )
+                    .labelled_by(label.id);
+                    let changed = edit.as_str() != value.as_deref().unwrap_or_default();
+                    let (category, name) = key;
+                    if ui
+                        .add_enabled(changed, egui::Button::new(self.i18n.tr("advanced-set")))
+                        .clicked()
+                    {
+                        changes.push((category.clone(), name.clone(), Some(edit.clone())));
+                    }
+                    if ui
+                        .add_enabled(
+                            value.is_some(),
+                            egui::Button::new(self.i18n.tr("advanced-reset")),
+                        )
+                        .on_hover_text(self.i18n.tr("advanced-reset-hint"))
+                        .clicked()
+                    {
+                        changes.push((category, name, None));
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.separator();
+        ui.strong(self.i18n.tr("advanced-raw"));
+        let mut look_up = false;
+        ui.horizontal(|ui| {
+            let advanced = &mut self.advanced;
+            for (text, id) in [
+                (&mut advanced.category, "advanced-category"),
+                (&mut advanced.name, "advanced-name"),
+            ] {
+                let hint = self.i18n.tr(id);
+                let response = ui.add(
+                    egui::TextEdit::singleline(text)
+                        .hint_text(hint.clone())
+                        .desired_width(120.0),
+                );
+                a11y::name(ui, &response, hint);
+            }
+            look_up = ui
+                .add_enabled(
+                    !advanced.category.is_empty() && !advanced.name.is_empty(),
+                    egui::Button::new(self.i18n.tr("advanced-look-up")),
+                )
+                .clicked();
+        });
+        if look_up {
+            let key = (
+                std::mem::take(&mut self.advanced.category),
+                std::mem::take(&mut self.advanced.name),
+            );
+            if !self.advanced.extra.contains(&key) {
+                self.advanced.extra.push(key.clone());
+            }
+            self.query(Query::ProfileParameter(key.0, key.1));
+        }
+
+        // Each change is read back, so the row shows what OBS actually stored.
+        for (category, name, value) in changes {
+            self.send(Action::SetProfileParameter(
+                category.clone(),
+                name.clone(),
+                value,
+            ));
+            self.query(Query::ProfileParameter(category, name));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{config::ChannelStyle, i18n::I18n};
+use fluent::fluent_args;
+use std::collections::BTreeMap;
+
+const SWATCHES: [[u8; 3]; 8] = [
+    [220, 60, 60],
+    [240, 140, 0],
+    [230, 200, 40],
+    [60, 180, 75],
+    [40, 170, 200],
+    [60, 100, 230],
+    [150, 80, 210],
+    [230, 90, 170],
+];
+
+pub(super) fn name(channels: &BTreeMap<String, ChannelStyle>, input: &str) -> String {
+    match channels.get(input) {
+        Some(style) if !style.label.is_empty() => style.label.clone(),
+        _ => input.to_string(),
+    }
+}
+
+pub(super) fn label(
+    ui: &egui::Ui,
+    channels: &BTreeMap<String, ChannelStyle>,
+    input: &str,
+) -> egui::WidgetText {
+    let name = name(channels, input);
+    let Some([r, g, b]) = channels.get(input).and_then(|style| style.color) else {
+        return name.into();
+    };
+    let font = egui::TextStyle::Button.resolve(ui.style());
+    let mut job = egui::text::LayoutJob::default();
+    job.append(
+        "■ ",
+        0.0,
+        egui::TextFormat::simple(font.clone(), egui::Color32::from_rgb(r, g, b)),
+    );
+    job.append(
+        &name,
+        0.0,
+        egui::TextFormat::simple(font, ui.visuals().text_color()),
+    );
+    job.into()
+}
+
+// Preset swatches rather than a color picker, since a picker's popup would
+// close the context menu it lives in.
+pub(super) fn edit(
+    ui: &mut egui::Ui,
+    channels: &mut BTreeMap<String, ChannelStyle>,
+    input: &str,
+    i18n: &I18n,
+) {
+    let style = channels.entry(input.to_string()).or_default();
+    ui.weak(input);
+    ui.add(
+        egui::TextEdit::singleline(&mut style.label)
+            .hint_text(i18n.tr("channel-label"))
+            .desired_width(140.0),
+    );
+    ui.horizontal(|ui| {
+        for [r, g, b] in SWATCHES {
+            let color = egui::Color32::from_rgb(r, g, b);
+            let selected = style.color == Some([r, g, b]);
+            if ui
+                .selectable_label(selected, egui::RichText::new("■").color(color))
+                .clicked()
+            {
+                style.color = (!selected).then_some([r, g, b]);
+            }
+        }
+    });
+    if ui.button(i18n.tr("channel-reset")).clicked() {
+        *style = ChannelStyle::default();
+        ui.close_menu();
+    }
+    if !style.group.is_empty()
+        && ui
+            .button(i18n.tr_args(
+                "channel-leave-group",
+                &fluent_args!["group" => style.group.as_str()],
+            ))
+            .clicked()
+    {
+        style.group.clear();
+    }
+    channels.retain(|_, style| {
+        !style.label.is_empty() || style.color.is_some() || !style.group.is_empty()
+    });
+}
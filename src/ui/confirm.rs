@@ -0,0 +1,79 @@
+use super::App;
+use crate::{config::Confirmation, obs_worker::Action};
+
+pub(super) struct Pending {
+    action: Action,
+    kind: Confirmation,
+    dont_ask: bool,
+}
+
+impl App {
+    // Sends an action, asking first if it could end a broadcast.
+    pub(super) fn send_confirmed(&mut self, action: Action) {
+        match Confirmation::for_action(&action) {
+            Some(kind) if !self.skip_confirmations.contains(&kind) => {
+                self.confirming = Some(Pending {
+                    action,
+                    kind,
+                    dont_ask: false,
+                });
+            }
+            _ => self.send(action),
+        }
+    }
+
+    // The backdrop swallows clicks so nothing else can be touched while the
+    // dialog is up.
+    pub(super) fn show_confirm(&mut self, ctx: &egui::Context) {
+        if self.confirming.is_none() {
+            return;
+        }
+        if self.window_hidden {
+            self.set_window_hidden(ctx, false);
+        }
+        let screen = ctx.screen_rect();
+        egui::Area::new("confirm_backdrop")
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen.min)
+            .show(ctx, |ui| {
+                ui.painter()
+                    .rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+                ui.allocate_rect(screen, egui::Sense::click());
+            });
+
+        let Some(pending) = &mut self.confirming else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled =
+            ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape));
+        let id = pending.kind.message_id();
+        egui::Area::new("confirm")
+            .order(egui::Order::Tooltip)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.heading(self.i18n.tr(&format!("{}-title", id)));
+                    ui.label(self.i18n.tr(&format!("{}-body", id)));
+                    ui.checkbox(&mut pending.dont_ask, self.i18n.tr("confirm-dont-ask"));
+                    ui.horizontal(|ui| {
+                        let confirm = egui::Button::new(self.i18n.tr(&format!("{}-button", id)))
+                            .fill(ui.visuals().error_fg_color.gamma_multiply(0.5));
+                        confirmed = ui.add(confirm).clicked();
+                        cancelled |= ui.button(self.i18n.tr("confirm-cancel")).clicked();
+                    });
+                });
+            });
+
+        if confirmed {
+            if let Some(pending) = self.confirming.take() {
+                if pending.dont_ask {
+                    self.skip_confirmations.insert(pending.kind);
+                }
+                self.send(pending.action);
+            }
+        } else if cancelled {
+            self.confirming = None;
+        }
+    }
+}
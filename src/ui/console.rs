@@ -0,0 +1,101 @@
+use super::{a11y, App};
+use crate::obs_worker::Query;
+use fluent::fluent_args;
+
+// Not listed anywhere; Ctrl+Shift+D opens it.
+#[derive(Default)]
+pub(super) struct Console {
+    open: bool,
+    request_type: String,
+    payload: String,
+    pub(super) output: String,
+}
+
+impl Console {
+    pub(super) fn close(&mut self) {
+        self.open = false;
+    }
+}
+
+impl App {
+    pub(super) fn show_console(&mut self, ctx: &egui::Context) {
+        if !self.locked()
+            && ctx.input_mut(|i| {
+                i.consume_key(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::D,
+                )
+            })
+        {
+            self.console.open = !self.console.open;
+        }
+        let mut open = self.console.open;
+        let mut send = false;
+        egui::Window::new(self.i18n.tr("console"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let console = &mut self.console;
+                ui.horizontal(|ui| {
+                    let hint = self.i18n.tr("console-request");
+                    let request = ui.add(
+                        egui::TextEdit::singleline(&mut console.request_type)
+                            .hint_text(hint.clone())
+                            .desired_width(240.0),
+                    );
+                    a11y::name(ui, &request, hint);
+                    send = ui
+                        .add_enabled(
+                            !console.request_type.is_empty(),
+                            egui::Button::new(self.i18n.tr("console-send")),
+                        )
+                        .clicked();
+                });
+                let hint = self.i18n.tr("console-payload");
+                let payload = ui.add(
+                    egui::TextEdit::multiline(&mut console.payload)
+                        .code_editor()
+                        .hint_text(hint.clone())
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(4),
+                );
+                a11y::name(ui, &payload, hint);
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .drag_to_scroll(self.touch_mode)
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut console.output.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+            });
+        self.console.open = open;
+        if !send {
+            return;
+        }
+        let payload = self.console.payload.trim();
+        let data = if payload.is_empty() {
+            Ok(serde_json::Value::Null)
+        } else {
+            serde_json::from_str(payload)
+        };
+        match data {
+            Ok(data) => {
+                self.console.output = self.i18n.tr("console-waiting");
+                self.query(Query::Raw(
+                    self.console.request_type.trim().to_string(),
+                    data,
+                ));
+            }
+            Err(err) => {
+                self.console.output = self.i18n.tr_args(
+                    "console-bad-json",
+                    &fluent_args!["error" => err.to_string()],
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use super::App;
+use crate::{
+    dbus::{DbusCommand, DbusStatus},
+    obs_worker::Action,
+};
+
+impl App {
+    pub(super) fn handle_dbus(&mut self, ctx: &egui::Context) {
+        let Some(dbus) = &mut self.dbus else {
+            return;
+        };
+        let mic_muted = self
+            .mic_input_name
+            .as_ref()
+            .and_then(|name| self.obs_state.muted.get(name))
+            .copied()
+            .unwrap_or(false);
+        dbus.update(DbusStatus {
+            connected: self.connected,
+            streaming: self.obs_state.streaming,
+            recording: self.obs_state.recording,
+            mic_muted,
+            scene: self.obs_state.current_scene.clone(),
+            scenes: self.obs_state.scenes.clone(),
+        });
+
+        for command in dbus.commands() {
+            match command {
+                DbusCommand::Raise => self.set_window_hidden(ctx, false),
+                _ if !self.connected => {}
+                DbusCommand::ToggleMic => self.toggle_mute(self.mic_input_name.clone()),
+                DbusCommand::SetMicMuted(muted) => {
+                    if let Some(name) = self.mic_input_name.clone() {
+                        if self.obs_state.muted.get(&name) != Some(&muted) {
+                            self.perform(Action::SetMute(name, muted));
+                        }
+                    }
+                }
+                DbusCommand::SetScene(scene) => {
+                    if self.obs_state.scenes.contains(&scene) {
+                        self.send(Action::SetScene(scene));
+                    }
+                }
+                DbusCommand::NextScene => self.step_scene(1),
+                DbusCommand::PreviousScene => self.step_scene(-1),
+            }
+        }
+    }
+
+    // Media keys wrap around the scene list in either direction.
+    fn step_scene(&mut self, step: isize) {
+        let scenes = &self.obs_state.scenes;
+        if scenes.is_empty() {
+            return;
+        }
+        let current = self
+            .obs_state
+            .current_scene
+            .as_ref()
+            .and_then(|scene| scenes.iter().position(|name| name == scene))
+            .unwrap_or(0) as isize;
+        let next = (current + step).rem_euclid(scenes.len() as isize) as usize;
+        self.send(Action::SetScene(scenes[next].clone()));
+    }
+}
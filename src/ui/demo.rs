@@ -0,0 +1,55 @@
+use super::App;
+use crate::{
+    demo::Demo,
+    obs_worker::{Action, ObsInfo},
+};
+
+impl App {
+    // Puts a made-up OBS where the worker would be, for talks and for UI work
+    // on a machine without OBS. Nothing sent reaches the worker, and nothing
+    // is saved on exit.
+    pub fn start_demo(&mut self) {
+        // rec.toml may have connected on its own already.
+        if let Err(err) = self.action_tx.try_send(Action::LogOut) {
+            tracing::warn!("failed to send action: {}", err);
+        }
+        let mut demo = Demo::new();
+        let mic = self
+            .mic_input_name
+            .get_or_insert_with(|| "Mic/Aux".to_string());
+        demo.handle(Action::SetAlias("mic".to_string(), Some(mic.clone())));
+        let desktop = self
+            .desktop_input_name
+            .get_or_insert_with(|| "Desktop Audio".to_string());
+        demo.handle(Action::SetAlias(
+            "desktop".to_string(),
+            Some(desktop.clone()),
+        ));
+        self.demo = Some(demo);
+        self.logged_in = true;
+        tracing::info!("demo mode started");
+    }
+
+    pub(super) fn tick_demo(&mut self, ctx: &egui::Context) {
+        let Some(demo) = &mut self.demo else {
+            return;
+        };
+        let infos = demo.tick();
+        ctx.request_repaint_after(demo.next_chatter());
+        for info in infos {
+            // Logging out ends the demo, and the login screen talks to OBS again.
+            if matches!(info, ObsInfo::Disconnected) {
+                self.demo = None;
+                tracing::info!("demo mode stopped");
+            }
+            self.handle_obs_info(info);
+        }
+    }
+
+    pub(super) fn show_demo_badge(&self, ui: &mut egui::Ui) {
+        if self.demo.is_some() {
+            ui.colored_label(ui.visuals().warn_fg_color, self.i18n.tr("demo-badge"))
+                .on_hover_text(self.i18n.tr("demo-hint"));
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use super::App;
+use crate::obs_worker::{Action, ObsInfo};
+use egui_plot::{Bar, BarChart, Plot};
+use fluent::fluent_args;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+// Enough for a minute of frames at 60 fps.
+const SAMPLES: usize = 3600;
+// An action that gets no event back by then is counted as lost.
+const ROUND_TRIP_TIMEOUT: Duration = Duration::from_secs(5);
+const BUCKETS: usize = 20;
+
+// What an action is waiting to hear back about. Only actions OBS answers
+// with an event of their own are timed.
+#[derive(PartialEq, Eq, Hash)]
+enum Probe {
+    Scene(String),
+    Mute(String),
+    Volume(String),
+}
+
+// Frame times and action round trips, kept only while measuring so normal
+// use pays nothing for it.
+#[derive(Default)]
+pub(super) struct Diagnostics {
+    enabled: bool,
+    // Milliseconds.
+    frames: VecDeque<f32>,
+    round_trips: VecDeque<f32>,
+    pending: HashMap<Probe, Instant>,
+    lost: usize,
+}
+
+fn push(samples: &mut VecDeque<f32>, sample: f32) {
+    if samples.len() == SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+fn percentile(sorted: &[f32], fraction: f32) -> f32 {
+    let index = ((sorted.len() - 1) as f32 * fraction).round() as usize;
+    sorted[index]
+}
+
+impl Diagnostics {
+    pub(super) fn frame(&mut self, cpu_usage: Option<f32>) {
+        if let (true, Some(seconds)) = (self.enabled, cpu_usage) {
+            push(&mut self.frames, seconds * 1000.0);
+        }
+    }
+
+    fn sent(&mut self, probe: Probe) {
+        // Drags send many; the wait counts from the first one unanswered.
+        self.pending.entry(probe).or_insert_with(Instant::now);
+    }
+
+    fn received(&mut self, probe: Probe) {
+        if let Some(sent) = self.pending.remove(&probe) {
+            push(&mut self.round_trips, sent.elapsed().as_secs_f32() * 1000.0);
+        }
+    }
+
+    fn expire(&mut self) {
+        let before = self.pending.len();
+        self.pending
+            .retain(|_, sent| sent.elapsed() < ROUND_TRIP_TIMEOUT);
+        self.lost += before - self.pending.len();
+    }
+}
+
+impl App {
+    pub(super) fn probe_sent(&mut self, action: &Action) {
+        if !self.diagnostics.enabled {
+            return;
+        }
+        let probe = match action {
+            Action::SetScene(scene) => Probe::Scene(scene.clone()),
+            Action::SetMute(input, _) => match self.alias_input(input) {
+                Some(input) => Probe::Mute(input),
+                None => return,
+            },
+            Action::SetVolume(input, _) => match self.alias_input(input) {
+                Some(input) => Probe::Volume(input),
+                None => return,
+            },
+            _ => return,
+        };
+        self.diagnostics.sent(probe);
+    }
+
+    pub(super) fn probe_received(&mut self, obs_info: &ObsInfo) {
+        if !self.diagnostics.enabled {
+            return;
+        }
+        let probe = match obs_info {
+            ObsInfo::SceneChanged(scene) => Probe::Scene(scene.clone()),
+            ObsInfo::MuteChanged(input, _) => Probe::Mute(input.clone()),
+            ObsInfo::VolumeChanged(input, _) => Probe::Volume(input.clone()),
+            _ => return,
+        };
+        self.diagnostics.received(probe);
+    }
+
+    fn show_histogram(&self, ui: &mut egui::Ui, id: &str, title: &str, samples: &VecDeque<f32>) {
+        ui.strong(self.i18n.tr(title));
+        if samples.is_empty() {
+            ui.weak(self.i18n.tr("diagnostics-no-samples"));
+            return;
+        }
+        let mut sorted: Vec<f32> = samples.iter().copied().collect();
+        sorted.sort_by(f32::total_cmp);
+        ui.label(self.i18n.tr_args(
+            "diagnostics-summary",
+            &fluent_args![
+                "count" => sorted.len(),
+                "median" => format!("{:.1}", percentile(&sorted, 0.5)),
+                "p95" => format!("{:.1}", percentile(&sorted, 0.95)),
+                "max" => format!("{:.1}", sorted[sorted.len() - 1])
+            ],
+        ));
+        // Buckets run up to the 99th percentile so one stall doesn't squash
+        // everything else into the first bar; anything past it lands in the last.
+        let top = percentile(&sorted, 0.99).max(1.0);
+        let width = top / BUCKETS as f32;
+        let mut counts = [0usize; BUCKETS];
+        for sample in &sorted {
+            counts[((sample / width) as usize).min(BUCKETS - 1)] += 1;
+        }
+        let bars = counts
+            .iter()
+            .enumerate()
+            .map(|(bucket, count)| {
+                Bar::new((bucket as f64 + 0.5) * width as f64, *count as f64)
+                    .width(width as f64 * 0.9)
+            })
+            .collect();
+        Plot::new(id)
+            .height(120.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_y(false)
+            .x_axis_label(self.i18n.tr("diagnostics-ms"))
+            .show(ui, |plot| plot.bar_chart(BarChart::new(bars)));
+    }
+
+    pub(super) fn show_diagnostics(&mut self, ui: &mut egui::Ui) {
+        self.show_trace(ui);
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.diagnostics.enabled,
+                self.i18n.tr("diagnostics-enabled"),
+            )
+            .on_hover_text(self.i18n.tr("diagnostics-hint"));
+            if ui.button(self.i18n.tr("diagnostics-clear")).clicked() {
+                let enabled = self.diagnostics.enabled;
+                self.diagnostics = Diagnostics {
+                    enabled,
+                    ..Diagnostics::default()
+                };
+            }
+        });
+        if !self.diagnostics.enabled {
+            return;
+        }
+        // Frame times only come in while something draws.
+        ui.ctx().request_repaint();
+        self.diagnostics.expire();
+        self.show_histogram(
+            ui,
+            "diagnostics_frames",
+            "diagnostics-frames",
+            &self.diagnostics.frames,
+        );
+        ui.separator();
+        self.show_histogram(
+            ui,
+            "diagnostics_round_trips",
+            "diagnostics-round-trips",
+            &self.diagnostics.round_trips,
+        );
+        if self.diagnostics.lost > 0 {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                self.i18n.tr_args(
+                    "diagnostics-lost",
+                    &fluent_args!["count" => self.diagnostics.lost],
+                ),
+            );
+        }
+    }
+}
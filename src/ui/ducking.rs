@@ -0,0 +1,178 @@
+use super::{a11y, timers::picker, App};
+use crate::{
+    audio::{Ducking, DuckingPair},
+    obs_worker::Action,
+};
+use fluent::fluent_args;
+
+// A made-up burst of speech the envelope sketch ducks under.
+const SKETCH_SPEECH_MS: f32 = 800.0;
+const SKETCH_LEAD_MS: f32 = 100.0;
+// Reductions are drawn against this depth, so a deeper duck looks deeper.
+const SKETCH_DEPTH_DB: f32 = 40.0;
+
+enum Change {
+    Apply(usize),
+    Remove(usize),
+    Add(String, String),
+}
+
+// The ducked level over time while the trigger talks for a moment: it drops
+// over the attack, stays down through the hold and comes back over the
+// release.
+fn sketch(ui: &mut egui::Ui, ducking: &Ducking) -> egui::Response {
+    let (response, painter) = ui.allocate_painter(egui::vec2(240.0, 60.0), egui::Sense::hover());
+    let rect = response.rect.shrink(4.0);
+    let attack = ducking.attack_ms as f32;
+    let hold = ducking.hold_ms as f32;
+    let release = ducking.release_ms as f32;
+    let speech_end = SKETCH_LEAD_MS + SKETCH_SPEECH_MS;
+    let total = speech_end + hold + release + SKETCH_LEAD_MS;
+    let x = |ms: f32| rect.left() + rect.width() * ms / total;
+    let y = |db: f32| rect.top() + rect.height() * (-db / SKETCH_DEPTH_DB).min(1.0);
+
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+    painter.rect_filled(
+        egui::Rect::from_x_y_ranges(x(SKETCH_LEAD_MS)..=x(speech_end), rect.y_range()),
+        0.0,
+        visuals.selection.bg_fill.gamma_multiply(0.3),
+    );
+    // A short burst may end before the attack has finished.
+    let reduction = ducking.reduction * (SKETCH_SPEECH_MS / attack.max(1.0)).min(1.0);
+    let bottom = SKETCH_LEAD_MS + attack.min(SKETCH_SPEECH_MS);
+    let points = vec![
+        egui::pos2(x(0.0), y(0.0)),
+        egui::pos2(x(SKETCH_LEAD_MS), y(0.0)),
+        egui::pos2(x(bottom), y(-reduction)),
+        egui::pos2(x(speech_end + hold), y(-reduction)),
+        egui::pos2(x(speech_end + hold + release), y(0.0)),
+        egui::pos2(x(total), y(0.0)),
+    ];
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(2.0, visuals.strong_text_color()),
+    ));
+    response
+}
+
+impl App {
+    pub(super) fn show_ducking(&mut self, ui: &mut egui::Ui) {
+        let inputs: Vec<String> = self
+            .input_info
+            .iter()
+            .filter(|input| input.kind.contains("input") || input.kind.contains("output"))
+            .map(|input| input.name.clone())
+            .collect();
+        let mut changes = Vec::new();
+        egui::CollapsingHeader::new(self.i18n.tr("ducking-title"))
+            .id_source("ducking")
+            .show(ui, |ui| {
+                ui.weak(self.i18n.tr("ducking-hint"));
+                for (index, pair) in self.ducking.iter_mut().enumerate() {
+                    ui.separator();
+                    ui.strong(self.i18n.tr_args(
+                        "ducking-pair",
+                        &fluent_args![
+                            "ducked" => pair.ducked.as_str(),
+                            "trigger" => pair.trigger.as_str()
+                        ],
+                    ));
+                    let ducking = &mut pair.ducking;
+                    egui::Grid::new(("ducking_pair", index))
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            for (id, value, range, suffix) in [
+                                (
+                                    "ducking-threshold",
+                                    &mut ducking.threshold,
+                                    -60.0..=0.0,
+                                    " dB",
+                                ),
+                                (
+                                    "ducking-reduction",
+                                    &mut ducking.reduction,
+                                    0.0..=40.0,
+                                    " dB",
+                                ),
+                            ] {
+                                let label = ui.label(self.i18n.tr(id));
+                                ui.add(
+                                    egui::Slider::new(value, range)
+                                        .suffix(suffix)
+                                        .fixed_decimals(0),
+                                )
+                                .labelled_by(label.id);
+                                ui.end_row();
+                            }
+                            for (id, value, range) in [
+                                ("ducking-attack", &mut ducking.attack_ms, 1..=500),
+                                ("ducking-hold", &mut ducking.hold_ms, 0..=2000),
+                                ("ducking-release", &mut ducking.release_ms, 10..=3000),
+                            ] {
+                                let label = ui.label(self.i18n.tr(id));
+                                ui.add(egui::Slider::new(value, range).suffix(" ms"))
+                                    .labelled_by(label.id);
+                                ui.end_row();
+                            }
+                        });
+                    let response = sketch(ui, ducking);
+                    let description = self.i18n.tr_args(
+                        "ducking-sketch",
+                        &fluent_args![
+                            "reduction" => format!("{:.0}", ducking.reduction),
+                            "ratio" => format!("{:.1}", ducking.ratio())
+                        ],
+                    );
+                    a11y::name(ui, &response, description.clone());
+                    response.on_hover_text(description);
+                    ui.horizontal(|ui| {
+                        if ui.button(self.i18n.tr("ducking-apply")).clicked() {
+                            changes.push(Change::Apply(index));
+                        }
+                        if ui.button(self.i18n.tr("ducking-remove")).clicked() {
+                            changes.push(Change::Remove(index));
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.horizontal_wrapped(|ui| {
+                    let (ducked, trigger) = &mut self.ducking_new;
+                    picker(ui, &self.i18n, "ducking-ducked", &inputs, ducked);
+                    picker(ui, &self.i18n, "ducking-trigger", &inputs, trigger);
+                    let ready = ducked.is_some() && trigger.is_some() && ducked != trigger;
+                    if ui
+                        .add_enabled(ready, egui::Button::new(self.i18n.tr("ducking-add")))
+                        .clicked()
+                    {
+                        if let (Some(ducked), Some(trigger)) = (ducked.take(), trigger.take()) {
+                            changes.push(Change::Add(ducked, trigger));
+                        }
+                    }
+                });
+            });
+
+        for change in changes {
+            match change {
+                Change::Apply(index) => self.send(Action::SetDucking(self.ducking[index].clone())),
+                Change::Remove(index) => {
+                    let pair = self.ducking.remove(index);
+                    self.send(Action::StopDucking(pair.ducked));
+                }
+                // An input ducks under one trigger at a time, like the single
+                // compressor it gets in OBS.
+                Change::Add(ducked, trigger) => {
+                    let pair = DuckingPair {
+                        ducked,
+                        trigger,
+                        ducking: Ducking::default(),
+                    };
+                    self.ducking.retain(|other| other.ducked != pair.ducked);
+                    self.ducking.push(pair.clone());
+                    self.send(Action::SetDucking(pair));
+                }
+            }
+        }
+    }
+}
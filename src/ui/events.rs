@@ -0,0 +1,122 @@
+use super::{a11y, App};
+use chrono::{DateTime, Local};
+use fluent::fluent_args;
+use std::collections::VecDeque;
+
+const EVENT_LOG_CAPACITY: usize = 5000;
+
+struct EventEntry {
+    time: DateTime<Local>,
+    kind: String,
+    details: String,
+}
+
+// Every event OBS sent this session, oldest dropped first, for piecing
+// together what happened during a glitch.
+#[derive(Default)]
+pub(super) struct EventLog {
+    entries: VecDeque<EventEntry>,
+    filter: String,
+}
+
+impl EventLog {
+    pub(super) fn push(&mut self, kind: String, details: String) {
+        if self.entries.len() == EVENT_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(EventEntry {
+            time: Local::now(),
+            kind,
+            details,
+        });
+    }
+
+    fn matching(&self) -> Vec<&EventEntry> {
+        let filter = self.filter.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.kind.to_lowercase().contains(&filter)
+                    || entry.details.to_lowercase().contains(&filter)
+            })
+            .collect()
+    }
+
+    // What's shown, filter included, next to the other logs.
+    fn export(&self) -> anyhow::Result<std::path::PathBuf> {
+        use anyhow::Context;
+        let dirs = directories::ProjectDirs::from("", "", "rec").context("no home directory")?;
+        let dir = dirs.data_dir().join("logs");
+        std::fs::create_dir_all(&dir).context("failed to create log directory")?;
+        let path = dir.join(format!(
+            "events-{}.json",
+            Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        let entries: Vec<_> = self
+            .matching()
+            .into_iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "time": entry.time.to_rfc3339(),
+                    "kind": entry.kind,
+                    "details": entry.details,
+                })
+            })
+            .collect();
+        std::fs::write(&path, serde_json::to_string_pretty(&entries)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+impl App {
+    pub(super) fn show_events(&mut self, ui: &mut egui::Ui) {
+        let mut export = false;
+        ui.horizontal(|ui| {
+            let hint = self.i18n.tr("events-filter");
+            let filter = ui.add(
+                egui::TextEdit::singleline(&mut self.events.filter)
+                    .hint_text(hint.clone())
+                    .desired_width(200.0),
+            );
+            a11y::name(ui, &filter, hint);
+            export = ui.button(self.i18n.tr("events-export")).clicked();
+            if ui.button(self.i18n.tr("log-clear")).clicked() {
+                self.events.entries.clear();
+            }
+        });
+        if export {
+            match self.events.export() {
+                Ok(path) => self.toasts.notice(self.i18n.tr_args(
+                    "events-exported",
+                    &fluent_args!["path" => path.display().to_string()],
+                )),
+                Err(err) => self.toasts.push(format!("{:#}", err)),
+            }
+        }
+
+        let entries = self.events.matching();
+        ui.weak(self.i18n.tr_args(
+            "events-count",
+            &fluent_args!["shown" => entries.len(), "total" => self.events.entries.len()],
+        ));
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::both()
+            .drag_to_scroll(self.touch_mode)
+            .stick_to_bottom(true)
+            .auto_shrink([false, false])
+            .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                for entry in &entries[rows] {
+                    ui.horizontal(|ui| {
+                        ui.monospace(entry.time.format("%H:%M:%S%.3f").to_string());
+                        ui.strong(&entry.kind);
+                        ui.add(
+                            egui::Label::new(egui::RichText::new(&entry.details).monospace())
+                                .wrap(false),
+                        )
+                        .on_hover_text(&entry.details);
+                    });
+                }
+            });
+    }
+}
@@ -0,0 +1,124 @@
+use super::App;
+use crate::{config::Shortcut, global_keys::KeyEvent};
+use fluent::fluent_args;
+
+impl App {
+    pub(super) fn handle_global_keys(&mut self) {
+        let Some(keys) = &self.global_keys else {
+            return;
+        };
+        keys.set_bound(self.global_key_bindings.keys().cloned());
+        for event in keys.events() {
+            match event {
+                KeyEvent::Captured(key) => {
+                    self.global_key_bindings
+                        .insert(key, self.global_key_target.clone());
+                }
+                KeyEvent::Pressed(key) if self.logged_in => {
+                    if let Some(shortcut) = self.global_key_bindings.get(&key).cloned() {
+                        self.run_shortcut(shortcut);
+                    }
+                }
+                KeyEvent::Pressed(_) => {}
+            }
+        }
+    }
+
+    fn shortcut_label(&self, shortcut: &Shortcut) -> String {
+        let i18n = &self.i18n;
+        match shortcut {
+            Shortcut::ToggleMuteMic => i18n.tr("shortcut-mute-mic"),
+            Shortcut::ToggleMuteDesktop => i18n.tr("shortcut-mute-desktop"),
+            Shortcut::ToggleRecord => i18n.tr("shortcut-record"),
+            Shortcut::ToggleStream => i18n.tr("shortcut-stream"),
+            Shortcut::Scene(number) => {
+                let scene = number
+                    .checked_sub(1)
+                    .and_then(|index| self.obs_state.scenes.get(index))
+                    .cloned()
+                    .unwrap_or_default();
+                i18n.tr_args(
+                    "shortcut-scene",
+                    &fluent_args!["number" => *number, "scene" => scene],
+                )
+            }
+            Shortcut::Button(name) => {
+                i18n.tr_args("shortcut-button", &fluent_args!["name" => name.as_str()])
+            }
+            Shortcut::TeleprompterToggle => i18n.tr("shortcut-teleprompter-toggle"),
+            Shortcut::TeleprompterFaster => i18n.tr("shortcut-teleprompter-faster"),
+            Shortcut::TeleprompterSlower => i18n.tr("shortcut-teleprompter-slower"),
+            Shortcut::TeleprompterRestart => i18n.tr("shortcut-teleprompter-restart"),
+            Shortcut::RundownGo => i18n.tr("shortcut-go"),
+            Shortcut::Intermission => i18n.tr("shortcut-intermission"),
+        }
+    }
+
+    // Hidden where there's no hook to bind to.
+    pub(super) fn show_global_key_settings(&mut self, ui: &mut egui::Ui) {
+        let Some(keys) = &self.global_keys else {
+            return;
+        };
+        ui.strong(self.i18n.tr("global-keys-title"));
+        ui.weak(self.i18n.tr("global-keys-hint"));
+
+        let mut removed = None;
+        for (key, shortcut) in &self.global_key_bindings {
+            ui.horizontal(|ui| {
+                ui.monospace(key);
+                ui.label(self.shortcut_label(shortcut));
+                if ui
+                    .small_button(self.i18n.tr("global-keys-remove"))
+                    .clicked()
+                {
+                    removed = Some(key.clone());
+                }
+            });
+        }
+        if let Some(key) = removed {
+            self.global_key_bindings.remove(&key);
+        }
+
+        let mut targets = vec![
+            Shortcut::ToggleMuteMic,
+            Shortcut::ToggleMuteDesktop,
+            Shortcut::ToggleRecord,
+            Shortcut::ToggleStream,
+            Shortcut::RundownGo,
+            Shortcut::Intermission,
+            Shortcut::TeleprompterToggle,
+        ];
+        targets.extend((1..=self.obs_state.scenes.len()).map(Shortcut::Scene));
+        targets.extend(
+            self.all_macros()
+                .into_iter()
+                .map(|obs_macro| Shortcut::Button(obs_macro.name)),
+        );
+        let capturing = keys.capturing();
+        let mut target = self.global_key_target.clone();
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("global-keys-action"));
+            ui.add_enabled_ui(!capturing, |ui| {
+                egui::ComboBox::from_id_source("global_key_target")
+                    .selected_text(self.shortcut_label(&target))
+                    .show_ui(ui, |ui| {
+                        for option in targets {
+                            let text = self.shortcut_label(&option);
+                            ui.selectable_value(&mut target, option, text);
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id);
+            });
+            if capturing {
+                ui.label(self.i18n.tr("global-keys-press"));
+                if ui.button(self.i18n.tr("global-keys-cancel")).clicked() {
+                    keys.capture(false);
+                }
+            } else if ui.button(self.i18n.tr("global-keys-bind")).clicked() {
+                keys.capture(true);
+            }
+        });
+        self.global_key_target = target;
+    }
+}
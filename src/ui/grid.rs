@@ -0,0 +1,163 @@
+use super::{a11y, App};
+use crate::{
+    features::Feature,
+    obs_worker::{Action, Macro, MacroStep, Query},
+    session::SessionMessage,
+};
+
+// The filter a button only switches, so the button can light up with it.
+fn filter_of(obs_macro: &Macro) -> Option<(&String, &String)> {
+    match obs_macro.steps.as_slice() {
+        [MacroStep::Do(
+            Action::ToggleFilter(source, filter) | Action::SetFilterEnabled(source, filter, _),
+        )] => Some((source, filter)),
+        _ => None,
+    }
+}
+
+impl App {
+    fn filter_enabled(&mut self, source: &str, filter: &str) -> bool {
+        let known = self
+            .obs_state
+            .filters
+            .get(source)
+            .and_then(|filters| filters.get(filter))
+            .copied();
+        if known.is_none()
+            && self.connected
+            && self
+                .requested_filters
+                .insert((source.to_string(), filter.to_string()))
+        {
+            self.query(Query::FilterEnabled(source.to_string(), filter.to_string()));
+        }
+        known.unwrap_or(false)
+    }
+
+    pub(super) fn show_grid(&mut self, ui: &mut egui::Ui) {
+        let macros = self.all_macros();
+        let lit: Vec<bool> = macros
+            .iter()
+            .map(|obs_macro| {
+                filter_of(obs_macro)
+                    .map(|(source, filter)| (source.clone(), filter.clone()))
+                    .map_or(false, |(source, filter)| {
+                        self.filter_enabled(&source, &filter)
+                    })
+            })
+            .collect();
+        let mut clicked = None;
+        ui.horizontal_wrapped(|ui| {
+            if ui
+                .button(self.i18n.tr("grid-marker"))
+                .on_hover_text(self.i18n.tr("grid-marker-hint"))
+                .clicked()
+            {
+                clicked = Some(Action::AddMarker(String::new()));
+            }
+            if self.twitch.logged_in() && ui.button(self.i18n.tr("twitch-clip")).clicked() {
+                self.twitch.create_clip();
+            }
+            for (obs_macro, lit) in macros.into_iter().zip(lit) {
+                if ui
+                    .add(egui::Button::new(&obs_macro.name).selected(lit))
+                    .clicked()
+                {
+                    clicked = Some(Action::RunMacro(obs_macro));
+                }
+            }
+        });
+        if let Some(action) = clicked {
+            if let Action::RunMacro(obs_macro) = &action {
+                self.share(SessionMessage::Pressed {
+                    operator: self.operator_name.clone(),
+                    button: obs_macro.name.clone(),
+                });
+            }
+            self.send(action);
+        }
+
+        if self.features.enabled(Feature::Scripting) {
+            ui.collapsing(self.i18n.tr("grid-scripts"), |ui| {
+                let name = ui.add(
+                    egui::TextEdit::singleline(&mut self.script_name)
+                        .hint_text(self.i18n.tr("grid-button-name")),
+                );
+                a11y::name(ui, &name, self.i18n.tr("grid-button-name"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.script_source)
+                        .code_editor()
+                        .hint_text("set_volume(\"Mic/Aux\", volume(\"Mic/Aux\") - 10.0);"),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button(self.i18n.tr("grid-run")).clicked() {
+                        self.send(Action::RunScript(self.script_source.clone()));
+                    }
+                    if ui.button(self.i18n.tr("grid-stop-scripts")).clicked() {
+                        self.send(Action::StopScripts);
+                    }
+                    if ui.button(self.i18n.tr("grid-add-button")).clicked()
+                        && !self.script_name.is_empty()
+                    {
+                        self.macros.push(Macro {
+                            name: std::mem::take(&mut self.script_name),
+                            steps: vec![MacroStep::Do(Action::RunScript(
+                                self.script_source.clone(),
+                            ))],
+                        });
+                    }
+                });
+            });
+        }
+
+        self.show_hotkeys(ui);
+    }
+
+    // Every hotkey OBS and its plugins register, for what REC has no button of
+    // its own for.
+    fn show_hotkeys(&mut self, ui: &mut egui::Ui) {
+        let mut run = None;
+        ui.collapsing(self.i18n.tr("grid-hotkeys"), |ui| {
+            let hint = self.i18n.tr("grid-hotkey-search");
+            let search =
+                ui.add(egui::TextEdit::singleline(&mut self.hotkey_search).hint_text(hint.clone()));
+            a11y::name(ui, &search, hint);
+            if self.hotkeys.is_empty() {
+                ui.weak(self.i18n.tr("grid-hotkeys-none"));
+                return;
+            }
+            let search = self.hotkey_search.to_lowercase();
+            egui::ScrollArea::vertical()
+                .drag_to_scroll(self.touch_mode)
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("hotkeys")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for hotkey in &self.hotkeys {
+                                if !hotkey.to_lowercase().contains(&search) {
+                                    continue;
+                                }
+                                ui.monospace(hotkey);
+                                if ui.button(self.i18n.tr("grid-run")).clicked() {
+                                    run = Some(hotkey.clone());
+                                }
+                                if ui.button(self.i18n.tr("grid-add-button")).clicked() {
+                                    self.macros.push(Macro {
+                                        name: hotkey.clone(),
+                                        steps: vec![MacroStep::Do(Action::TriggerHotkey(
+                                            hotkey.clone(),
+                                        ))],
+                                    });
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+        if let Some(hotkey) = run {
+            self.send(Action::TriggerHotkey(hotkey));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use super::{channels, App};
+use crate::{
+    config::ChannelStyle,
+    i18n::I18n,
+    obs_worker::{Action, ObsInfo},
+};
+use chrono::{DateTime, Local};
+use fluent::fluent_args;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+const HISTORY_CAPACITY: usize = 500;
+// A slider drag sends a volume change every frame; changes to the same input
+// this close together are kept as a single entry.
+const COALESCE_WINDOW: Duration = Duration::from_secs(1);
+
+pub(super) struct HistoryEntry {
+    time: DateTime<Local>,
+    last_change: Instant,
+    action: Action,
+    undo: Action,
+    undone: bool,
+}
+
+fn describe(i18n: &I18n, channels: &BTreeMap<String, ChannelStyle>, action: &Action) -> String {
+    match action {
+        Action::SetVolume(name, volume) => i18n.tr_args(
+            "history-volume",
+            &fluent_args![
+                "input" => channels::name(channels, name),
+                "volume" => format!("{:.0}", volume)
+            ],
+        ),
+        Action::SetMute(name, true) => i18n.tr_args(
+            "history-mute",
+            &fluent_args!["input" => channels::name(channels, name)],
+        ),
+        Action::SetMute(name, false) => i18n.tr_args(
+            "history-unmute",
+            &fluent_args!["input" => channels::name(channels, name)],
+        ),
+        Action::SetScene(name) => {
+            i18n.tr_args("history-scene", &fluent_args!["scene" => name.as_str()])
+        }
+        Action::SetSceneItemEnabled(scene, source, enabled) => i18n.tr_args(
+            if *enabled {
+                "history-show-source"
+            } else {
+                "history-hide-source"
+            },
+            &fluent_args!["source" => source.as_str(), "scene" => scene.as_str()],
+        ),
+        _ => i18n.tr("history-other"),
+    }
+}
+
+impl App {
+    fn inverse(&self, action: &Action) -> Option<Action> {
+        match action {
+            Action::SetVolume(name, _) => {
+                let volume = *self.obs_state.volumes.get(name)?;
+                Some(Action::SetVolume(name.clone(), volume))
+            }
+            Action::SetMute(name, _) => {
+                let muted = *self.obs_state.muted.get(name)?;
+                Some(Action::SetMute(name.clone(), muted))
+            }
+            Action::SetScene(_) => Some(Action::SetScene(self.obs_state.current_scene.clone()?)),
+            // OBS doesn't say when a source is shown or hidden elsewhere, so one
+            // REC hasn't touched yet is taken to be the other way around.
+            Action::SetSceneItemEnabled(scene, source, enabled) => {
+                let was = self
+                    .obs_state
+                    .scene_items
+                    .get(scene)
+                    .and_then(|items| items.get(source))
+                    .copied()
+                    .unwrap_or(!enabled);
+                Some(Action::SetSceneItemEnabled(
+                    scene.clone(),
+                    source.clone(),
+                    was,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    // Volume, mute and visibility changes show up in the UI right away instead
+    // of waiting for OBS to echo them back.
+    pub(super) fn apply_optimistic(&mut self, action: &Action) {
+        match action {
+            Action::SetVolume(name, volume) => {
+                self.obs_state.volumes.insert(name.clone(), *volume);
+            }
+            Action::SetMute(name, muted) => {
+                self.obs_state.muted.insert(name.clone(), *muted);
+            }
+            Action::SetSceneItemEnabled(scene, source, enabled) => {
+                self.obs_state.apply(&ObsInfo::SceneItemChanged(
+                    scene.clone(),
+                    source.clone(),
+                    *enabled,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // Sends an action the operator took, remembering how to reverse it.
+    pub(super) fn perform(&mut self, action: Action) {
+        if let Some(undo) = self.inverse(&action) {
+            let now = Instant::now();
+            match self.history.last_mut() {
+                Some(last)
+                    if !last.undone
+                        && now - last.last_change < COALESCE_WINDOW
+                        && matches!(
+                            (&last.action, &action),
+                            (Action::SetVolume(a, _), Action::SetVolume(b, _)) if a == b
+                        ) =>
+                {
+                    last.action = action.clone();
+                    last.last_change = now;
+                }
+                _ => {
+                    self.history.push(HistoryEntry {
+                        time: Local::now(),
+                        last_change: now,
+                        action: action.clone(),
+                        undo,
+                        undone: false,
+                    });
+                    if self.history.len() > HISTORY_CAPACITY {
+                        self.history.remove(0);
+                    }
+                }
+            }
+        }
+        self.apply_optimistic(&action);
+        self.send(action);
+    }
+
+    fn undo(&mut self, index: usize) {
+        let entry = &mut self.history[index];
+        if entry.undone {
+            return;
+        }
+        entry.undone = true;
+        let undo = entry.undo.clone();
+        self.apply_optimistic(&undo);
+        self.send(undo);
+    }
+
+    pub(super) fn undo_last(&mut self) {
+        if let Some(index) = self.history.iter().rposition(|entry| !entry.undone) {
+            self.undo(index);
+        }
+    }
+
+    pub(super) fn show_history(&mut self, ui: &mut egui::Ui) {
+        let mut undo = None;
+        for (index, entry) in self.history.iter().enumerate().rev() {
+            ui.horizontal(|ui| {
+                let text = format!(
+                    "{} {}",
+                    entry.time.format("%H:%M:%S"),
+                    describe(&self.i18n, &self.channels, &entry.action)
+                );
+                if entry.undone {
+                    ui.label(egui::RichText::new(text).strikethrough().weak());
+                } else {
+                    ui.label(text);
+                    if ui.small_button(self.i18n.tr("history-undo")).clicked() {
+                        undo = Some(index);
+                    }
+                }
+            });
+        }
+        if let Some(index) = undo {
+            self.undo(index);
+        }
+    }
+}
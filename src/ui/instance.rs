@@ -0,0 +1,104 @@
+use super::App;
+use crate::{cli::Command, instance::Reply, obs_worker::Action};
+use fluent::fluent_args;
+
+impl App {
+    // Commands from later invocations run here, against the connection that's
+    // already open, instead of each opening its own.
+    pub(super) fn handle_instance(&mut self, ctx: &egui::Context) {
+        while let Some(incoming) = self.instance.as_ref().and_then(|listener| listener.next()) {
+            let reply = match &incoming.command {
+                None => {
+                    self.set_window_hidden(ctx, false);
+                    Reply::Done(String::new())
+                }
+                Some(command) => self.run_command(command),
+            };
+            incoming.reply(reply);
+        }
+    }
+
+    fn run_command(&mut self, command: &Command) -> Reply {
+        if let Command::Help = command {
+            return Reply::Done(self.i18n.tr("cli-usage"));
+        }
+        if !self.connected {
+            return Reply::Failed(self.i18n.tr("status-offline"));
+        }
+        let message = match command {
+            Command::Help => unreachable!(),
+            Command::Scene(scene) => {
+                if !self.obs_state.scenes.contains(scene) {
+                    return Reply::Failed(
+                        self.i18n
+                            .tr_args("cli-no-scene", &fluent_args!["scene" => scene.as_str()]),
+                    );
+                }
+                self.send(Action::SetScene(scene.clone()));
+                self.i18n
+                    .tr_args("cli-scene", &fluent_args!["scene" => scene.as_str()])
+            }
+            Command::Mute(input, change) => {
+                let input = self.alias_input(input).unwrap_or_else(|| input.clone());
+                let Some(&current) = self.obs_state.muted.get(&input) else {
+                    return Reply::Failed(
+                        self.i18n
+                            .tr_args("cli-no-input", &fluent_args!["input" => input]),
+                    );
+                };
+                let muted = change.apply(current);
+                if muted != current {
+                    self.perform(Action::SetMute(input.clone(), muted));
+                }
+                let id = if muted { "cli-muted" } else { "cli-unmuted" };
+                self.i18n.tr_args(id, &fluent_args!["input" => input])
+            }
+            Command::Record(change) => {
+                let recording = change.apply(self.obs_state.recording);
+                if recording != self.obs_state.recording {
+                    self.send(if recording {
+                        Action::StartRecord
+                    } else {
+                        Action::StopRecord
+                    });
+                }
+                self.i18n.tr(if recording {
+                    "cli-recording"
+                } else {
+                    "cli-not-recording"
+                })
+            }
+            Command::Stream(change) => {
+                let streaming = change.apply(self.obs_state.streaming);
+                if streaming != self.obs_state.streaming {
+                    self.send(if streaming {
+                        Action::StartStream
+                    } else {
+                        Action::StopStream
+                    });
+                }
+                self.i18n.tr(if streaming {
+                    "cli-streaming"
+                } else {
+                    "cli-not-streaming"
+                })
+            }
+            Command::Macro(name) => {
+                let found = self
+                    .all_macros()
+                    .into_iter()
+                    .find(|obs_macro| obs_macro.name == *name);
+                let Some(obs_macro) = found else {
+                    return Reply::Failed(
+                        self.i18n
+                            .tr_args("cli-no-macro", &fluent_args!["name" => name.as_str()]),
+                    );
+                };
+                self.send(Action::RunMacro(obs_macro));
+                self.i18n
+                    .tr_args("cli-macro", &fluent_args!["name" => name.as_str()])
+            }
+        };
+        Reply::Done(message)
+    }
+}
@@ -0,0 +1,142 @@
+use super::{a11y, App};
+use crate::{
+    audio::{Level, SceneCue},
+    obs_worker::{Action, Macro, MacroStep},
+};
+use std::{collections::BTreeMap, time::Duration};
+
+// What the show looked like when the break started, to go back to.
+pub(super) struct Intermission {
+    scene: Option<String>,
+    // Volume and whether it was muted, for every input the break touches.
+    audio: BTreeMap<String, (Option<f32>, Option<bool>)>,
+}
+
+impl App {
+    pub(super) fn alias_input(&self, name: &str) -> Option<String> {
+        match name {
+            "mic" => self.mic_input_name.clone(),
+            "desktop" => self.desktop_input_name.clone(),
+            name => Some(name.to_string()),
+        }
+    }
+
+    pub(super) fn toggle_intermission(&mut self) {
+        if self.intermission.is_some() {
+            self.end_intermission();
+        } else {
+            self.start_intermission();
+        }
+    }
+
+    fn start_intermission(&mut self) {
+        let Some(config) = self.intermission_config.clone() else {
+            return;
+        };
+        let audio = config
+            .mute
+            .iter()
+            .chain(config.audio.keys())
+            .filter_map(|name| self.alias_input(name))
+            .map(|input| {
+                let volume = self.obs_state.volumes.get(&input).copied();
+                let muted = self.obs_state.muted.get(&input).copied();
+                (input, (volume, muted))
+            })
+            .collect();
+        self.intermission = Some(Intermission {
+            scene: self.obs_state.current_scene.clone(),
+            audio,
+        });
+        tracing::info!(scene = config.scene, "intermission started");
+
+        let mut actions: Vec<_> = config
+            .mute
+            .iter()
+            .map(|input| Action::SetMute(input.clone(), true))
+            .collect();
+        actions.push(Action::SceneCue(SceneCue {
+            scene: Some(config.scene),
+            audio: config.audio,
+            fade_ms: config.fade_ms,
+        }));
+        actions.push(Action::Intermission(true));
+        self.send(Action::RunMacro(Macro {
+            name: self.i18n.tr("intermission"),
+            steps: actions.into_iter().map(MacroStep::Do).collect(),
+        }));
+        if let Some(minutes) = config.minutes {
+            self.countdown
+                .start_for(Duration::from_secs(minutes as u64 * 60));
+        }
+        if let (Some(message), false) = (config.chat_start, self.rehearsal) {
+            self.twitch.post(message);
+        }
+    }
+
+    fn end_intermission(&mut self) {
+        let Some(intermission) = self.intermission.take() else {
+            return;
+        };
+        tracing::info!("intermission ended");
+        let fade_ms = self
+            .intermission_config
+            .as_ref()
+            .map_or(0, |config| config.fade_ms);
+        let (muted, unmuted): (Vec<_>, Vec<_>) = intermission
+            .audio
+            .iter()
+            .filter_map(|(input, (_, muted))| Some((input, (*muted)?)))
+            .partition(|(_, muted)| *muted);
+        // Unmuted first so the fade back up is heard.
+        let mut actions: Vec<_> = unmuted
+            .into_iter()
+            .map(|(input, _)| Action::SetMute(input.clone(), false))
+            .collect();
+        actions.push(Action::SceneCue(SceneCue {
+            scene: intermission.scene,
+            audio: intermission
+                .audio
+                .iter()
+                .filter_map(|(input, (volume, _))| {
+                    Some((input.clone(), Level::from_volume((*volume)?)))
+                })
+                .collect(),
+            fade_ms,
+        }));
+        actions.extend(
+            muted
+                .into_iter()
+                .map(|(input, _)| Action::SetMute(input.clone(), true)),
+        );
+        actions.push(Action::Intermission(false));
+        self.send(Action::RunMacro(Macro {
+            name: self.i18n.tr("intermission"),
+            steps: actions.into_iter().map(MacroStep::Do).collect(),
+        }));
+        self.countdown.reset();
+        let message = self
+            .intermission_config
+            .as_ref()
+            .and_then(|config| config.chat_end.clone());
+        if let (Some(message), false) = (message, self.rehearsal) {
+            self.twitch.post(message);
+        }
+    }
+
+    pub(super) fn show_intermission_button(&mut self, ui: &mut egui::Ui) {
+        if self.intermission_config.is_none() {
+            return;
+        }
+        let button = ui
+            .selectable_label(
+                self.intermission.is_some(),
+                format!("☕ {}", self.i18n.tr("intermission")),
+            )
+            .on_hover_text(self.i18n.tr("intermission-hint"));
+        a11y::name(ui, &button, self.i18n.tr("intermission"));
+        if button.clicked() {
+            self.toggle_intermission();
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::{channels, App};
+use crate::{obs_worker::Action, source_record::IsoRecorder};
+
+#[derive(Default)]
+pub(super) struct Iso {
+    recorders: Vec<IsoRecorder>,
+    // Listed once per connection without asking; Refresh picks up new ones.
+    listed: bool,
+}
+
+impl Iso {
+    pub(super) fn set(&mut self, recorders: Vec<IsoRecorder>) {
+        self.recorders = recorders;
+    }
+
+    pub(super) fn clear(&mut self) {
+        *self = Iso::default();
+    }
+}
+
+impl App {
+    fn iso_enabled(&self, recorder: &IsoRecorder) -> bool {
+        self.obs_state
+            .filters
+            .get(&recorder.source)
+            .and_then(|filters| filters.get(&recorder.filter))
+            .copied()
+            .unwrap_or(recorder.enabled)
+    }
+
+    pub(super) fn show_iso(&mut self, ui: &mut egui::Ui) {
+        if self.connected && !self.iso.listed {
+            self.iso.listed = true;
+            self.send(Action::ListIsoRecorders);
+        }
+        let mut changes = Vec::new();
+        ui.horizontal(|ui| {
+            if ui.button(self.i18n.tr("iso-refresh")).clicked() {
+                changes.push(Action::ListIsoRecorders);
+            }
+            for (enabled, id) in [(true, "iso-arm-all"), (false, "iso-disarm-all")] {
+                if ui
+                    .add_enabled(
+                        !self.iso.recorders.is_empty(),
+                        egui::Button::new(self.i18n.tr(id)),
+                    )
+                    .clicked()
+                {
+                    changes.extend(self.iso.recorders.iter().map(|recorder| {
+                        Action::SetFilterEnabled(
+                            recorder.source.clone(),
+                            recorder.filter.clone(),
+                            enabled,
+                        )
+                    }));
+                }
+            }
+        });
+        if self.iso.recorders.is_empty() {
+            ui.weak(self.i18n.tr("iso-none"));
+        }
+        egui::Grid::new("iso")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for recorder in &self.iso.recorders {
+                    let enabled = self.iso_enabled(recorder);
+                    let active = enabled
+                        && recorder
+                            .mode
+                            .active(self.obs_state.streaming, self.obs_state.recording);
+                    ui.vertical(|ui| {
+                        ui.label(channels::name(&self.channels, &recorder.source));
+                        ui.weak(&recorder.filter);
+                    });
+                    ui.weak(self.i18n.tr(recorder.mode.message_id()));
+                    if active {
+                        ui.colored_label(egui::Color32::RED, self.i18n.tr("iso-recording"));
+                    } else if enabled {
+                        ui.colored_label(ui.visuals().warn_fg_color, self.i18n.tr("iso-armed"));
+                    } else {
+                        ui.weak(self.i18n.tr("iso-off"));
+                    }
+                    let id = if enabled { "iso-disarm" } else { "iso-arm" };
+                    if ui.button(self.i18n.tr(id)).clicked() {
+                        changes.push(Action::SetFilterEnabled(
+                            recorder.source.clone(),
+                            recorder.filter.clone(),
+                            !enabled,
+                        ));
+                    }
+                    ui.end_row();
+                }
+            });
+        for action in changes {
+            self.send(action);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::{a11y, App};
+use crate::layout::Panel;
+
+// All a volunteer gets: the mixer and the scenes picked for them.
+const VOLUNTEER_PANELS: [Panel; 2] = [Panel::Mixer, Panel::Scenes];
+
+#[derive(Default)]
+pub(super) struct Unlock {
+    open: bool,
+    pin: String,
+    wrong: bool,
+}
+
+impl App {
+    pub(super) fn kiosk(&self) -> bool {
+        self.kiosk_pin.is_some()
+    }
+
+    pub(super) fn volunteer(&self) -> bool {
+        self.volunteer_pin.is_some()
+    }
+
+    // Either way of handing REC to someone else, both of which keep the setup
+    // out of reach.
+    pub(super) fn locked(&self) -> bool {
+        self.kiosk() || self.volunteer()
+    }
+
+    pub(super) fn panel_locked(&self, panel: Panel) -> bool {
+        matches!(panel, Panel::Settings | Panel::Advanced) && self.locked()
+            || self.volunteer() && !VOLUNTEER_PANELS.contains(&panel)
+    }
+
+    // Unlike kiosk mode the window stays as it is, for a volunteer working
+    // next to other programs.
+    pub(super) fn enter_volunteer(&mut self, ctx: &egui::Context, pin: String) {
+        self.volunteer_pin = Some(pin);
+        if self.mini_mode {
+            self.toggle_mini_mode(ctx);
+        }
+        self.palette.close();
+        self.console.close();
+        self.popped_out.clear();
+    }
+
+    // Scenes left out of the volunteer's list; with none picked they see all.
+    pub(super) fn volunteer_hides(&self, scene: &str) -> bool {
+        self.volunteer()
+            && !self.volunteer_scenes.is_empty()
+            && !self.volunteer_scenes.contains(scene)
+    }
+
+    pub(super) fn enter_kiosk(&mut self, ctx: &egui::Context, pin: String) {
+        self.kiosk_pin = Some(pin);
+        if self.mini_mode {
+            self.toggle_mini_mode(ctx);
+        }
+        self.palette.close();
+        self.popped_out.clear();
+        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
+    }
+
+    fn exit_kiosk(&mut self, ctx: &egui::Context) {
+        self.kiosk_pin = None;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(true));
+    }
+
+    // Closing the window is as much a way out as anything else.
+    pub(super) fn handle_kiosk(&mut self, ctx: &egui::Context) {
+        if self.kiosk() && ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.unlock.open = true;
+        }
+    }
+
+    pub(super) fn request_unlock(&mut self) {
+        self.unlock = Unlock {
+            open: true,
+            ..Unlock::default()
+        };
+    }
+
+    // A keypad, since a dedicated touchscreen usually has no keyboard.
+    pub(super) fn show_unlock(&mut self, ctx: &egui::Context) {
+        if !self.unlock.open || !self.locked() {
+            return;
+        }
+        let title = if self.kiosk() {
+            "kiosk-unlock-title"
+        } else {
+            "volunteer-unlock-title"
+        };
+        let mut submit = ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter));
+        let mut cancel = false;
+        let unlock = &mut self.unlock;
+        egui::Window::new(self.i18n.tr(title))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut unlock.pin)
+                        .password(true)
+                        .desired_width(160.0),
+                );
+                a11y::name(ui, &field, self.i18n.tr("kiosk-pin"));
+                if unlock.wrong {
+                    ui.colored_label(ui.visuals().error_fg_color, self.i18n.tr("kiosk-wrong-pin"));
+                }
+                egui::Grid::new("keypad").show(ui, |ui| {
+                    for row in [["1", "2", "3"], ["4", "5", "6"], ["7", "8", "9"]] {
+                        for digit in row {
+                            if ui.button(digit).clicked() {
+                                unlock.pin.push_str(digit);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                    let back = ui.button("⌫");
+                    a11y::name(ui, &back, self.i18n.tr("kiosk-backspace"));
+                    if back.clicked() {
+                        unlock.pin.pop();
+                    }
+                    if ui.button("0").clicked() {
+                        unlock.pin.push('0');
+                    }
+                    submit |= ui.button(self.i18n.tr("kiosk-unlock")).clicked();
+                    ui.end_row();
+                });
+                cancel = ui.button(self.i18n.tr("confirm-cancel")).clicked();
+            });
+
+        if submit {
+            if self.kiosk_pin.as_ref() == Some(&self.unlock.pin) {
+                self.unlock = Unlock::default();
+                self.exit_kiosk(ctx);
+            } else if self.volunteer_pin.as_ref() == Some(&self.unlock.pin) {
+                self.unlock = Unlock::default();
+                self.volunteer_pin = None;
+            } else {
+                self.unlock.pin.clear();
+                self.unlock.wrong = true;
+            }
+        } else if cancel {
+            self.unlock = Unlock::default();
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use super::{a11y, App};
+use crate::{
+    i18n::I18n,
+    lighting::{Device, LightingMapping},
+    obs_worker::Action,
+    state::Condition,
+};
+
+impl App {
+    // Text fields only apply once left, so every keystroke doesn't flash the
+    // lights.
+    pub(super) fn show_lighting_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut pair = false;
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("lighting-hue-bridge"));
+            changed |= ui
+                .add(egui::TextEdit::singleline(&mut self.lighting.hue_bridge).desired_width(120.0))
+                .labelled_by(label.id)
+                .lost_focus();
+            pair = ui
+                .add_enabled(
+                    !self.lighting.hue_bridge.is_empty(),
+                    egui::Button::new(self.i18n.tr("lighting-pair")),
+                )
+                .on_hover_text(self.i18n.tr("lighting-pair-hint"))
+                .clicked();
+            if !self.lighting.hue_username.is_empty() {
+                ui.weak(self.i18n.tr("lighting-paired"));
+            }
+        });
+        if pair {
+            self.send(Action::PairHue(self.lighting.hue_bridge.clone()));
+        }
+
+        let scenes = self.obs_state.scenes.clone();
+        let mut inputs: Vec<String> = self.obs_state.muted.keys().cloned().collect();
+        inputs.sort();
+        // The mic goes first, so mute conditions start out on it.
+        if let Some(mic) = &self.mic_input_name {
+            inputs.retain(|input| input != mic);
+            inputs.insert(0, mic.clone());
+        }
+        let mut remove = None;
+        let mut raise = None;
+        egui::Grid::new("lighting_mappings")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, mapping) in self.lighting.mappings.iter_mut().enumerate() {
+                    ui.push_id(index, |ui| {
+                        changed |=
+                            condition_picker(ui, &self.i18n, &scenes, &inputs, &mut mapping.when);
+                    });
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source(("lighting_device", index))
+                            .selected_text(self.i18n.tr(mapping.device.message_id()))
+                            .show_ui(ui, |ui| {
+                                let target = mapping.device.target_mut().clone();
+                                for device in [
+                                    Device::HueGroup(target.clone()),
+                                    Device::Wled(target.clone()),
+                                    Device::OpenRgb(target),
+                                ] {
+                                    let text = self.i18n.tr(device.message_id());
+                                    changed |= ui
+                                        .selectable_value(&mut mapping.device, device, text)
+                                        .changed();
+                                }
+                            });
+                        let hint = self.i18n.tr(match mapping.device {
+                            Device::HueGroup(_) => "lighting-group-hint",
+                            Device::Wled(_) | Device::OpenRgb(_) => "lighting-host-hint",
+                        });
+                        let response = ui.add(
+                            egui::TextEdit::singleline(mapping.device.target_mut())
+                                .hint_text(hint.clone())
+                                .desired_width(100.0),
+                        );
+                        a11y::name(ui, &response, hint);
+                        changed |= response.lost_focus();
+                    });
+                    let hint = self.i18n.tr("lighting-preset");
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut mapping.preset)
+                            .hint_text(hint.clone())
+                            .desired_width(100.0),
+                    );
+                    a11y::name(ui, &response, hint);
+                    changed |= response.lost_focus();
+                    let up = ui.add_enabled(index > 0, egui::Button::new("⏶"));
+                    a11y::name(ui, &up, self.i18n.tr("lighting-raise"));
+                    if up.clicked() {
+                        raise = Some(index);
+                    }
+                    let delete = ui.button("🗑");
+                    a11y::name(ui, &delete, self.i18n.tr("lighting-remove"));
+                    if delete.clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = raise {
+            self.lighting.mappings.swap(index - 1, index);
+            changed = true;
+        }
+        if let Some(index) = remove {
+            self.lighting.mappings.remove(index);
+            changed = true;
+        }
+        ui.horizontal(|ui| {
+            if ui.button(self.i18n.tr("lighting-add")).clicked() {
+                self.lighting.mappings.push(LightingMapping {
+                    when: Condition::Streaming,
+                    device: Device::HueGroup(String::new()),
+                    preset: String::new(),
+                });
+            }
+            ui.weak(self.i18n.tr("lighting-order-hint"));
+        });
+        if changed {
+            self.send(Action::SetLighting(self.lighting.clone()));
+        }
+    }
+}
+
+// Only what makes sense for lights is offered here; anything else written by
+// hand into the saved state is shown but left alone.
+fn condition_picker(
+    ui: &mut egui::Ui,
+    i18n: &I18n,
+    scenes: &[String],
+    inputs: &[String],
+    condition: &mut Condition,
+) -> bool {
+    let mut changed = false;
+    let first_scene = scenes.first().cloned().unwrap_or_default();
+    let first_input = inputs.first().cloned().unwrap_or_default();
+    ui.horizontal(|ui| {
+        let selected = match condition {
+            Condition::Streaming => "lighting-when-streaming",
+            Condition::Recording => "lighting-when-recording",
+            Condition::SceneIs(_) => "lighting-when-scene",
+            Condition::InputMuted(_) => "lighting-when-muted",
+            Condition::Not(inner) if matches!(**inner, Condition::InputMuted(_)) => {
+                "lighting-when-live"
+            }
+            _ => "lighting-when-other",
+        };
+        egui::ComboBox::from_id_source("lighting_when")
+            .selected_text(i18n.tr(selected))
+            .show_ui(ui, |ui| {
+                for (option, id) in [
+                    (Condition::Streaming, "lighting-when-streaming"),
+                    (Condition::Recording, "lighting-when-recording"),
+                    (Condition::SceneIs(first_scene), "lighting-when-scene"),
+                    (
+                        Condition::InputMuted(first_input.clone()),
+                        "lighting-when-muted",
+                    ),
+                    (
+                        Condition::Not(Box::new(Condition::InputMuted(first_input))),
+                        "lighting-when-live",
+                    ),
+                ] {
+                    let current = selected == id;
+                    if ui.selectable_label(current, i18n.tr(id)).clicked() && !current {
+                        *condition = option;
+                        changed = true;
+                    }
+                }
+            });
+        if let Condition::SceneIs(scene) = condition {
+            egui::ComboBox::from_id_source("lighting_scene")
+                .selected_text(scene.as_str())
+                .show_ui(ui, |ui| {
+                    for name in scenes {
+                        changed |= ui.selectable_value(scene, name.clone(), name).changed();
+                    }
+                });
+        }
+        let input = match condition {
+            Condition::InputMuted(input) => Some(input),
+            Condition::Not(inner) => match &mut **inner {
+                Condition::InputMuted(input) => Some(input),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(input) = input {
+            egui::ComboBox::from_id_source("lighting_input")
+                .selected_text(input.as_str())
+                .show_ui(ui, |ui| {
+                    for name in inputs {
+                        changed |= ui.selectable_value(input, name.clone(), name).changed();
+                    }
+                });
+        }
+    });
+    changed
+}
@@ -0,0 +1,74 @@
+use super::App;
+use crate::{lights::LightChange, obs_worker::Action};
+
+impl App {
+    // Sliders only send once let go, since every change is an HTTP request.
+    pub(super) fn show_lights(&mut self, ui: &mut egui::Ui) {
+        if self.lights.is_empty() {
+            ui.weak(self.i18n.tr("lights-none"));
+            return;
+        }
+        let mut changes = Vec::new();
+        let mut all = None;
+        ui.horizontal(|ui| {
+            for (id, on) in [("lights-all-on", true), ("lights-all-off", false)] {
+                if ui.button(self.i18n.tr(id)).clicked() {
+                    all = Some(LightChange {
+                        on: Some(on),
+                        ..LightChange::default()
+                    });
+                }
+            }
+        });
+        egui::Grid::new("lights").num_columns(4).show(ui, |ui| {
+            for light in self.lights.values_mut() {
+                let name = light.name.clone();
+                if ui.checkbox(&mut light.on, &light.name).changed() {
+                    changes.push((
+                        name.clone(),
+                        LightChange {
+                            on: Some(light.on),
+                            ..LightChange::default()
+                        },
+                    ));
+                }
+                let brightness = ui.add(
+                    egui::Slider::new(&mut light.brightness, 3..=100)
+                        .suffix("%")
+                        .text(self.i18n.tr("lights-brightness")),
+                );
+                if brightness.drag_released() || brightness.changed() && !brightness.dragged() {
+                    changes.push((
+                        name.clone(),
+                        LightChange {
+                            brightness: Some(light.brightness),
+                            ..LightChange::default()
+                        },
+                    ));
+                }
+                let temperature = ui.add(
+                    egui::Slider::new(&mut light.temperature, 2900..=7000)
+                        .suffix("K")
+                        .step_by(50.0)
+                        .text(self.i18n.tr("lights-temperature")),
+                );
+                if temperature.drag_released() || temperature.changed() && !temperature.dragged() {
+                    changes.push((
+                        name,
+                        LightChange {
+                            temperature: Some(light.temperature),
+                            ..LightChange::default()
+                        },
+                    ));
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(change) = all {
+            self.send(Action::SetKeyLights(change));
+        }
+        for (name, change) in changes {
+            self.send(Action::SetKeyLight(name, change));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use super::{a11y, timers::picker, App};
+use crate::{
+    config::{LowerThird, LowerThirdSettings},
+    obs_worker::Action,
+};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+pub(super) struct LowerThirds {
+    pub(super) settings: LowerThirdSettings,
+    // The entry on screen and when it goes away.
+    showing: Option<(usize, Instant)>,
+    new_name: String,
+    new_title: String,
+}
+
+impl App {
+    pub(super) fn tick_lower_third(&mut self, ctx: &egui::Context) {
+        let Some((_, hide_at)) = self.lower_thirds.showing else {
+            return;
+        };
+        match hide_at.checked_duration_since(Instant::now()) {
+            Some(left) if !left.is_zero() => ctx.request_repaint_after(left),
+            _ => self.hide_lower_third(),
+        }
+    }
+
+    fn show_lower_third(&mut self, index: usize) {
+        let settings = &self.lower_thirds.settings;
+        let Some(entry) = settings.entries.get(index) else {
+            return;
+        };
+        let mut actions = Vec::new();
+        for (input, text) in [
+            (&settings.name_input, &entry.name),
+            (&settings.title_input, &entry.title),
+        ] {
+            if let Some(input) = input {
+                actions.push(Action::SetText(input.clone(), text.clone()));
+            }
+        }
+        let shown = settings
+            .scene
+            .as_ref()
+            .map(|scene| Action::SetSceneItemEnabled(scene.clone(), settings.source.clone(), true));
+        let hide_at = Instant::now() + Duration::from_secs(settings.seconds as u64);
+        for action in actions {
+            self.send(action);
+        }
+        // Showing one by hand can be undone; the timer hiding it isn't
+        // something the operator did.
+        if let Some(action) = shown {
+            self.perform(action);
+        }
+        self.lower_thirds.showing = Some((index, hide_at));
+    }
+
+    fn hide_lower_third(&mut self) {
+        self.lower_thirds.showing = None;
+        if let Some(scene) = &self.lower_thirds.settings.scene {
+            self.send(Action::SetSceneItemEnabled(
+                scene.clone(),
+                self.lower_thirds.settings.source.clone(),
+                false,
+            ));
+        }
+    }
+
+    pub(super) fn show_lower_thirds(&mut self, ui: &mut egui::Ui) {
+        let showing = self.lower_thirds.showing.map(|(index, _)| index);
+        let mut show = None;
+        let mut remove = None;
+        egui::Grid::new("lower_thirds")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, entry) in self.lower_thirds.settings.entries.iter().enumerate() {
+                    ui.vertical(|ui| {
+                        ui.strong(&entry.name);
+                        ui.weak(&entry.title);
+                    });
+                    let text = if showing == Some(index) {
+                        self.i18n.tr("lower-third-showing")
+                    } else {
+                        self.i18n.tr("lower-third-show")
+                    };
+                    if ui
+                        .add(egui::Button::new(text).selected(showing == Some(index)))
+                        .clicked()
+                    {
+                        show = Some(index);
+                    }
+                    let delete = ui.button("🗑");
+                    a11y::name(ui, &delete, self.i18n.tr("lower-third-remove"));
+                    if delete.clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = show {
+            self.show_lower_third(index);
+        }
+        if let Some(index) = remove {
+            self.lower_thirds.settings.entries.remove(index);
+            if showing.is_some() {
+                self.hide_lower_third();
+            }
+        }
+        if showing.is_some() && ui.button(self.i18n.tr("lower-third-hide")).clicked() {
+            self.hide_lower_third();
+        }
+
+        ui.horizontal(|ui| {
+            let lower_thirds = &mut self.lower_thirds;
+            for (text, id) in [
+                (&mut lower_thirds.new_name, "lower-third-name"),
+                (&mut lower_thirds.new_title, "lower-third-title"),
+            ] {
+                let hint = self.i18n.tr(id);
+                let response = ui.add(
+                    egui::TextEdit::singleline(text)
+                        .hint_text(hint.clone())
+                        .desired_width(120.0),
+                );
+                a11y::name(ui, &response, hint);
+            }
+            if ui
+                .add_enabled(
+                    !lower_thirds.new_name.is_empty(),
+                    egui::Button::new(self.i18n.tr("lower-third-add")),
+                )
+                .clicked()
+            {
+                lower_thirds.settings.entries.push(LowerThird {
+                    name: std::mem::take(&mut lower_thirds.new_name),
+                    title: std::mem::take(&mut lower_thirds.new_title),
+                });
+            }
+        });
+
+        let text_inputs = self.text_inputs();
+        let scenes = self.obs_state.scenes.clone();
+        ui.collapsing(self.i18n.tr("lower-third-setup"), |ui| {
+            let settings = &mut self.lower_thirds.settings;
+            egui::Grid::new("lower_third_setup")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    picker(
+                        ui,
+                        &self.i18n,
+                        "lower-third-scene",
+                        &scenes,
+                        &mut settings.scene,
+                    );
+                    ui.end_row();
+                    let label = ui.label(self.i18n.tr("lower-third-source"));
+                    ui.text_edit_singleline(&mut settings.source)
+                        .labelled_by(label.id);
+                    ui.end_row();
+                    picker(
+                        ui,
+                        &self.i18n,
+                        "lower-third-name-source",
+                        &text_inputs,
+                        &mut settings.name_input,
+                    );
+                    ui.end_row();
+                    picker(
+                        ui,
+                        &self.i18n,
+                        "lower-third-title-source",
+                        &text_inputs,
+                        &mut settings.title_input,
+                    );
+                    ui.end_row();
+                    let label = ui.label(self.i18n.tr("lower-third-seconds"));
+                    ui.add(egui::DragValue::new(&mut settings.seconds).clamp_range(1..=120))
+                        .labelled_by(label.id);
+                    ui.end_row();
+                });
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use super::{a11y, App};
+use crate::obs_worker::Action;
+
+const MINI_SIZE: [f32; 2] = [420.0, 96.0];
+
+impl App {
+    pub(super) fn toggle_mini_mode(&mut self, ctx: &egui::Context) {
+        self.mini_mode = !self.mini_mode;
+        if self.mini_mode {
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+                egui::WindowLevel::AlwaysOnTop,
+            ));
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(MINI_SIZE.into()));
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+                egui::WindowLevel::Normal,
+            ));
+            if let Some(window) = self.window {
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window.size.into()));
+            }
+        }
+    }
+
+    fn mute_button(&mut self, ui: &mut egui::Ui, label: String, input: Option<String>) {
+        let Some(name) = input else {
+            ui.add_enabled(false, egui::Button::new(label));
+            return;
+        };
+        let muted = self.obs_state.muted.get(&name).copied().unwrap_or(false);
+        let mut button = egui::Button::new(label.clone());
+        if muted {
+            button = button.fill(egui::Color32::RED);
+        }
+        let response = ui.add(button);
+        a11y::toggle(&response, &label, muted);
+        if response.clicked() {
+            self.perform(Action::SetMute(name, !muted));
+        }
+    }
+
+    pub(super) fn show_mini(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                self.mute_button(ui, self.i18n.tr("mini-mic"), self.mic_input_name.clone());
+                self.mute_button(
+                    ui,
+                    self.i18n.tr("mini-desktop"),
+                    self.desktop_input_name.clone(),
+                );
+                ui.separator();
+                let live = egui::Color32::RED;
+                let idle = ui.visuals().weak_text_color();
+                // The indicators only differ by color, so they're named by state.
+                let streaming = ui.colored_label(
+                    if self.obs_state.streaming { live } else { idle },
+                    self.i18n.tr("mini-live"),
+                );
+                a11y::name(
+                    ui,
+                    &streaming,
+                    self.i18n.tr(if self.obs_state.streaming {
+                        "status-streaming"
+                    } else {
+                        "status-not-streaming"
+                    }),
+                );
+                let recording = ui.colored_label(
+                    if self.obs_state.recording { live } else { idle },
+                    self.i18n.tr("mini-rec"),
+                );
+                a11y::name(
+                    ui,
+                    &recording,
+                    self.i18n.tr(if self.obs_state.recording {
+                        "status-recording"
+                    } else {
+                        "status-not-recording"
+                    }),
+                );
+                if !self.connected {
+                    ui.colored_label(ui.visuals().warn_fg_color, self.i18n.tr("status-offline"));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let full_window = ui
+                        .button("⛶")
+                        .on_hover_text(self.i18n.tr("mini-full-window"));
+                    a11y::name(ui, &full_window, self.i18n.tr("mini-full-window"));
+                    if full_window.clicked() {
+                        self.toggle_mini_mode(ctx);
+                    }
+                });
+            });
+            let mut clicked = None;
+            egui::ScrollArea::horizontal()
+                .drag_to_scroll(self.touch_mode)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        for obs_macro in self.all_macros() {
+                            if ui.button(&obs_macro.name).clicked() {
+                                clicked = Some(Action::RunMacro(obs_macro));
+                            }
+                        }
+                    });
+                });
+            if let Some(action) = clicked {
+                self.send(action);
+            }
+        });
+    }
+}
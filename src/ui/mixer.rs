@@ -0,0 +1,463 @@
+use super::{a11y, channels, tooltip, App};
+use crate::{
+    config::SliderMode,
+    layout::Panel,
+    obs_worker::{Action, Macro, MacroStep, Query},
+    session::SessionMessage,
+};
+use fluent::fluent_args;
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+enum Bulk {
+    Mute(bool),
+    // In dB.
+    Level(f32),
+    Group(String),
+    Clear,
+}
+
+// At most 20 volume changes a second per input while throttled.
+const VOLUME_THROTTLE: Duration = Duration::from_millis(50);
+// How long after a drag OBS gets to echo the final volume back. Echoes of
+// values sent earlier in the drag arrive first and are ignored meanwhile.
+const RECONCILE_WINDOW: Duration = Duration::from_millis(500);
+
+impl App {
+    // The local value wins while a slider is held and shortly after; from
+    // then on OBS's does again, including changes made elsewhere meanwhile.
+    pub(super) fn sync_levels(&mut self) {
+        self.volume_holds
+            .retain(|_, released| released.map_or(true, |at| at.elapsed() < RECONCILE_WINDOW));
+        for (name, level) in [
+            (&self.mic_input_name, &mut self.mic_level),
+            (&self.desktop_input_name, &mut self.desktop_level),
+        ] {
+            let Some(name) = name else {
+                continue;
+            };
+            if self.volume_holds.contains_key(name) {
+                continue;
+            }
+            if let Some(volume) = self.obs_state.volumes.get(name) {
+                *level = *volume;
+            }
+        }
+    }
+
+    fn slide_volume(&mut self, response: &egui::Response, name: Option<String>, level: f32) {
+        let Some(name) = name else {
+            return;
+        };
+        let released = response.drag_released();
+        if response.dragged() {
+            self.volume_holds.insert(name.clone(), None);
+        } else if released {
+            self.volume_holds.insert(name.clone(), Some(Instant::now()));
+            response.ctx.request_repaint_after(RECONCILE_WINDOW);
+        }
+        let send = match self.slider_mode {
+            SliderMode::Continuous => response.changed(),
+            // The last value of a drag always goes out, even if it came too
+            // soon after the one before.
+            SliderMode::Throttled => {
+                released
+                    || response.changed()
+                        && self
+                            .volume_sent
+                            .get(&name)
+                            .map_or(true, |sent| sent.elapsed() >= VOLUME_THROTTLE)
+            }
+            // Clicks and keys change it without a drag.
+            SliderMode::OnRelease => released || response.changed() && !response.dragged(),
+        };
+        if send {
+            self.volume_sent.insert(name.clone(), Instant::now());
+            self.perform(Action::SetVolume(name, level));
+        }
+    }
+
+    // The mute buttons stay out of the collapsible part, so a minimal setup
+    // can fold away everything else.
+    pub(super) fn show_mixer(&mut self, ui: &mut egui::Ui) {
+        self.section(ui, Panel::Mixer, "section-channels", |app, ui| {
+            app.show_channels(ui)
+        });
+        egui::Grid::new("Mutes").show(ui, |ui| {
+            match self.mic_input_name.clone() {
+                Some(name) => {
+                    let mic_muted = self.obs_state.muted.get(&name).copied().unwrap_or(false);
+                    let mut mic_button: egui::Button =
+                        egui::Button::new(self.i18n.tr("mixer-mute-mic"));
+                    if mic_muted {
+                        mic_button = egui::Button::new(self.i18n.tr("mixer-unmute-mic"));
+                        mic_button = mic_button.fill(egui::Color32::RED);
+                    }
+                    let response = ui.add(mic_button);
+                    a11y::toggle(&response, &self.i18n.tr("mixer-mute-mic"), mic_muted);
+                    if response.clicked() {
+                        self.perform(Action::SetMute(name, !mic_muted));
+                    }
+                }
+                None => {
+                    let label = egui::Label::new(self.i18n.tr("mixer-no-mic"));
+                    ui.add(label).highlight();
+                }
+            }
+            match self.desktop_input_name.clone() {
+                Some(name) => {
+                    let desktop_muted = self.obs_state.muted.get(&name).copied().unwrap_or(false);
+                    let mut desktop_button: egui::Button =
+                        egui::Button::new(self.i18n.tr("mixer-mute-desktop"));
+                    if desktop_muted {
+                        desktop_button = egui::Button::new(self.i18n.tr("mixer-unmute-desktop"));
+                        desktop_button = desktop_button.fill(egui::Color32::RED);
+                    }
+                    let response = ui.add(desktop_button);
+                    a11y::toggle(
+                        &response,
+                        &self.i18n.tr("mixer-mute-desktop"),
+                        desktop_muted,
+                    );
+                    if response.clicked() {
+                        self.perform(Action::SetMute(name, !desktop_muted));
+                    }
+                }
+                None => {
+                    let label = egui::Label::new(self.i18n.tr("mixer-no-desktop"));
+                    ui.add(label).highlight();
+                }
+            }
+        });
+        self.show_groups(ui);
+    }
+
+    fn show_channels(&mut self, ui: &mut egui::Ui) {
+        let mut selected = None;
+        // Ctrl-clicked (Cmd on macOS), for changing several at once.
+        let mut toggled = None;
+        let mut calibrate = None;
+        let mut chain = None;
+        egui::Grid::new("Sliders").show(ui, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for input in &self.input_info {
+                    if !input.kind.contains("input") {
+                        continue;
+                    }
+
+                    let label = channels::label(ui, &self.channels, &input.name);
+                    let chosen = self.mic_input_name.as_ref() == Some(&input.name);
+                    let response = ui.selectable_label(
+                        chosen || self.selected_channels.contains(&input.name),
+                        label,
+                    );
+                    let text = tooltip::input_text(&self.i18n, input);
+                    let mut menu = |ui: &mut egui::Ui| {
+                        channels::edit(ui, &mut self.channels, &input.name, &self.i18n);
+                        ui.separator();
+                        if ui
+                            .button(self.i18n.tr("mixer-calibrate-gate"))
+                            .on_hover_text(self.i18n.tr("mixer-calibrate-gate-hint"))
+                            .clicked()
+                        {
+                            calibrate = Some(input.name.clone());
+                            ui.close_menu();
+                        }
+                        if ui
+                            .button(self.i18n.tr("mixer-voice-chain"))
+                            .on_hover_text(self.i18n.tr("mixer-voice-chain-hint"))
+                            .clicked()
+                        {
+                            chain = Some(input.name.clone());
+                            ui.close_menu();
+                        }
+                    };
+                    let clicked = tooltip::rich_tooltip(ui, response, text)
+                        .context_menu(&mut menu)
+                        .clicked();
+                    // Right-click needs a mouse.
+                    if self.touch_mode {
+                        ui.menu_button(self.i18n.tr("mixer-more"), menu);
+                    }
+                    if clicked {
+                        if ui.input(|i| i.modifiers.command) {
+                            toggled = Some(input.name.clone());
+                        } else {
+                            self.mic_input_name = Some(input.name.clone());
+                            selected = Some(("mic", input.name.clone()));
+                        }
+                    }
+                }
+            });
+
+            ui.vertical_centered_justified(|ui| {
+                for input in &self.input_info {
+                    if !input.kind.contains("output") {
+                        continue;
+                    }
+
+                    let label = channels::label(ui, &self.channels, &input.name);
+                    let chosen = self.desktop_input_name.as_ref() == Some(&input.name);
+                    let response = ui.selectable_label(
+                        chosen || self.selected_channels.contains(&input.name),
+                        label,
+                    );
+                    let text = tooltip::input_text(&self.i18n, input);
+                    let mut menu = |ui: &mut egui::Ui| {
+                        channels::edit(ui, &mut self.channels, &input.name, &self.i18n)
+                    };
+                    let clicked = tooltip::rich_tooltip(ui, response, text)
+                        .context_menu(&mut menu)
+                        .clicked();
+                    if self.touch_mode {
+                        ui.menu_button(self.i18n.tr("mixer-more"), menu);
+                    }
+                    if clicked {
+                        if ui.input(|i| i.modifiers.command) {
+                            toggled = Some(input.name.clone());
+                        } else {
+                            self.desktop_input_name = Some(input.name.clone());
+                            selected = Some(("desktop", input.name.clone()));
+                        }
+                    }
+                }
+            });
+
+            ui.end_row();
+
+            ui.visuals_mut().selection.bg_fill = self.theme.slider_color();
+            ui.visuals_mut().slider_trailing_fill = true;
+            let response = ui.add(
+                egui::Slider::new(&mut self.mic_level, 0.0..=100.0)
+                    .text(self.i18n.tr("mixer-mic-volume"))
+                    .orientation(egui::SliderOrientation::Vertical),
+            );
+            let text = tooltip::volume_text(&self.i18n, self.mic_level);
+            let response = tooltip::rich_tooltip(ui, response, text);
+            self.slide_volume(&response, self.mic_input_name.clone(), self.mic_level);
+
+            let response = ui.add(
+                egui::Slider::new(&mut self.desktop_level, 0.0..=100.0)
+                    .text(self.i18n.tr("mixer-desktop-volume"))
+                    .orientation(egui::SliderOrientation::Vertical),
+            );
+            let text = tooltip::volume_text(&self.i18n, self.desktop_level);
+            let response = tooltip::rich_tooltip(ui, response, text).context_menu(|ui| {
+                for input in &self.input_info {
+                    if !input.kind.contains("output") {
+                        continue;
+                    }
+
+                    let label = channels::label(ui, &self.channels, &input.name);
+                    if ui
+                        .selectable_value(
+                            &mut self.desktop_input_name,
+                            Some(input.name.clone()),
+                            label,
+                        )
+                        .clicked()
+                    {
+                        selected = Some(("desktop", input.name.clone()));
+                    }
+                }
+            });
+            self.slide_volume(
+                &response,
+                self.desktop_input_name.clone(),
+                self.desktop_level,
+            );
+        });
+
+        if let Some((alias, name)) = selected {
+            self.share(SessionMessage::Selected {
+                operator: self.operator_name.clone(),
+                alias: alias.to_string(),
+                input: name.clone(),
+            });
+            self.query(Query::Volume(name.clone()));
+            self.query(Query::Muted(name.clone()));
+            self.send(Action::SetAlias(alias.to_string(), Some(name)));
+        }
+        if let Some(name) = calibrate {
+            self.send(Action::CalibrateGate(name));
+        }
+        if let Some(name) = chain {
+            self.send(Action::AddVoiceChain(name));
+        }
+        if let Some(name) = toggled {
+            if !self.selected_channels.remove(&name) {
+                self.selected_channels.insert(name);
+            }
+        }
+        self.show_selection(ui);
+        self.show_audio_presets(ui);
+        if !self.locked() {
+            self.show_ducking(ui);
+        }
+    }
+
+    fn show_audio_presets(&mut self, ui: &mut egui::Ui) {
+        if self.audio_presets.is_empty() {
+            return;
+        }
+        let mut chosen = None;
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("mixer-audio-preset"));
+            egui::ComboBox::from_id_source("audio_preset")
+                .selected_text(self.i18n.tr("mixer-audio-preset-pick"))
+                .show_ui(ui, |ui| {
+                    for name in &self.audio_presets {
+                        if ui.selectable_label(false, name).clicked() {
+                            chosen = Some(name.clone());
+                        }
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+        });
+        if let Some(name) = chosen {
+            self.send(Action::ApplyAudioPreset(name));
+        }
+    }
+
+    // Sent as one macro, so the whole change goes through the worker in one
+    // go instead of interleaving with anything else.
+    fn perform_all(&mut self, name: String, actions: Vec<Action>) {
+        if actions.is_empty() {
+            return;
+        }
+        for action in &actions {
+            self.apply_optimistic(action);
+        }
+        self.send(Action::RunMacro(Macro {
+            name,
+            steps: actions.into_iter().map(MacroStep::Do).collect(),
+        }));
+    }
+
+    fn show_selection(&mut self, ui: &mut egui::Ui) {
+        if self.selected_channels.is_empty() {
+            return;
+        }
+        let names: Vec<String> = self.selected_channels.iter().cloned().collect();
+        let mut bulk = None;
+        ui.separator();
+        ui.horizontal_wrapped(|ui| {
+            ui.label(
+                self.i18n
+                    .tr_args("mixer-selected", &fluent_args!["count" => names.len()]),
+            );
+            if ui.button(self.i18n.tr("mixer-mute-all")).clicked() {
+                bulk = Some(Bulk::Mute(true));
+            }
+            if ui.button(self.i18n.tr("mixer-unmute-all")).clicked() {
+                bulk = Some(Bulk::Mute(false));
+            }
+            let db = ui.add(
+                egui::DragValue::new(&mut self.bulk_db)
+                    .clamp_range(-100.0..=0.0)
+                    .suffix(" dB"),
+            );
+            a11y::name(ui, &db, self.i18n.tr("mixer-level"));
+            if ui.button(self.i18n.tr("mixer-set-all")).clicked() {
+                bulk = Some(Bulk::Level(self.bulk_db));
+            }
+        });
+        ui.horizontal(|ui| {
+            let hint = self.i18n.tr("mixer-group");
+            let group = ui.add(
+                egui::TextEdit::singleline(&mut self.group_name)
+                    .hint_text(hint.clone())
+                    .desired_width(120.0),
+            );
+            a11y::name(ui, &group, hint);
+            if ui
+                .add_enabled(
+                    !self.group_name.trim().is_empty(),
+                    egui::Button::new(self.i18n.tr("mixer-add-to-group")),
+                )
+                .clicked()
+            {
+                bulk = Some(Bulk::Group(self.group_name.trim().to_string()));
+            }
+            if ui.button(self.i18n.tr("mixer-clear-selection")).clicked() {
+                bulk = Some(Bulk::Clear);
+            }
+        });
+        let description = self.i18n.tr("mixer-selection");
+        match bulk {
+            Some(Bulk::Mute(muted)) => self.perform_all(
+                description,
+                names
+                    .into_iter()
+                    .map(|name| Action::SetMute(name, muted))
+                    .collect(),
+            ),
+            Some(Bulk::Level(db)) => {
+                let volume = 10f32.powf(db / 20.0) * 100.0;
+                self.perform_all(
+                    description,
+                    names
+                        .into_iter()
+                        .map(|name| Action::SetVolume(name, volume))
+                        .collect(),
+                );
+            }
+            Some(Bulk::Group(group)) => {
+                for name in names {
+                    self.channels.entry(name).or_default().group = group.clone();
+                }
+                self.selected_channels.clear();
+            }
+            Some(Bulk::Clear) => self.selected_channels.clear(),
+            None => {}
+        }
+    }
+
+    // A mute toggle per group; a group counts as muted once all of it is.
+    fn show_groups(&mut self, ui: &mut egui::Ui) {
+        let mut groups: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for (name, style) in &self.channels {
+            if !style.group.is_empty() {
+                groups.entry(&style.group).or_default().push(name.clone());
+            }
+        }
+        if groups.is_empty() {
+            return;
+        }
+        let mut clicked = None;
+        ui.horizontal_wrapped(|ui| {
+            for (group, names) in &groups {
+                let muted = names
+                    .iter()
+                    .all(|name| self.obs_state.muted.get(name).copied().unwrap_or(false));
+                let mut button = egui::Button::new(*group);
+                if muted {
+                    button = button.fill(egui::Color32::RED);
+                }
+                let response = ui.add(button);
+                a11y::toggle(
+                    &response,
+                    &self
+                        .i18n
+                        .tr_args("mixer-group-mute", &fluent_args!["group" => *group]),
+                    muted,
+                );
+                if response.clicked() {
+                    clicked = Some((group.to_string(), names.clone(), !muted));
+                }
+            }
+        });
+        if let Some((group, names, muted)) = clicked {
+            self.perform_all(
+                group,
+                names
+                    .into_iter()
+                    .map(|name| Action::SetMute(name, muted))
+                    .collect(),
+            );
+        }
+    }
+}
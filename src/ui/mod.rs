@@ -0,0 +1,1266 @@
+mod a11y;
+mod advanced;
+mod channels;
+mod confirm;
+mod console;
+mod dbus;
+mod demo;
+mod diagnostics;
+mod ducking;
+mod events;
+mod global_keys;
+mod grid;
+mod history;
+mod instance;
+mod intermission;
+mod iso;
+mod kiosk;
+mod lighting;
+mod lights;
+mod lower_thirds;
+mod mini;
+mod mixer;
+mod pairing;
+mod palette;
+mod plugins;
+mod preview;
+mod recovery;
+mod rehearsal;
+mod rundown;
+mod scenes;
+mod scoreboard;
+mod session;
+mod settings;
+mod shortcuts;
+mod sources;
+mod status_bar;
+mod teleprompter;
+mod templates;
+mod timers;
+mod toasts;
+mod tooltip;
+mod trace;
+mod tray;
+mod twitch;
+mod update;
+mod usage;
+mod viewports;
+mod window;
+mod youtube;
+
+use crate::{
+    audio::{self, AudioPreset, DuckingPair},
+    config::{
+        ChannelStyle, ConfigWatcher, Confirmation, ConnectionConfig, IntermissionConfig, RecConfig,
+        Rule, SavedState, Scoreboard, Shortcut, SliderMode, VendorTrigger, WindowGeometry,
+    },
+    dbus::Dbus,
+    demo::Demo,
+    features::Flags,
+    global_keys::GlobalKeys,
+    i18n::{self, I18n},
+    instance::Listener,
+    journal::{self, Recovery},
+    layout::{self, Layout, Orientation, Panel},
+    lighting::LightingConfig,
+    lights::KeyLight,
+    logging::LogBuffer,
+    migrate::{Migration, STATE_VERSION},
+    obs_worker::{Action, Macro, Notifier, ObsInfo, Query, Response},
+    pairing::{Pairing, Role},
+    plugin::{self, LoadedPlugin},
+    state::ObsState,
+    theme::{self, Theme},
+    tray::Tray,
+    usage::Usage,
+};
+use chrono::{DateTime, Local};
+use fluent::fluent_args;
+use obws::responses::{inputs::Input, outputs::Output};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc::error::TrySendError;
+
+const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+const UI_SCALE_STEP: f32 = 0.1;
+
+pub struct App {
+    action_tx: tokio::sync::mpsc::Sender<Action>,
+    obs_info_rx: tokio::sync::mpsc::Receiver<ObsInfo>,
+    input_info: Vec<Input>,
+    output_info: Vec<Output>,
+    obs_state: ObsState,
+    obs_version: Option<String>,
+    obs_platform: String,
+    stream_started: Option<Instant>,
+    record_started: Option<Instant>,
+    scene_switched: HashMap<String, DateTime<Local>>,
+
+    mic_input_name: Option<String>,
+    desktop_input_name: Option<String>,
+    channels: BTreeMap<String, ChannelStyle>,
+
+    mic_level: f32,
+    desktop_level: f32,
+    logged_in: bool,
+    connected: bool,
+    queue_offline: bool,
+    pending_actions: usize,
+    relay_running: bool,
+    // As last reported by the worker, which does the blocking.
+    rehearsal: bool,
+    intermission_config: Option<IntermissionConfig>,
+    // Set while the show is on a break.
+    intermission: Option<intermission::Intermission>,
+    relay_port: String,
+    // Name shown to the other operators in a shared session.
+    operator_name: String,
+    // Relay address of the instance to join.
+    session_host: String,
+    session_joined: bool,
+    owners: BTreeMap<Panel, String>,
+    osc_running: bool,
+    osc_port: String,
+    osc_feedback: String,
+    remote_running: bool,
+    remote_port: String,
+    pairing: Pairing,
+    pair_name: String,
+    pair_role: Role,
+    mqtt_running: bool,
+    mqtt_host: String,
+    mqtt_port: String,
+    mqtt_prefix: String,
+    mqtt_username: String,
+    mqtt_password: String,
+
+    macros: Vec<Macro>,
+    script_name: String,
+    script_source: String,
+    hotkeys: Vec<String>,
+    hotkey_search: String,
+    plugins: Vec<LoadedPlugin>,
+    config_buttons: Vec<Macro>,
+    rules: Vec<Rule>,
+    rule_states: Vec<bool>,
+    vendor_triggers: Vec<VendorTrigger>,
+    // Names of the built-in and configured audio presets.
+    audio_presets: Vec<String>,
+    config_audio_presets: Vec<AudioPreset>,
+    // Brought in with the last show template opened.
+    show_audio_presets: Vec<AudioPreset>,
+    templates: templates::Templates,
+    config_watcher: Option<ConfigWatcher>,
+    // Where later invocations send their commands.
+    instance: Option<Listener>,
+    twitch: twitch::Twitch,
+    lights: BTreeMap<String, KeyLight>,
+    lighting: LightingConfig,
+    countdown: timers::Countdown,
+    stopwatch: timers::Stopwatch,
+    lower_thirds: lower_thirds::LowerThirds,
+    scoreboard: Scoreboard,
+    teleprompter: teleprompter::Teleprompter,
+    rundown: rundown::Rundown,
+    iso: iso::Iso,
+    preview: preview::Preview,
+    sources: sources::Sources,
+    advanced: advanced::Advanced,
+    youtube: youtube::YouTube,
+
+    toasts: toasts::Toasts,
+    last_error: Option<(DateTime<Local>, String)>,
+    // Only kept once the operator opts in.
+    usage: Option<Usage>,
+    diagnostics: diagnostics::Diagnostics,
+    features: Flags,
+    update: update::Update,
+    trace: trace::Trace,
+    demo: Option<Demo>,
+    // From rec.toml; read once at startup.
+    check_updates: bool,
+    announcement: Option<String>,
+    log: LogBuffer,
+    history: Vec<history::HistoryEntry>,
+    palette: palette::Palette,
+    console: console::Console,
+    events: events::EventLog,
+    recovery: Option<Recovery>,
+    // The config each worker service was last started with, serialized, so a
+    // reload only restarts what changed and never stops what isn't running.
+    service_configs: HashMap<&'static str, String>,
+    // Sent reliably but not yet taken by the worker, oldest first.
+    held_actions: VecDeque<Action>,
+
+    next_query_id: u64,
+    pending_queries: HashMap<u64, Query>,
+    // Filters on grid buttons whose state has been asked for.
+    requested_filters: HashSet<(String, String)>,
+
+    connection: ConnectionConfig,
+    i18n: I18n,
+    window: Option<WindowGeometry>,
+    monitor_checked: bool,
+    theme: Theme,
+    ui_scale: f32,
+    // Bigger controls, hints and menus behind buttons instead of hover and
+    // right-click, and lists that scroll by dragging.
+    touch_mode: bool,
+    mini_mode: bool,
+    tray: Option<Tray>,
+    // Mic, scenes and status on the session bus, for MPRIS and scripts.
+    dbus: Option<Dbus>,
+    minimize_to_tray: bool,
+    // Windows only: keys that work while REC isn't focused.
+    global_keys: Option<GlobalKeys>,
+    global_key_bindings: BTreeMap<String, Shortcut>,
+    // What the next captured key is bound to.
+    global_key_target: Shortcut,
+    window_hidden: bool,
+    kiosk_pin: Option<String>,
+    kiosk_new_pin: String,
+    volunteer_pin: Option<String>,
+    volunteer_new_pin: String,
+    // Scenes a volunteer is shown; empty shows them all.
+    volunteer_scenes: BTreeSet<String>,
+    unlock: kiosk::Unlock,
+    skip_confirmations: BTreeSet<Confirmation>,
+    confirming: Option<confirm::Pending>,
+    popped_out: HashSet<Panel>,
+    dock: Layout,
+    vertical_dock: Layout,
+    orientation: Orientation,
+    slider_mode: SliderMode,
+    // When each input's volume was last sent, for throttling slider drags.
+    volume_sent: HashMap<String, Instant>,
+    // Inputs whose slider is held (None) or was let go of (when), during
+    // which OBS's volume doesn't move the handle.
+    volume_holds: HashMap<String, Option<Instant>>,
+    selected_channels: BTreeSet<String>,
+    ducking: Vec<DuckingPair>,
+    // Ducked input and trigger picked for a new pair.
+    ducking_new: (Option<String>, Option<String>),
+    bulk_db: f32,
+    group_name: String,
+    vertical: bool,
+    collapsed: BTreeSet<Panel>,
+    layouts: BTreeMap<String, Layout>,
+    layout_windows: BTreeMap<String, WindowGeometry>,
+    layout_name: String,
+    shortcuts: Vec<(egui::Key, Shortcut)>,
+}
+
+impl App {
+    // Takes the context rather than eframe's creation context so the app can
+    // also be run headless, as the UI tests do.
+    pub fn new(
+        egui_ctx: &egui::Context,
+        action_tx: tokio::sync::mpsc::Sender<Action>,
+        obs_info_rx: tokio::sync::mpsc::Receiver<ObsInfo>,
+        log: LogBuffer,
+        saved: SavedState,
+    ) -> Self {
+        let ctx = egui_ctx.clone();
+        let config_watcher = ConfigWatcher::new(Arc::new(move || ctx.request_repaint()))
+            .map_err(|err| tracing::error!("{:#}", err))
+            .ok();
+        let notifier = |ctx: &egui::Context| -> Notifier {
+            let ctx = ctx.clone();
+            Arc::new(move || ctx.request_repaint())
+        };
+        let mut app = Self {
+            action_tx,
+            obs_info_rx,
+            mic_level: 0.0,
+            desktop_level: 0.0,
+            input_info: Vec::new(),
+            output_info: Vec::new(),
+            obs_state: ObsState::default(),
+            obs_version: None,
+            obs_platform: String::new(),
+            stream_started: None,
+            record_started: None,
+            scene_switched: HashMap::new(),
+            mic_input_name: saved.mic_input_name,
+            desktop_input_name: saved.desktop_input_name,
+            channels: saved.channels,
+            logged_in: false,
+            connected: false,
+            queue_offline: saved.queue_offline,
+            pending_actions: 0,
+            relay_running: false,
+            rehearsal: false,
+            intermission_config: None,
+            intermission: None,
+            relay_port: saved.relay_port,
+            operator_name: saved.operator_name,
+            session_host: saved.session_host,
+            session_joined: false,
+            owners: BTreeMap::new(),
+            osc_running: false,
+            osc_port: saved.osc_port,
+            osc_feedback: saved.osc_feedback,
+            remote_running: false,
+            remote_port: saved.remote_port,
+            pairing: saved.pairing,
+            pair_name: String::new(),
+            pair_role: Role::Operator,
+            mqtt_running: false,
+            mqtt_host: saved.mqtt_host,
+            mqtt_port: saved.mqtt_port,
+            mqtt_prefix: saved.mqtt_prefix,
+            mqtt_username: saved.mqtt_username,
+            mqtt_password: String::new(),
+            macros: saved.macros,
+            script_name: String::new(),
+            script_source: String::new(),
+            hotkeys: Vec::new(),
+            hotkey_search: String::new(),
+            plugins: plugin::load_all(),
+            config_buttons: Vec::new(),
+            rules: Vec::new(),
+            rule_states: Vec::new(),
+            vendor_triggers: Vec::new(),
+            audio_presets: Vec::new(),
+            config_audio_presets: Vec::new(),
+            show_audio_presets: saved.show_audio_presets,
+            templates: templates::Templates::default(),
+            config_watcher,
+            instance: Listener::start(notifier(egui_ctx))
+                .map_err(|err| tracing::warn!("{:#}", err))
+                .ok(),
+            lights: BTreeMap::new(),
+            lighting: saved.lighting,
+            countdown: timers::Countdown::new(saved.countdown),
+            stopwatch: timers::Stopwatch {
+                input: saved.stopwatch_input,
+                ..timers::Stopwatch::default()
+            },
+            lower_thirds: lower_thirds::LowerThirds {
+                settings: saved.lower_thirds,
+                ..lower_thirds::LowerThirds::default()
+            },
+            scoreboard: saved.scoreboard,
+            preview: preview::Preview::new(saved.preview_enabled, saved.preview_fps),
+            usage: saved.usage_stats.then(Usage::load),
+            diagnostics: diagnostics::Diagnostics::default(),
+            features: saved.features,
+            update: update::Update::default(),
+            trace: trace::Trace::default(),
+            demo: None,
+            check_updates: true,
+            sources: sources::Sources::default(),
+            advanced: advanced::Advanced::default(),
+            teleprompter: teleprompter::Teleprompter {
+                settings: saved.teleprompter,
+                ..teleprompter::Teleprompter::default()
+            },
+            iso: iso::Iso::default(),
+            rundown: rundown::Rundown {
+                segment_input: saved.segment_input,
+                segment_on_prompter: saved.segment_on_prompter,
+                ..rundown::Rundown::new(saved.rundown)
+            },
+            twitch: twitch::Twitch::new(notifier(egui_ctx), saved.twitch_auth),
+            youtube: youtube::YouTube::new(notifier(egui_ctx), saved.youtube_auth),
+            toasts: toasts::Toasts::default(),
+            last_error: None,
+            announcement: None,
+            log,
+            history: Vec::new(),
+            palette: palette::Palette::default(),
+            console: console::Console::default(),
+            events: events::EventLog::default(),
+            recovery: None,
+            service_configs: HashMap::new(),
+            held_actions: VecDeque::new(),
+            next_query_id: 0,
+            pending_queries: HashMap::new(),
+            requested_filters: HashSet::new(),
+            connection: saved.connection,
+            i18n: I18n::new(
+                saved
+                    .language
+                    .as_deref()
+                    .unwrap_or_else(i18n::system_language),
+            ),
+            window: saved.window,
+            monitor_checked: false,
+            theme: saved.theme,
+            ui_scale: saved.ui_scale,
+            touch_mode: saved.touch_mode,
+            mini_mode: false,
+            tray: Tray::new(egui_ctx.clone())
+                .map_err(|err| tracing::warn!("tray icon unavailable: {:#}", err))
+                .ok(),
+            dbus: Dbus::new(notifier(egui_ctx))
+                .map_err(|err| tracing::warn!("D-Bus interface unavailable: {:#}", err))
+                .ok(),
+            minimize_to_tray: saved.minimize_to_tray,
+            global_keys: GlobalKeys::new(notifier(egui_ctx))
+                .map_err(|err| tracing::warn!("global keys unavailable: {:#}", err))
+                .ok(),
+            global_key_bindings: saved.global_keys,
+            global_key_target: Shortcut::ToggleMuteMic,
+            window_hidden: false,
+            kiosk_pin: None,
+            kiosk_new_pin: String::new(),
+            volunteer_pin: saved.volunteer_pin,
+            volunteer_new_pin: String::new(),
+            volunteer_scenes: saved.volunteer_scenes,
+            unlock: kiosk::Unlock::default(),
+            skip_confirmations: saved.skip_confirmations,
+            confirming: None,
+            popped_out: HashSet::new(),
+            dock: saved.layout.unwrap_or_else(layout::default_layout),
+            vertical_dock: saved
+                .vertical_layout
+                .unwrap_or_else(layout::vertical_layout),
+            orientation: saved.orientation,
+            slider_mode: saved.slider_mode,
+            volume_sent: HashMap::new(),
+            volume_holds: HashMap::new(),
+            selected_channels: BTreeSet::new(),
+            ducking: saved.ducking,
+            ducking_new: (None, None),
+            bulk_db: -10.0,
+            group_name: String::new(),
+            vertical: false,
+            collapsed: saved.collapsed,
+            layouts: saved.layouts,
+            layout_windows: saved.layout_windows,
+            layout_name: String::new(),
+            shortcuts: shortcuts::default_shortcuts(),
+        };
+        layout::complete(&mut app.dock);
+        layout::complete(&mut app.vertical_dock);
+        app.apply_style(egui_ctx);
+
+        // The worker starts out knowing nothing, so restored selections are
+        // handed over up front.
+        if app.queue_offline {
+            app.send(Action::SetOfflineQueue(true));
+        }
+        app.send(Action::SetFeatures(app.features.clone()));
+        for (alias, name) in [
+            ("mic", app.mic_input_name.clone()),
+            ("desktop", app.desktop_input_name.clone()),
+        ] {
+            if let Some(name) = name {
+                app.send(Action::SetAlias(alias.to_string(), Some(name)));
+            }
+        }
+        if app.preview.enabled {
+            app.send(app.preview.action());
+        }
+        if !app.ducking.is_empty() {
+            app.send(Action::SetDuckingPairs(app.ducking.clone()));
+        }
+        if !app.lighting.mappings.is_empty() {
+            app.send(Action::SetLighting(app.lighting.clone()));
+        }
+        if app.pairing.enabled {
+            app.send_reliably(Action::SetPairing(app.pairing.clone()));
+        }
+        app.reload_config(true);
+        if app.check_updates {
+            app.update.check(notifier(egui_ctx));
+        }
+        if let Some(migration) = saved.migration {
+            app.report_migration(migration);
+        }
+        if let Some(pin) = saved.kiosk_pin.or(app.kiosk_pin.take()) {
+            app.enter_kiosk(egui_ctx, pin);
+        }
+        app
+    }
+
+    // Told once at startup, with errors for anything lost so they stay up
+    // until read.
+    fn report_migration(&mut self, migration: Migration) {
+        if migration.is_quiet() {
+            return;
+        }
+        let backup = match &migration.backup {
+            Some(backup) => backup.display().to_string(),
+            None => self.i18n.tr("migrate-no-backup"),
+        };
+        if migration.unreadable {
+            self.toasts.push(self.i18n.tr_args(
+                "migrate-unreadable",
+                &fluent_args!["path" => backup.as_str()],
+            ));
+            return;
+        }
+        let summary = if migration.from > STATE_VERSION {
+            "migrate-newer"
+        } else {
+            "migrate-upgraded"
+        };
+        self.toasts.notice(
+            self.i18n
+                .tr_args(summary, &fluent_args!["path" => backup.as_str()]),
+        );
+        for change in migration.changes {
+            self.toasts.notice(self.i18n.tr(change));
+        }
+        for setting in migration.dropped {
+            self.toasts.push(self.i18n.tr_args(
+                "migrate-dropped",
+                &fluent_args!["setting" => setting, "path" => backup.as_str()],
+            ));
+        }
+    }
+
+    fn reload_config(&mut self, startup: bool) {
+        let config = match RecConfig::load() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("{:#}", err);
+                self.toasts.push(format!("{:#}", err));
+                return;
+            }
+        };
+
+        if let Some(connection) = config.connection {
+            self.connection.addr = connection.addr;
+            self.connection.port = connection.port;
+            self.connection.pass = connection.password;
+            if startup && connection.auto_connect {
+                self.log_in();
+            }
+        }
+        if let Some(name) = config.ui.mic_input {
+            self.mic_input_name = Some(name.clone());
+            self.send(Action::SetAlias("mic".to_string(), Some(name)));
+        }
+        if let Some(name) = config.ui.desktop_input {
+            self.desktop_input_name = Some(name.clone());
+            self.send(Action::SetAlias("desktop".to_string(), Some(name)));
+        }
+        if startup {
+            self.kiosk_pin = config.ui.kiosk_pin;
+            self.check_updates = config.ui.check_updates.unwrap_or(true);
+        }
+        if let Some(queue_offline) = config.ui.queue_offline {
+            self.queue_offline = queue_offline;
+            self.send(Action::SetOfflineQueue(queue_offline));
+        }
+        self.config_buttons = config.buttons;
+        self.sync_service("tally", config.tally, Action::StartTally, Action::StopTally);
+        self.send(Action::SetWebhooks(config.webhooks));
+        self.config_audio_presets = config.audio_presets;
+        self.sync_audio_presets();
+        self.sync_service(
+            "metrics",
+            config.metrics.map(|metrics| metrics.port),
+            Action::StartMetrics,
+            Action::StopMetrics,
+        );
+        self.sync_service(
+            "now_playing",
+            config.now_playing,
+            Action::StartNowPlaying,
+            Action::StopNowPlaying,
+        );
+        self.intermission_config = config.intermission;
+        if !config.features.is_empty() {
+            for (feature, enabled) in config.features {
+                self.features.set(feature, enabled);
+            }
+            self.send(Action::SetFeatures(self.features.clone()));
+        }
+        self.twitch.config = config.twitch;
+        self.sync_chat();
+        self.youtube.config = config.youtube;
+        self.shortcuts = match config.shortcuts {
+            Some(shortcuts) => shortcuts
+                .into_iter()
+                .filter_map(|(name, shortcut)| match egui::Key::from_name(&name) {
+                    Some(key) => Some((key, shortcut)),
+                    None => {
+                        self.toasts.push(
+                            self.i18n
+                                .tr_args("toast-unknown-shortcut", &fluent_args!["key" => name]),
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            None => shortcuts::default_shortcuts(),
+        };
+        // Rules that already hold when the file is (re)loaded don't fire until
+        // they have been false once.
+        self.rule_states = config
+            .rules
+            .iter()
+            .map(|rule| self.obs_state.check(&rule.when))
+            .collect();
+        self.rules = config.rules;
+        self.vendor_triggers = config.vendor_events;
+    }
+
+    // Sends nothing when the service already runs with this config, or isn't
+    // running and shouldn't be, so reloading doesn't flood the action channel.
+    pub(super) fn sync_service<T: Serialize>(
+        &mut self,
+        name: &'static str,
+        config: Option<T>,
+        start: impl FnOnce(T) -> Action,
+        stop: Action,
+    ) {
+        let serialized = config
+            .as_ref()
+            .and_then(|config| serde_json::to_string(config).ok());
+        if self.service_configs.get(name) == serialized.as_ref() {
+            return;
+        }
+        match (config, serialized) {
+            (Some(config), Some(serialized)) => {
+                self.service_configs.insert(name, serialized);
+                self.send_reliably(start(config));
+            }
+            _ => {
+                self.service_configs.remove(name);
+                self.send_reliably(stop);
+            }
+        }
+    }
+
+    fn run_rules(&mut self) {
+        let mut triggered = Vec::new();
+        for (rule, was_active) in self.rules.iter().zip(&mut self.rule_states) {
+            let active = self.obs_state.check(&rule.when);
+            if active && !*was_active {
+                tracing::info!(rule = rule.name, "rule triggered");
+                triggered.push(Macro {
+                    name: rule.name.clone(),
+                    steps: rule.steps.clone(),
+                });
+            }
+            *was_active = active;
+        }
+        for obs_macro in triggered {
+            self.send(Action::RunMacro(obs_macro));
+        }
+    }
+
+    // Grid buttons from the UI, the config file and plugins, in that order.
+    fn sync_audio_presets(&mut self) {
+        let audio_presets = audio::presets(
+            self.config_audio_presets
+                .iter()
+                .chain(&self.show_audio_presets)
+                .cloned()
+                .collect(),
+        );
+        self.audio_presets = audio_presets
+            .iter()
+            .map(|preset| preset.name.clone())
+            .collect();
+        self.send(Action::SetAudioPresets(audio_presets));
+    }
+
+    fn all_macros(&self) -> Vec<Macro> {
+        let plugin_macros = self.plugins.iter().flat_map(|plugin| plugin.macros());
+        self.macros
+            .iter()
+            .chain(&self.config_buttons)
+            .cloned()
+            .chain(plugin_macros)
+            .collect()
+    }
+
+    fn log_in(&mut self) {
+        match self.connection.login_action() {
+            Ok(action) => {
+                self.send(action);
+                self.logged_in = true;
+            }
+            Err(err) => self.toasts.push(format!("{:#}", err)),
+        }
+    }
+
+    fn saved_state(&self) -> SavedState {
+        SavedState {
+            version: STATE_VERSION,
+            migration: None,
+            connection: ConnectionConfig {
+                addr: self.connection.addr.clone(),
+                port: self.connection.port.clone(),
+                pass: String::new(),
+            },
+            mic_input_name: self.mic_input_name.clone(),
+            desktop_input_name: self.desktop_input_name.clone(),
+            channels: self.channels.clone(),
+            queue_offline: self.queue_offline,
+            relay_port: self.relay_port.clone(),
+            operator_name: self.operator_name.clone(),
+            session_host: self.session_host.clone(),
+            osc_port: self.osc_port.clone(),
+            osc_feedback: self.osc_feedback.clone(),
+            remote_port: self.remote_port.clone(),
+            pairing: self.pairing.clone(),
+            mqtt_host: self.mqtt_host.clone(),
+            mqtt_port: self.mqtt_port.clone(),
+            mqtt_prefix: self.mqtt_prefix.clone(),
+            mqtt_username: self.mqtt_username.clone(),
+            macros: self.macros.clone(),
+            window: self.window,
+            theme: self.theme.clone(),
+            ui_scale: self.ui_scale,
+            language: Some(self.i18n.language().to_string()),
+            touch_mode: self.touch_mode,
+            minimize_to_tray: self.minimize_to_tray,
+            global_keys: self.global_key_bindings.clone(),
+            kiosk_pin: self.kiosk_pin.clone(),
+            volunteer_pin: self.volunteer_pin.clone(),
+            volunteer_scenes: self.volunteer_scenes.clone(),
+            skip_confirmations: self.skip_confirmations.clone(),
+            orientation: self.orientation,
+            slider_mode: self.slider_mode,
+            ducking: self.ducking.clone(),
+            show_audio_presets: self.show_audio_presets.clone(),
+            layout: Some(self.dock.clone()),
+            vertical_layout: Some(self.vertical_dock.clone()),
+            collapsed: self.collapsed.clone(),
+            layouts: self.layouts.clone(),
+            layout_windows: self.layout_windows.clone(),
+            twitch_auth: self.twitch.auth.clone(),
+            youtube_auth: self.youtube.auth.clone(),
+            lighting: self.lighting.clone(),
+            countdown: self.countdown.settings.clone(),
+            stopwatch_input: self.stopwatch.input.clone(),
+            lower_thirds: self.lower_thirds.settings.clone(),
+            scoreboard: self.scoreboard.clone(),
+            teleprompter: self.teleprompter.settings.clone(),
+            rundown: self.rundown.cues.clone(),
+            segment_input: self.rundown.segment_input.clone(),
+            segment_on_prompter: self.rundown.segment_on_prompter,
+            preview_enabled: self.preview.enabled,
+            preview_fps: self.preview.fps,
+            usage_stats: self.usage.is_some(),
+            features: self.features.clone(),
+        }
+    }
+
+    // Traces, counts and probes an action on its way out. Nothing is left to
+    // send while a trace replays, or once the demo has answered it.
+    fn outgoing(&mut self, action: Action) -> Option<Action> {
+        if self.trace.replaying() {
+            tracing::debug!("replaying a trace, action not sent");
+            return None;
+        }
+        self.trace_action(&action);
+        if let (Some(usage), None) = (&mut self.usage, &self.demo) {
+            usage.count_action(&action);
+        }
+        self.probe_sent(&action);
+        if let Some(demo) = &mut self.demo {
+            demo.handle(action);
+            return None;
+        }
+        Some(action)
+    }
+
+    // For actions the UI counts as done once sent, like starting a service.
+    // When the channel is full they wait and go out on a later frame, in
+    // order, instead of being dropped.
+    fn send_reliably(&mut self, action: Action) {
+        if let Some(action) = self.outgoing(action) {
+            self.held_actions.push_back(action);
+            self.flush_held_actions();
+        }
+    }
+
+    fn flush_held_actions(&mut self) {
+        while let Some(action) = self.held_actions.pop_front() {
+            match self.action_tx.try_send(action) {
+                Ok(()) => {}
+                Err(TrySendError::Full(action)) => {
+                    self.held_actions.push_front(action);
+                    break;
+                }
+                Err(TrySendError::Closed(_)) => {
+                    tracing::warn!("worker stopped, dropping held actions");
+                    self.held_actions.clear();
+                }
+            }
+        }
+    }
+
+    fn send(&mut self, action: Action) {
+        let Some(action) = self.outgoing(action) else {
+            return;
+        };
+        if let Err(err) = self.action_tx.try_send(action) {
+            tracing::warn!("failed to send action: {}", err);
+            self.toasts.push(self.i18n.tr_args(
+                "toast-send-failed",
+                &fluent_args!["error" => err.to_string()],
+            ));
+        }
+    }
+
+    fn query(&mut self, query: Query) {
+        let id = self.next_query_id;
+        self.next_query_id += 1;
+        self.pending_queries.insert(id, query.clone());
+        self.send(Action::Query(id, query));
+    }
+
+    fn handle_response(&mut self, id: u64, response: Result<Response, String>) {
+        let Some(query) = self.pending_queries.remove(&id) else {
+            return;
+        };
+        match (query, response) {
+            (Query::InputSettings(name), Ok(Response::InputSettings(settings))) => {
+                self.sources.settings.insert(name, settings);
+            }
+            (Query::ListItems(name, property), Ok(Response::ListItems(items))) => {
+                self.sources.items.insert((name, property), items);
+            }
+            (Query::FilterEnabled(source, filter), Ok(Response::FilterEnabled(enabled))) => {
+                self.obs_state
+                    .apply(&ObsInfo::FilterChanged(source, filter, enabled));
+            }
+            (
+                Query::ProfileParameter(category, name),
+                Ok(Response::ProfileParameter(value, default)),
+            ) => {
+                self.advanced.loaded(category, name, value, default);
+            }
+            (Query::Raw(..), Ok(Response::Raw(response))) => {
+                self.console.output = serde_json::to_string_pretty(&response).unwrap_or_default();
+            }
+            (Query::Raw(..), Err(err)) => self.console.output = err,
+            (_, Ok(Response::Hotkeys(hotkeys))) => self.hotkeys = hotkeys,
+            (Query::Volume(name), Ok(Response::Volume(volume))) => {
+                self.obs_state.volumes.insert(name, volume);
+            }
+            (Query::Muted(name), Ok(Response::Muted(muted))) => {
+                self.obs_state.muted.insert(name, muted);
+            }
+            (_, Ok(Response::CurrentScene(name))) => self.obs_state.current_scene = Some(name),
+            (_, Ok(Response::Streaming(active))) => self.obs_state.streaming = active,
+            (_, Ok(Response::Recording(active))) => self.obs_state.recording = active,
+            (_, Err(err)) => {
+                self.last_error = Some((Local::now(), err.clone()));
+                self.toasts.push(err);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_obs_info(&mut self, obs_info: ObsInfo) {
+        self.trace_info(&obs_info);
+        self.obs_state.apply(&obs_info);
+        self.probe_received(&obs_info);
+        self.announce_obs_info(&obs_info);
+        let mut actions = Vec::new();
+        for plugin in &mut self.plugins {
+            actions.extend(plugin.on_event(&obs_info, &self.obs_state));
+        }
+        for action in actions {
+            self.send(action);
+        }
+        match obs_info {
+            ObsInfo::InputInfo(input_info) => {
+                self.input_info = input_info;
+                self.sources.forget();
+            }
+            ObsInfo::OutputInfo(output_info) => {
+                self.output_info = output_info;
+            }
+            ObsInfo::InputRemoved(name) => {
+                self.input_info.retain(|input| input.name != name);
+                self.toasts.notice(self.i18n.tr_args(
+                    "notice-input-removed",
+                    &fluent_args!["input" => channels::name(&self.channels, &name)],
+                ));
+            }
+            ObsInfo::Notice(notice) => {
+                self.toasts.notice(toasts::notice_text(&self.i18n, &notice));
+            }
+            ObsInfo::Light(light) => {
+                self.lights.insert(light.name.clone(), light);
+            }
+            ObsInfo::Event(kind, details) => self.events.push(kind, details),
+            ObsInfo::VendorEvent(vendor, event, _) => {
+                let triggered: Vec<Macro> = self
+                    .vendor_triggers
+                    .iter()
+                    .filter(|trigger| trigger.vendor == vendor && trigger.event == event)
+                    .map(|trigger| Macro {
+                        name: trigger.name.clone(),
+                        steps: trigger.steps.clone(),
+                    })
+                    .collect();
+                for obs_macro in triggered {
+                    tracing::info!(trigger = obs_macro.name, "vendor event triggered");
+                    self.send(Action::RunMacro(obs_macro));
+                }
+            }
+            ObsInfo::Slide(input, slide) => {
+                self.sources.slides.insert(input, slide);
+            }
+            ObsInfo::HuePaired(username) => {
+                self.lighting.hue_username = username;
+                self.send(Action::SetLighting(self.lighting.clone()));
+                self.toasts.notice(self.i18n.tr("lighting-paired"));
+            }
+            ObsInfo::Marker(description, recording) => {
+                self.twitch.add_marker(description);
+                self.toasts.notice(match recording {
+                    Some(position) => self.i18n.tr_args(
+                        "notice-marker-recording",
+                        &fluent_args!["position" => status_bar::format_duration(position)],
+                    ),
+                    None => self.i18n.tr("notice-marker"),
+                });
+            }
+            ObsInfo::InputRenamed(old_name, new_name) => {
+                for input in &mut self.input_info {
+                    if input.name == old_name {
+                        input.name = new_name.clone();
+                    }
+                }
+                if let Some(style) = self.channels.remove(&old_name) {
+                    self.channels.insert(new_name.clone(), style);
+                }
+                for selected in [&mut self.mic_input_name, &mut self.desktop_input_name] {
+                    if selected.as_ref() == Some(&old_name) {
+                        *selected = Some(new_name.clone());
+                    }
+                }
+            }
+            ObsInfo::SceneChanged(name) => {
+                self.twitch.scene_changed(&name);
+                self.scene_switched.insert(name, Local::now());
+            }
+            ObsInfo::State(_) => {
+                self.connected = true;
+            }
+            ObsInfo::Session(session) => {
+                let now = Instant::now();
+                self.obs_version = Some(session.obs_version);
+                self.obs_platform = session.platform;
+                self.query(Query::Hotkeys);
+                self.stream_started = self
+                    .obs_state
+                    .streaming
+                    .then(|| now.checked_sub(session.stream_elapsed))
+                    .flatten();
+                self.record_started = self
+                    .obs_state
+                    .recording
+                    .then(|| now.checked_sub(session.record_elapsed))
+                    .flatten();
+            }
+            ObsInfo::StreamState(active) => {
+                if !active {
+                    self.stream_started = None;
+                } else if self.stream_started.is_none() {
+                    self.stream_started = Some(Instant::now());
+                }
+            }
+            ObsInfo::RecordState(active) => {
+                if !active {
+                    self.record_started = None;
+                } else if self.record_started.is_none() {
+                    self.record_started = Some(Instant::now());
+                }
+            }
+            ObsInfo::Disconnected => {
+                self.logged_in = false;
+                self.connected = false;
+                self.obs_version = None;
+                self.iso.clear();
+                self.stream_started = None;
+                self.record_started = None;
+                self.requested_filters.clear();
+            }
+            ObsInfo::ConnectionLost => {
+                self.connected = false;
+            }
+            ObsInfo::PendingActions(count) => {
+                self.pending_actions = count;
+            }
+            ObsInfo::RelayRunning(running) => {
+                self.relay_running = running;
+                if !running {
+                    self.owners.clear();
+                }
+            }
+            ObsInfo::Peer(message) => self.handle_peer(message),
+            ObsInfo::SessionJoined(joined) => {
+                self.session_joined = joined;
+                if !joined {
+                    self.owners.clear();
+                }
+            }
+            ObsInfo::OscRunning(running) => {
+                self.osc_running = running;
+            }
+            ObsInfo::MqttRunning(running) => {
+                self.mqtt_running = running;
+            }
+            ObsInfo::RemoteRunning(running) => {
+                self.remote_running = running;
+            }
+            ObsInfo::Rehearsal(rehearsal) => {
+                self.rehearsal = rehearsal;
+            }
+            ObsInfo::IsoRecorders(recorders) => self.iso.set(recorders),
+            ObsInfo::Recovery(recovery) => {
+                self.recovery = Some(recovery);
+            }
+            ObsInfo::Error(err) => {
+                if let Some(usage) = &mut self.usage {
+                    usage.count_error(&err);
+                }
+                self.last_error = Some((Local::now(), err.clone()));
+                self.toasts.push(err);
+            }
+            ObsInfo::Response(id, response) => {
+                self.handle_response(id, response);
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_style(&self, ctx: &egui::Context) {
+        let mut style = egui::Style {
+            visuals: self.theme.visuals(),
+            ..egui::Style::default()
+        };
+        if self.touch_mode {
+            theme::touch_style(&mut style);
+        }
+        ctx.set_style(style);
+    }
+
+    // The scale is relative to the monitor's own pixels per point, so the same
+    // setting looks the same on a laptop and a 4K TV.
+    fn apply_ui_scale(&mut self, ctx: &egui::Context) {
+        let (zoom_in, zoom_out, reset) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::PlusEquals),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Minus),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Num0),
+            )
+        });
+        if zoom_in {
+            self.ui_scale += UI_SCALE_STEP;
+        }
+        if zoom_out {
+            self.ui_scale -= UI_SCALE_STEP;
+        }
+        if reset {
+            self.ui_scale = 1.0;
+        }
+        self.ui_scale = self
+            .ui_scale
+            .clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end());
+
+        let native = ctx
+            .input(|i| i.viewport().native_pixels_per_point)
+            .unwrap_or(1.0);
+        let pixels_per_point = native * self.ui_scale;
+        if (ctx.pixels_per_point() - pixels_per_point).abs() > f32::EPSILON {
+            ctx.set_pixels_per_point(pixels_per_point);
+        }
+    }
+
+    fn show_log(&mut self, ui: &mut egui::Ui) {
+        if ui.small_button(self.i18n.tr("log-clear")).clicked() {
+            self.log.clear();
+        }
+        egui::ScrollArea::vertical()
+            .drag_to_scroll(self.touch_mode)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in self.log.entries() {
+                    let color = if entry.level == tracing::Level::ERROR {
+                        ui.visuals().error_fg_color
+                    } else {
+                        ui.visuals().warn_fg_color
+                    };
+                    ui.colored_label(
+                        color,
+                        format!(
+                            "{} {} {}: {}",
+                            entry.time.format("%H:%M:%S"),
+                            entry.level,
+                            entry.target,
+                            entry.message
+                        ),
+                    );
+                }
+            });
+    }
+}
+
+impl App {
+    // One frame of the whole UI, with or without a window around it.
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.track_window(ctx);
+
+        // Volume changes arrive at drag rate, so only the latest one per input in
+        // each run is applied. The run is flushed before any other message to
+        // keep ordering intact.
+        let mut volumes = HashMap::new();
+        while let Ok(obs_info) = self.obs_info_rx.try_recv() {
+            match obs_info {
+                ObsInfo::VolumeChanged(name, volume) => {
+                    volumes.insert(name, volume);
+                }
+                // Frames bypass the state, plugins and announcements.
+                ObsInfo::Preview(frame) => self.show_preview_frame(ctx, frame),
+                obs_info => {
+                    for (name, volume) in volumes.drain() {
+                        self.handle_obs_info(ObsInfo::VolumeChanged(name, volume));
+                    }
+                    self.handle_obs_info(obs_info);
+                }
+            }
+        }
+        for (name, volume) in volumes {
+            self.handle_obs_info(ObsInfo::VolumeChanged(name, volume));
+        }
+        self.handle_twitch();
+        self.handle_youtube();
+        self.flush_held_actions();
+        if !self.held_actions.is_empty() {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        }
+
+        if self
+            .config_watcher
+            .as_ref()
+            .is_some_and(|watcher| watcher.changed())
+        {
+            tracing::info!("config file changed, reloading");
+            self.reload_config(false);
+        }
+        self.run_rules();
+        self.tick_countdown(ctx);
+        self.tick_stopwatch(ctx);
+        self.tick_segment(ctx);
+        self.tick_lower_third(ctx);
+        self.tick_replay(ctx);
+        self.tick_demo(ctx);
+
+        self.sync_levels();
+
+        // Text fields have their own undo, so Ctrl+Z only reaches the history
+        // when none of them has focus.
+        if !ctx.wants_keyboard_input()
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z))
+        {
+            self.undo_last();
+        }
+        self.handle_kiosk(ctx);
+        self.handle_tray(ctx);
+        self.handle_instance(ctx);
+        self.handle_dbus(ctx);
+        self.handle_global_keys();
+        self.handle_shortcuts(ctx);
+        self.show_palette(ctx);
+        self.show_console(ctx);
+        self.apply_ui_scale(ctx);
+        if !self.locked()
+            && ctx.input_mut(|i| {
+                i.consume_key(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::M,
+                )
+            })
+        {
+            self.toggle_mini_mode(ctx);
+        }
+        if self.mini_mode {
+            self.show_mini(ctx);
+            self.show_confirm(ctx);
+            self.show_toasts(ctx);
+            self.show_rehearsal_watermark(ctx);
+            return;
+        }
+
+        self.vertical = self.orientation.is_vertical(ctx.screen_rect().size());
+        self.show_update_banner(ctx);
+        self.show_status_bar(ctx);
+        self.show_notification_drawer(ctx);
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading(self.i18n.tr("app-heading"));
+            if !self.logged_in && self.locked() {
+                ui.label(self.i18n.tr("kiosk-not-connected"));
+                return;
+            }
+            if !self.logged_in {
+                ui.vertical_centered_justified(|ui| {
+                    let connection = &mut self.connection;
+                    for (text, id) in [
+                        (&mut connection.addr, "login-address"),
+                        (&mut connection.port, "login-port"),
+                        (&mut connection.pass, "login-password"),
+                    ] {
+                        let label = self.i18n.tr(id);
+                        let response =
+                            ui.add(egui::TextEdit::singleline(text).hint_text(label.clone()));
+                        a11y::name(ui, &response, label);
+                    }
+                    if ui.button(self.i18n.tr("login-log-in")).clicked() {
+                        self.log_in();
+                    }
+                });
+                let label = egui::Label::new(self.i18n.tr("login-not-logged-in"));
+                ui.add(label).highlight();
+                ui.collapsing(self.i18n.tr("panel-log"), |ui| self.show_log(ui));
+                self.show_trace_picker(ui);
+                return;
+            }
+
+            if !self.locked() {
+                self.show_layouts(ui);
+            }
+            self.show_dock(ui);
+        });
+
+        if self.logged_in {
+            self.show_popouts(ctx);
+        }
+        self.show_recovery(ctx);
+        self.show_confirm(ctx);
+        self.show_unlock(ctx);
+        self.show_template_remap(ctx);
+        self.show_toasts(ctx);
+        self.show_rehearsal_watermark(ctx);
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.diagnostics.frame(frame.info().cpu_usage);
+        self.show(ctx);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // A demo's made-up inputs and scenes shouldn't end up in the real state.
+        if self.demo.is_none() {
+            if let Err(err) = self.saved_state().save() {
+                tracing::error!("failed to save state: {:#}", err);
+            }
+        }
+        if let Some(Err(err)) = self.usage.as_ref().map(Usage::save) {
+            tracing::error!("failed to save usage counts: {:#}", err);
+        }
+        if let Err(err) = journal::mark_shutdown() {
+            tracing::error!("failed to close journal: {:#}", err);
+        }
+    }
+}
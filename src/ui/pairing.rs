@@ -0,0 +1,89 @@
+use super::App;
+use crate::{
+    instance,
+    obs_worker::Action,
+    pairing::{self, Grant, Role},
+};
+
+impl App {
+    pub(super) fn show_pairing_settings(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("pairing-title"));
+        ui.weak(self.i18n.tr("pairing-hint"));
+        let toggled = ui
+            .checkbox(&mut self.pairing.enabled, self.i18n.tr("pairing-enabled"))
+            .changed();
+
+        let mut revoked = None;
+        for (index, grant) in self.pairing.grants.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(&grant.name);
+                ui.weak(self.i18n.tr(grant.role.message_id()));
+                if ui.button(self.i18n.tr("pairing-copy-remote")).clicked() {
+                    let link = format!(
+                        "http://{}:{}/?token={}",
+                        pairing::link_host(),
+                        self.remote_port,
+                        grant.token
+                    );
+                    ui.output_mut(|output| output.copied_text = link);
+                }
+                // Pasted into the host field when joining a shared session.
+                if ui.button(self.i18n.tr("pairing-copy-relay")).clicked() {
+                    let link = format!(
+                        "{}:{}/?token={}",
+                        pairing::link_host(),
+                        self.relay_port,
+                        grant.token
+                    );
+                    ui.output_mut(|output| output.copied_text = link);
+                }
+                if ui.button(self.i18n.tr("pairing-revoke")).clicked() {
+                    revoked = Some(index);
+                }
+            });
+        }
+
+        let mut paired = false;
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("pairing-name"));
+            ui.add(egui::TextEdit::singleline(&mut self.pair_name).desired_width(100.0))
+                .labelled_by(label.id);
+            egui::ComboBox::from_id_source("pair_role")
+                .selected_text(self.i18n.tr(self.pair_role.message_id()))
+                .show_ui(ui, |ui| {
+                    for role in Role::ALL {
+                        ui.selectable_value(
+                            &mut self.pair_role,
+                            role,
+                            self.i18n.tr(role.message_id()),
+                        );
+                    }
+                });
+            paired = ui
+                .add_enabled(
+                    !self.pair_name.trim().is_empty(),
+                    egui::Button::new(self.i18n.tr("pairing-pair")),
+                )
+                .on_hover_text(self.i18n.tr(match self.pair_role {
+                    Role::Viewer => "pairing-viewer-hint",
+                    Role::Operator => "pairing-operator-hint",
+                    Role::Admin => "pairing-admin-hint",
+                }))
+                .clicked();
+        });
+
+        if paired {
+            self.pairing.grants.push(Grant {
+                name: std::mem::take(&mut self.pair_name).trim().to_string(),
+                role: self.pair_role,
+                token: instance::new_token(),
+            });
+        }
+        if let Some(index) = revoked {
+            self.pairing.grants.remove(index);
+        }
+        if toggled || paired || revoked.is_some() {
+            self.send_reliably(Action::SetPairing(self.pairing.clone()));
+        }
+    }
+}
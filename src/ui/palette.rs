@@ -0,0 +1,214 @@
+use super::{channels, App};
+use crate::obs_worker::Action;
+use fluent::fluent_args;
+
+const MAX_RESULTS: usize = 12;
+
+#[derive(Default)]
+pub(super) struct Palette {
+    open: bool,
+    query: String,
+    selected: usize,
+}
+
+enum Command {
+    Perform(Action),
+    Send(Action),
+    Undo,
+    Intermission,
+}
+
+// Matches when the query's characters appear in order; the score is how far
+// apart they are, so tighter matches sort first.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<usize> {
+    let candidate = candidate.to_lowercase();
+    let mut chars = candidate.char_indices();
+    let mut span = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let (index, _) = chars.find(|(_, c)| *c == q)?;
+        span = Some(span.map_or((index, index), |(start, _)| (start, index)));
+    }
+    Some(span.map_or(0, |(start, end)| end - start))
+}
+
+impl Palette {
+    pub(super) fn close(&mut self) {
+        self.open = false;
+    }
+}
+
+impl App {
+    fn palette_commands(&self) -> Vec<(String, Command)> {
+        let mut commands = Vec::new();
+        for scene in &self.obs_state.scenes {
+            commands.push((
+                self.i18n
+                    .tr_args("palette-scene", &fluent_args!["scene" => scene.as_str()]),
+                Command::Perform(Action::SetScene(scene.clone())),
+            ));
+        }
+        for input in &self.input_info {
+            let muted = self
+                .obs_state
+                .muted
+                .get(&input.name)
+                .copied()
+                .unwrap_or(false);
+            let id = if muted {
+                "palette-unmute"
+            } else {
+                "palette-mute"
+            };
+            commands.push((
+                self.i18n.tr_args(
+                    id,
+                    &fluent_args!["input" => channels::name(&self.channels, &input.name)],
+                ),
+                Command::Perform(Action::SetMute(input.name.clone(), !muted)),
+            ));
+        }
+        for obs_macro in self.all_macros() {
+            commands.push((
+                self.i18n.tr_args(
+                    "palette-macro",
+                    &fluent_args!["name" => obs_macro.name.as_str()],
+                ),
+                Command::Send(Action::RunMacro(obs_macro)),
+            ));
+        }
+        commands.push(if self.obs_state.streaming {
+            (
+                self.i18n.tr("palette-stop-stream"),
+                Command::Send(Action::StopStream),
+            )
+        } else {
+            (
+                self.i18n.tr("palette-start-stream"),
+                Command::Send(Action::StartStream),
+            )
+        });
+        commands.push(if self.obs_state.recording {
+            (
+                self.i18n.tr("palette-stop-record"),
+                Command::Send(Action::StopRecord),
+            )
+        } else {
+            (
+                self.i18n.tr("palette-start-record"),
+                Command::Send(Action::StartRecord),
+            )
+        });
+        commands.push((
+            self.i18n.tr(if self.rehearsal {
+                "palette-stop-rehearsal"
+            } else {
+                "palette-start-rehearsal"
+            }),
+            Command::Send(Action::SetRehearsal(!self.rehearsal)),
+        ));
+        if self.intermission_config.is_some() {
+            commands.push((
+                self.i18n.tr(if self.intermission.is_some() {
+                    "palette-end-intermission"
+                } else {
+                    "palette-start-intermission"
+                }),
+                Command::Intermission,
+            ));
+        }
+        commands.push((self.i18n.tr("palette-undo"), Command::Undo));
+        if self.logged_in && !self.locked() {
+            commands.push((
+                self.i18n.tr("palette-log-out"),
+                Command::Send(Action::LogOut),
+            ));
+        }
+        commands
+    }
+
+    pub(super) fn show_palette(&mut self, ctx: &egui::Context) {
+        // Everything it lists is what a locked app keeps out of reach.
+        if self.locked() {
+            self.palette.open = false;
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+            self.palette = Palette {
+                open: !self.palette.open,
+                ..Palette::default()
+            };
+        }
+        if !self.palette.open {
+            return;
+        }
+
+        let mut matches: Vec<_> = self
+            .palette_commands()
+            .into_iter()
+            .filter_map(|(label, command)| {
+                Some((fuzzy_score(&self.palette.query, &label)?, label, command))
+            })
+            .collect();
+        matches.sort_by_key(|(score, label, _)| (*score, label.len()));
+        matches.truncate(MAX_RESULTS);
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        let palette = &mut self.palette;
+        if up {
+            palette.selected = palette.selected.saturating_sub(1);
+        }
+        if down {
+            palette.selected += 1;
+        }
+        palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+
+        let mut chosen = enter.then_some(palette.selected);
+        let hint = self.i18n.tr("palette-hint");
+        egui::Window::new("Command Palette")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut palette.query)
+                        .hint_text(hint)
+                        .desired_width(300.0),
+                );
+                response.request_focus();
+                if response.changed() {
+                    palette.selected = 0;
+                }
+                for (index, (_, label, _)) in matches.iter().enumerate() {
+                    if ui
+                        .selectable_label(index == palette.selected, label)
+                        .clicked()
+                    {
+                        chosen = Some(index);
+                    }
+                }
+            });
+
+        if escape {
+            self.palette.open = false;
+        }
+        let Some(index) = chosen else {
+            return;
+        };
+        self.palette.open = false;
+        if let Some((_, _, command)) = matches.into_iter().nth(index) {
+            match command {
+                Command::Perform(action) => self.perform(action),
+                Command::Send(action) => self.send_confirmed(action),
+                Command::Undo => self.undo_last(),
+                Command::Intermission => self.toggle_intermission(),
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use super::App;
+
+impl App {
+    pub(super) fn show_plugins(&mut self, ui: &mut egui::Ui) {
+        let mut actions = Vec::new();
+        for plugin in &mut self.plugins {
+            let name = plugin.name().to_string();
+            ui.collapsing(name, |ui| {
+                actions.extend(plugin.show(ui, &self.obs_state));
+            });
+        }
+        for action in actions {
+            self.send(action);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use super::App;
+use crate::obs_worker::{Action, PreviewFrame};
+
+pub(super) struct Preview {
+    pub(super) enabled: bool,
+    pub(super) fps: u32,
+    texture: Option<egui::TextureHandle>,
+}
+
+impl Preview {
+    pub(super) fn new(enabled: bool, fps: u32) -> Self {
+        Self {
+            enabled,
+            fps,
+            texture: None,
+        }
+    }
+
+    pub(super) fn action(&self) -> Action {
+        if self.enabled {
+            Action::StartPreview(self.fps)
+        } else {
+            Action::StopPreview
+        }
+    }
+}
+
+impl App {
+    pub(super) fn show_preview_frame(&mut self, ctx: &egui::Context, frame: PreviewFrame) {
+        let image = egui::ColorImage::from_rgba_unmultiplied(frame.size, &frame.rgba);
+        match &mut self.preview.texture {
+            Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+            None => {
+                self.preview.texture =
+                    Some(ctx.load_texture("program_preview", image, egui::TextureOptions::LINEAR));
+            }
+        }
+    }
+
+    pub(super) fn show_preview(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            changed |= ui
+                .checkbox(&mut self.preview.enabled, self.i18n.tr("preview-enabled"))
+                .changed();
+            let label = ui.label(self.i18n.tr("preview-fps"));
+            changed |= ui
+                .add(egui::Slider::new(&mut self.preview.fps, 1..=10))
+                .labelled_by(label.id)
+                .drag_released();
+        });
+        if changed {
+            if !self.preview.enabled {
+                self.preview.texture = None;
+            }
+            self.send(self.preview.action());
+        }
+        match &self.preview.texture {
+            Some(texture) if self.preview.enabled => {
+                let size = texture.size_vec2();
+                let width = ui.available_width().min(size.x * 2.0);
+                ui.image((texture.id(), egui::vec2(width, width * size.y / size.x)));
+            }
+            _ => {
+                ui.weak(self.i18n.tr("preview-off"));
+            }
+        }
+    }
+}
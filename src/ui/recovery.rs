@@ -0,0 +1,138 @@
+use super::App;
+use crate::{
+    journal::Recovery,
+    obs_worker::{Action, Macro, MacroStep},
+};
+use fluent::fluent_args;
+
+impl App {
+    fn resume(&mut self, recovery: Recovery) {
+        let mut steps = Vec::new();
+        for action in recovery.controls {
+            match action {
+                Action::LogIn(addr, port, _) => {
+                    self.connection.addr = addr.to_string();
+                    self.connection.port = port.to_string();
+                    match self.connection.login_action() {
+                        Ok(action) => {
+                            steps.push(MacroStep::Do(action));
+                            self.logged_in = true;
+                        }
+                        Err(err) => self.toasts.push(format!("{:#}", err)),
+                    }
+                }
+                Action::StartMqtt(mut config) => {
+                    if let Some((_, password)) = &mut config.credentials {
+                        password.clone_from(&self.mqtt_password);
+                    }
+                    steps.push(MacroStep::Do(Action::StartMqtt(config)));
+                }
+                action => {
+                    match &action {
+                        Action::SetOfflineQueue(enabled) => self.queue_offline = *enabled,
+                        Action::SetAlias(alias, input) if alias == "mic" => {
+                            self.mic_input_name.clone_from(input)
+                        }
+                        Action::SetAlias(alias, input) if alias == "desktop" => {
+                            self.desktop_input_name.clone_from(input)
+                        }
+                        _ => {}
+                    }
+                    steps.push(MacroStep::Do(action));
+                }
+            }
+        }
+        steps.extend(recovery.pending_actions.into_iter().map(MacroStep::Do));
+        // Sent as one macro so a long list can't overflow the action channel.
+        self.send(Action::RunMacro(Macro {
+            name: self.i18n.tr("recovery-macro-name"),
+            steps,
+        }));
+    }
+
+    pub(super) fn show_recovery(&mut self, ctx: &egui::Context) {
+        let Some(recovery) = &self.recovery else {
+            return;
+        };
+        let mut resume = false;
+        let mut discard = false;
+        let mut rerun = None;
+        egui::Window::new(self.i18n.tr("recovery-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(self.i18n.tr("recovery-unclean"));
+                if recovery
+                    .controls
+                    .iter()
+                    .any(|action| matches!(action, Action::LogIn(..)))
+                {
+                    ui.label(self.i18n.tr("recovery-reconnect"));
+                }
+                let services = recovery
+                    .controls
+                    .iter()
+                    .filter(|action| {
+                        matches!(
+                            action,
+                            Action::StartRelay(_)
+                                | Action::StartOsc(..)
+                                | Action::StartMqtt(_)
+                                | Action::StartRemote(_)
+                        )
+                    })
+                    .count();
+                if services > 0 {
+                    ui.label(self.i18n.tr_args(
+                        "recovery-restart-services",
+                        &fluent_args!["count" => services],
+                    ));
+                }
+                if !recovery.pending_actions.is_empty() {
+                    ui.label(self.i18n.tr_args(
+                        "recovery-send-queued",
+                        &fluent_args!["count" => recovery.pending_actions.len()],
+                    ));
+                }
+                if !recovery.unfinished_macros.is_empty() {
+                    ui.separator();
+                    ui.label(self.i18n.tr("recovery-interrupted"));
+                    for (index, obs_macro) in recovery.unfinished_macros.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(&obs_macro.name);
+                            if ui
+                                .small_button(self.i18n.tr("recovery-run-again"))
+                                .clicked()
+                            {
+                                rerun = Some(index);
+                            }
+                        });
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    resume = ui.button(self.i18n.tr("recovery-resume")).clicked();
+                    discard = ui.button(self.i18n.tr("recovery-discard")).clicked();
+                });
+            });
+
+        if let Some(index) = rerun {
+            if let Some(recovery) = &mut self.recovery {
+                let obs_macro = recovery.unfinished_macros.remove(index);
+                self.send(Action::RunMacro(obs_macro));
+            }
+        }
+        if resume {
+            if let Some(recovery) = self.recovery.take() {
+                self.resume(recovery);
+            }
+        }
+        if discard {
+            self.recovery = None;
+        }
+        if resume || discard {
+            self.send(Action::ForgetRecovery);
+        }
+    }
+}
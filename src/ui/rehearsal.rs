@@ -0,0 +1,41 @@
+use super::{a11y, App};
+use crate::obs_worker::Action;
+
+const WATERMARK_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 140, 0);
+
+impl App {
+    pub(super) fn show_rehearsal_toggle(&mut self, ui: &mut egui::Ui) {
+        let toggle = ui
+            .selectable_label(self.rehearsal, format!("🎭 {}", self.i18n.tr("rehearsal")))
+            .on_hover_text(self.i18n.tr("rehearsal-hint"));
+        a11y::name(ui, &toggle, self.i18n.tr("rehearsal"));
+        if toggle.clicked() {
+            self.send(Action::SetRehearsal(!self.rehearsal));
+        }
+    }
+
+    // A frame around the whole window and a faint word across it, drawn over
+    // everything so no screenshot or glance at the screen can miss it.
+    pub(super) fn show_rehearsal_watermark(&self, ctx: &egui::Context) {
+        if !self.rehearsal {
+            return;
+        }
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("rehearsal_watermark"),
+        ));
+        let rect = ctx.screen_rect();
+        painter.rect_stroke(
+            rect.shrink(2.0),
+            0.0,
+            egui::Stroke::new(4.0, WATERMARK_COLOR),
+        );
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            self.i18n.tr("rehearsal-watermark"),
+            egui::FontId::proportional(rect.width().min(rect.height()) / 6.0),
+            WATERMARK_COLOR.gamma_multiply(0.15),
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use super::{
+    a11y,
+    timers::{format_clock, picker},
+    App,
+};
+use crate::{
+    audio::{Level, SceneCue},
+    config::Cue,
+    obs_worker::{Action, Macro, MacroStep, MediaCommand},
+};
+use fluent::fluent_args;
+use std::time::{Duration, Instant};
+
+// The segment clock turns yellow with this share of the planned length left,
+// and red once it's up.
+const WARN_FRACTION: f64 = 0.2;
+
+#[derive(Default)]
+pub(super) struct Rundown {
+    pub(super) cues: Vec<Cue>,
+    // The cue last run; GO runs the one after it.
+    current: Option<usize>,
+    // When the current cue went on air.
+    started: Option<Instant>,
+    pub(super) segment_input: Option<String>,
+    pub(super) segment_on_prompter: bool,
+    // Segment clock last written to the text source.
+    shown: Option<String>,
+    new: Cue,
+    minutes: u64,
+    seconds: u64,
+    capture_audio: bool,
+    text_input: Option<String>,
+    text: String,
+    media: Option<String>,
+}
+
+enum Edit {
+    Jump(usize),
+    Up(usize),
+    Remove(usize),
+}
+
+impl Rundown {
+    pub(super) fn new(cues: Vec<Cue>) -> Self {
+        Self {
+            cues,
+            capture_audio: true,
+            ..Self::default()
+        }
+    }
+
+    fn next(&self) -> usize {
+        self.current.map_or(0, |current| current + 1)
+    }
+
+    // For a show template, which starts from the top.
+    pub(super) fn replace(&mut self, cues: Vec<Cue>) {
+        self.cues = cues;
+        self.rewind(None);
+    }
+
+    fn rewind(&mut self, current: Option<usize>) {
+        self.current = current;
+        self.started = None;
+    }
+
+    // Time left in the segment on air, counting up with a plus once it runs
+    // over, and its colour if it needs one.
+    pub(super) fn segment_clock(&self) -> Option<(String, Option<egui::Color32>)> {
+        let planned = self.cues.get(self.current?)?.duration?;
+        let elapsed = self.started?.elapsed().as_secs();
+        Some(match planned.checked_sub(elapsed) {
+            Some(0) | None => (
+                format!("+{}", format_clock(elapsed - planned)),
+                Some(egui::Color32::RED),
+            ),
+            Some(left) if left as f64 <= planned as f64 * WARN_FRACTION => {
+                (format_clock(left), Some(egui::Color32::YELLOW))
+            }
+            Some(left) => (format_clock(left), None),
+        })
+    }
+}
+
+// Everything in a cue, run by the worker in one go. Inputs coming on are
+// unmuted before the fade and those going off muted after it.
+fn cue_macro(cue: &Cue) -> Macro {
+    let (muted, unmuted): (Vec<_>, Vec<_>) = cue.audio.iter().partition(|(_, (_, muted))| *muted);
+    let mut actions: Vec<_> = unmuted
+        .into_iter()
+        .map(|(input, _)| Action::SetMute(input.clone(), false))
+        .collect();
+    actions.push(Action::SceneCue(SceneCue {
+        scene: cue.scene.clone(),
+        audio: cue
+            .audio
+            .iter()
+            .map(|(input, (volume, _))| (input.clone(), Level::from_volume(*volume)))
+            .collect(),
+        fade_ms: cue.fade_ms,
+    }));
+    actions.extend(
+        muted
+            .into_iter()
+            .map(|(input, _)| Action::SetMute(input.clone(), true)),
+    );
+    for (input, text) in &cue.texts {
+        actions.push(Action::SetText(input.clone(), text.clone()));
+    }
+    for input in &cue.media {
+        actions.push(Action::Media(input.clone(), MediaCommand::Restart));
+    }
+    Macro {
+        name: cue.name.clone(),
+        steps: actions.into_iter().map(MacroStep::Do).collect(),
+    }
+}
+
+impl App {
+    // Runs the next cue and puts the one after it in preview, if OBS is in
+    // studio mode.
+    pub(super) fn go(&mut self) {
+        let next = self.rundown.next();
+        let Some(cue) = self.rundown.cues.get(next) else {
+            return;
+        };
+        let obs_macro = cue_macro(cue);
+        self.rundown.current = Some(next);
+        self.rundown.started = Some(Instant::now());
+        self.send(Action::RunMacro(obs_macro));
+        let standby = self
+            .rundown
+            .cues
+            .get(next + 1)
+            .and_then(|cue| cue.scene.clone());
+        if let (Some(scene), Some(_)) = (standby, &self.obs_state.preview_scene) {
+            self.send(Action::SetPreviewScene(scene));
+        }
+    }
+
+    // Runs every frame like the countdown, so the text source keeps up with
+    // the panel closed. It's cleared once there's no timed segment on air.
+    pub(super) fn tick_segment(&mut self, ctx: &egui::Context) {
+        let clock = self.rundown.segment_clock().map(|(clock, _)| clock);
+        if clock.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+        if self.rundown.shown == clock {
+            return;
+        }
+        self.rundown.shown = clock.clone();
+        if let Some(input) = &self.rundown.segment_input {
+            self.send(Action::SetText(input.clone(), clock.unwrap_or_default()));
+        }
+    }
+
+    fn media_inputs(&self) -> Vec<String> {
+        self.input_info
+            .iter()
+            .filter(|input| matches!(input.kind.as_str(), "ffmpeg_source" | "vlc_source"))
+            .map(|input| input.name.clone())
+            .collect()
+    }
+
+    fn audio_snapshot(&self) -> Vec<(String, (f32, bool))> {
+        self.input_info
+            .iter()
+            .filter(|input| input.kind.contains("input") || input.kind.contains("output"))
+            .filter_map(|input| {
+                let volume = *self.obs_state.volumes.get(&input.name)?;
+                let muted = self.obs_state.muted.get(&input.name).copied()?;
+                Some((input.name.clone(), (volume, muted)))
+            })
+            .collect()
+    }
+
+    fn show_cue_card(
+        &self,
+        ui: &mut egui::Ui,
+        id: &str,
+        cue: Option<&Cue>,
+        clock: Option<(String, Option<egui::Color32>)>,
+    ) {
+        ui.group(|ui| {
+            ui.set_min_width(180.0);
+            ui.weak(self.i18n.tr(id));
+            match cue {
+                Some(cue) => {
+                    ui.heading(&cue.name);
+                    if let Some((clock, color)) = clock {
+                        let text = egui::RichText::new(clock).heading().monospace();
+                        ui.label(match color {
+                            Some(color) => text.color(color),
+                            None => text,
+                        });
+                    }
+                    if let Some(scene) = &cue.scene {
+                        ui.label(scene);
+                    }
+                    if !cue.notes.is_empty() {
+                        ui.weak(&cue.notes);
+                    }
+                }
+                None => {
+                    ui.weak(self.i18n.tr("rundown-none"));
+                }
+            }
+        });
+    }
+
+    pub(super) fn show_rundown(&mut self, ui: &mut egui::Ui) {
+        let next = self.rundown.next();
+        let mut go = false;
+        ui.horizontal(|ui| {
+            let rundown = &self.rundown;
+            let current = rundown.current.and_then(|index| rundown.cues.get(index));
+            let clock = rundown.segment_clock();
+            self.show_cue_card(ui, "rundown-on-air", current, clock);
+            self.show_cue_card(ui, "rundown-standby", rundown.cues.get(next), None);
+            let button =
+                egui::Button::new(egui::RichText::new(self.i18n.tr("rundown-go")).heading())
+                    .fill(egui::Color32::DARK_GREEN)
+                    .min_size(egui::vec2(120.0, 60.0));
+            go = ui.add_enabled(next < rundown.cues.len(), button).clicked();
+        });
+        if go {
+            self.go();
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    self.rundown.current.is_some(),
+                    egui::Button::new(self.i18n.tr("rundown-back")),
+                )
+                .clicked()
+            {
+                let current = self.rundown.current.and_then(|index| index.checked_sub(1));
+                self.rundown.rewind(current);
+            }
+            if ui.button(self.i18n.tr("rundown-reset")).clicked() {
+                self.rundown.rewind(None);
+            }
+        });
+        let text_inputs = self.text_inputs();
+        ui.horizontal_wrapped(|ui| {
+            let rundown = &mut self.rundown;
+            if picker(
+                ui,
+                &self.i18n,
+                "rundown-segment-input",
+                &text_inputs,
+                &mut rundown.segment_input,
+            ) {
+                rundown.shown = None;
+            }
+            ui.checkbox(
+                &mut rundown.segment_on_prompter,
+                self.i18n.tr("rundown-segment-prompter"),
+            );
+        });
+
+        ui.separator();
+        let mut edit = None;
+        egui::Grid::new("rundown")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, cue) in self.rundown.cues.iter().enumerate() {
+                    let text = match cue.duration {
+                        Some(duration) => {
+                            format!("{}. {} ({})", index + 1, cue.name, format_clock(duration))
+                        }
+                        None => format!("{}. {}", index + 1, cue.name),
+                    };
+                    let marker = if self.rundown.current == Some(index) {
+                        egui::RichText::new(text).strong()
+                    } else if index == next {
+                        egui::RichText::new(text).italics()
+                    } else {
+                        egui::RichText::new(text)
+                    };
+                    ui.label(marker);
+                    ui.weak(self.i18n.tr_args(
+                        "rundown-summary",
+                        &fluent_args![
+                            "scene" => cue.scene.clone().unwrap_or_default(),
+                            "audio" => cue.audio.len(),
+                            "texts" => cue.texts.len() + cue.media.len()
+                        ],
+                    ));
+                    if ui
+                        .button(self.i18n.tr("rundown-jump"))
+                        .on_hover_text(self.i18n.tr("rundown-jump-hint"))
+                        .clicked()
+                    {
+                        edit = Some(Edit::Jump(index));
+                    }
+                    ui.horizontal(|ui| {
+                        let up = ui.add_enabled(index > 0, egui::Button::new("⬆"));
+                        a11y::name(ui, &up, self.i18n.tr("rundown-up"));
+                        if up.clicked() {
+                            edit = Some(Edit::Up(index));
+                        }
+                        let delete = ui.button("🗑");
+                        a11y::name(ui, &delete, self.i18n.tr("rundown-remove"));
+                        if delete.clicked() {
+                            edit = Some(Edit::Remove(index));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        match edit {
+            Some(Edit::Jump(index)) => self.rundown.rewind(index.checked_sub(1)),
+            Some(Edit::Up(index)) => self.rundown.cues.swap(index - 1, index),
+            Some(Edit::Remove(index)) => {
+                self.rundown.cues.remove(index);
+                self.rundown.rewind(None);
+            }
+            None => {}
+        }
+
+        self.show_cue_editor(ui);
+    }
+
+    fn show_cue_editor(&mut self, ui: &mut egui::Ui) {
+        let scenes = self.obs_state.scenes.clone();
+        let text_inputs = self.text_inputs();
+        let media_inputs = self.media_inputs();
+        let mut add = false;
+        ui.collapsing(self.i18n.tr("rundown-add"), |ui| {
+            let rundown = &mut self.rundown;
+            egui::Grid::new("cue_editor").num_columns(2).show(ui, |ui| {
+                for (text, id) in [
+                    (&mut rundown.new.name, "rundown-name"),
+                    (&mut rundown.new.notes, "rundown-notes"),
+                ] {
+                    let label = ui.label(self.i18n.tr(id));
+                    ui.text_edit_singleline(text).labelled_by(label.id);
+                    ui.end_row();
+                }
+                picker(
+                    ui,
+                    &self.i18n,
+                    "rundown-scene",
+                    &scenes,
+                    &mut rundown.new.scene,
+                );
+                ui.end_row();
+                let label = ui.label(self.i18n.tr("rundown-duration"));
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut rundown.minutes).suffix(" min"))
+                        .labelled_by(label.id);
+                    ui.add(
+                        egui::DragValue::new(&mut rundown.seconds)
+                            .clamp_range(0..=59)
+                            .suffix(" s"),
+                    )
+                    .labelled_by(label.id);
+                });
+                ui.end_row();
+                picker(
+                    ui,
+                    &self.i18n,
+                    "rundown-text-input",
+                    &text_inputs,
+                    &mut rundown.text_input,
+                );
+                let text = ui.add_enabled(
+                    rundown.text_input.is_some(),
+                    egui::TextEdit::singleline(&mut rundown.text)
+                        .hint_text(self.i18n.tr("rundown-text")),
+                );
+                a11y::name(ui, &text, self.i18n.tr("rundown-text"));
+                ui.end_row();
+                picker(
+                    ui,
+                    &self.i18n,
+                    "rundown-media",
+                    &media_inputs,
+                    &mut rundown.media,
+                );
+                ui.end_row();
+            });
+            ui.checkbox(
+                &mut rundown.capture_audio,
+                self.i18n.tr("rundown-capture-audio"),
+            )
+            .on_hover_text(self.i18n.tr("rundown-capture-audio-hint"));
+            ui.horizontal(|ui| {
+                let label = ui.label(self.i18n.tr("rundown-fade"));
+                ui.add(
+                    egui::DragValue::new(&mut rundown.new.fade_ms)
+                        .clamp_range(0..=10_000)
+                        .speed(50)
+                        .suffix(" ms"),
+                )
+                .labelled_by(label.id)
+                .on_hover_text(self.i18n.tr("rundown-fade-hint"));
+            });
+            add = ui
+                .add_enabled(
+                    !rundown.new.name.is_empty(),
+                    egui::Button::new(self.i18n.tr("rundown-add-cue")),
+                )
+                .clicked();
+        });
+        if !add {
+            return;
+        }
+        let audio = if self.rundown.capture_audio {
+            self.audio_snapshot().into_iter().collect()
+        } else {
+            Default::default()
+        };
+        let rundown = &mut self.rundown;
+        let mut cue = std::mem::take(&mut rundown.new);
+        cue.audio = audio;
+        if let Some(input) = rundown.text_input.take() {
+            cue.texts.push((input, std::mem::take(&mut rundown.text)));
+        }
+        cue.media.extend(rundown.media.take());
+        let duration =
+            std::mem::take(&mut rundown.minutes) * 60 + std::mem::take(&mut rundown.seconds);
+        cue.duration = (duration > 0).then_some(duration);
+        rundown.cues.push(cue);
+    }
+}
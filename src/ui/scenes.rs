@@ -0,0 +1,78 @@
+use super::{tooltip, App};
+use crate::{layout::Panel, obs_worker::Action};
+use fluent::fluent_args;
+
+impl App {
+    pub(super) fn show_scenes(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if self.obs_state.streaming {
+                ui.colored_label(egui::Color32::RED, self.i18n.tr("status-streaming"));
+            } else {
+                ui.label(self.i18n.tr("status-not-streaming"));
+            }
+            if self.obs_state.recording {
+                ui.colored_label(egui::Color32::RED, self.i18n.tr("status-recording"));
+            } else {
+                ui.label(self.i18n.tr("status-not-recording"));
+            }
+            ui.separator();
+            if !self.connected {
+                ui.colored_label(ui.visuals().warn_fg_color, self.i18n.tr("status-offline"));
+            }
+            if self.pending_actions > 0 {
+                ui.label(self.i18n.tr_args(
+                    "status-pending",
+                    &fluent_args!["count" => self.pending_actions],
+                ));
+            }
+            if ui
+                .checkbox(
+                    &mut self.queue_offline,
+                    self.i18n.tr("scenes-queue-offline"),
+                )
+                .changed()
+            {
+                self.send(Action::SetOfflineQueue(self.queue_offline));
+            }
+            if self.locked() {
+                return;
+            }
+            self.show_intermission_button(ui);
+            if ui
+                .button(self.i18n.tr("scenes-mini"))
+                .on_hover_text(self.i18n.tr("scenes-mini-hint"))
+                .clicked()
+            {
+                self.toggle_mini_mode(ui.ctx());
+            }
+            if ui.button(self.i18n.tr("scenes-log-out")).clicked() {
+                self.send(Action::LogOut);
+            }
+        });
+        self.section(ui, Panel::Scenes, "section-scenes", |app, ui| {
+            let mut clicked = None;
+            ui.horizontal_wrapped(|ui| {
+                for scene in &app.obs_state.scenes {
+                    if app.volunteer_hides(scene) {
+                        continue;
+                    }
+                    let current = app.obs_state.current_scene.as_ref() == Some(scene);
+                    let text = match app.scene_switched.get(scene) {
+                        Some(time) => app.i18n.tr_args(
+                            "tooltip-scene-switched",
+                            &fluent_args!["time" => time.format("%H:%M:%S").to_string()],
+                        ),
+                        None => app.i18n.tr("tooltip-scene-not-switched"),
+                    };
+                    let response = ui.selectable_label(current, scene);
+                    if tooltip::rich_tooltip(ui, response, text).clicked() {
+                        clicked = Some(Action::SetScene(scene.clone()));
+                    }
+                }
+            });
+            if let Some(action) = clicked {
+                app.perform(action);
+            }
+        });
+    }
+}
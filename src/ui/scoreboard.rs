@@ -0,0 +1,127 @@
+use super::{a11y, timers::picker, App};
+use crate::obs_worker::Action;
+
+impl App {
+    // Everything mapped is written on any change, so a source that was edited
+    // by hand in OBS can't stay out of step.
+    fn write_scoreboard(&mut self) {
+        let scoreboard = &self.scoreboard;
+        let mut texts = vec![(&scoreboard.map_input, scoreboard.map.to_string())];
+        for team in &scoreboard.teams {
+            texts.push((&team.name_input, team.name.clone()));
+            texts.push((&team.score_input, team.score.to_string()));
+        }
+        let actions: Vec<_> = texts
+            .into_iter()
+            .filter_map(|(input, text)| Some(Action::SetText(input.clone()?, text)))
+            .collect();
+        for action in actions {
+            self.send(action);
+        }
+    }
+
+    pub(super) fn show_scoreboard(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        egui::Grid::new("scoreboard").num_columns(4).show(ui, |ui| {
+            for (index, team) in self.scoreboard.teams.iter_mut().enumerate() {
+                let hint = self.i18n.tr(if index == 0 {
+                    "scoreboard-left"
+                } else {
+                    "scoreboard-right"
+                });
+                let name = ui.add(
+                    egui::TextEdit::singleline(&mut team.name)
+                        .hint_text(hint.clone())
+                        .desired_width(120.0),
+                );
+                a11y::name(ui, &name, hint);
+                changed |= name.lost_focus();
+                let minus = ui.add_enabled(team.score > 0, egui::Button::new("−"));
+                a11y::name(ui, &minus, self.i18n.tr("scoreboard-minus"));
+                if minus.clicked() {
+                    team.score -= 1;
+                    changed = true;
+                }
+                ui.heading(team.score.to_string());
+                let plus = ui.button("+");
+                a11y::name(ui, &plus, self.i18n.tr("scoreboard-plus"));
+                if plus.clicked() {
+                    team.score += 1;
+                    changed = true;
+                }
+                ui.end_row();
+            }
+            ui.label(self.i18n.tr("scoreboard-map"));
+            let minus = ui.add_enabled(self.scoreboard.map > 1, egui::Button::new("−"));
+            a11y::name(ui, &minus, self.i18n.tr("scoreboard-minus"));
+            if minus.clicked() {
+                self.scoreboard.map -= 1;
+                changed = true;
+            }
+            ui.heading(self.scoreboard.map.to_string());
+            let plus = ui.button("+");
+            a11y::name(ui, &plus, self.i18n.tr("scoreboard-plus"));
+            if plus.clicked() {
+                self.scoreboard.map += 1;
+                changed = true;
+            }
+            ui.end_row();
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(self.i18n.tr("scoreboard-swap")).clicked() {
+                let [left, right] = &mut self.scoreboard.teams;
+                // The sources stay where they are on screen; only what they
+                // show moves.
+                std::mem::swap(&mut left.name, &mut right.name);
+                std::mem::swap(&mut left.score, &mut right.score);
+                changed = true;
+            }
+            if ui.button(self.i18n.tr("scoreboard-reset")).clicked() {
+                for team in &mut self.scoreboard.teams {
+                    team.score = 0;
+                }
+                self.scoreboard.map = 1;
+                changed = true;
+            }
+        });
+
+        let text_inputs = self.text_inputs();
+        ui.collapsing(self.i18n.tr("scoreboard-sources"), |ui| {
+            let scoreboard = &mut self.scoreboard;
+            egui::Grid::new("scoreboard_sources")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let [left, right] = &mut scoreboard.teams;
+                    for (team, name_id, score_id) in [
+                        (left, "scoreboard-left-name", "scoreboard-left-score"),
+                        (right, "scoreboard-right-name", "scoreboard-right-score"),
+                    ] {
+                        changed |=
+                            picker(ui, &self.i18n, name_id, &text_inputs, &mut team.name_input);
+                        ui.end_row();
+                        changed |= picker(
+                            ui,
+                            &self.i18n,
+                            score_id,
+                            &text_inputs,
+                            &mut team.score_input,
+                        );
+                        ui.end_row();
+                    }
+                    changed |= picker(
+                        ui,
+                        &self.i18n,
+                        "scoreboard-map-source",
+                        &text_inputs,
+                        &mut scoreboard.map_input,
+                    );
+                    ui.end_row();
+                });
+        });
+
+        if changed {
+            self.write_scoreboard();
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use super::App;
+use crate::{
+    layout::Panel,
+    obs_worker::{Action, Query},
+    session::SessionMessage,
+};
+use fluent::fluent_args;
+
+// Panels that only show this instance's own things aren't handed around.
+fn shared(panel: Panel) -> bool {
+    !matches!(
+        panel,
+        Panel::Settings | Panel::Log | Panel::Events | Panel::History | Panel::Diagnostics
+    )
+}
+
+impl App {
+    pub(super) fn in_session(&self) -> bool {
+        self.relay_running || self.session_joined
+    }
+
+    // Tells the other operators, if there are any.
+    pub(super) fn share(&mut self, message: SessionMessage) {
+        if self.in_session() {
+            self.send(Action::Session(message));
+        }
+    }
+
+    pub(super) fn handle_peer(&mut self, message: SessionMessage) {
+        match message {
+            SessionMessage::Owners { owners } => self.owners = owners,
+            SessionMessage::Pressed { operator, button } if operator != self.operator_name => {
+                self.toasts.push(self.i18n.tr_args(
+                    "session-pressed",
+                    &fluent_args!["operator" => operator, "button" => button],
+                ));
+            }
+            SessionMessage::Selected {
+                operator,
+                alias,
+                input,
+            } if operator != self.operator_name => {
+                let name = match alias.as_str() {
+                    "mic" => &mut self.mic_input_name,
+                    "desktop" => &mut self.desktop_input_name,
+                    _ => return,
+                };
+                *name = Some(input.clone());
+                self.query(Query::Volume(input.clone()));
+                self.query(Query::Muted(input.clone()));
+                self.send(Action::SetAlias(alias, Some(input)));
+            }
+            _ => {}
+        }
+    }
+
+    // Who controls the panel, with a button to claim or hand it back. Returns
+    // whether someone else has it, in which case it's shown but disabled.
+    pub(super) fn show_owner(&mut self, ui: &mut egui::Ui, panel: Panel) -> bool {
+        if !self.in_session() || !shared(panel) {
+            return false;
+        }
+        let owner = self.owners.get(&panel).cloned();
+        let mut message = None;
+        ui.horizontal(|ui| match &owner {
+            Some(owner) if *owner == self.operator_name => {
+                ui.weak(self.i18n.tr("session-yours"));
+                if ui.small_button(self.i18n.tr("session-release")).clicked() {
+                    message = Some(SessionMessage::Release {
+                        panel,
+                        operator: self.operator_name.clone(),
+                    });
+                }
+            }
+            Some(owner) => {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    self.i18n
+                        .tr_args("session-owned", &fluent_args!["operator" => owner.as_str()]),
+                );
+                if ui
+                    .small_button(self.i18n.tr("session-take-over"))
+                    .on_hover_text(self.i18n.tr("session-take-over-hint"))
+                    .clicked()
+                {
+                    message = Some(SessionMessage::Claim {
+                        panel,
+                        operator: self.operator_name.clone(),
+                    });
+                }
+            }
+            None => {
+                if ui.small_button(self.i18n.tr("session-claim")).clicked() {
+                    message = Some(SessionMessage::Claim {
+                        panel,
+                        operator: self.operator_name.clone(),
+                    });
+                }
+            }
+        });
+        if let Some(message) = message {
+            self.share(message);
+        }
+        owner.map_or(false, |owner| owner != self.operator_name)
+    }
+
+    pub(super) fn show_session_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("session-operator"));
+            ui.add_enabled(
+                !self.in_session(),
+                egui::TextEdit::singleline(&mut self.operator_name).desired_width(100.0),
+            )
+            .labelled_by(label.id);
+            let label = ui.label(self.i18n.tr("session-host"));
+            ui.add_enabled(
+                !self.session_joined && !self.relay_running,
+                egui::TextEdit::singleline(&mut self.session_host)
+                    .hint_text("192.168.1.20:4456")
+                    .desired_width(140.0),
+            )
+            .labelled_by(label.id);
+            if self.session_joined {
+                if ui.button(self.i18n.tr("session-leave")).clicked() {
+                    self.send(Action::LeaveSession);
+                }
+            } else if ui
+                .add_enabled(
+                    !self.relay_running
+                        && !self.session_host.is_empty()
+                        && !self.operator_name.is_empty(),
+                    egui::Button::new(self.i18n.tr("session-join")),
+                )
+                .on_hover_text(self.i18n.tr("session-join-hint"))
+                .clicked()
+            {
+                self.send(Action::JoinSession(
+                    self.session_host.trim().to_string(),
+                    self.operator_name.clone(),
+                ));
+            }
+        });
+    }
+}
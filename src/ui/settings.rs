@@ -0,0 +1,373 @@
+use super::{a11y, tooltip, App};
+use crate::{
+    config::{Confirmation, SliderMode},
+    features::Feature,
+    i18n::{I18n, LANGUAGES},
+    layout::Orientation,
+    mqtt::MqttConfig,
+    obs_worker::Action,
+    theme::ThemeKind,
+};
+use fluent::fluent_args;
+use std::net::SocketAddr;
+
+impl App {
+    pub(super) fn show_settings(&mut self, ui: &mut egui::Ui) {
+        let mut language = None;
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-language"));
+            let current = self.i18n.language();
+            egui::ComboBox::from_id_source("language")
+                .selected_text(
+                    LANGUAGES
+                        .iter()
+                        .find(|language| language.code == current)
+                        .map_or(current, |language| language.name),
+                )
+                .show_ui(ui, |ui| {
+                    for option in &LANGUAGES {
+                        if ui
+                            .selectable_label(option.code == current, option.name)
+                            .clicked()
+                        {
+                            language = Some(option.code);
+                        }
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+        });
+        if let Some(language) = language {
+            self.i18n = I18n::new(language);
+        }
+
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-orientation"));
+            egui::ComboBox::from_id_source("orientation")
+                .selected_text(self.i18n.tr(self.orientation.message_id()))
+                .show_ui(ui, |ui| {
+                    for orientation in Orientation::ALL {
+                        ui.selectable_value(
+                            &mut self.orientation,
+                            orientation,
+                            self.i18n.tr(orientation.message_id()),
+                        );
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+        });
+
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-slider-mode"));
+            egui::ComboBox::from_id_source("slider_mode")
+                .selected_text(self.i18n.tr(self.slider_mode.message_id()))
+                .show_ui(ui, |ui| {
+                    for mode in SliderMode::ALL {
+                        ui.selectable_value(
+                            &mut self.slider_mode,
+                            mode,
+                            self.i18n.tr(mode.message_id()),
+                        );
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+        });
+
+        let mut style_changed = false;
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-theme"));
+            egui::ComboBox::from_id_source("theme")
+                .selected_text(self.i18n.tr(self.theme.kind.message_id()))
+                .show_ui(ui, |ui| {
+                    for kind in ThemeKind::ALL {
+                        style_changed |= ui
+                            .selectable_value(
+                                &mut self.theme.kind,
+                                kind,
+                                self.i18n.tr(kind.message_id()),
+                            )
+                            .changed();
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+            if self.theme.kind == ThemeKind::Custom {
+                for (id, color) in [
+                    ("settings-accent", &mut self.theme.accent),
+                    ("settings-background", &mut self.theme.background),
+                    ("settings-sliders", &mut self.theme.slider),
+                ] {
+                    let label = ui.label(self.i18n.tr(id));
+                    style_changed |= ui
+                        .color_edit_button_srgb(color)
+                        .labelled_by(label.id)
+                        .changed();
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            style_changed |= ui
+                .checkbox(&mut self.touch_mode, self.i18n.tr("settings-touch-mode"))
+                .changed();
+            ui.add_enabled(
+                self.tray.is_some(),
+                egui::Checkbox::new(
+                    &mut self.minimize_to_tray,
+                    self.i18n.tr("settings-minimize-to-tray"),
+                ),
+            );
+        });
+        if style_changed {
+            self.apply_style(ui.ctx());
+        }
+        ui.horizontal(|ui| {
+            ui.label(self.i18n.tr("settings-confirm"));
+            for kind in Confirmation::ALL {
+                let mut ask = !self.skip_confirmations.contains(&kind);
+                if ui
+                    .checkbox(&mut ask, self.i18n.tr(kind.message_id()))
+                    .changed()
+                {
+                    if ask {
+                        self.skip_confirmations.remove(&kind);
+                    } else {
+                        self.skip_confirmations.insert(kind);
+                    }
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-ui-scale"));
+            let response = ui
+                .add(
+                    egui::Slider::new(&mut self.ui_scale, super::UI_SCALE_RANGE)
+                        .step_by(super::UI_SCALE_STEP as f64),
+                )
+                .labelled_by(label.id);
+            tooltip::hint(
+                ui,
+                self.touch_mode,
+                response,
+                self.i18n.tr("settings-ui-scale-hint"),
+            );
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("kiosk-pin"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.kiosk_new_pin)
+                    .password(true)
+                    .desired_width(60.0),
+            )
+            .labelled_by(label.id);
+            let response = ui.add_enabled(
+                !self.kiosk_new_pin.is_empty(),
+                egui::Button::new(self.i18n.tr("kiosk-enter")),
+            );
+            let hint = self.i18n.tr("kiosk-enter-hint");
+            if tooltip::hint(ui, self.touch_mode, response, hint).clicked() {
+                let pin = std::mem::take(&mut self.kiosk_new_pin);
+                self.enter_kiosk(ui.ctx(), pin);
+            }
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("volunteer-pin"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.volunteer_new_pin)
+                    .password(true)
+                    .desired_width(60.0),
+            )
+            .labelled_by(label.id);
+            let response = ui.add_enabled(
+                !self.volunteer_new_pin.is_empty(),
+                egui::Button::new(self.i18n.tr("volunteer-enter")),
+            );
+            let hint = self.i18n.tr("volunteer-enter-hint");
+            if tooltip::hint(ui, self.touch_mode, response, hint).clicked() {
+                let pin = std::mem::take(&mut self.volunteer_new_pin);
+                self.enter_volunteer(ui.ctx(), pin);
+            }
+        });
+        ui.collapsing(self.i18n.tr("volunteer-scenes"), |ui| {
+            ui.weak(self.i18n.tr("volunteer-scenes-hint"));
+            for scene in &self.obs_state.scenes {
+                let mut shown = self.volunteer_scenes.contains(scene);
+                if ui.checkbox(&mut shown, scene).changed() {
+                    if shown {
+                        self.volunteer_scenes.insert(scene.clone());
+                    } else {
+                        self.volunteer_scenes.remove(scene);
+                    }
+                }
+            }
+        });
+        if self.features.enabled(Feature::Relay) {
+            ui.horizontal(|ui| {
+                let label = ui.label(self.i18n.tr("settings-relay-port"));
+                ui.add_enabled(
+                    !self.relay_running,
+                    egui::TextEdit::singleline(&mut self.relay_port).desired_width(60.0),
+                )
+                .labelled_by(label.id);
+                if self.relay_running {
+                    if ui.button(self.i18n.tr("settings-stop-relay")).clicked() {
+                        self.send(Action::StopRelay);
+                    }
+                } else if ui.button(self.i18n.tr("settings-start-relay")).clicked() {
+                    match self.relay_port.parse::<u16>() {
+                        Ok(port) => self.send(Action::StartRelay(port)),
+                        Err(err) => self.toasts.push(invalid_port(&self.i18n, err)),
+                    }
+                }
+            });
+        }
+        self.show_session_settings(ui);
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-osc-port"));
+            ui.add_enabled(
+                !self.osc_running,
+                egui::TextEdit::singleline(&mut self.osc_port).desired_width(60.0),
+            )
+            .labelled_by(label.id);
+            let feedback = ui.add_enabled(
+                !self.osc_running,
+                egui::TextEdit::singleline(&mut self.osc_feedback)
+                    .hint_text(self.i18n.tr("settings-osc-feedback"))
+                    .desired_width(140.0),
+            );
+            a11y::name(ui, &feedback, self.i18n.tr("settings-osc-feedback"));
+            if self.osc_running {
+                if ui.button(self.i18n.tr("settings-stop-osc")).clicked() {
+                    self.send(Action::StopOsc);
+                }
+            } else if ui.button(self.i18n.tr("settings-start-osc")).clicked() {
+                let feedback = match self.osc_feedback.trim() {
+                    "" => Ok(None),
+                    feedback => feedback.parse::<SocketAddr>().map(Some),
+                };
+                match (self.osc_port.parse::<u16>(), feedback) {
+                    (Ok(port), Ok(feedback)) => self.send(Action::StartOsc(port, feedback)),
+                    (Err(err), _) => self.toasts.push(invalid_port(&self.i18n, err)),
+                    (_, Err(err)) => self.toasts.push(self.i18n.tr_args(
+                        "toast-invalid-feedback",
+                        &fluent_args!["error" => err.to_string()],
+                    )),
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("settings-remote-port"));
+            ui.add_enabled(
+                !self.remote_running,
+                egui::TextEdit::singleline(&mut self.remote_port).desired_width(60.0),
+            )
+            .labelled_by(label.id);
+            if self.remote_running {
+                if ui.button(self.i18n.tr("settings-stop-remote")).clicked() {
+                    self.send(Action::StopRemote);
+                }
+                ui.weak(self.i18n.tr_args(
+                    "settings-remote-hint",
+                    &fluent_args!["port" => self.remote_port.as_str()],
+                ));
+            } else if ui.button(self.i18n.tr("settings-start-remote")).clicked() {
+                match self.remote_port.parse::<u16>() {
+                    Ok(port) => self.send(Action::StartRemote(port)),
+                    Err(err) => self.toasts.push(invalid_port(&self.i18n, err)),
+                }
+            }
+        });
+        self.show_pairing_settings(ui);
+        ui.horizontal(|ui| {
+            ui.label(self.i18n.tr("settings-mqtt-broker"));
+            ui.add_enabled_ui(!self.mqtt_running, |ui| {
+                for (text, id, width, password) in [
+                    (&mut self.mqtt_host, "settings-mqtt-host", 120.0, false),
+                    (&mut self.mqtt_port, "settings-mqtt-port", 50.0, false),
+                    (&mut self.mqtt_prefix, "settings-mqtt-prefix", 60.0, false),
+                    (
+                        &mut self.mqtt_username,
+                        "settings-mqtt-username",
+                        80.0,
+                        false,
+                    ),
+                    (
+                        &mut self.mqtt_password,
+                        "settings-mqtt-password",
+                        80.0,
+                        true,
+                    ),
+                ] {
+                    let hint = self.i18n.tr(id);
+                    let response = ui.add(
+                        egui::TextEdit::singleline(text)
+                            .password(password)
+                            .hint_text(hint.clone())
+                            .desired_width(width),
+                    );
+                    a11y::name(ui, &response, hint);
+                }
+            });
+            if self.mqtt_running {
+                if ui.button(self.i18n.tr("settings-stop-mqtt")).clicked() {
+                    self.send(Action::StopMqtt);
+                }
+            } else if ui.button(self.i18n.tr("settings-start-mqtt")).clicked() {
+                match self.mqtt_port.parse::<u16>() {
+                    Ok(port) => {
+                        let credentials = (!self.mqtt_username.is_empty())
+                            .then(|| (self.mqtt_username.clone(), self.mqtt_password.clone()));
+                        self.send(Action::StartMqtt(MqttConfig {
+                            host: self.mqtt_host.clone(),
+                            port,
+                            prefix: self.mqtt_prefix.clone(),
+                            credentials,
+                        }));
+                    }
+                    Err(err) => self.toasts.push(invalid_port(&self.i18n, err)),
+                }
+            }
+        });
+        if self.global_keys.is_some() {
+            ui.separator();
+            self.show_global_key_settings(ui);
+        }
+        ui.separator();
+        self.show_lighting_settings(ui);
+        ui.separator();
+        self.show_templates(ui);
+        ui.separator();
+        self.show_feature_settings(ui);
+        ui.separator();
+        self.show_usage_settings(ui);
+    }
+
+    fn show_feature_settings(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("features-title"));
+        ui.weak(self.i18n.tr("features-hint"));
+        let mut changed = false;
+        for feature in Feature::ALL {
+            let mut enabled = self.features.enabled(feature);
+            if ui
+                .checkbox(&mut enabled, self.i18n.tr(feature.message_id()))
+                .on_hover_text(self.i18n.tr(feature.hint_id()))
+                .changed()
+            {
+                tracing::info!(enabled, feature = feature.message_id(), "feature switched");
+                self.features.set(feature, enabled);
+                changed = true;
+            }
+        }
+        if changed {
+            self.send(Action::SetFeatures(self.features.clone()));
+        }
+    }
+}
+
+fn invalid_port(i18n: &I18n, err: std::num::ParseIntError) -> String {
+    i18n.tr_args(
+        "toast-invalid-port",
+        &fluent_args!["error" => err.to_string()],
+    )
+}
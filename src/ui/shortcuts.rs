@@ -0,0 +1,105 @@
+use super::App;
+use crate::{config::Shortcut, obs_worker::Action};
+
+pub(super) fn default_shortcuts() -> Vec<(egui::Key, Shortcut)> {
+    let scenes = [
+        egui::Key::Num1,
+        egui::Key::Num2,
+        egui::Key::Num3,
+        egui::Key::Num4,
+        egui::Key::Num5,
+        egui::Key::Num6,
+        egui::Key::Num7,
+        egui::Key::Num8,
+        egui::Key::Num9,
+    ];
+    let mut shortcuts = vec![
+        (egui::Key::M, Shortcut::ToggleMuteMic),
+        (egui::Key::D, Shortcut::ToggleMuteDesktop),
+        (egui::Key::R, Shortcut::ToggleRecord),
+    ];
+    shortcuts.extend(
+        scenes
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (key, Shortcut::Scene(index + 1))),
+    );
+    shortcuts
+}
+
+impl App {
+    pub(super) fn toggle_mute(&mut self, name: Option<String>) {
+        if let Some(name) = name {
+            let muted = self.obs_state.muted.get(&name).copied().unwrap_or(false);
+            self.perform(Action::SetMute(name, !muted));
+        }
+    }
+
+    // Plain keys only fire while no text field has focus, so typing a name
+    // never mutes anything.
+    pub(super) fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if !self.logged_in || ctx.wants_keyboard_input() {
+            return;
+        }
+        let pressed: Vec<Shortcut> = ctx.input_mut(|i| {
+            self.shortcuts
+                .iter()
+                .filter(|(key, _)| i.consume_key(egui::Modifiers::NONE, *key))
+                .map(|(_, shortcut)| shortcut.clone())
+                .collect()
+        });
+        for shortcut in pressed {
+            self.run_shortcut(shortcut);
+        }
+    }
+
+    pub(super) fn run_shortcut(&mut self, shortcut: Shortcut) {
+        // Whoever the app was handed to keeps the mutes and scenes they can
+        // see, and nothing else.
+        let allowed = matches!(
+            shortcut,
+            Shortcut::ToggleMuteMic | Shortcut::ToggleMuteDesktop | Shortcut::Scene(_)
+        );
+        if self.locked() && !allowed {
+            return;
+        }
+        match shortcut {
+            Shortcut::ToggleMuteMic => self.toggle_mute(self.mic_input_name.clone()),
+            Shortcut::ToggleMuteDesktop => self.toggle_mute(self.desktop_input_name.clone()),
+            Shortcut::ToggleRecord => self.send_confirmed(if self.obs_state.recording {
+                Action::StopRecord
+            } else {
+                Action::StartRecord
+            }),
+            Shortcut::ToggleStream => self.send_confirmed(if self.obs_state.streaming {
+                Action::StopStream
+            } else {
+                Action::StartStream
+            }),
+            Shortcut::Scene(number) => {
+                if let Some(scene) = number
+                    .checked_sub(1)
+                    .and_then(|index| self.obs_state.scenes.get(index))
+                    .filter(|scene| !self.volunteer_hides(scene))
+                {
+                    self.perform(Action::SetScene(scene.clone()));
+                }
+            }
+            Shortcut::Button(name) => {
+                if let Some(obs_macro) = self
+                    .all_macros()
+                    .into_iter()
+                    .find(|obs_macro| obs_macro.name == name)
+                {
+                    self.send(Action::RunMacro(obs_macro));
+                }
+            }
+            Shortcut::TeleprompterToggle => self.teleprompter.toggle(),
+            Shortcut::TeleprompterFaster => self.teleprompter.change_speed(true),
+            Shortcut::TeleprompterSlower => self.teleprompter.change_speed(false),
+            Shortcut::TeleprompterRestart => self.teleprompter.restart(),
+            Shortcut::RundownGo => self.go(),
+            Shortcut::Intermission => self.toggle_intermission(),
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use super::{a11y, timers::picker, App};
+use crate::obs_worker::{Action, MediaCommand, Query};
+use fluent::fluent_args;
+use std::collections::{HashMap, HashSet};
+
+// Per capture input kind: the property holding the device, the properties
+// worth offering besides it, and settings needed for those to take effect.
+const CAPTURE_KINDS: [(&str, &str, &[(&str, &str)], &str); 3] = [
+    (
+        "v4l2_input",
+        "device_id",
+        &[
+            ("resolution", "sources-resolution"),
+            ("framerate", "sources-fps"),
+        ],
+        "{}",
+    ),
+    (
+        "dshow_input",
+        "video_device_id",
+        &[
+            ("resolution", "sources-resolution"),
+            ("frame_interval", "sources-fps"),
+        ],
+        r#"{ "res_type": 1 }"#,
+    ),
+    (
+        "av_capture_input",
+        "device",
+        &[("preset", "sources-preset")],
+        r#"{ "use_preset": true }"#,
+    ),
+];
+
+// Settings are fetched the first time an input is shown, not for every input
+// on connect.
+#[derive(Default)]
+pub(super) struct Sources {
+    pub(super) settings: HashMap<String, serde_json::Value>,
+    requested: HashSet<String>,
+    urls: HashMap<String, String>,
+    // Choices of list properties, keyed by input and property.
+    pub(super) items: HashMap<(String, String), Vec<(String, serde_json::Value)>>,
+    requested_items: HashSet<(String, String)>,
+    // Slide shown and number of slides, per slideshow.
+    pub(super) slides: HashMap<String, (usize, usize)>,
+    app_audio_name: String,
+    app_audio_scene: Option<String>,
+}
+
+// OBS 28's application audio capture, only on Windows.
+const APP_AUDIO_KIND: &str = "wasapi_process_output_capture";
+// Its setting naming the process, as "title:class:executable".
+const APP_AUDIO_PROPERTY: &str = "window";
+
+impl Sources {
+    pub(super) fn forget(&mut self) {
+        self.settings.clear();
+        self.requested.clear();
+        self.items.clear();
+        self.requested_items.clear();
+    }
+
+    // Which resolutions and rates exist depends on the device, so everything
+    // is fetched again after a change.
+    fn forget_input(&mut self, name: &str) {
+        self.settings.remove(name);
+        self.requested.remove(name);
+        self.items.retain(|(input, _), _| input != name);
+        self.requested_items.retain(|(input, _)| input != name);
+    }
+}
+
+impl App {
+    fn inputs_of_kind(&self, kind: &str) -> Vec<String> {
+        self.input_info
+            .iter()
+            .filter(|input| input.kind.starts_with(kind))
+            .map(|input| input.name.clone())
+            .collect()
+    }
+
+    fn request_settings(&mut self, inputs: &[String]) {
+        for name in inputs {
+            if self.sources.requested.insert(name.clone()) {
+                self.query(Query::InputSettings(name.clone()));
+            }
+        }
+    }
+
+    fn request_items(&mut self, input: &str, property: &str) {
+        let key = (input.to_string(), property.to_string());
+        if self.sources.requested_items.insert(key) {
+            self.query(Query::ListItems(input.to_string(), property.to_string()));
+        }
+    }
+
+    pub(super) fn show_sources(&mut self, ui: &mut egui::Ui) {
+        self.show_browser_sources(ui);
+        ui.separator();
+        self.show_capture_devices(ui);
+        ui.separator();
+        self.show_slideshows(ui);
+        if self.obs_platform == "windows" {
+            ui.separator();
+            self.show_app_audio(ui);
+        }
+    }
+
+    // Creating the input first is what makes OBS list the running programs,
+    // so picking one comes second.
+    fn show_app_audio(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("sources-app-audio"));
+        let scenes = self.obs_state.scenes.clone();
+        let mut create = None;
+        ui.horizontal(|ui| {
+            let hint = self.i18n.tr("sources-app-audio-name");
+            let name = ui.add(
+                egui::TextEdit::singleline(&mut self.sources.app_audio_name)
+                    .hint_text(hint.clone())
+                    .desired_width(140.0),
+            );
+            a11y::name(ui, &name, hint);
+            picker(
+                ui,
+                &self.i18n,
+                "sources-app-audio-scene",
+                &scenes,
+                &mut self.sources.app_audio_scene,
+            );
+            let name = self.sources.app_audio_name.trim();
+            let taken = self.input_info.iter().any(|input| input.name == name);
+            if let Some(scene) = &self.sources.app_audio_scene {
+                if ui
+                    .add_enabled(
+                        !name.is_empty() && !taken,
+                        egui::Button::new(self.i18n.tr("sources-app-audio-create")),
+                    )
+                    .clicked()
+                {
+                    create = Some(Action::CreateInput(
+                        scene.clone(),
+                        name.to_string(),
+                        APP_AUDIO_KIND.to_string(),
+                    ));
+                }
+            }
+        });
+        if let Some(action) = create {
+            self.sources.app_audio_name.clear();
+            self.send(action);
+        }
+
+        let inputs = self.inputs_of_kind(APP_AUDIO_KIND);
+        self.request_settings(&inputs);
+        let mut actions = Vec::new();
+        egui::Grid::new("app_audio").num_columns(2).show(ui, |ui| {
+            for input in inputs {
+                self.request_items(&input, APP_AUDIO_PROPERTY);
+                let label = ui.label(&input);
+                let (Some(settings), Some(items)) = (
+                    self.sources.settings.get(&input),
+                    self.sources
+                        .items
+                        .get(&(input.clone(), APP_AUDIO_PROPERTY.to_string())),
+                ) else {
+                    ui.spinner();
+                    ui.end_row();
+                    continue;
+                };
+                let current = &settings[APP_AUDIO_PROPERTY];
+                let selected = items
+                    .iter()
+                    .find(|(_, value)| value == current)
+                    .map_or_else(
+                        || self.i18n.tr("sources-app-audio-pick"),
+                        |(name, _)| name.clone(),
+                    );
+                egui::ComboBox::from_id_source(("app_audio", &input))
+                    .selected_text(selected)
+                    .width(240.0)
+                    .show_ui(ui, |ui| {
+                        for (name, value) in items {
+                            if ui.selectable_label(value == current, name).clicked() {
+                                let mut change = serde_json::json!({});
+                                change[APP_AUDIO_PROPERTY] = value.clone();
+                                actions.push(Action::SetInputSettings(input.clone(), change));
+                            }
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id);
+                ui.end_row();
+            }
+        });
+        for action in actions {
+            if let Action::SetInputSettings(name, _) = &action {
+                self.sources.forget_input(name);
+            }
+            self.send(action);
+        }
+    }
+
+    fn show_slideshows(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("sources-slideshows"));
+        let slideshows = self.inputs_of_kind("slideshow");
+        if slideshows.is_empty() {
+            ui.weak(self.i18n.tr("sources-none"));
+            return;
+        }
+        let mut actions = Vec::new();
+        egui::Grid::new("slideshows")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for input in slideshows {
+                    ui.vertical(|ui| {
+                        ui.label(&input);
+                        if let Some((slide, count)) = self.sources.slides.get(&input) {
+                            ui.weak(self.i18n.tr_args(
+                                "sources-slide",
+                                &fluent_args!["slide" => slide + 1, "count" => *count],
+                            ));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        for (text, id, command) in [
+                            ("⏮", "sources-previous", MediaCommand::Previous),
+                            ("▶", "sources-play", MediaCommand::Play),
+                            ("⏸", "sources-pause", MediaCommand::Pause),
+                            ("⏭", "sources-next", MediaCommand::Next),
+                            ("⟲", "sources-restart", MediaCommand::Restart),
+                        ] {
+                            let button = ui.button(text);
+                            a11y::name(ui, &button, self.i18n.tr(id));
+                            if button.clicked() {
+                                actions.push(Action::Media(input.clone(), command));
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        for action in actions {
+            self.send(action);
+        }
+    }
+
+    fn show_capture_devices(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("sources-capture"));
+        let mut any = false;
+        let mut actions = Vec::new();
+        for (kind, device, properties, extra) in CAPTURE_KINDS {
+            let inputs = self.inputs_of_kind(kind);
+            self.request_settings(&inputs);
+            for input in inputs {
+                any = true;
+                let mut rows = vec![(device, "sources-device")];
+                rows.extend_from_slice(properties);
+                for (property, _) in &rows {
+                    self.request_items(&input, property);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(&input);
+                    if ui
+                        .button(self.i18n.tr("sources-reactivate"))
+                        .on_hover_text(self.i18n.tr("sources-reactivate-hint"))
+                        .clicked()
+                    {
+                        actions.push(Action::ReactivateInput(input.clone(), device.to_string()));
+                    }
+                });
+                let Some(settings) = self.sources.settings.get(&input) else {
+                    ui.spinner();
+                    continue;
+                };
+                egui::Grid::new(("capture", &input))
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (property, id) in rows {
+                            let Some(items) = self
+                                .sources
+                                .items
+                                .get(&(input.clone(), property.to_string()))
+                            else {
+                                continue;
+                            };
+                            let current = &settings[property];
+                            let selected = items
+                                .iter()
+                                .find(|(_, value)| value == current)
+                                .map_or_else(|| current.to_string(), |(name, _)| name.clone());
+                            let label = ui.label(self.i18n.tr(id));
+                            egui::ComboBox::from_id_source((&input, property))
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    for (name, value) in items {
+                                        if ui.selectable_label(value == current, name).clicked() {
+                                            let mut change: serde_json::Value =
+                                                if property == device {
+                                                    serde_json::json!({})
+                                                } else {
+                                                    serde_json::from_str(extra).unwrap_or_default()
+                                                };
+                                            change[property] = value.clone();
+                                            actions.push(Action::SetInputSettings(
+                                                input.clone(),
+                                                change,
+                                            ));
+                                        }
+                                    }
+                                })
+                                .response
+                                .labelled_by(label.id);
+                            ui.end_row();
+                        }
+                    });
+            }
+        }
+        if !any {
+            ui.weak(self.i18n.tr("sources-none"));
+        }
+        for action in actions {
+            if let Action::SetInputSettings(name, _) = &action {
+                self.sources.forget_input(name);
+            }
+            self.send(action);
+        }
+    }
+
+    fn show_browser_sources(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("sources-browser"));
+        let browsers = self.inputs_of_kind("browser_source");
+        if browsers.is_empty() {
+            ui.weak(self.i18n.tr("sources-none"));
+            return;
+        }
+        self.request_settings(&browsers);
+        let mut actions = Vec::new();
+        egui::Grid::new("browser_sources")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for name in &browsers {
+                    ui.label(name);
+                    match self.sources.settings.get(name) {
+                        Some(settings) => {
+                            let current = settings["url"].as_str().unwrap_or_default().to_string();
+                            let url = self
+                                .sources
+                                .urls
+                                .entry(name.clone())
+                                .or_insert_with(|| current.clone());
+                            let response = ui.add(
+                                egui::TextEdit::singleline(url)
+                                    .hint_text("https://")
+                                    .desired_width(240.0),
+                            );
+                            a11y::name(ui, &response, self.i18n.tr("sources-url"));
+                            let submitted = response.lost_focus()
+                                && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                            if submitted && *url != current {
+                                actions.push(Action::SetInputSettings(
+                                    name.clone(),
+                                    serde_json::json!({ "url": url }),
+                                ));
+                            }
+                        }
+                        None => {
+                            ui.spinner();
+                        }
+                    }
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(self.i18n.tr("sources-refresh"))
+                            .on_hover_text(self.i18n.tr("sources-refresh-hint"))
+                            .clicked()
+                        {
+                            actions.push(Action::PressInputButton(
+                                name.clone(),
+                                "refreshnocache".to_string(),
+                            ));
+                        }
+                        if ui.button(self.i18n.tr("sources-interact")).clicked() {
+                            actions.push(Action::OpenInteract(name.clone()));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        for action in actions {
+            if let Action::SetInputSettings(name, settings) = &action {
+                if let Some(current) = self.sources.settings.get_mut(name) {
+                    current["url"] = settings["url"].clone();
+                }
+            }
+            self.send(action);
+        }
+    }
+}
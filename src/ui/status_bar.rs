@@ -0,0 +1,100 @@
+use super::{a11y, App};
+use egui::accesskit;
+use fluent::fluent_args;
+use std::time::Duration;
+
+pub(super) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+impl App {
+    pub(super) fn show_status_bar(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let (color, id) = match (self.logged_in, self.connected) {
+                    (false, _) => (ui.visuals().weak_text_color(), "login-not-logged-in"),
+                    (true, false) => (ui.visuals().warn_fg_color, "status-offline"),
+                    (true, true) => (egui::Color32::GREEN, "status-connected"),
+                };
+                let notifications = ui
+                    .selectable_label(
+                        self.toasts.drawer_open(),
+                        format!("🔔 {}", self.toasts.history_len()),
+                    )
+                    .on_hover_text(self.i18n.tr("notifications-title"));
+                a11y::name(ui, &notifications, self.i18n.tr("notifications-title"));
+                if notifications.clicked() {
+                    self.toasts.toggle_drawer();
+                }
+                if self.locked() {
+                    let lock = ui
+                        .button("🔒")
+                        .on_hover_text(self.i18n.tr("kiosk-unlock-title"));
+                    a11y::name(ui, &lock, self.i18n.tr("kiosk-unlock-title"));
+                    if lock.clicked() {
+                        self.request_unlock();
+                    }
+                }
+                if !self.locked() {
+                    self.show_rehearsal_toggle(ui);
+                }
+                self.show_demo_badge(ui);
+                ui.separator();
+                ui.colored_label(color, "●");
+                ui.label(self.i18n.tr(id));
+                if let Some(version) = &self.obs_version {
+                    ui.separator();
+                    ui.label(self.i18n.tr_args(
+                        "status-obs-version",
+                        &fluent_args!["version" => version.as_str()],
+                    ));
+                }
+                if let Some(scene) = &self.obs_state.current_scene {
+                    ui.separator();
+                    ui.label(scene);
+                }
+                for (started, id) in [
+                    (self.stream_started, "status-stream-timer"),
+                    (self.record_started, "status-record-timer"),
+                ] {
+                    if let Some(started) = started {
+                        ui.separator();
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            self.i18n.tr_args(
+                                id,
+                                &fluent_args!["elapsed" => format_duration(started.elapsed())],
+                            ),
+                        );
+                        ctx.request_repaint_after(Duration::from_secs(1));
+                    }
+                }
+                if let Some(viewers) = self.youtube.viewers {
+                    ui.separator();
+                    ui.label(
+                        self.i18n
+                            .tr_args("status-viewers", &fluent_args!["count" => viewers]),
+                    );
+                }
+                if let Some((time, err)) = &self.last_error {
+                    ui.separator();
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!("{} {}", time.format("%H:%M:%S"), err),
+                    );
+                }
+                // The last announcement sits in a live region, so screen
+                // readers speak it whenever it changes.
+                if let Some(announcement) = &self.announcement {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let response = ui.weak(announcement);
+                        ctx.accesskit_node_builder(response.id, |builder| {
+                            builder.set_live(accesskit::Live::Polite)
+                        });
+                    });
+                }
+            });
+        });
+    }
+}
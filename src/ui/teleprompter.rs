@@ -0,0 +1,176 @@
+use super::{a11y, App};
+use crate::config::TeleprompterSettings;
+use fluent::fluent_args;
+
+// Points per second, and how much one press of faster/slower changes it.
+const SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.0..=400.0;
+const SPEED_STEP: f32 = 10.0;
+
+#[derive(Default)]
+pub(super) struct Teleprompter {
+    pub(super) settings: TeleprompterSettings,
+    offset: f32,
+    playing: bool,
+    editing: bool,
+    path: String,
+}
+
+impl Teleprompter {
+    pub(super) fn toggle(&mut self) {
+        self.playing = !self.playing;
+    }
+
+    pub(super) fn restart(&mut self) {
+        self.offset = 0.0;
+    }
+
+    pub(super) fn change_speed(&mut self, faster: bool) {
+        let step = if faster { SPEED_STEP } else { -SPEED_STEP };
+        self.settings.speed =
+            (self.settings.speed + step).clamp(*SPEED_RANGE.start(), *SPEED_RANGE.end());
+    }
+}
+
+impl App {
+    pub(super) fn show_teleprompter(&mut self, ui: &mut egui::Ui) {
+        let mut load = false;
+        ui.horizontal(|ui| {
+            let prompter = &mut self.teleprompter;
+            let play = if prompter.playing {
+                self.i18n.tr("teleprompter-pause")
+            } else {
+                self.i18n.tr("teleprompter-play")
+            };
+            if ui.button(play).clicked() {
+                prompter.toggle();
+            }
+            if ui.button(self.i18n.tr("teleprompter-restart")).clicked() {
+                prompter.restart();
+            }
+            let label = ui.label(self.i18n.tr("teleprompter-speed"));
+            ui.add(egui::Slider::new(&mut prompter.settings.speed, SPEED_RANGE))
+                .labelled_by(label.id);
+            let label = ui.label(self.i18n.tr("teleprompter-size"));
+            ui.add(
+                egui::DragValue::new(&mut prompter.settings.font_size).clamp_range(12.0..=160.0),
+            )
+            .labelled_by(label.id);
+            ui.checkbox(
+                &mut prompter.settings.mirror,
+                self.i18n.tr("teleprompter-mirror"),
+            );
+            ui.toggle_value(&mut prompter.editing, self.i18n.tr("teleprompter-edit"));
+        });
+        if self.teleprompter.editing {
+            ui.horizontal(|ui| {
+                let hint = self.i18n.tr("teleprompter-path");
+                let path = ui.add(
+                    egui::TextEdit::singleline(&mut self.teleprompter.path)
+                        .hint_text(hint.clone())
+                        .desired_width(240.0),
+                );
+                a11y::name(ui, &path, hint);
+                load = ui.button(self.i18n.tr("teleprompter-load")).clicked();
+            });
+            ui.add(
+                egui::TextEdit::multiline(&mut self.teleprompter.settings.script)
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(8),
+            );
+            if load {
+                match std::fs::read_to_string(self.teleprompter.path.trim()) {
+                    Ok(script) => {
+                        self.teleprompter.settings.script = script;
+                        self.teleprompter.restart();
+                    }
+                    Err(err) => self.toasts.push(self.i18n.tr_args(
+                        "teleprompter-load-failed",
+                        &fluent_args!["error" => err.to_string()],
+                    )),
+                }
+            }
+            return;
+        }
+        self.show_script(ui);
+    }
+
+    // Drawn by hand rather than in a scroll area, so it can scroll smoothly on
+    // its own and be flipped for a beam-splitter glass.
+    fn show_script(&mut self, ui: &mut egui::Ui) {
+        let clock = self
+            .rundown
+            .segment_on_prompter
+            .then(|| self.rundown.segment_clock())
+            .flatten();
+        let prompter = &mut self.teleprompter;
+        let (rect, response) =
+            ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+        ui.painter().rect_filled(rect, 0.0, egui::Color32::BLACK);
+        let galley = ui.painter().layout(
+            prompter.settings.script.clone(),
+            egui::FontId::proportional(prompter.settings.font_size),
+            egui::Color32::WHITE,
+            rect.width() - 2.0 * prompter.settings.font_size,
+        );
+        // Dragging and the wheel move the script by hand, playing or not.
+        prompter.offset -= response.drag_delta().y;
+        if response.hovered() {
+            prompter.offset -= ui.input(|i| i.scroll_delta.y);
+        }
+        if prompter.playing {
+            prompter.offset += prompter.settings.speed * ui.input(|i| i.stable_dt);
+            ui.ctx().request_repaint();
+        }
+        // The first line starts at the reading line, a third of the way down.
+        let max = galley.size().y;
+        prompter.offset = prompter.offset.clamp(0.0, max);
+        if prompter.offset >= max {
+            prompter.playing = false;
+        }
+        let reading_line = rect.top() + rect.height() / 3.0;
+        let pos = egui::pos2(
+            rect.left() + prompter.settings.font_size,
+            reading_line - prompter.offset,
+        );
+        let mut shapes = vec![egui::Shape::galley(pos, galley)];
+        // The time left in the segment sits small in a corner, in the script's
+        // direction so it reads the same through the glass.
+        if let Some((clock, color)) = clock {
+            let galley = ui.painter().layout_no_wrap(
+                clock,
+                egui::FontId::monospace(prompter.settings.font_size / 2.0),
+                color.unwrap_or(egui::Color32::GRAY),
+            );
+            let pos = rect.right_top() + egui::vec2(-galley.size().x - 8.0, 8.0);
+            shapes.push(egui::Shape::galley(pos, galley));
+        }
+        let painter = ui.painter_at(rect);
+        if prompter.settings.mirror {
+            let pixels_per_point = ui.ctx().pixels_per_point();
+            for primitive in ui.ctx().tessellate(
+                shapes
+                    .into_iter()
+                    .map(|shape| egui::epaint::ClippedShape {
+                        clip_rect: rect,
+                        shape,
+                    })
+                    .collect(),
+                pixels_per_point,
+            ) {
+                if let egui::epaint::Primitive::Mesh(mut mesh) = primitive.primitive {
+                    for vertex in &mut mesh.vertices {
+                        vertex.pos.x = rect.left() + rect.right() - vertex.pos.x;
+                    }
+                    painter.add(mesh);
+                }
+            }
+        } else {
+            painter.extend(shapes);
+        }
+        painter.hline(
+            rect.x_range(),
+            reading_line,
+            egui::Stroke::new(1.0, egui::Color32::from_white_alpha(40)),
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use super::{a11y, App};
+use crate::{
+    obs_worker::Action,
+    template::{self, ShowTemplate},
+};
+use fluent::fluent_args;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Default)]
+pub(super) struct Templates {
+    name: String,
+    chosen: Option<String>,
+    // Opened but waiting on the operator to say where its missing scenes and
+    // sources went.
+    pending: Option<Pending>,
+}
+
+struct Pending {
+    template: ShowTemplate,
+    // None keeps the template's name.
+    scenes: BTreeMap<String, Option<String>>,
+    sources: BTreeMap<String, Option<String>>,
+}
+
+// One row per missing name, with what to use instead.
+fn remap_rows(
+    ui: &mut egui::Ui,
+    keep: &str,
+    id: &str,
+    options: &[String],
+    rows: &mut BTreeMap<String, Option<String>>,
+) {
+    for (name, choice) in rows {
+        let label = ui.label(name.as_str());
+        egui::ComboBox::from_id_source((id, name.as_str()))
+            .selected_text(choice.clone().unwrap_or_else(|| keep.to_string()))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(choice, None, keep);
+                for option in options {
+                    ui.selectable_value(choice, Some(option.clone()), option);
+                }
+            })
+            .response
+            .labelled_by(label.id);
+        ui.end_row();
+    }
+}
+
+impl App {
+    fn export_template(&mut self) {
+        let connection = (!self.connection.addr.is_empty())
+            .then(|| (self.connection.addr.clone(), self.connection.port.clone()));
+        let template = ShowTemplate {
+            name: self.templates.name.trim().to_string(),
+            macros: self.macros.clone(),
+            rundown: self.rundown.cues.clone(),
+            audio_presets: self
+                .config_audio_presets
+                .iter()
+                .chain(&self.show_audio_presets)
+                .cloned()
+                .collect(),
+            ducking: self.ducking.clone(),
+            connection,
+        };
+        match template.save() {
+            Ok(path) => self.toasts.notice(self.i18n.tr_args(
+                "templates-exported",
+                &fluent_args!["path" => path.display().to_string()],
+            )),
+            Err(err) => self.toasts.push(format!("{:#}", err)),
+        }
+    }
+
+    fn open_template(&mut self, name: &str) {
+        let mut template = match ShowTemplate::load(name) {
+            Ok(template) => template,
+            Err(err) => {
+                self.toasts.push(format!("{:#}", err));
+                return;
+            }
+        };
+        let inputs: Vec<String> = self
+            .input_info
+            .iter()
+            .map(|input| input.name.clone())
+            .collect();
+        let missing = template.missing(&self.obs_state.scenes, &inputs);
+        if missing.is_empty() {
+            self.install_template(template);
+            return;
+        }
+        let none = |names: BTreeSet<String>| -> BTreeMap<String, Option<String>> {
+            names.into_iter().map(|name| (name, None)).collect()
+        };
+        self.templates.pending = Some(Pending {
+            template,
+            scenes: none(missing.scenes),
+            sources: none(missing.sources),
+        });
+    }
+
+    // Replaces this show's macros, rundown, presets and ducking with the
+    // template's. Kiosk PINs, layouts and the like stay as they are.
+    fn install_template(&mut self, template: ShowTemplate) {
+        tracing::info!(name = template.name, "show template opened");
+        self.toasts.notice(self.i18n.tr_args(
+            "templates-opened",
+            &fluent_args!["name" => template.name.as_str()],
+        ));
+        self.macros = template.macros;
+        self.rundown.replace(template.rundown);
+        self.show_audio_presets = template.audio_presets;
+        self.sync_audio_presets();
+        let old = std::mem::replace(&mut self.ducking, template.ducking);
+        for pair in old {
+            if !self.ducking.iter().any(|new| new.ducked == pair.ducked) {
+                self.send(Action::StopDucking(pair.ducked));
+            }
+        }
+        self.send(Action::SetDuckingPairs(self.ducking.clone()));
+        for pair in self.ducking.clone() {
+            self.send(Action::SetDucking(pair));
+        }
+        // Never switches an instance that's already connected.
+        if let (Some((addr, port)), false) = (template.connection, self.logged_in) {
+            self.connection.addr = addr;
+            self.connection.port = port;
+        }
+    }
+
+    pub(super) fn show_templates(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("templates-title"));
+        ui.weak(self.i18n.tr("templates-hint"));
+        let mut export = false;
+        let mut open = None;
+        ui.horizontal(|ui| {
+            let hint = self.i18n.tr("templates-name");
+            let name = ui.add(
+                egui::TextEdit::singleline(&mut self.templates.name)
+                    .hint_text(hint.clone())
+                    .desired_width(160.0),
+            );
+            a11y::name(ui, &name, hint);
+            export = ui
+                .add_enabled(
+                    !self.templates.name.trim().is_empty(),
+                    egui::Button::new(self.i18n.tr("templates-export")),
+                )
+                .clicked();
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(self.i18n.tr("templates-saved"));
+            let chosen = &mut self.templates.chosen;
+            egui::ComboBox::from_id_source("show_template")
+                .selected_text(chosen.clone().unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    // Listed only while open, so new files show up.
+                    for name in template::list() {
+                        ui.selectable_value(chosen, Some(name.clone()), name);
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+            if ui
+                .add_enabled(
+                    chosen.is_some() && self.templates.pending.is_none(),
+                    egui::Button::new(self.i18n.tr("templates-open")),
+                )
+                .on_hover_text(self.i18n.tr("templates-open-hint"))
+                .clicked()
+            {
+                open = chosen.clone();
+            }
+        });
+        if export {
+            self.export_template();
+        }
+        if let Some(name) = open {
+            self.open_template(&name);
+        }
+    }
+
+    pub(super) fn show_template_remap(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &mut self.templates.pending else {
+            return;
+        };
+        let inputs: Vec<String> = self
+            .input_info
+            .iter()
+            .map(|input| input.name.clone())
+            .collect();
+        let keep = self.i18n.tr("templates-keep");
+        let mut done = None;
+        egui::Window::new(self.i18n.tr_args(
+            "templates-remap-title",
+            &fluent_args!["name" => pending.template.name.as_str()],
+        ))
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(self.i18n.tr("templates-remap-hint"));
+            egui::Grid::new("template_remap")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    if !pending.scenes.is_empty() {
+                        ui.strong(self.i18n.tr("templates-scenes"));
+                        ui.end_row();
+                        remap_rows(
+                            ui,
+                            &keep,
+                            "remap_scene",
+                            &self.obs_state.scenes,
+                            &mut pending.scenes,
+                        );
+                    }
+                    if !pending.sources.is_empty() {
+                        ui.strong(self.i18n.tr("templates-sources"));
+                        ui.end_row();
+                        remap_rows(ui, &keep, "remap_source", &inputs, &mut pending.sources);
+                    }
+                });
+            ui.horizontal(|ui| {
+                if ui.button(self.i18n.tr("templates-apply")).clicked() {
+                    done = Some(true);
+                }
+                if ui.button(self.i18n.tr("confirm-cancel")).clicked() {
+                    done = Some(false);
+                }
+            });
+        });
+        let Some(apply) = done else {
+            return;
+        };
+        let Some(pending) = self.templates.pending.take() else {
+            return;
+        };
+        if !apply {
+            return;
+        }
+        let chosen = |rows: BTreeMap<String, Option<String>>| -> BTreeMap<String, String> {
+            rows.into_iter()
+                .filter_map(|(name, choice)| Some((name, choice?)))
+                .collect()
+        };
+        let mut template = pending.template;
+        template.remap(&chosen(pending.scenes), &chosen(pending.sources));
+        self.install_template(template);
+    }
+}
@@ -0,0 +1,323 @@
+use super::App;
+use crate::{config::CountdownSettings, i18n::I18n, obs_worker::Action};
+use fluent::fluent_args;
+use std::time::{Duration, Instant};
+
+pub(super) struct Countdown {
+    pub(super) settings: CountdownSettings,
+    // What was left when last paused or reset.
+    left: Duration,
+    started: Option<Instant>,
+    // Whole seconds last written to the text source.
+    shown: Option<u64>,
+}
+
+impl Countdown {
+    pub(super) fn new(settings: CountdownSettings) -> Self {
+        Self {
+            left: Duration::from_secs(settings.minutes as u64 * 60),
+            settings,
+            started: None,
+            shown: None,
+        }
+    }
+
+    fn remaining(&self) -> Duration {
+        match self.started {
+            Some(started) => self.left.saturating_sub(started.elapsed()),
+            None => self.left,
+        }
+    }
+
+    pub(super) fn start_for(&mut self, duration: Duration) {
+        self.left = duration;
+        self.started = Some(Instant::now());
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.left = Duration::from_secs(self.settings.minutes as u64 * 60);
+        self.started = None;
+    }
+
+    fn pause(&mut self) {
+        self.left = self.remaining();
+        self.started = None;
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Stopwatch {
+    pub(super) input: Option<String>,
+    // Time counted before the last start.
+    before: Duration,
+    started: Option<Instant>,
+    // Total time at each lap.
+    laps: Vec<Duration>,
+    shown: Option<u64>,
+}
+
+impl Stopwatch {
+    fn elapsed(&self) -> Duration {
+        self.before
+            + self
+                .started
+                .map_or(Duration::ZERO, |started| started.elapsed())
+    }
+
+    // One line per segment, for pasting into show notes or a split file.
+    fn report(&self, i18n: &I18n) -> String {
+        let mut previous = Duration::ZERO;
+        let mut report = String::new();
+        for (index, lap) in self.laps.iter().enumerate() {
+            report += &i18n.tr_args(
+                "stopwatch-report-line",
+                &fluent_args![
+                    "segment" => index + 1,
+                    "length" => format_clock((*lap - previous).as_secs()),
+                    "total" => format_clock(lap.as_secs())
+                ],
+            );
+            report.push('\n');
+            previous = *lap;
+        }
+        report
+    }
+}
+
+// Rounded up, so the text reads 0:00 only once time is really up.
+fn whole_seconds(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+// Hours only when there are any, as it reads on screen.
+pub(super) fn format_clock(seconds: u64) -> String {
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+impl App {
+    // Runs every frame whether or not the panel is open, so the text source
+    // keeps counting with the panel hidden.
+    pub(super) fn tick_countdown(&mut self, ctx: &egui::Context) {
+        let remaining = self.countdown.remaining();
+        let seconds = whole_seconds(remaining);
+        if self.countdown.shown != Some(seconds) {
+            self.countdown.shown = Some(seconds);
+            if let Some(input) = &self.countdown.settings.input {
+                self.send(Action::SetText(input.clone(), format_clock(seconds)));
+            }
+        }
+        if self.countdown.started.is_none() {
+            return;
+        }
+        if remaining.is_zero() {
+            self.countdown.pause();
+            tracing::info!("countdown finished");
+            self.toasts.notice(self.i18n.tr("countdown-finished"));
+            let then = self.countdown.settings.then.clone();
+            if let Some(obs_macro) = self
+                .all_macros()
+                .into_iter()
+                .find(|obs_macro| Some(&obs_macro.name) == then.as_ref())
+            {
+                self.send(Action::RunMacro(obs_macro));
+            }
+            return;
+        }
+        ctx.request_repaint_after(remaining - Duration::from_secs(seconds - 1));
+    }
+
+    pub(super) fn tick_stopwatch(&mut self, ctx: &egui::Context) {
+        let seconds = self.stopwatch.elapsed().as_secs();
+        if self.stopwatch.shown != Some(seconds) {
+            self.stopwatch.shown = Some(seconds);
+            if let Some(input) = &self.stopwatch.input {
+                self.send(Action::SetText(input.clone(), format_clock(seconds)));
+            }
+        }
+        if self.stopwatch.started.is_some() {
+            let elapsed = self.stopwatch.elapsed();
+            ctx.request_repaint_after(Duration::from_secs(seconds + 1) - elapsed);
+        }
+    }
+
+    pub(super) fn show_timers(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("countdown"));
+        self.show_countdown(ui);
+        ui.separator();
+        ui.strong(self.i18n.tr("stopwatch"));
+        self.show_stopwatch(ui);
+    }
+
+    pub(super) fn text_inputs(&self) -> Vec<String> {
+        self.input_info
+            .iter()
+            .filter(|input| input.kind.starts_with("text_"))
+            .map(|input| input.name.clone())
+            .collect()
+    }
+
+    fn show_stopwatch(&mut self, ui: &mut egui::Ui) {
+        let elapsed = self.stopwatch.elapsed();
+        ui.heading(format_clock(elapsed.as_secs()));
+        ui.horizontal(|ui| {
+            let stopwatch = &mut self.stopwatch;
+            if stopwatch.started.is_some() {
+                if ui.button(self.i18n.tr("countdown-pause")).clicked() {
+                    stopwatch.before = elapsed;
+                    stopwatch.started = None;
+                }
+                if ui.button(self.i18n.tr("stopwatch-lap")).clicked() {
+                    stopwatch.laps.push(elapsed);
+                }
+            } else if ui.button(self.i18n.tr("countdown-start")).clicked() {
+                stopwatch.started = Some(Instant::now());
+            }
+            if ui.button(self.i18n.tr("countdown-reset")).clicked() {
+                *stopwatch = Stopwatch {
+                    input: stopwatch.input.take(),
+                    ..Stopwatch::default()
+                };
+            }
+            if ui
+                .add_enabled(
+                    !stopwatch.laps.is_empty(),
+                    egui::Button::new(self.i18n.tr("stopwatch-copy")),
+                )
+                .clicked()
+            {
+                ui.output_mut(|output| output.copied_text = stopwatch.report(&self.i18n));
+            }
+        });
+
+        if !self.stopwatch.laps.is_empty() {
+            egui::Grid::new("laps")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    let mut previous = Duration::ZERO;
+                    for (index, lap) in self.stopwatch.laps.iter().enumerate() {
+                        ui.label(format!("{}", index + 1));
+                        ui.monospace(format_clock((*lap - previous).as_secs()));
+                        ui.weak(format_clock(lap.as_secs()));
+                        previous = *lap;
+                        ui.end_row();
+                    }
+                    ui.label(self.i18n.tr("stopwatch-current"));
+                    ui.monospace(format_clock((elapsed - previous).as_secs()));
+                    ui.end_row();
+                });
+        }
+
+        let text_inputs = self.text_inputs();
+        ui.horizontal(|ui| {
+            if picker(
+                ui,
+                &self.i18n,
+                "stopwatch-text-source",
+                &text_inputs,
+                &mut self.stopwatch.input,
+            ) {
+                self.stopwatch.shown = None;
+            }
+        });
+    }
+
+    fn show_countdown(&mut self, ui: &mut egui::Ui) {
+        let remaining = self.countdown.remaining();
+        ui.heading(format_clock(whole_seconds(remaining)));
+        ui.horizontal(|ui| {
+            let running = self.countdown.started.is_some();
+            let label = ui.label(self.i18n.tr("countdown-minutes"));
+            let minutes = ui
+                .add_enabled(
+                    !running,
+                    egui::DragValue::new(&mut self.countdown.settings.minutes).clamp_range(0..=600),
+                )
+                .labelled_by(label.id);
+            if minutes.changed() {
+                self.countdown.reset();
+            }
+            if running {
+                if ui.button(self.i18n.tr("countdown-pause")).clicked() {
+                    self.countdown.pause();
+                }
+            } else if ui
+                .add_enabled(
+                    !remaining.is_zero(),
+                    egui::Button::new(self.i18n.tr("countdown-start")),
+                )
+                .clicked()
+            {
+                self.countdown.started = Some(Instant::now());
+            }
+            if ui.button(self.i18n.tr("countdown-reset")).clicked() {
+                self.countdown.reset();
+            }
+        });
+
+        let text_inputs = self.text_inputs();
+        let macros: Vec<String> = self
+            .all_macros()
+            .into_iter()
+            .map(|obs_macro| obs_macro.name)
+            .collect();
+        let settings = &mut self.countdown.settings;
+        let mut input_changed = false;
+        egui::Grid::new("countdown").num_columns(2).show(ui, |ui| {
+            input_changed = picker(
+                ui,
+                &self.i18n,
+                "countdown-text-source",
+                &text_inputs,
+                &mut settings.input,
+            );
+            ui.end_row();
+            picker(
+                ui,
+                &self.i18n,
+                "countdown-then",
+                &macros,
+                &mut settings.then,
+            );
+            ui.end_row();
+        });
+        if input_changed {
+            self.countdown.shown = None;
+        }
+        if let Some(then) = &self.countdown.settings.then {
+            ui.weak(self.i18n.tr_args(
+                "countdown-then-hint",
+                &fluent_args!["macro" => then.as_str()],
+            ));
+        }
+    }
+}
+
+// A labelled combo box of names, with "None" first.
+pub(super) fn picker(
+    ui: &mut egui::Ui,
+    i18n: &I18n,
+    id: &str,
+    options: &[String],
+    selected: &mut Option<String>,
+) -> bool {
+    let none = i18n.tr("countdown-none");
+    let label = ui.label(i18n.tr(id));
+    let mut changed = false;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected.clone().unwrap_or_else(|| none.clone()))
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(selected, None, &none).changed();
+            for option in options {
+                changed |= ui
+                    .selectable_value(selected, Some(option.clone()), option)
+                    .changed();
+            }
+        })
+        .response
+        .labelled_by(label.id);
+    changed
+}
@@ -0,0 +1,173 @@
+use super::{a11y, App};
+use crate::{i18n::I18n, obs_worker::Notice};
+use chrono::{DateTime, Local};
+use fluent::fluent_args;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+const NOTICE_DURATION: Duration = Duration::from_secs(6);
+const HISTORY_CAPACITY: usize = 200;
+
+struct Toast {
+    message: String,
+    error: bool,
+    shown: Instant,
+}
+
+// Errors stay up until dismissed; notices fade on their own. Both end up in
+// the history drawer.
+#[derive(Default)]
+pub(super) struct Toasts {
+    active: Vec<Toast>,
+    history: VecDeque<(DateTime<Local>, String, bool)>,
+    drawer_open: bool,
+}
+
+impl Toasts {
+    pub(super) fn push(&mut self, message: String) {
+        self.add(message, true);
+    }
+
+    pub(super) fn notice(&mut self, message: String) {
+        self.add(message, false);
+    }
+
+    fn add(&mut self, message: String, error: bool) {
+        self.history
+            .push_back((Local::now(), message.clone(), error));
+        if self.history.len() > HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.active.push(Toast {
+            message,
+            error,
+            shown: Instant::now(),
+        });
+    }
+
+    pub(super) fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub(super) fn drawer_open(&self) -> bool {
+        self.drawer_open
+    }
+
+    pub(super) fn toggle_drawer(&mut self) {
+        self.drawer_open = !self.drawer_open;
+    }
+}
+
+pub(super) fn notice_text(i18n: &I18n, notice: &Notice) -> String {
+    match notice {
+        Notice::RecordingStarted(Some(path)) => i18n.tr_args(
+            "notice-recording-started-path",
+            &fluent_args!["path" => path.as_str()],
+        ),
+        Notice::RecordingStarted(None) => i18n.tr("notice-recording-started"),
+        Notice::RecordingStopped(Some(path)) => i18n.tr_args(
+            "notice-recording-saved",
+            &fluent_args!["path" => path.as_str()],
+        ),
+        Notice::RecordingStopped(None) => i18n.tr("notice-recording-stopped"),
+        Notice::ReplaySaved(path) => i18n.tr_args(
+            "notice-replay-saved",
+            &fluent_args!["path" => path.as_str()],
+        ),
+        Notice::StreamReconnecting => i18n.tr("notice-stream-reconnecting"),
+        Notice::StreamReconnected => i18n.tr("notice-stream-reconnected"),
+        Notice::InputAdded(name) => i18n.tr_args(
+            "notice-input-added",
+            &fluent_args!["input" => name.as_str()],
+        ),
+        Notice::DroppedFrames(dropped) => i18n.tr_args(
+            "notice-dropped-frames",
+            &fluent_args!["percent" => format!("{:.1}", dropped)],
+        ),
+        Notice::GateCalibrated(input, open, close) => i18n.tr_args(
+            "notice-gate-calibrated",
+            &fluent_args![
+                "input" => input.as_str(),
+                "open" => format!("{:.1}", open),
+                "close" => format!("{:.1}", close)
+            ],
+        ),
+        Notice::VoiceChainAdded(input, added) => i18n.tr_args(
+            "notice-voice-chain",
+            &fluent_args!["input" => input.as_str(), "added" => *added],
+        ),
+        Notice::RehearsalBlocked => i18n.tr("notice-rehearsal-blocked"),
+        Notice::FeatureDisabled(feature) => i18n.tr_args(
+            "notice-feature-disabled",
+            &fluent_args!["feature" => i18n.tr(feature.message_id())],
+        ),
+    }
+}
+
+impl App {
+    pub(super) fn show_toasts(&mut self, ctx: &egui::Context) {
+        let toasts = &mut self.toasts;
+        toasts
+            .active
+            .retain(|toast| toast.error || toast.shown.elapsed() < NOTICE_DURATION);
+        if let Some(remaining) = toasts
+            .active
+            .iter()
+            .filter(|toast| !toast.error)
+            .map(|toast| NOTICE_DURATION.saturating_sub(toast.shown.elapsed()))
+            .min()
+        {
+            ctx.request_repaint_after(remaining);
+        }
+        egui::Area::new("toasts")
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-8.0, -8.0])
+            .show(ctx, |ui| {
+                toasts.active.retain(|toast| {
+                    let mut keep = true;
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            if toast.error {
+                                ui.colored_label(ui.visuals().error_fg_color, &toast.message);
+                            } else {
+                                ui.label(&toast.message);
+                            }
+                            let dismiss = ui.small_button("✖");
+                            a11y::name(ui, &dismiss, self.i18n.tr("a11y-dismiss"));
+                            if dismiss.clicked() {
+                                keep = false;
+                            }
+                        });
+                    });
+                    keep
+                });
+            });
+    }
+
+    pub(super) fn show_notification_drawer(&mut self, ctx: &egui::Context) {
+        if !self.toasts.drawer_open {
+            return;
+        }
+        egui::SidePanel::right("notifications").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(self.i18n.tr("notifications-title"));
+                if ui.small_button(self.i18n.tr("log-clear")).clicked() {
+                    self.toasts.history.clear();
+                }
+            });
+            egui::ScrollArea::vertical()
+                .drag_to_scroll(self.touch_mode)
+                .show(ui, |ui| {
+                    for (time, message, error) in self.toasts.history.iter().rev() {
+                        let text = format!("{} {}", time.format("%H:%M:%S"), message);
+                        if *error {
+                            ui.colored_label(ui.visuals().error_fg_color, text);
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                });
+        });
+    }
+}
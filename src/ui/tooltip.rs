@@ -0,0 +1,75 @@
+use crate::i18n::I18n;
+use fluent::fluent_args;
+use obws::responses::inputs::Input;
+
+const LONG_PRESS_SECS: f64 = 0.5;
+
+// Touchscreens never hover, so holding a control down shows its tooltip too.
+pub(super) fn rich_tooltip(
+    ui: &egui::Ui,
+    response: egui::Response,
+    text: impl Into<String>,
+) -> egui::Response {
+    let text = text.into();
+    if response.is_pointer_button_down_on() {
+        let held = ui.input(|i| {
+            i.pointer
+                .press_start_time()
+                .map_or(0.0, |start| i.time - start)
+        });
+        if held >= LONG_PRESS_SECS {
+            egui::show_tooltip_text(ui.ctx(), response.id.with("long_press"), text.clone());
+        } else {
+            ui.ctx().request_repaint();
+        }
+    }
+    response.on_hover_text(text)
+}
+
+// A hint for a control that normally only shows on hover. In touch mode a "?"
+// button next to the control shows it too.
+pub(super) fn hint(
+    ui: &mut egui::Ui,
+    touch_mode: bool,
+    response: egui::Response,
+    text: impl Into<String>,
+) -> egui::Response {
+    let text = text.into();
+    if touch_mode {
+        let button = ui.small_button("?");
+        let popup = response.id.with("hint");
+        if button.clicked() {
+            ui.memory_mut(|memory| memory.toggle_popup(popup));
+        }
+        egui::popup_below_widget(ui, popup, &button, |ui| {
+            ui.set_min_width(200.0);
+            ui.label(text.clone());
+        });
+    }
+    response.on_hover_text(text)
+}
+
+pub(super) fn input_text(i18n: &I18n, input: &Input) -> String {
+    i18n.tr_args(
+        "tooltip-input",
+        &fluent_args![
+            "name" => input.name.as_str(),
+            "kind" => input.kind.as_str(),
+            "unversioned" => input.unversioned_kind.as_str()
+        ],
+    )
+}
+
+// Volumes are kept as OBS's multiplier times 100.
+pub(super) fn volume_text(i18n: &I18n, volume: f32) -> String {
+    let mul = volume / 100.0;
+    let db = if mul > 0.0 {
+        format!("{:.1}", 20.0 * mul.log10())
+    } else {
+        "-inf".to_string()
+    };
+    i18n.tr_args(
+        "tooltip-volume",
+        &fluent_args!["db" => db, "mul" => format!("{:.3}", mul)],
+    )
+}
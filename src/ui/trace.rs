@@ -0,0 +1,293 @@
+use super::App;
+use crate::{
+    obs_worker::{Action, ObsInfo},
+    state::ObsState,
+    trace::{self, Recorder, TraceEntry, Traced},
+};
+use fluent::fluent_args;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+// Longest an entry is shown in the list before it's cut off.
+const ENTRY_CHARS: usize = 160;
+
+#[derive(Default)]
+pub(super) struct Trace {
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
+    chosen: Option<PathBuf>,
+}
+
+struct Replay {
+    name: String,
+    entries: Vec<TraceEntry>,
+    // The next entry to apply.
+    cursor: usize,
+    // When playing, the moment it started and the trace time it started at.
+    playing: Option<(Instant, u64)>,
+}
+
+impl Trace {
+    pub(super) fn replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+}
+
+fn describe(entry: &TraceEntry) -> String {
+    let mut text = serde_json::to_string(&entry.traced).unwrap_or_default();
+    if let Some((cut, _)) = text.char_indices().nth(ENTRY_CHARS) {
+        text.truncate(cut);
+        text.push('…');
+    }
+    text
+}
+
+impl App {
+    pub(super) fn trace_action(&mut self, action: &Action) {
+        if let Some(recorder) = &mut self.trace.recorder {
+            recorder.action(action);
+        }
+    }
+
+    pub(super) fn trace_info(&mut self, info: &ObsInfo) {
+        if let Some(recorder) = &mut self.trace.recorder {
+            recorder.info(info);
+        }
+    }
+
+    fn toggle_trace_recording(&mut self) {
+        if let Some(recorder) = self.trace.recorder.take() {
+            self.toasts.notice(self.i18n.tr_args(
+                "trace-saved",
+                &fluent_args!["path" => recorder.path.display().to_string()],
+            ));
+            return;
+        }
+        match Recorder::start() {
+            Ok(recorder) => self.trace.recorder = Some(recorder),
+            Err(err) => self.toasts.push(format!("{:#}", err)),
+        }
+    }
+
+    // Stands in for OBS: the UI behaves as if connected, and nothing it sends
+    // goes anywhere until the replay stops.
+    fn start_replay(&mut self, path: PathBuf) {
+        let entries = match trace::load(&path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.toasts.push(format!("{:#}", err));
+                return;
+            }
+        };
+        tracing::info!(path = %path.display(), "replaying trace");
+        self.trace.replay = Some(Replay {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            entries,
+            cursor: 0,
+            playing: None,
+        });
+        self.obs_state = ObsState::default();
+        self.input_info.clear();
+        self.logged_in = true;
+    }
+
+    fn stop_replay(&mut self) {
+        self.trace.replay = None;
+        self.handle_obs_info(ObsInfo::Disconnected);
+        tracing::info!("trace replay stopped");
+    }
+
+    fn step_replay(&mut self) {
+        let Some(replay) = &mut self.trace.replay else {
+            return;
+        };
+        let Some(entry) = replay.entries.get(replay.cursor).cloned() else {
+            replay.playing = None;
+            return;
+        };
+        replay.cursor += 1;
+        if let Traced::Info(info) = entry.traced {
+            self.handle_obs_info(info.into_info());
+            // A disconnect in the trace shouldn't drop back to the login screen
+            // and hide the replay controls.
+            self.logged_in = true;
+        }
+    }
+
+    // Going back rebuilds OBS's state from the start without the toasts and
+    // side effects of stepping, which already happened once.
+    fn rewind_replay(&mut self, to: usize) {
+        let Some(replay) = &mut self.trace.replay else {
+            return;
+        };
+        replay.cursor = to;
+        replay.playing = None;
+        let mut state = ObsState::default();
+        let mut inputs = Vec::new();
+        for entry in &replay.entries[..to] {
+            if let Traced::Info(info) = &entry.traced {
+                let info = info.clone().into_info();
+                if let ObsInfo::InputInfo(input_info) = &info {
+                    inputs = input_info.clone();
+                }
+                state.apply(&info);
+            }
+        }
+        self.obs_state = state;
+        self.input_info = inputs;
+    }
+
+    // Plays at the speed it was recorded.
+    pub(super) fn tick_replay(&mut self, ctx: &egui::Context) {
+        let Some((started, from)) = self.trace.replay.as_ref().and_then(|r| r.playing) else {
+            return;
+        };
+        let now = from + started.elapsed().as_millis() as u64;
+        loop {
+            let next = self
+                .trace
+                .replay
+                .as_ref()
+                .and_then(|replay| replay.entries.get(replay.cursor))
+                .map(|entry| entry.at_ms);
+            match next {
+                Some(at_ms) if at_ms <= now => self.step_replay(),
+                Some(at_ms) => {
+                    ctx.request_repaint_after(Duration::from_millis(at_ms - now));
+                    return;
+                }
+                None => {
+                    self.step_replay();
+                    return;
+                }
+            }
+        }
+    }
+
+    // On the login screen, since a replay needs no connection.
+    pub(super) fn show_trace_picker(&mut self, ui: &mut egui::Ui) {
+        let mut open = None;
+        ui.collapsing(self.i18n.tr("trace-replay-title"), |ui| {
+            ui.weak(self.i18n.tr("trace-replay-hint"));
+            ui.horizontal(|ui| {
+                let chosen = &mut self.trace.chosen;
+                let name = |path: &PathBuf| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default()
+                };
+                egui::ComboBox::from_id_source("trace_file")
+                    .selected_text(chosen.as_ref().map(name).unwrap_or_default())
+                    .show_ui(ui, |ui| {
+                        for path in trace::list() {
+                            let text = name(&path);
+                            ui.selectable_value(chosen, Some(path), text);
+                        }
+                    });
+                if ui
+                    .add_enabled(
+                        chosen.is_some(),
+                        egui::Button::new(self.i18n.tr("trace-replay")),
+                    )
+                    .clicked()
+                {
+                    open = chosen.clone();
+                }
+            });
+        });
+        if let Some(path) = open {
+            self.start_replay(path);
+        }
+    }
+
+    pub(super) fn show_trace(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("trace-title"));
+        if !self.trace.replaying() {
+            let recording = self.trace.recorder.is_some();
+            if ui
+                .selectable_label(recording, format!("⏺ {}", self.i18n.tr("trace-record")))
+                .on_hover_text(self.i18n.tr("trace-record-hint"))
+                .clicked()
+            {
+                self.toggle_trace_recording();
+            }
+            return;
+        }
+        let mut step = false;
+        let mut rewind = None;
+        let mut stop = false;
+        if let Some(replay) = &mut self.trace.replay {
+            ui.label(self.i18n.tr_args(
+                "trace-replaying",
+                &fluent_args![
+                    "name" => replay.name.as_str(),
+                    "at" => replay.cursor,
+                    "count" => replay.entries.len()
+                ],
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("⏮").clicked() {
+                    rewind = Some(0);
+                }
+                if ui
+                    .add_enabled(replay.cursor > 0, egui::Button::new("◀"))
+                    .clicked()
+                {
+                    rewind = Some(replay.cursor - 1);
+                }
+                let playing = replay.playing.is_some();
+                if ui.button(if playing { "⏸" } else { "▶" }).clicked() {
+                    replay.playing = if playing {
+                        None
+                    } else {
+                        let at = replay.entries.get(replay.cursor).map_or(0, |e| e.at_ms);
+                        Some((Instant::now(), at))
+                    };
+                }
+                step = ui
+                    .add_enabled(
+                        replay.cursor < replay.entries.len(),
+                        egui::Button::new("▶|"),
+                    )
+                    .clicked();
+                stop = ui.button(self.i18n.tr("trace-stop")).clicked();
+            });
+            egui::ScrollArea::vertical()
+                .drag_to_scroll(self.touch_mode)
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (index, entry) in replay.entries.iter().enumerate() {
+                        let text = format!("{:>8} ms  {}", entry.at_ms, describe(entry));
+                        let label = egui::RichText::new(text).monospace();
+                        let label = match entry.traced {
+                            Traced::Action(_) => label.weak(),
+                            Traced::Info(_) => label,
+                        };
+                        let response = ui.selectable_label(index + 1 == replay.cursor, label);
+                        if response.clicked() {
+                            rewind = Some(index + 1);
+                        }
+                    }
+                });
+        }
+        if stop {
+            self.stop_replay();
+        } else if let Some(to) = rewind {
+            let cursor = self.trace.replay.as_ref().map_or(0, |replay| replay.cursor);
+            if to < cursor {
+                self.rewind_replay(to);
+            } else {
+                for _ in cursor..to {
+                    self.step_replay();
+                }
+            }
+        } else if step {
+            self.step_replay();
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::App;
+use crate::{
+    obs_worker::Action,
+    tray::{TrayCommand, TrayStatus},
+};
+
+impl App {
+    pub(super) fn set_window_hidden(&mut self, ctx: &egui::Context, hidden: bool) {
+        self.window_hidden = hidden;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(!hidden));
+        if !hidden {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+    }
+
+    pub(super) fn handle_tray(&mut self, ctx: &egui::Context) {
+        let Some(tray) = &mut self.tray else {
+            return;
+        };
+        let mic_muted = self
+            .mic_input_name
+            .as_ref()
+            .and_then(|name| self.obs_state.muted.get(name))
+            .copied()
+            .unwrap_or(false);
+        let i18n = &self.i18n;
+        let tooltip = match (self.obs_state.streaming, self.obs_state.recording) {
+            (true, true) => i18n.tr("tray-live-recording"),
+            (true, false) => i18n.tr("tray-live"),
+            (false, true) => i18n.tr("tray-recording"),
+            (false, false) => "REC".to_string(),
+        };
+        tray.update(TrayStatus {
+            streaming: self.obs_state.streaming,
+            recording: self.obs_state.recording,
+            tooltip,
+            mute_label: i18n.tr(if mic_muted {
+                "tray-unmute-mic"
+            } else {
+                "tray-mute-mic"
+            }),
+            record_label: i18n.tr(if self.obs_state.recording {
+                "tray-stop-recording"
+            } else {
+                "tray-start-recording"
+            }),
+            window_label: i18n.tr("tray-window"),
+            quit_label: i18n.tr("tray-quit"),
+        });
+
+        for command in tray.commands() {
+            match command {
+                TrayCommand::ToggleMute => self.toggle_mute(self.mic_input_name.clone()),
+                TrayCommand::ToggleRecord => self.send_confirmed(if self.obs_state.recording {
+                    Action::StopRecord
+                } else {
+                    Action::StartRecord
+                }),
+                TrayCommand::ToggleWindow if !self.kiosk() => {
+                    self.set_window_hidden(ctx, !self.window_hidden)
+                }
+                TrayCommand::ToggleWindow => {}
+                TrayCommand::Quit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            }
+        }
+
+        if self.minimize_to_tray
+            && !self.window_hidden
+            && ctx.input(|i| i.viewport().minimized) == Some(true)
+        {
+            self.set_window_hidden(ctx, true);
+        }
+    }
+}
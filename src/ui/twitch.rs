@@ -0,0 +1,250 @@
+use super::{a11y, status_bar, App};
+use crate::{
+    chat::ChatConfig,
+    obs_worker::{Action, Notifier},
+    twitch::{self, Auth, ChannelInfo, Request, TwitchConfig, TwitchInfo},
+};
+use chrono::{DateTime, Local};
+use fluent::fluent_args;
+use std::sync::mpsc;
+
+pub(super) struct Twitch {
+    request_tx: mpsc::Sender<Request>,
+    info_rx: mpsc::Receiver<TwitchInfo>,
+    pub(super) config: TwitchConfig,
+    pub(super) auth: Option<Auth>,
+    device_code: Option<(String, String)>,
+    channel: ChannelInfo,
+    categories: Vec<String>,
+    clips: Vec<(DateTime<Local>, String)>,
+}
+
+impl Twitch {
+    pub(super) fn new(notify: Notifier, auth: Option<Auth>) -> Self {
+        let (request_tx, info_rx) = twitch::spawn(notify);
+        let twitch = Self {
+            request_tx,
+            info_rx,
+            config: TwitchConfig::default(),
+            auth,
+            device_code: None,
+            channel: ChannelInfo::default(),
+            categories: Vec::new(),
+            clips: Vec::new(),
+        };
+        if let Some(auth) = &twitch.auth {
+            twitch.request(Request::Restore(auth.clone()));
+        }
+        twitch
+    }
+
+    fn request(&self, request: Request) {
+        if self.request_tx.send(request).is_err() {
+            tracing::warn!("twitch thread is gone");
+        }
+    }
+
+    pub(super) fn scene_changed(&self, scene: &str) {
+        if self.auth.is_none() {
+            return;
+        }
+        if let Some(info) = self.config.scenes.get(scene) {
+            tracing::info!(scene, "updating twitch channel for scene");
+            self.request(Request::Update(info.clone()));
+        }
+    }
+
+    pub(super) fn logged_in(&self) -> bool {
+        self.auth.is_some()
+    }
+
+    pub(super) fn create_clip(&self) {
+        self.request(Request::Clip);
+    }
+
+    pub(super) fn post(&self, message: String) {
+        if self.logged_in() {
+            self.request(Request::Chat(message));
+        }
+    }
+
+    pub(super) fn add_marker(&self, description: String) {
+        if self.auth.is_some() {
+            self.request(Request::Marker(description));
+        }
+    }
+}
+
+impl App {
+    pub(super) fn handle_twitch(&mut self) {
+        while let Ok(info) = self.twitch.info_rx.try_recv() {
+            match info {
+                TwitchInfo::DeviceCode {
+                    user_code,
+                    verification_uri,
+                } => self.twitch.device_code = Some((user_code, verification_uri)),
+                TwitchInfo::LoggedIn(auth) => {
+                    self.twitch.auth = Some(auth);
+                    self.twitch.device_code = None;
+                    self.sync_chat();
+                }
+                TwitchInfo::LoggedOut => {
+                    self.twitch.auth = None;
+                    self.twitch.device_code = None;
+                    self.sync_chat();
+                }
+                TwitchInfo::Channel(channel) => self.twitch.channel = channel,
+                TwitchInfo::Categories(categories) => self.twitch.categories = categories,
+                TwitchInfo::Marker(position) => self.toasts.notice(self.i18n.tr_args(
+                    "notice-twitch-marker",
+                    &fluent_args!["position" => status_bar::format_duration(position)],
+                )),
+                TwitchInfo::Clip(url) => {
+                    self.toasts.notice(
+                        self.i18n
+                            .tr_args("notice-twitch-clip", &fluent_args!["url" => url.as_str()]),
+                    );
+                    self.twitch.clips.push((Local::now(), url));
+                }
+                TwitchInfo::Error(err) => {
+                    self.twitch.device_code = None;
+                    self.toasts.push(
+                        self.i18n
+                            .tr_args("toast-twitch-error", &fluent_args!["error" => err]),
+                    );
+                }
+            }
+        }
+    }
+
+    // Chat only needs to be joined while there are commands to listen for.
+    // A refreshed token restarts it too.
+    pub(super) fn sync_chat(&mut self) {
+        let config = match &self.twitch.auth {
+            Some(auth) if !self.twitch.config.commands.is_empty() => Some(ChatConfig {
+                login: auth.login.clone(),
+                token: auth.access_token.clone(),
+                commands: self.twitch.config.commands.clone(),
+            }),
+            _ => None,
+        };
+        self.sync_service("chat", config, Action::StartChat, Action::StopChat);
+    }
+
+    pub(super) fn show_twitch(&mut self, ui: &mut egui::Ui) {
+        let mut request = None;
+        self.show_twitch_channel(ui, &mut request);
+        if let Some(request) = request {
+            self.twitch.request(request);
+        }
+    }
+
+    fn show_twitch_channel(&mut self, ui: &mut egui::Ui, request: &mut Option<Request>) {
+        let twitch = &mut self.twitch;
+        let Some(client_id) = twitch.config.client_id.clone() else {
+            ui.weak(self.i18n.tr("twitch-no-client-id"));
+            return;
+        };
+        let Some(auth) = &twitch.auth else {
+            match &twitch.device_code {
+                Some((user_code, verification_uri)) => {
+                    ui.label(self.i18n.tr_args(
+                        "twitch-enter-code",
+                        &fluent_args!["code" => user_code.as_str()],
+                    ));
+                    ui.hyperlink(verification_uri);
+                    if ui.button(self.i18n.tr("confirm-cancel")).clicked() {
+                        *request = Some(Request::LogOut);
+                    }
+                }
+                None => {
+                    if ui.button(self.i18n.tr("twitch-log-in")).clicked() {
+                        *request = Some(Request::LogIn(client_id));
+                    }
+                }
+            }
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(self.i18n.tr_args(
+                "twitch-logged-in",
+                &fluent_args!["login" => auth.login.as_str()],
+            ));
+            if ui.button(self.i18n.tr("twitch-log-out")).clicked() {
+                *request = Some(Request::LogOut);
+            }
+        });
+        egui::Grid::new("twitch_channel")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let label = ui.label(self.i18n.tr("twitch-title"));
+                ui.add(egui::TextEdit::singleline(&mut twitch.channel.title).desired_width(240.0))
+                    .labelled_by(label.id);
+                ui.end_row();
+
+                let label = ui.label(self.i18n.tr("twitch-category"));
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut twitch.channel.category)
+                            .desired_width(160.0),
+                    )
+                    .labelled_by(label.id);
+                    let search = ui.button("🔍").on_hover_text(self.i18n.tr("twitch-search"));
+                    a11y::name(ui, &search, self.i18n.tr("twitch-search"));
+                    if search.clicked() && !twitch.channel.category.is_empty() {
+                        *request = Some(Request::Search(twitch.channel.category.clone()));
+                    }
+                });
+                ui.end_row();
+            });
+        if !twitch.categories.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                let mut picked = None;
+                for category in &twitch.categories {
+                    if ui.selectable_label(false, category).clicked() {
+                        picked = Some(category.clone());
+                    }
+                }
+                if let Some(category) = picked {
+                    twitch.channel.category = category;
+                    twitch.categories.clear();
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            if ui.button(self.i18n.tr("twitch-update")).clicked() {
+                *request = Some(Request::Update(twitch.channel.clone()));
+            }
+            if ui.button(self.i18n.tr("twitch-clip")).clicked() {
+                *request = Some(Request::Clip);
+            }
+        });
+        if !twitch.clips.is_empty() {
+            ui.separator();
+            ui.weak(self.i18n.tr("twitch-recent-clips"));
+            for (time, url) in twitch.clips.iter().rev() {
+                ui.horizontal(|ui| {
+                    ui.label(time.format("%H:%M:%S").to_string());
+                    ui.hyperlink(url);
+                });
+            }
+        }
+        if !twitch.config.commands.is_empty() {
+            ui.separator();
+            ui.weak(self.i18n.tr("twitch-chat-commands"));
+            ui.horizontal_wrapped(|ui| {
+                for command in &twitch.config.commands {
+                    ui.monospace(&command.command);
+                }
+            });
+        }
+        if !twitch.config.scenes.is_empty() {
+            ui.separator();
+            ui.weak(self.i18n.tr("twitch-scene-presets"));
+            for (scene, info) in &twitch.config.scenes {
+                ui.label(format!("{}: {} · {}", scene, info.title, info.category));
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::App;
+use crate::{
+    obs_worker::Notifier,
+    update::{self, Release},
+};
+use fluent::fluent_args;
+use std::sync::mpsc;
+
+#[derive(Default)]
+pub(super) struct Update {
+    release_rx: Option<mpsc::Receiver<Release>>,
+    available: Option<Release>,
+    dismissed: bool,
+}
+
+impl Update {
+    pub(super) fn check(&mut self, notify: Notifier) {
+        self.release_rx = Some(update::spawn(notify));
+    }
+}
+
+impl App {
+    // A strip across the top until dismissed; never in kiosk mode, where
+    // nobody at the screen could act on it.
+    pub(super) fn show_update_banner(&mut self, ctx: &egui::Context) {
+        if let Some(release) = self
+            .update
+            .release_rx
+            .as_ref()
+            .and_then(|release_rx| release_rx.try_recv().ok())
+        {
+            self.update.available = Some(release);
+        }
+        let Some(release) = &self.update.available else {
+            return;
+        };
+        if self.update.dismissed || self.locked() {
+            return;
+        }
+        let mut dismiss = false;
+        egui::TopBottomPanel::top("update_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(self.i18n.tr_args(
+                    "update-available",
+                    &fluent_args!["version" => release.tag_name.as_str()],
+                ));
+                ui.hyperlink_to(self.i18n.tr("update-download"), &release.html_url);
+                dismiss = ui.button(self.i18n.tr("update-dismiss")).clicked();
+            });
+            if let Some(body) = release
+                .body
+                .as_deref()
+                .filter(|body| !body.trim().is_empty())
+            {
+                ui.collapsing(self.i18n.tr("update-changelog"), |ui| {
+                    egui::ScrollArea::vertical()
+                        .drag_to_scroll(self.touch_mode)
+                        .max_height(200.0)
+                        .show(ui, |ui| ui.label(body));
+                });
+            }
+        });
+        if dismiss {
+            self.update.dismissed = true;
+        }
+    }
+}
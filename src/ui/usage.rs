@@ -0,0 +1,61 @@
+use super::App;
+use crate::usage::Usage;
+use fluent::fluent_args;
+
+impl App {
+    pub(super) fn show_usage_settings(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.i18n.tr("usage-title"));
+        let mut enabled = self.usage.is_some();
+        if ui
+            .checkbox(&mut enabled, self.i18n.tr("usage-enabled"))
+            .on_hover_text(self.i18n.tr("usage-hint"))
+            .changed()
+        {
+            if enabled {
+                tracing::info!("usage counting turned on");
+                self.usage = Some(Usage::load());
+            } else if let Some(mut usage) = self.usage.take() {
+                tracing::info!("usage counting turned off");
+                if let Err(err) = usage.clear() {
+                    self.toasts.push(format!("{:#}", err));
+                }
+            }
+        }
+        let Some(usage) = &mut self.usage else {
+            return;
+        };
+        ui.weak(self.i18n.tr("usage-local"));
+        let report = usage.report();
+        egui::CollapsingHeader::new(self.i18n.tr("usage-report"))
+            .id_source("usage_report")
+            .show(ui, |ui| {
+                ui.monospace(report.as_str());
+            });
+        let mut export = false;
+        let mut clear = false;
+        ui.horizontal(|ui| {
+            export = ui.button(self.i18n.tr("usage-export")).clicked();
+            if ui
+                .button(self.i18n.tr("usage-copy"))
+                .on_hover_text(self.i18n.tr("usage-copy-hint"))
+                .clicked()
+            {
+                ui.output_mut(|output| output.copied_text = report.clone());
+            }
+            clear = ui.button(self.i18n.tr("usage-clear")).clicked();
+        });
+        if export {
+            match usage.export() {
+                Ok(path) => self.toasts.notice(self.i18n.tr_args(
+                    "usage-exported",
+                    &fluent_args!["path" => path.display().to_string()],
+                )),
+                Err(err) => self.toasts.push(format!("{:#}", err)),
+            }
+        } else if clear {
+            if let Err(err) = usage.clear() {
+                self.toasts.push(format!("{:#}", err));
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use super::{a11y, App};
+use crate::layout::{self, Layout, Panel};
+use fluent::fluent_args;
+
+struct Tabs<'a> {
+    app: &'a mut App,
+}
+
+impl egui_dock::TabViewer for Tabs<'_> {
+    type Tab = Panel;
+
+    fn title(&mut self, panel: &mut Panel) -> egui::WidgetText {
+        self.app.i18n.tr(panel.message_id()).into()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, panel: &mut Panel) {
+        let panel = *panel;
+        if self.app.panel_locked(panel) {
+            ui.weak(self.app.i18n.tr(if self.app.kiosk() {
+                "kiosk-settings-locked"
+            } else {
+                "volunteer-locked"
+            }));
+            return;
+        }
+        if self.app.popped_out.contains(&panel) {
+            ui.horizontal(|ui| {
+                let title = self.app.i18n.tr(panel.message_id());
+                ui.weak(
+                    self.app
+                        .i18n
+                        .tr_args("panel-popped-out", &fluent_args!["panel" => title]),
+                );
+                if ui.small_button(self.app.i18n.tr("panel-dock")).clicked() {
+                    self.app.popped_out.remove(&panel);
+                }
+            });
+            return;
+        }
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+            if self.app.locked() {
+                return;
+            }
+            // Spelled out in touch mode, where the tooltip can't be seen.
+            let pop_out = if self.app.touch_mode {
+                ui.small_button(format!("⬈ {}", self.app.i18n.tr("panel-pop-out")))
+            } else {
+                ui.small_button("⬈")
+            }
+            .on_hover_text(self.app.i18n.tr("panel-pop-out"));
+            a11y::name(ui, &pop_out, self.app.i18n.tr("panel-pop-out"));
+            if pop_out.clicked() {
+                self.app.popped_out.insert(panel);
+            }
+        });
+        self.app.show_panel(panel, ui);
+    }
+}
+
+impl App {
+    // Each orientation keeps its own arrangement, so flipping the window
+    // doesn't lose either.
+    fn dock_mut(&mut self) -> &mut Layout {
+        if self.vertical {
+            &mut self.vertical_dock
+        } else {
+            &mut self.dock
+        }
+    }
+
+    fn show_panel(&mut self, panel: Panel, ui: &mut egui::Ui) {
+        // Another operator's panel stays in view but can't be touched.
+        let locked = self.show_owner(ui, panel);
+        ui.add_enabled_ui(!locked, |ui| self.show_panel_contents(panel, ui));
+    }
+
+    fn show_panel_contents(&mut self, panel: Panel, ui: &mut egui::Ui) {
+        // The log keeps its own scroll area pinned to the newest entry, and
+        // the teleprompter scrolls itself.
+        if panel == Panel::Log {
+            self.show_log(ui);
+            return;
+        }
+        if panel == Panel::Events {
+            self.show_events(ui);
+            return;
+        }
+        if panel == Panel::Teleprompter {
+            self.show_teleprompter(ui);
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .drag_to_scroll(self.touch_mode)
+            .show(ui, |ui| match panel {
+                Panel::Mixer => self.show_mixer(ui),
+                Panel::Scenes => self.show_scenes(ui),
+                Panel::Preview => self.show_preview(ui),
+                Panel::Sources => self.show_sources(ui),
+                Panel::Grid => self.section(ui, Panel::Grid, "section-buttons", |app, ui| {
+                    app.show_grid(ui)
+                }),
+                Panel::History => self.show_history(ui),
+                Panel::Plugins => self.show_plugins(ui),
+                Panel::Twitch => self.show_twitch(ui),
+                Panel::YouTube => self.show_youtube(ui),
+                Panel::Lights => self.show_lights(ui),
+                Panel::Timers => self.show_timers(ui),
+                Panel::LowerThirds => self.show_lower_thirds(ui),
+                Panel::Scoreboard => self.show_scoreboard(ui),
+                Panel::Rundown => self.show_rundown(ui),
+                Panel::Iso => self.show_iso(ui),
+                Panel::Advanced => self.show_advanced(ui),
+                Panel::Diagnostics => self.show_diagnostics(ui),
+                Panel::Settings => self.show_settings(ui),
+                Panel::Log | Panel::Teleprompter | Panel::Events => {}
+            });
+    }
+
+    // Open/closed state is kept with the rest of the saved state rather than
+    // in egui's memory, so it survives a restart.
+    pub(super) fn section(
+        &mut self,
+        ui: &mut egui::Ui,
+        panel: Panel,
+        title: &str,
+        add: impl FnOnce(&mut Self, &mut egui::Ui),
+    ) {
+        let open = !self.collapsed.contains(&panel);
+        let response = egui::CollapsingHeader::new(self.i18n.tr(title))
+            .id_source(panel)
+            .open(Some(open))
+            .show(ui, |ui| add(self, ui));
+        if response.header_response.clicked() && !self.collapsed.remove(&panel) {
+            self.collapsed.insert(panel);
+        }
+    }
+
+    pub(super) fn show_layouts(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut load = None;
+            egui::ComboBox::from_id_source("layouts")
+                .selected_text(self.i18n.tr("layout-layouts"))
+                .show_ui(ui, |ui| {
+                    for name in self.layouts.keys() {
+                        if ui.selectable_label(false, name).clicked() {
+                            load = Some(name.clone());
+                        }
+                    }
+                });
+            if let Some(name) = load {
+                if let Some(saved) = self.layouts.get(&name).cloned() {
+                    let dock = self.dock_mut();
+                    *dock = saved;
+                    layout::complete(dock);
+                }
+                if let Some(window) = self.layout_windows.get(&name).copied() {
+                    self.apply_window(ui.ctx(), window);
+                }
+                self.layout_name = name;
+            }
+            let name = ui.add(
+                egui::TextEdit::singleline(&mut self.layout_name)
+                    .hint_text(self.i18n.tr("layout-name"))
+                    .desired_width(120.0),
+            );
+            a11y::name(ui, &name, self.i18n.tr("layout-name"));
+            if ui.button(self.i18n.tr("layout-save")).clicked() && !self.layout_name.is_empty() {
+                let dock = self.dock_mut().clone();
+                self.layouts.insert(self.layout_name.clone(), dock);
+                if let Some(window) = self.window {
+                    self.layout_windows.insert(self.layout_name.clone(), window);
+                }
+            }
+            if ui.button(self.i18n.tr("layout-delete")).clicked() {
+                self.layouts.remove(&self.layout_name);
+                self.layout_windows.remove(&self.layout_name);
+            }
+            if ui.button(self.i18n.tr("layout-reset")).clicked() {
+                *self.dock_mut() = if self.vertical {
+                    layout::vertical_layout()
+                } else {
+                    layout::default_layout()
+                };
+            }
+        });
+    }
+
+    pub(super) fn show_dock(&mut self, ui: &mut egui::Ui) {
+        // Taken out for the duration so the tabs can borrow the rest of the app.
+        let mut dock = std::mem::replace(self.dock_mut(), egui_dock::DockState::new(Vec::new()));
+        egui_dock::DockArea::new(&mut dock)
+            .style(egui_dock::Style::from_egui(ui.style().as_ref()))
+            .show_close_buttons(false)
+            .draggable_tabs(!self.locked())
+            .show_inside(ui, &mut Tabs { app: self });
+        *self.dock_mut() = dock;
+    }
+
+    // Immediate viewports are drawn as part of this frame, so the panels can
+    // borrow the app like they do in the main window. Backends without
+    // multi-viewport support show them as embedded windows instead.
+    pub(super) fn show_popouts(&mut self, ctx: &egui::Context) {
+        for panel in Panel::ALL {
+            if !self.popped_out.contains(&panel) {
+                continue;
+            }
+            let mut closed = false;
+            let title = self.i18n.tr(panel.message_id());
+            ctx.show_viewport_immediate(
+                egui::ViewportId::from_hash_of(panel),
+                egui::ViewportBuilder::default()
+                    .with_title(format!("REC - {}", title))
+                    .with_inner_size([420.0, 320.0]),
+                |ctx, _class| {
+                    egui::CentralPanel::default().show(ctx, |ui| self.show_panel(panel, ui));
+                    closed = ctx.input(|i| i.viewport().close_requested());
+                },
+            );
+            if closed {
+                self.popped_out.remove(&panel);
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::App;
+use crate::config::WindowGeometry;
+
+impl App {
+    pub(super) fn track_window(&mut self, ctx: &egui::Context) {
+        let (inner_rect, outer_rect, maximized, fullscreen, monitor_size) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.inner_rect,
+                viewport.outer_rect,
+                viewport.maximized.unwrap_or(false),
+                viewport.fullscreen.unwrap_or(false),
+                viewport.monitor_size,
+            )
+        });
+        if !self.monitor_checked {
+            if let Some(monitor_size) = monitor_size {
+                self.monitor_checked = true;
+                self.fit_monitor(ctx, monitor_size);
+            }
+        }
+        // Neither the mini strip nor the kiosk's fullscreen is worth restoring
+        // on the next launch.
+        let Some(inner_rect) = inner_rect else {
+            return;
+        };
+        if self.mini_mode || fullscreen {
+            return;
+        }
+        let monitor_size = monitor_size.map(|size| [size.x, size.y]);
+        match (&mut self.window, maximized) {
+            // Keep the normal geometry, so un-maximizing after a restart
+            // lands where it used to.
+            (Some(window), true) => {
+                window.maximized = true;
+                window.monitor_size = monitor_size;
+            }
+            _ => {
+                self.window = Some(WindowGeometry {
+                    position: outer_rect.map(|rect| [rect.min.x, rect.min.y]),
+                    size: [inner_rect.width(), inner_rect.height()],
+                    maximized,
+                    monitor_size,
+                });
+            }
+        }
+    }
+
+    // A window saved on a monitor that has since been unplugged or swapped
+    // for a smaller one gets shrunk onto the one it opened on.
+    fn fit_monitor(&self, ctx: &egui::Context, monitor_size: egui::Vec2) {
+        let Some(window) = self.window else {
+            return;
+        };
+        let saved = window.monitor_size.map(egui::Vec2::from);
+        let size = egui::Vec2::from(window.size);
+        if saved == Some(monitor_size) || size.x <= monitor_size.x && size.y <= monitor_size.y {
+            return;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(
+            size.min(monitor_size * 0.9),
+        ));
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::Pos2::ZERO));
+    }
+
+    pub(super) fn apply_window(&mut self, ctx: &egui::Context, window: WindowGeometry) {
+        if self.kiosk() {
+            return;
+        }
+        if self.mini_mode {
+            self.toggle_mini_mode(ctx);
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window.size.into()));
+        if let Some(position) = window.position {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position.into()));
+        }
+        if window.maximized {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(true));
+        }
+        self.window = Some(window);
+    }
+}
@@ -0,0 +1,192 @@
+use super::App;
+use crate::{
+    obs_worker::{Action, Notifier},
+    youtube::{self, Auth, Broadcast, Privacy, Request, YouTubeConfig, YouTubeInfo},
+};
+use fluent::fluent_args;
+use std::sync::mpsc;
+
+pub(super) struct YouTube {
+    request_tx: mpsc::Sender<Request>,
+    info_rx: mpsc::Receiver<YouTubeInfo>,
+    pub(super) config: YouTubeConfig,
+    pub(super) auth: Option<Auth>,
+    device_code: Option<(String, String)>,
+    broadcasts: Vec<Broadcast>,
+    selected: Option<String>,
+    bound: Option<String>,
+    pub(super) viewers: Option<u64>,
+    new_title: String,
+    privacy: Privacy,
+}
+
+impl YouTube {
+    pub(super) fn new(notify: Notifier, auth: Option<Auth>) -> Self {
+        let (request_tx, info_rx) = youtube::spawn(notify);
+        let youtube = Self {
+            request_tx,
+            info_rx,
+            config: YouTubeConfig::default(),
+            auth,
+            device_code: None,
+            broadcasts: Vec::new(),
+            selected: None,
+            bound: None,
+            viewers: None,
+            new_title: String::new(),
+            privacy: Privacy::Unlisted,
+        };
+        if let Some(auth) = &youtube.auth {
+            youtube.request(Request::Restore(auth.clone()));
+        }
+        youtube
+    }
+
+    fn request(&self, request: Request) {
+        if self.request_tx.send(request).is_err() {
+            tracing::warn!("youtube thread is gone");
+        }
+    }
+}
+
+impl App {
+    pub(super) fn handle_youtube(&mut self) {
+        while let Ok(info) = self.youtube.info_rx.try_recv() {
+            match info {
+                YouTubeInfo::DeviceCode {
+                    user_code,
+                    verification_url,
+                } => self.youtube.device_code = Some((user_code, verification_url)),
+                YouTubeInfo::LoggedIn(auth) => {
+                    self.youtube.auth = Some(auth);
+                    self.youtube.device_code = None;
+                }
+                YouTubeInfo::LoggedOut => {
+                    self.youtube.auth = None;
+                    self.youtube.device_code = None;
+                    self.youtube.bound = None;
+                    self.youtube.viewers = None;
+                }
+                YouTubeInfo::Broadcasts(broadcasts) => self.youtube.broadcasts = broadcasts,
+                YouTubeInfo::Bound {
+                    broadcast,
+                    server,
+                    key,
+                } => {
+                    self.youtube.bound = Some(broadcast);
+                    self.send(Action::SetStreamService(server, key));
+                    self.toasts.notice(self.i18n.tr("youtube-bound"));
+                }
+                YouTubeInfo::Viewers(viewers) => self.youtube.viewers = viewers,
+                YouTubeInfo::Error(err) => {
+                    self.youtube.device_code = None;
+                    self.toasts.push(
+                        self.i18n
+                            .tr_args("toast-youtube-error", &fluent_args!["error" => err]),
+                    );
+                }
+            }
+        }
+    }
+
+    pub(super) fn show_youtube(&mut self, ui: &mut egui::Ui) {
+        let mut request = None;
+        self.show_youtube_broadcasts(ui, &mut request);
+        if let Some(request) = request {
+            self.youtube.request(request);
+        }
+    }
+
+    fn show_youtube_broadcasts(&mut self, ui: &mut egui::Ui, request: &mut Option<Request>) {
+        let youtube = &mut self.youtube;
+        let (Some(client_id), Some(client_secret)) = (
+            youtube.config.client_id.clone(),
+            youtube.config.client_secret.clone(),
+        ) else {
+            ui.weak(self.i18n.tr("youtube-no-client-id"));
+            return;
+        };
+        if youtube.auth.is_none() {
+            match &youtube.device_code {
+                Some((user_code, verification_url)) => {
+                    ui.label(self.i18n.tr_args(
+                        "youtube-enter-code",
+                        &fluent_args!["code" => user_code.as_str()],
+                    ));
+                    ui.hyperlink(verification_url);
+                    if ui.button(self.i18n.tr("confirm-cancel")).clicked() {
+                        *request = Some(Request::LogOut);
+                    }
+                }
+                None => {
+                    if ui.button(self.i18n.tr("youtube-log-in")).clicked() {
+                        *request = Some(Request::LogIn(client_id, client_secret));
+                    }
+                }
+            }
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button(self.i18n.tr("youtube-refresh")).clicked() {
+                *request = Some(Request::ListBroadcasts);
+            }
+            if ui.button(self.i18n.tr("youtube-log-out")).clicked() {
+                *request = Some(Request::LogOut);
+            }
+        });
+        if youtube.broadcasts.is_empty() {
+            ui.weak(self.i18n.tr("youtube-no-broadcasts"));
+        }
+        for broadcast in &youtube.broadcasts {
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut youtube.selected,
+                    Some(broadcast.id.clone()),
+                    &broadcast.title,
+                );
+                ui.weak(&broadcast.status);
+                if youtube.bound.as_ref() == Some(&broadcast.id) {
+                    ui.colored_label(egui::Color32::GREEN, self.i18n.tr("youtube-bound-label"));
+                }
+            });
+        }
+        if let Some(selected) = &youtube.selected {
+            if ui.button(self.i18n.tr("youtube-bind")).clicked() {
+                *request = Some(Request::Bind(selected.clone()));
+            }
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut youtube.new_title)
+                    .hint_text(self.i18n.tr("youtube-new-title"))
+                    .desired_width(180.0),
+            );
+            egui::ComboBox::from_id_source("youtube_privacy")
+                .selected_text(self.i18n.tr(youtube.privacy.message_id()))
+                .show_ui(ui, |ui| {
+                    for privacy in Privacy::ALL {
+                        ui.selectable_value(
+                            &mut youtube.privacy,
+                            privacy,
+                            self.i18n.tr(privacy.message_id()),
+                        );
+                    }
+                });
+            if ui
+                .add_enabled(
+                    !youtube.new_title.is_empty(),
+                    egui::Button::new(self.i18n.tr("youtube-create")),
+                )
+                .clicked()
+            {
+                *request = Some(Request::CreateBroadcast(
+                    std::mem::take(&mut youtube.new_title),
+                    youtube.privacy,
+                ));
+            }
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use crate::obs_worker::Notifier;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{sync::mpsc, thread, time::Duration};
+
+const LATEST_URL: &str = "https://api.github.com/repos/suhrm/REC/releases/latest";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub name: Option<String>,
+    // The changelog, in Markdown.
+    pub body: Option<String>,
+    pub html_url: String,
+}
+
+// "v1.2.3" or "1.2.3" as numbers; pre-release suffixes are dropped.
+fn version(tag: &str) -> Option<Vec<u64>> {
+    let tag = tag.trim_start_matches('v');
+    let core = tag.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+pub fn is_newer(tag: &str, current: &str) -> bool {
+    match (version(tag), version(current)) {
+        (Some(tag), Some(current)) => tag > current,
+        _ => false,
+    }
+}
+
+pub fn latest() -> Result<Release> {
+    let response = ureq::get(LATEST_URL)
+        .timeout(TIMEOUT)
+        // GitHub turns away requests without one.
+        .set("User-Agent", concat!("rec/", env!("CARGO_PKG_VERSION")))
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .context("failed to check for updates")?;
+    serde_json::from_reader(response.into_reader()).context("unexpected response from GitHub")
+}
+
+// Checks once in the background; the receiver only ever gets a release newer
+// than this build. Failures are logged and otherwise ignored, since a venue
+// without internet is no reason to bother anyone.
+pub fn spawn(notify: Notifier) -> mpsc::Receiver<Release> {
+    let (release_tx, release_rx) = mpsc::channel();
+    thread::spawn(move || match latest() {
+        Ok(release) if is_newer(&release.tag_name, env!("CARGO_PKG_VERSION")) => {
+            tracing::info!(version = release.tag_name, "update available");
+            if release_tx.send(release).is_ok() {
+                notify();
+            }
+        }
+        Ok(_) => tracing::debug!("REC is up to date"),
+        Err(err) => tracing::info!("{:#}", err),
+    });
+    release_rx
+}
@@ -0,0 +1,129 @@
+use crate::obs_worker::Action;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+
+// Counts of what gets used and what goes wrong, kept on this machine only.
+// Nothing leaves it unless the operator exports the report and sends it on;
+// names of scenes, inputs and the text of errors are never recorded.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Usage {
+    // The day counting started, as YYYY-MM-DD.
+    pub since: Option<String>,
+    pub features: BTreeMap<String, u64>,
+    pub errors: BTreeMap<String, u64>,
+}
+
+fn path() -> Result<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "rec").context("no home directory")?;
+    Ok(dirs.data_dir().join("usage.json"))
+}
+
+// The variant name alone, since its fields can name the operator's scenes.
+fn feature(action: &Action) -> Option<String> {
+    match serde_json::to_value(action).ok()? {
+        serde_json::Value::String(name) => Some(name),
+        serde_json::Value::Object(map) => map.into_iter().next().map(|(name, _)| name),
+        _ => None,
+    }
+}
+
+// A rough bucket for an error message, by the words OBS and the network
+// libraries tend to use.
+fn category(error: &str) -> &'static str {
+    let error = error.to_lowercase();
+    let categories = [
+        ("auth", ["password", "authenticat", "401"].as_slice()),
+        ("timeout", ["timed out", "timeout"].as_slice()),
+        (
+            "connection",
+            ["connect", "socket", "refused", "closed"].as_slice(),
+        ),
+        (
+            "not-found",
+            ["not found", "no such", "doesn't exist"].as_slice(),
+        ),
+        ("config", ["toml", "invalid", "parse"].as_slice()),
+        ("script", ["script"].as_slice()),
+    ];
+    categories
+        .iter()
+        .find(|(_, words)| words.iter().any(|word| error.contains(word)))
+        .map_or("other", |(category, _)| category)
+}
+
+impl Usage {
+    pub fn load() -> Self {
+        let Ok(text) = path().and_then(|path| Ok(std::fs::read_to_string(path)?)) else {
+            return Self::default();
+        };
+        serde_json::from_str(&text).unwrap_or_else(|err| {
+            tracing::warn!("ignoring unreadable usage counts: {}", err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn count_action(&mut self, action: &Action) {
+        if let Some(feature) = feature(action) {
+            self.count(feature);
+        }
+    }
+
+    fn start(&mut self) {
+        self.since
+            .get_or_insert_with(|| Local::now().format("%Y-%m-%d").to_string());
+    }
+
+    fn count(&mut self, feature: String) {
+        self.start();
+        *self.features.entry(feature).or_default() += 1;
+    }
+
+    pub fn count_error(&mut self, error: &str) {
+        self.start();
+        *self.errors.entry(category(error).to_string()).or_default() += 1;
+    }
+
+    // Forgets everything counted so far, on disk too.
+    pub fn clear(&mut self) -> Result<()> {
+        *self = Self::default();
+        match std::fs::remove_file(path()?) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    // What gets shared, with the version and platform added so reports from
+    // different releases can be told apart.
+    pub fn report(&self) -> String {
+        let report = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "since": self.since,
+            "features": self.features,
+            "errors": self.errors,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    pub fn export(&self) -> Result<PathBuf> {
+        let path = path()?.with_file_name(format!(
+            "usage-report-{}.json",
+            Local::now().format("%Y-%m-%d")
+        ));
+        std::fs::write(&path, self.report())
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
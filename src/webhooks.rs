@@ -0,0 +1,86 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    StreamStarted,
+    StreamStopped,
+    RecordingStarted,
+    RecordingStopped,
+    DroppedFrames,
+    IntermissionStarted,
+    IntermissionEnded,
+}
+
+impl WebhookEvent {
+    fn default_message(self) -> &'static str {
+        match self {
+            WebhookEvent::StreamStarted => "We're live!",
+            WebhookEvent::StreamStopped => "The stream has ended.",
+            WebhookEvent::RecordingStarted => "Recording started.",
+            WebhookEvent::RecordingStopped => "Recording finished: {path}",
+            WebhookEvent::DroppedFrames => {
+                "{dropped}% of frames were dropped in the last 30 seconds."
+            }
+            WebhookEvent::IntermissionStarted => "We're taking a short break.",
+            WebhookEvent::IntermissionEnded => "We're back!",
+        }
+    }
+}
+
+// What the receiving end expects the body to look like.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub enum WebhookKind {
+    Discord,
+    Slack,
+    #[default]
+    Generic,
+}
+
+// A `[[webhooks]]` entry of rec.toml.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+    pub events: Vec<WebhookEvent>,
+    // `{path}` and `{dropped}` are filled in where they apply.
+    pub message: Option<String>,
+}
+
+// Details of what happened, for the message placeholders.
+#[derive(Clone, Default)]
+pub struct Details {
+    pub path: Option<String>,
+    pub dropped: Option<f64>,
+}
+
+pub fn post(webhook: &Webhook, event: WebhookEvent, details: &Details) -> Result<()> {
+    let text = webhook
+        .message
+        .as_deref()
+        .unwrap_or(event.default_message())
+        .replace("{path}", details.path.as_deref().unwrap_or_default())
+        .replace(
+            "{dropped}",
+            &details
+                .dropped
+                .map(|dropped| format!("{:.1}", dropped))
+                .unwrap_or_default(),
+        );
+    let body = match webhook.kind {
+        WebhookKind::Discord => serde_json::json!({ "content": text }),
+        WebhookKind::Slack => serde_json::json!({ "text": text }),
+        WebhookKind::Generic => serde_json::json!({
+            "event": event,
+            "message": text,
+            "path": details.path,
+            "dropped": details.dropped,
+        }),
+    };
+    ureq::post(&webhook.url)
+        .set("Content-Type", "application/json")
+        .send_string(&body.to_string())
+        .with_context(|| format!("failed to post webhook to {}", webhook.url))?;
+    Ok(())
+}
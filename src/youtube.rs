@@ -0,0 +1,434 @@
+use crate::obs_worker::Notifier;
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+const SCOPE: &str = "https://www.googleapis.com/auth/youtube";
+const DEVICE_URL: &str = "https://oauth2.googleapis.com/device/code";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const API_URL: &str = "https://www.googleapis.com/youtube/v3";
+const VIEWERS_INTERVAL: Duration = Duration::from_secs(30);
+
+// The `[youtube]` table of rec.toml. Google only hands out device-flow
+// tokens to a "TVs and Limited Input devices" OAuth client, which comes with
+// a secret that isn't actually secret for installed apps.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct YouTubeConfig {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Auth {
+    pub client_id: String,
+    pub client_secret: String,
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Privacy {
+    Public,
+    Unlisted,
+    Private,
+}
+
+impl Privacy {
+    pub const ALL: [Privacy; 3] = [Privacy::Public, Privacy::Unlisted, Privacy::Private];
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Privacy::Public => "youtube-public",
+            Privacy::Unlisted => "youtube-unlisted",
+            Privacy::Private => "youtube-private",
+        }
+    }
+
+    fn api_name(self) -> &'static str {
+        match self {
+            Privacy::Public => "public",
+            Privacy::Unlisted => "unlisted",
+            Privacy::Private => "private",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Broadcast {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+pub enum Request {
+    LogIn(String, String),
+    Restore(Auth),
+    LogOut,
+    ListBroadcasts,
+    CreateBroadcast(String, Privacy),
+    Bind(String),
+}
+
+pub enum YouTubeInfo {
+    DeviceCode {
+        user_code: String,
+        verification_url: String,
+    },
+    LoggedIn(Auth),
+    LoggedOut,
+    Broadcasts(Vec<Broadcast>),
+    // The broadcast is bound; OBS should stream to this server and key.
+    Bound {
+        broadcast: String,
+        server: String,
+        key: String,
+    },
+    Viewers(Option<u64>),
+    Error(String),
+}
+
+// Same arrangement as the Twitch thread: blocking calls kept off the OBS
+// worker. While a broadcast is bound its viewer count is polled.
+pub fn spawn(notify: Notifier) -> (mpsc::Sender<Request>, mpsc::Receiver<YouTubeInfo>) {
+    let (request_tx, request_rx) = mpsc::channel();
+    let (info_tx, info_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut client = Client {
+            auth: None,
+            bound: None,
+            info_tx,
+            notify,
+        };
+        loop {
+            let result = match request_rx.recv_timeout(VIEWERS_INTERVAL) {
+                Ok(request) => client.handle(request, &request_rx),
+                Err(mpsc::RecvTimeoutError::Timeout) => client.poll_viewers(),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+            if let Err(err) = result {
+                tracing::warn!("youtube: {:#}", err);
+                client.send(YouTubeInfo::Error(format!("{:#}", err)));
+            }
+        }
+    });
+    (request_tx, info_rx)
+}
+
+struct Client {
+    auth: Option<Auth>,
+    bound: Option<String>,
+    info_tx: mpsc::Sender<YouTubeInfo>,
+    notify: Notifier,
+}
+
+#[derive(Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_url: String,
+    expires_in: u64,
+    interval: u64,
+}
+
+#[derive(Deserialize)]
+struct Token {
+    access_token: String,
+    // Only sent with the first token, not on refresh.
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Items<T> {
+    items: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LiveBroadcast {
+    id: String,
+    snippet: BroadcastSnippet,
+    status: BroadcastStatus,
+}
+
+#[derive(Deserialize)]
+struct BroadcastSnippet {
+    title: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BroadcastStatus {
+    life_cycle_status: String,
+}
+
+#[derive(Deserialize)]
+struct LiveStream {
+    id: String,
+    cdn: Cdn,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Cdn {
+    ingestion_info: IngestionInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IngestionInfo {
+    ingestion_address: String,
+    stream_name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Video {
+    live_streaming_details: Option<LiveStreamingDetails>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LiveStreamingDetails {
+    concurrent_viewers: Option<String>,
+}
+
+impl Client {
+    fn send(&self, info: YouTubeInfo) {
+        if self.info_tx.send(info).is_ok() {
+            (self.notify)();
+        }
+    }
+
+    fn handle(&mut self, request: Request, request_rx: &mpsc::Receiver<Request>) -> Result<()> {
+        match request {
+            Request::LogIn(client_id, client_secret) => {
+                self.log_in(client_id, client_secret, request_rx)?
+            }
+            Request::Restore(auth) => {
+                self.auth = Some(auth);
+                self.list_broadcasts()?;
+            }
+            Request::LogOut => {
+                self.auth = None;
+                self.bound = None;
+                self.send(YouTubeInfo::LoggedOut);
+            }
+            Request::ListBroadcasts => self.list_broadcasts()?,
+            Request::CreateBroadcast(title, privacy) => {
+                let start = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+                let body = json!({
+                    "snippet": { "title": title, "scheduledStartTime": start },
+                    "status": {
+                        "privacyStatus": privacy.api_name(),
+                        "selfDeclaredMadeForKids": false,
+                    },
+                    // Going live and ending follow the OBS stream, so starting
+                    // the stream in REC is all it takes.
+                    "contentDetails": { "enableAutoStart": true, "enableAutoStop": true },
+                });
+                self.post("/liveBroadcasts?part=snippet,status,contentDetails", &body)?;
+                tracing::info!(title, "created youtube broadcast");
+                self.list_broadcasts()?;
+            }
+            Request::Bind(broadcast) => {
+                let stream = self.stream()?;
+                self.post(
+                    &format!(
+                        "/liveBroadcasts/bind?part=id&id={}&streamId={}",
+                        broadcast, stream.id
+                    ),
+                    &json!({}),
+                )?;
+                tracing::info!(broadcast, "bound youtube broadcast");
+                self.bound = Some(broadcast.clone());
+                self.send(YouTubeInfo::Bound {
+                    broadcast,
+                    server: stream.cdn.ingestion_info.ingestion_address,
+                    key: stream.cdn.ingestion_info.stream_name,
+                });
+                self.poll_viewers()?;
+            }
+        }
+        Ok(())
+    }
+
+    // Google's device flow: like Twitch's, but pending logins answer 428.
+    fn log_in(
+        &mut self,
+        client_id: String,
+        client_secret: String,
+        request_rx: &mpsc::Receiver<Request>,
+    ) -> Result<()> {
+        let code: DeviceCode = read(
+            ureq::post(DEVICE_URL)
+                .send_form(&[("client_id", client_id.as_str()), ("scope", SCOPE)]),
+        )
+        .context("failed to start YouTube login")?;
+        self.send(YouTubeInfo::DeviceCode {
+            user_code: code.user_code.clone(),
+            verification_url: code.verification_url.clone(),
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(code.expires_in);
+        let token = loop {
+            match request_rx.recv_timeout(Duration::from_secs(code.interval.max(1))) {
+                Ok(Request::LogOut) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    self.send(YouTubeInfo::LoggedOut);
+                    return Ok(());
+                }
+                Ok(_) => tracing::debug!("ignoring youtube request while logging in"),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+            }
+            if Instant::now() > deadline {
+                bail!("the YouTube login code expired");
+            }
+            match ureq::post(TOKEN_URL).send_form(&[
+                ("client_id", client_id.as_str()),
+                ("client_secret", &client_secret),
+                ("device_code", &code.device_code),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ]) {
+                Ok(response) => break json::<Token>(response)?,
+                Err(ureq::Error::Status(428, _)) => {}
+                Err(err) => return Err(err).context("failed to finish YouTube login"),
+            }
+        };
+        let auth = Auth {
+            client_id,
+            client_secret,
+            access_token: token.access_token,
+            refresh_token: token
+                .refresh_token
+                .context("YouTube sent no refresh token")?,
+        };
+        tracing::info!("logged in to youtube");
+        self.auth = Some(auth.clone());
+        self.send(YouTubeInfo::LoggedIn(auth));
+        self.list_broadcasts()
+    }
+
+    fn list_broadcasts(&mut self) -> Result<()> {
+        let broadcasts: Items<LiveBroadcast> = self.get(
+            "/liveBroadcasts?part=id,snippet,status&mine=true&maxResults=25&broadcastType=all",
+        )?;
+        self.send(YouTubeInfo::Broadcasts(
+            broadcasts
+                .items
+                .into_iter()
+                .filter(|broadcast| broadcast.status.life_cycle_status != "complete")
+                .map(|broadcast| Broadcast {
+                    id: broadcast.id,
+                    title: broadcast.snippet.title,
+                    status: broadcast.status.life_cycle_status,
+                })
+                .collect(),
+        ));
+        Ok(())
+    }
+
+    // The channel's first stream key is reused rather than making a new one
+    // per broadcast, so OBS keeps working if the binding is changed on the web.
+    fn stream(&mut self) -> Result<LiveStream> {
+        let streams: Items<LiveStream> = self.get("/liveStreams?part=id,cdn&mine=true")?;
+        if let Some(stream) = streams.items.into_iter().next() {
+            return Ok(stream);
+        }
+        let body = json!({
+            "snippet": { "title": "REC" },
+            "cdn": { "frameRate": "variable", "ingestionType": "rtmp", "resolution": "variable" },
+        });
+        let response = self.post("/liveStreams?part=id,snippet,cdn", &body)?;
+        json(response)
+    }
+
+    fn poll_viewers(&mut self) -> Result<()> {
+        let Some(broadcast) = self.bound.clone() else {
+            return Ok(());
+        };
+        let videos: Items<Video> = self.get(&format!(
+            "/videos?part=liveStreamingDetails&id={}",
+            broadcast
+        ))?;
+        let viewers = videos
+            .items
+            .into_iter()
+            .next()
+            .and_then(|video| video.live_streaming_details)
+            .and_then(|details| details.concurrent_viewers)
+            .and_then(|viewers| viewers.parse().ok());
+        self.send(YouTubeInfo::Viewers(viewers));
+        Ok(())
+    }
+
+    fn auth(&self) -> Result<&Auth> {
+        self.auth.as_ref().context("not logged in to YouTube")
+    }
+
+    fn get<T: DeserializeOwned>(&mut self, path: &str) -> Result<T> {
+        let response = self.api(|auth| api_request("GET", path, auth).call())?;
+        json(response)
+    }
+
+    fn post(&mut self, path: &str, body: &serde_json::Value) -> Result<ureq::Response> {
+        self.api(|auth| {
+            api_request("POST", path, auth)
+                .set("Content-Type", "application/json")
+                .send_string(&body.to_string())
+        })
+    }
+
+    // Access tokens last an hour; a 401 refreshes once and retries.
+    fn api(
+        &mut self,
+        call: impl Fn(&Auth) -> Result<ureq::Response, ureq::Error>,
+    ) -> Result<ureq::Response> {
+        match call(self.auth()?) {
+            Err(ureq::Error::Status(401, _)) => {}
+            result => return result.context("YouTube request failed"),
+        }
+        self.refresh()?;
+        call(self.auth()?).context("YouTube request failed")
+    }
+
+    fn refresh(&mut self) -> Result<()> {
+        let auth = self.auth()?;
+        let token: Token = match read(ureq::post(TOKEN_URL).send_form(&[
+            ("client_id", auth.client_id.as_str()),
+            ("client_secret", &auth.client_secret),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", &auth.refresh_token),
+        ])) {
+            Ok(token) => token,
+            Err(err) => {
+                self.auth = None;
+                self.bound = None;
+                self.send(YouTubeInfo::LoggedOut);
+                return Err(err.context("YouTube login expired, log in again"));
+            }
+        };
+        let auth = self.auth.as_mut().context("not logged in to YouTube")?;
+        auth.access_token = token.access_token;
+        let auth = auth.clone();
+        self.send(YouTubeInfo::LoggedIn(auth));
+        Ok(())
+    }
+}
+
+fn api_request(method: &str, path: &str, auth: &Auth) -> ureq::Request {
+    ureq::request(method, &format!("{}{}", API_URL, path))
+        .set("Authorization", &format!("Bearer {}", auth.access_token))
+}
+
+fn read<T: DeserializeOwned>(response: Result<ureq::Response, ureq::Error>) -> Result<T> {
+    json(response?)
+}
+
+fn json<T: DeserializeOwned>(response: ureq::Response) -> Result<T> {
+    serde_json::from_reader(response.into_reader()).context("unexpected response from YouTube")
+}
@@ -0,0 +1,369 @@
+// A stand-in for OBS speaking just enough obs-websocket v5 for the worker:
+// the Hello/Identify handshake, the requests REC sends, and the events real
+// OBS emits in response to them. Tests can also inject events directly.
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+const OP_HELLO: u64 = 0;
+const OP_IDENTIFY: u64 = 1;
+const OP_IDENTIFIED: u64 = 2;
+const OP_EVENT: u64 = 5;
+const OP_REQUEST: u64 = 6;
+const OP_REQUEST_RESPONSE: u64 = 7;
+const OP_REQUEST_BATCH: u64 = 8;
+const OP_REQUEST_BATCH_RESPONSE: u64 = 9;
+
+#[derive(Default)]
+pub struct ObsModel {
+    // (name, kind)
+    pub inputs: Vec<(String, String)>,
+    pub muted: HashMap<String, bool>,
+    // Multipliers, as OBS reports them.
+    pub volumes: HashMap<String, f64>,
+    pub scenes: Vec<String>,
+    pub current_scene: String,
+    pub streaming: bool,
+    pub recording: bool,
+    // Every request received, in order, as (requestType, requestData).
+    pub requests: Vec<(String, Value)>,
+    // The request types of each batch received, in order.
+    pub batches: Vec<Vec<String>>,
+}
+
+pub struct MockObs {
+    pub port: u16,
+    model: Arc<Mutex<ObsModel>>,
+    events_tx: broadcast::Sender<Value>,
+}
+
+impl MockObs {
+    pub async fn start(model: ObsModel) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let model = Arc::new(Mutex::new(model));
+        let (events_tx, _) = broadcast::channel(64);
+        tokio::spawn(accept(listener, model.clone(), events_tx.clone()));
+        Self {
+            port,
+            model,
+            events_tx,
+        }
+    }
+
+    pub fn model(&self) -> MutexGuard<'_, ObsModel> {
+        self.model.lock().unwrap()
+    }
+
+    pub fn emit(&self, event_type: &str, data: Value) {
+        let _ = self.events_tx.send(event(event_type, data));
+    }
+
+    pub async fn wait_for_request(&self, request_type: &str) -> Value {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let found = self
+                    .model()
+                    .requests
+                    .iter()
+                    .rev()
+                    .find(|(kind, _)| kind == request_type)
+                    .map(|(_, data)| data.clone());
+                if let Some(data) = found {
+                    return data;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("no {} request received", request_type))
+    }
+}
+
+fn event(event_type: &str, data: Value) -> Value {
+    let mut d = json!({ "eventType": event_type, "eventIntent": 0 });
+    if !data.is_null() {
+        d["eventData"] = data;
+    }
+    json!({ "op": OP_EVENT, "d": d })
+}
+
+async fn accept(
+    listener: TcpListener,
+    model: Arc<Mutex<ObsModel>>,
+    events_tx: broadcast::Sender<Value>,
+) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(handle_connection(stream, model.clone(), events_tx.clone()));
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    model: Arc<Mutex<ObsModel>>,
+    events_tx: broadcast::Sender<Value>,
+) {
+    let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let hello = json!({
+        "op": OP_HELLO,
+        "d": { "obsWebSocketVersion": "5.3.0", "rpcVersion": 1 },
+    });
+    if ws.send(Message::Text(hello.to_string())).await.is_err() {
+        return;
+    }
+
+    let mut events_rx = events_tx.subscribe();
+    loop {
+        tokio::select! {
+            message = ws.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => continue,
+                };
+                let Ok(message) = serde_json::from_str::<Value>(&text) else {
+                    continue;
+                };
+                let reply = match message["op"].as_u64() {
+                    Some(OP_IDENTIFY) => json!({
+                        "op": OP_IDENTIFIED,
+                        "d": { "negotiatedRpcVersion": 1 },
+                    }),
+                    Some(OP_REQUEST) => json!({
+                        "op": OP_REQUEST_RESPONSE,
+                        "d": request_reply(&model, &events_tx, &message["d"]),
+                    }),
+                    Some(OP_REQUEST_BATCH) => {
+                        let d = &message["d"];
+                        let requests = d["requests"].as_array().cloned().unwrap_or_default();
+                        model.lock().unwrap().batches.push(
+                            requests
+                                .iter()
+                                .map(|request| {
+                                    request["requestType"].as_str().unwrap_or_default().to_string()
+                                })
+                                .collect(),
+                        );
+                        let halt_on_failure = d["haltOnFailure"].as_bool().unwrap_or(false);
+                        let mut results = Vec::new();
+                        for request in &requests {
+                            let result = request_reply(&model, &events_tx, request);
+                            let failed = result["requestStatus"]["result"] != json!(true);
+                            results.push(result);
+                            if failed && halt_on_failure {
+                                break;
+                            }
+                        }
+                        json!({
+                            "op": OP_REQUEST_BATCH_RESPONSE,
+                            "d": { "requestId": d["requestId"], "results": results },
+                        })
+                    }
+                    _ => continue,
+                };
+                if ws.send(Message::Text(reply.to_string())).await.is_err() {
+                    return;
+                }
+            }
+            event = events_rx.recv() => {
+                let Ok(event) = event else {
+                    continue;
+                };
+                if ws.send(Message::Text(event.to_string())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// The response to one request, alone or in a batch, with the events it causes
+// sent on.
+fn request_reply(
+    model: &Mutex<ObsModel>,
+    events_tx: &broadcast::Sender<Value>,
+    d: &Value,
+) -> Value {
+    let request_type = d["requestType"].as_str().unwrap_or_default();
+    let request_data = d.get("requestData").cloned().unwrap_or(Value::Null);
+    let (response, events) = {
+        let mut model = model.lock().unwrap();
+        model
+            .requests
+            .push((request_type.to_string(), request_data.clone()));
+        respond(&mut model, request_type, &request_data)
+    };
+    let mut reply = json!({
+        "requestType": request_type,
+        "requestId": d["requestId"],
+    });
+    match response {
+        Some(data) => {
+            reply["requestStatus"] = json!({ "result": true, "code": 100 });
+            if !data.is_null() {
+                reply["responseData"] = data;
+            }
+        }
+        // What real OBS answers for a request type it doesn't know.
+        None => {
+            reply["requestStatus"] = json!({
+                "result": false,
+                "code": 204,
+                "comment": "unknown request type",
+            });
+        }
+    }
+    for event in events {
+        let _ = events_tx.send(event);
+    }
+    reply
+}
+
+fn volume_db(mul: f64) -> f64 {
+    if mul > 0.0 {
+        20.0 * mul.log10()
+    } else {
+        -100.0
+    }
+}
+
+fn output_state(active: bool) -> &'static str {
+    if active {
+        "OBS_WEBSOCKET_OUTPUT_STARTED"
+    } else {
+        "OBS_WEBSOCKET_OUTPUT_STOPPED"
+    }
+}
+
+// Returns the response data (None for an unknown request) and any events the
+// request causes.
+fn respond(model: &mut ObsModel, request_type: &str, data: &Value) -> (Option<Value>, Vec<Value>) {
+    let input = data["inputName"].as_str().unwrap_or_default().to_string();
+    let mut events = Vec::new();
+    let response = match request_type {
+        "GetVersion" => json!({
+            "obsVersion": "30.0.0",
+            "obsWebSocketVersion": "5.3.0",
+            "rpcVersion": 1,
+            "availableRequests": [],
+            "supportedImageFormats": [],
+            "platform": "mock",
+            "platformDescription": "mock obs-websocket server",
+        }),
+        "GetInputList" => json!({
+            "inputs": model.inputs.iter().map(|(name, kind)| json!({
+                "inputName": name,
+                "inputKind": kind,
+                "unversionedInputKind": kind,
+            })).collect::<Vec<_>>(),
+        }),
+        "GetOutputList" => json!({ "outputs": [] }),
+        "GetSceneList" => json!({
+            "currentProgramSceneName": model.current_scene,
+            "currentPreviewSceneName": model.current_scene,
+            "scenes": model.scenes.iter().enumerate().map(|(index, name)| json!({
+                "sceneName": name,
+                "sceneIndex": index,
+            })).collect::<Vec<_>>(),
+        }),
+        "GetStreamStatus" => json!({
+            "outputActive": model.streaming,
+            "outputReconnecting": false,
+            "outputTimecode": "00:00:00.000",
+            "outputDuration": 0,
+            "outputCongestion": 0.0,
+            "outputBytes": 0,
+            "outputSkippedFrames": 0,
+            "outputTotalFrames": 0,
+        }),
+        "GetRecordStatus" => json!({
+            "outputActive": model.recording,
+            "outputPaused": false,
+            "outputTimecode": "00:00:00.000",
+            "outputDuration": 0,
+            "outputBytes": 0,
+        }),
+        "GetInputMute" => json!({
+            "inputMuted": model.muted.get(&input).copied().unwrap_or(false),
+        }),
+        "GetInputVolume" => {
+            let mul = model.volumes.get(&input).copied().unwrap_or(1.0);
+            json!({ "inputVolumeMul": mul, "inputVolumeDb": volume_db(mul) })
+        }
+        "SetInputMute" => {
+            let muted = data["inputMuted"].as_bool().unwrap_or(false);
+            model.muted.insert(input.clone(), muted);
+            events.push(event(
+                "InputMuteStateChanged",
+                json!({ "inputName": input, "inputMuted": muted }),
+            ));
+            Value::Null
+        }
+        "SetInputVolume" => {
+            let mul = match data["inputVolumeMul"].as_f64() {
+                Some(mul) => mul,
+                None => 10f64.powf(data["inputVolumeDb"].as_f64().unwrap_or(0.0) / 20.0),
+            };
+            model.volumes.insert(input.clone(), mul);
+            events.push(event(
+                "InputVolumeChanged",
+                json!({
+                    "inputName": input,
+                    "inputVolumeMul": mul,
+                    "inputVolumeDb": volume_db(mul),
+                }),
+            ));
+            Value::Null
+        }
+        "SetCurrentProgramScene" => {
+            let scene = data["sceneName"].as_str().unwrap_or_default().to_string();
+            model.current_scene = scene.clone();
+            events.push(event(
+                "CurrentProgramSceneChanged",
+                json!({ "sceneName": scene }),
+            ));
+            Value::Null
+        }
+        "StartStream" | "StopStream" => {
+            model.streaming = request_type == "StartStream";
+            events.push(event(
+                "StreamStateChanged",
+                json!({
+                    "outputActive": model.streaming,
+                    "outputState": output_state(model.streaming),
+                }),
+            ));
+            Value::Null
+        }
+        "StartRecord" | "StopRecord" => {
+            model.recording = request_type == "StartRecord";
+            events.push(event(
+                "RecordStateChanged",
+                json!({
+                    "outputActive": model.recording,
+                    "outputState": output_state(model.recording),
+                    "outputPath": "/tmp/mock.mkv",
+                }),
+            ));
+            if model.recording {
+                Value::Null
+            } else {
+                json!({ "outputPath": "/tmp/mock.mkv" })
+            }
+        }
+        _ => return (None, events),
+    };
+    (Some(response), events)
+}
@@ -0,0 +1,50 @@
+use rec::{
+    mqtt,
+    obs_worker::{Action, Aliases},
+    state::ObsState,
+};
+
+fn muted(action: Option<Action>) -> (String, bool) {
+    match action {
+        Some(Action::SetMute(input, muted)) => (input, muted),
+        _ => panic!("expected a mute"),
+    }
+}
+
+// A wall switch sends "toggle" to mute/mic; each press has to flip the mic
+// OBS knows under its own name.
+#[test]
+fn toggle_through_an_alias_flips_the_aliased_input() {
+    let aliases = Aliases::default();
+    aliases
+        .write()
+        .unwrap()
+        .insert("mic".to_string(), "Mic/Aux".to_string());
+    let mut state = ObsState::default();
+    state.muted.insert("Mic/Aux".to_string(), false);
+
+    let (input, now_muted) = muted(mqtt::to_action("mute/mic", "toggle", &state, &aliases));
+    assert_eq!(input, "Mic/Aux");
+    assert!(now_muted);
+
+    state.muted.insert(input, now_muted);
+    let (input, now_muted) = muted(mqtt::to_action("mute/mic", "toggle", &state, &aliases));
+    assert_eq!(input, "Mic/Aux");
+    assert!(!now_muted);
+}
+
+#[test]
+fn toggle_by_input_name_still_works() {
+    let aliases = Aliases::default();
+    let mut state = ObsState::default();
+    state.muted.insert("Desktop Audio".to_string(), true);
+
+    let (input, now_muted) = muted(mqtt::to_action(
+        "mute/Desktop Audio",
+        "toggle",
+        &state,
+        &aliases,
+    ));
+    assert_eq!(input, "Desktop Audio");
+    assert!(!now_muted);
+}
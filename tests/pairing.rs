@@ -0,0 +1,60 @@
+use rec::{
+    instance,
+    pairing::{self, Grant, Pairing, Role},
+};
+use std::sync::{Arc, RwLock};
+
+fn paired(enabled: bool, grants: Vec<Grant>) -> pairing::Shared {
+    Arc::new(RwLock::new(Pairing { enabled, grants }))
+}
+
+fn grant(role: Role, token: &str) -> Grant {
+    Grant {
+        name: "Phone".to_string(),
+        role,
+        token: token.to_string(),
+    }
+}
+
+#[test]
+fn same_token_needs_every_byte_to_match() {
+    assert!(pairing::same_token("0123abcd", "0123abcd"));
+    assert!(!pairing::same_token("0123abcd", "0123abce"));
+    assert!(!pairing::same_token("0123abcd", "1123abcd"));
+    assert!(!pairing::same_token("0123abcd", "0123abc"));
+    assert!(!pairing::same_token("0123abcd", "0123abcde"));
+    assert!(!pairing::same_token("0123abcd", ""));
+}
+
+#[test]
+fn new_tokens_are_128_random_bits() {
+    let token = instance::new_token();
+    assert_eq!(token.len(), 32);
+    assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(token, instance::new_token());
+}
+
+// Anyone may watch while pairing is off, but only watch.
+#[test]
+fn without_pairing_everyone_is_a_viewer() {
+    let pairing = paired(false, vec![grant(Role::Admin, "good")]);
+    assert!(pairing::role(&pairing, None) == Some(Role::Viewer));
+    assert!(pairing::role(&pairing, Some("wrong")) == Some(Role::Viewer));
+}
+
+#[test]
+fn with_pairing_only_granted_tokens_get_in() {
+    let pairing = paired(true, vec![grant(Role::Operator, "good")]);
+    assert!(pairing::role(&pairing, Some("good")) == Some(Role::Operator));
+    assert!(pairing::role(&pairing, Some("wrong")).is_none());
+    assert!(pairing::role(&pairing, Some("goo")).is_none());
+    assert!(pairing::role(&pairing, None).is_none());
+}
+
+// Revoking the last device doesn't open everything up again.
+#[test]
+fn with_pairing_and_no_grants_nobody_gets_in() {
+    let pairing = paired(true, Vec::new());
+    assert!(pairing::role(&pairing, None).is_none());
+    assert!(pairing::role(&pairing, Some("")).is_none());
+}
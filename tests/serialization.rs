@@ -0,0 +1,678 @@
+// Anything REC can save, it must be able to load back unchanged, whatever the
+// operator typed into it. Values are compared by what they serialize to, since
+// most of these types have no PartialEq.
+
+use egui_dock::{DockState, NodeIndex};
+use proptest::{collection, prelude::*};
+use rec::{
+    audio::{AudioPreset, Ducking, DuckingPair, InputPreset, Level, Monitor, SceneCue},
+    config::{
+        ChannelStyle, Confirmation, Cue, SavedState, SliderMode, TeleprompterSettings,
+        WindowGeometry,
+    },
+    features::{Feature, Flags},
+    layout::{Layout, Orientation, Panel},
+    migrate::{self, STATE_VERSION},
+    obs_worker::{Action, Macro, MacroStep, MediaCommand},
+    state::Condition,
+    template::ShowTemplate,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::PathBuf,
+};
+
+// Proptest wants to print failing values; these types only know how to be
+// JSON, which reads fine in a failure message anyway.
+#[derive(Clone)]
+struct Json<T>(T);
+
+impl<T: Serialize> fmt::Debug for Json<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_string(&self.0) {
+            Ok(json) => f.write_str(&json),
+            Err(err) => write!(f, "<unserializable: {}>", err),
+        }
+    }
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Result<(), TestCaseError> {
+    let saved = serde_json::to_string(value).map_err(|err| TestCaseError::fail(err.to_string()))?;
+    let loaded: T = serde_json::from_str(&saved)
+        .map_err(|err| TestCaseError::fail(format!("{} in {}", err, saved)))?;
+    prop_assert_eq!(serde_json::to_string(&loaded).unwrap(), saved);
+    Ok(())
+}
+
+// Names as operators type them, which is anything printable.
+fn text() -> impl Strategy<Value = String> {
+    "\\PC{0,12}"
+}
+
+fn level() -> impl Strategy<Value = Json<Level>> {
+    prop_oneof![
+        (-100f32..26.0).prop_map(|db| Json(Level::Db(db))),
+        (-30f32..30.0).prop_map(|change| Json(Level::Change(change))),
+    ]
+}
+
+fn scene_cue() -> impl Strategy<Value = Json<SceneCue>> {
+    (
+        proptest::option::of(text()),
+        collection::btree_map(text(), level(), 0..3),
+        0u64..10_000,
+    )
+        .prop_map(|(scene, audio, fade_ms)| {
+            Json(SceneCue {
+                scene,
+                audio: audio
+                    .into_iter()
+                    .map(|(name, level)| (name, level.0))
+                    .collect(),
+                fade_ms,
+            })
+        })
+}
+
+fn media_command() -> impl Strategy<Value = Json<MediaCommand>> {
+    prop_oneof![
+        Just(Json(MediaCommand::Play)),
+        Just(Json(MediaCommand::Pause)),
+        Just(Json(MediaCommand::Restart)),
+        Just(Json(MediaCommand::Stop)),
+        Just(Json(MediaCommand::Next)),
+        Just(Json(MediaCommand::Previous)),
+    ]
+}
+
+// What buttons, macros and rules are built from.
+fn action() -> impl Strategy<Value = Json<Action>> {
+    prop_oneof![
+        (text(), any::<bool>()).prop_map(|(input, muted)| Json(Action::SetMute(input, muted))),
+        (text(), 0f32..100.0).prop_map(|(input, volume)| Json(Action::SetVolume(input, volume))),
+        text().prop_map(|scene| Json(Action::SetScene(scene))),
+        text().prop_map(|scene| Json(Action::SetPreviewScene(scene))),
+        scene_cue().prop_map(|cue| Json(Action::SceneCue(cue.0))),
+        Just(Json(Action::StartStream)),
+        Just(Json(Action::StopStream)),
+        Just(Json(Action::StartRecord)),
+        Just(Json(Action::StopRecord)),
+        (text(), text(), any::<bool>()).prop_map(|(source, filter, enabled)| {
+            Json(Action::SetFilterEnabled(source, filter, enabled))
+        }),
+        (text(), text()).prop_map(|(source, filter)| Json(Action::ToggleFilter(source, filter))),
+        (text(), text()).prop_map(|(input, text)| Json(Action::SetText(input, text))),
+        (text(), media_command())
+            .prop_map(|(input, command)| Json(Action::Media(input, command.0))),
+        text().prop_map(|hotkey| Json(Action::TriggerHotkey(hotkey))),
+        text().prop_map(|description| Json(Action::AddMarker(description))),
+        any::<bool>().prop_map(|active| Json(Action::Intermission(active))),
+    ]
+}
+
+fn condition() -> impl Strategy<Value = Json<Condition>> {
+    let leaf = prop_oneof![
+        Just(Json(Condition::Streaming)),
+        Just(Json(Condition::Recording)),
+        text().prop_map(|scene| Json(Condition::SceneIs(scene))),
+        text().prop_map(|input| Json(Condition::InputMuted(input))),
+        (text(), text())
+            .prop_map(|(source, filter)| Json(Condition::FilterEnabled(source, filter))),
+    ];
+    leaf.prop_recursive(3, 8, 1, |inner| {
+        inner.prop_map(|condition| Json(Condition::Not(Box::new(condition.0))))
+    })
+}
+
+fn macro_step() -> impl Strategy<Value = Json<MacroStep>> {
+    let leaf = action().prop_map(|action| Json(MacroStep::Do(action.0)));
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        (
+            condition(),
+            collection::vec(inner.clone(), 0..3),
+            collection::vec(inner, 0..3),
+        )
+            .prop_map(|(condition, then, otherwise)| {
+                Json(MacroStep::If {
+                    condition: condition.0,
+                    then: then.into_iter().map(|step| step.0).collect(),
+                    otherwise: otherwise.into_iter().map(|step| step.0).collect(),
+                })
+            })
+    })
+}
+
+fn obs_macro() -> impl Strategy<Value = Json<Macro>> {
+    (text(), collection::vec(macro_step(), 0..5)).prop_map(|(name, steps)| {
+        Json(Macro {
+            name,
+            steps: steps.into_iter().map(|step| step.0).collect(),
+        })
+    })
+}
+
+fn panels() -> impl Strategy<Value = Vec<usize>> {
+    collection::vec(0..Panel::ALL.len(), 1..4)
+}
+
+// A docked layout split any which way, sometimes with a floating window.
+fn layout() -> impl Strategy<Value = Json<Layout>> {
+    (
+        panels(),
+        collection::vec((any::<bool>(), 0.1f32..0.9, panels()), 0..4),
+        proptest::option::of(panels()),
+    )
+        .prop_map(|(first, splits, window)| {
+            let tabs = |indices: Vec<usize>| -> Vec<Panel> {
+                indices.into_iter().map(|i| Panel::ALL[i]).collect()
+            };
+            let mut layout = DockState::new(tabs(first));
+            let surface = layout.main_surface_mut();
+            let mut node = NodeIndex::root();
+            for (below, fraction, split) in splits {
+                let [_, new] = if below {
+                    surface.split_below(node, fraction, tabs(split))
+                } else {
+                    surface.split_right(node, fraction, tabs(split))
+                };
+                node = new;
+            }
+            if let Some(window) = window {
+                layout.add_window(tabs(window));
+            }
+            Json(layout)
+        })
+}
+
+fn window_geometry() -> impl Strategy<Value = Json<WindowGeometry>> {
+    (
+        proptest::option::of([-4000f32..4000.0, -4000f32..4000.0]),
+        [100f32..8000.0, 100f32..8000.0],
+        any::<bool>(),
+        proptest::option::of([100f32..8000.0, 100f32..8000.0]),
+    )
+        .prop_map(|(position, size, maximized, monitor_size)| {
+            Json(WindowGeometry {
+                position,
+                size,
+                maximized,
+                monitor_size,
+            })
+        })
+}
+
+// A mixer snapshot, as the rundown keeps them.
+fn cue() -> impl Strategy<Value = Json<Cue>> {
+    (
+        text(),
+        proptest::option::of(text()),
+        collection::btree_map(text(), (0f32..100.0, any::<bool>()), 0..3),
+        collection::vec((text(), text()), 0..3),
+        collection::vec(text(), 0..3),
+        text(),
+        proptest::option::of(0u64..36_000),
+        0u64..10_000,
+    )
+        .prop_map(
+            |(name, scene, audio, texts, media, notes, duration, fade_ms)| {
+                Json(Cue {
+                    name,
+                    scene,
+                    audio,
+                    texts,
+                    media,
+                    notes,
+                    duration,
+                    fade_ms,
+                })
+            },
+        )
+}
+
+fn audio_preset() -> impl Strategy<Value = Json<AudioPreset>> {
+    let monitor = prop_oneof![
+        Just(Json(Monitor::Off)),
+        Just(Json(Monitor::MonitorOnly)),
+        Just(Json(Monitor::MonitorAndOutput)),
+    ];
+    let input = (
+        proptest::option::of(-100f32..26.0),
+        proptest::option::of(monitor),
+        proptest::option::of(collection::vec(1usize..=6, 0..6)),
+    )
+        .prop_map(|(volume_db, monitor, tracks)| {
+            Json(InputPreset {
+                volume_db,
+                monitor: monitor.map(|monitor| monitor.0),
+                tracks,
+            })
+        });
+    (
+        text(),
+        collection::btree_map(text(), input, 0..3),
+        any::<bool>(),
+    )
+        .prop_map(|(name, inputs, ducking)| {
+            Json(AudioPreset {
+                name,
+                inputs: inputs
+                    .into_iter()
+                    .map(|(name, preset)| (name, preset.0))
+                    .collect(),
+                ducking,
+            })
+        })
+}
+
+fn ducking_pair() -> impl Strategy<Value = Json<DuckingPair>> {
+    (
+        text(),
+        text(),
+        -60f32..0.0,
+        0f32..40.0,
+        (0u32..1000, 0u32..5000, 0u32..5000),
+    )
+        .prop_map(
+            |(ducked, trigger, threshold, reduction, (attack_ms, hold_ms, release_ms))| {
+                Json(DuckingPair {
+                    ducked,
+                    trigger,
+                    ducking: Ducking {
+                        threshold,
+                        reduction,
+                        attack_ms,
+                        hold_ms,
+                        release_ms,
+                    },
+                })
+            },
+        )
+}
+
+fn show_template() -> impl Strategy<Value = Json<ShowTemplate>> {
+    (
+        text(),
+        collection::vec(obs_macro(), 0..3),
+        collection::vec(cue(), 0..3),
+        collection::vec(audio_preset(), 0..3),
+        collection::vec(ducking_pair(), 0..2),
+        proptest::option::of((text(), text())),
+    )
+        .prop_map(
+            |(name, macros, rundown, audio_presets, ducking, connection)| {
+                Json(ShowTemplate {
+                    name,
+                    macros: macros.into_iter().map(|m| m.0).collect(),
+                    rundown: rundown.into_iter().map(|cue| cue.0).collect(),
+                    audio_presets: audio_presets.into_iter().map(|p| p.0).collect(),
+                    ducking: ducking.into_iter().map(|pair| pair.0).collect(),
+                    connection,
+                })
+            },
+        )
+}
+
+fn saved_state() -> impl Strategy<Value = Json<SavedState>> {
+    let names = (
+        proptest::option::of(text()),
+        proptest::option::of(text()),
+        collection::btree_map(
+            text(),
+            (text(), proptest::option::of(any::<[u8; 3]>()), text()),
+            0..4,
+        ),
+        text(),
+        proptest::option::of(text()),
+    );
+    let settings = (
+        0.5f32..3.0,
+        any::<bool>(),
+        0usize..3,
+        0usize..3,
+        collection::vec(any::<bool>(), Feature::ALL.len()),
+        collection::vec(any::<bool>(), 2),
+        collection::vec(0..Panel::ALL.len(), 0..4),
+        (1f32..200.0, 8f32..200.0, any::<bool>(), text()),
+    );
+    let shows = (
+        collection::vec(obs_macro(), 0..3),
+        proptest::option::of(layout()),
+        collection::btree_map(text(), layout(), 0..2),
+        collection::btree_map(text(), window_geometry(), 0..2),
+        proptest::option::of(window_geometry()),
+        collection::vec(cue(), 0..3),
+        collection::vec(ducking_pair(), 0..2),
+    );
+    (names, settings, shows).prop_map(|(names, settings, shows)| {
+        let (mic, desktop, channels, operator_name, language) = names;
+        let (
+            ui_scale,
+            touch_mode,
+            orientation,
+            slider_mode,
+            features,
+            confirmations,
+            collapsed,
+            (speed, font_size, mirror, script),
+        ) = settings;
+        let (macros, layout, layouts, layout_windows, window, rundown, ducking) = shows;
+        let mut flags = Flags::default();
+        for (feature, enabled) in Feature::ALL.into_iter().zip(features) {
+            flags.set(feature, enabled);
+        }
+        Json(SavedState {
+            mic_input_name: mic,
+            desktop_input_name: desktop,
+            channels: channels
+                .into_iter()
+                .map(|(input, (label, color, group))| {
+                    (
+                        input,
+                        ChannelStyle {
+                            label,
+                            color,
+                            group,
+                        },
+                    )
+                })
+                .collect(),
+            operator_name,
+            language,
+            ui_scale,
+            touch_mode,
+            orientation: Orientation::ALL[orientation],
+            slider_mode: SliderMode::ALL[slider_mode],
+            features: flags,
+            skip_confirmations: Confirmation::ALL
+                .into_iter()
+                .zip(confirmations)
+                .filter_map(|(confirmation, skip)| skip.then_some(confirmation))
+                .collect(),
+            collapsed: collapsed.into_iter().map(|i| Panel::ALL[i]).collect(),
+            teleprompter: TeleprompterSettings {
+                script,
+                speed,
+                font_size,
+                mirror,
+            },
+            macros: macros.into_iter().map(|m| m.0).collect(),
+            layout: layout.map(|layout| layout.0),
+            layouts: layouts
+                .into_iter()
+                .map(|(name, layout)| (name, layout.0))
+                .collect(),
+            layout_windows: layout_windows
+                .into_iter()
+                .map(|(name, window)| (name, window.0))
+                .collect::<BTreeMap<_, _>>(),
+            window: window.map(|window| window.0),
+            rundown: rundown.into_iter().map(|cue| cue.0).collect(),
+            ducking: ducking.into_iter().map(|pair| pair.0).collect(),
+            ..SavedState::default()
+        })
+    })
+}
+
+// Never there, so a migration's backup has nothing to copy.
+fn missing_state_path() -> PathBuf {
+    std::env::temp_dir()
+        .join("rec-serialization-tests-missing")
+        .join("state.json")
+}
+
+proptest! {
+    #[test]
+    fn macros_round_trip(obs_macro in obs_macro()) {
+        round_trip(&obs_macro.0)?;
+    }
+
+    #[test]
+    fn layouts_round_trip(layout in layout()) {
+        round_trip(&layout.0)?;
+    }
+
+    #[test]
+    fn cues_round_trip(cue in cue()) {
+        round_trip(&cue.0)?;
+    }
+
+    #[test]
+    fn show_templates_round_trip(template in show_template()) {
+        round_trip(&template.0)?;
+    }
+
+    #[test]
+    fn saved_state_round_trips(state in saved_state()) {
+        round_trip(&state.0)?;
+    }
+
+    // What this version saves, this version loads without migrating.
+    #[test]
+    fn saved_state_loads_without_migration(state in saved_state()) {
+        let saved = serde_json::to_string(&state.0).unwrap();
+        let (loaded, migration): (SavedState, _) =
+            migrate::load(&missing_state_path(), &saved);
+        prop_assert!(migration.is_none());
+        prop_assert_eq!(serde_json::to_string(&loaded).unwrap(), saved);
+    }
+
+    // A file from before the state was versioned loads into the same state,
+    // quietly.
+    #[test]
+    fn unversioned_saved_state_migrates(state in saved_state()) {
+        let mut fields = serde_json::to_value(&state.0).unwrap();
+        fields.as_object_mut().unwrap().remove("version");
+        let (loaded, migration): (SavedState, _) =
+            migrate::load(&missing_state_path(), &fields.to_string());
+        let migration = migration.expect("an unversioned file is migrated");
+        prop_assert_eq!(migration.from, 0);
+        prop_assert!(migration.is_quiet());
+        prop_assert_eq!(loaded.version, STATE_VERSION);
+        prop_assert_eq!(
+            serde_json::to_string(&loaded).unwrap(),
+            serde_json::to_string(&state.0).unwrap()
+        );
+    }
+}
+
+#[test]
+fn default_saved_state_round_trips() {
+    let saved = serde_json::to_string(&SavedState::default()).unwrap();
+    let (loaded, migration): (SavedState, _) = migrate::load(&missing_state_path(), &saved);
+    assert!(migration.is_none());
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), saved);
+}
+
+// Version 1 checked tokens whenever a device was paired, so those files keep
+// pairing on, and only those.
+#[test]
+fn version_1_pairing_stays_on_with_grants() {
+    let grant = serde_json::json!({ "name": "Phone", "role": "operator", "token": "abc" });
+    for (grants, enabled) in [(vec![grant], true), (Vec::new(), false)] {
+        let saved = serde_json::json!({ "version": 1, "grants": grants }).to_string();
+        let (loaded, migration): (SavedState, _) = migrate::load(&missing_state_path(), &saved);
+        assert_eq!(migration.expect("a version 1 file is migrated").from, 1);
+        assert_eq!(loaded.pairing.enabled, enabled);
+        assert_eq!(loaded.pairing.grants.len(), grants.len());
+    }
+}
+
+// A state file of its own in a fresh directory, for migrations that back it
+// up next to it.
+fn state_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "rec-serialization-tests-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("state.json");
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn old_state_migrates_and_is_backed_up() {
+    let saved = serde_json::json!({
+        "version": 1,
+        "grants": [{ "name": "Phone", "role": "viewer", "token": "abc" }],
+        "relay_port": "9999",
+        "macros": [
+            { "name": "Go live", "steps": [{ "Do": "StartStream" }] },
+            { "name": 5 },
+        ],
+        "ui_scale": "big",
+    })
+    .to_string();
+    let path = state_file("old", &saved);
+    let (loaded, migration): (SavedState, _) = migrate::load(&path, &saved);
+
+    let migration = migration.expect("an old file is migrated");
+    assert_eq!(migration.from, 1);
+    assert!(!migration.unreadable);
+    assert_eq!(migration.dropped, ["macros #2", "ui_scale"]);
+    let backup = migration.backup.expect("the old file is backed up");
+    assert_eq!(backup.parent(), path.parent());
+    assert_eq!(std::fs::read_to_string(&backup).unwrap(), saved);
+
+    assert_eq!(loaded.version, STATE_VERSION);
+    assert!(loaded.pairing.enabled);
+    assert_eq!(loaded.pairing.grants[0].token, "abc");
+    assert_eq!(loaded.relay_port, "9999");
+    assert_eq!(loaded.macros.len(), 1);
+    assert_eq!(loaded.macros[0].name, "Go live");
+    assert_eq!(loaded.ui_scale, SavedState::default().ui_scale);
+}
+
+#[test]
+fn unreadable_state_starts_from_defaults_and_is_backed_up() {
+    let path = state_file("unreadable", "{ not json");
+    let (loaded, migration): (SavedState, _) = migrate::load(&path, "{ not json");
+
+    let migration = migration.expect("an unreadable file is reported");
+    assert!(migration.unreadable);
+    assert!(!migration.is_quiet());
+    let backup = migration.backup.expect("the old file is backed up");
+    assert_eq!(std::fs::read_to_string(backup).unwrap(), "{ not json");
+    assert_eq!(
+        serde_json::to_string(&loaded).unwrap(),
+        serde_json::to_string(&SavedState::default()).unwrap()
+    );
+}
+
+// Whatever a newer REC added is let go, and the operator is told.
+#[test]
+fn newer_state_keeps_what_this_version_reads() {
+    let saved = serde_json::json!({
+        "version": STATE_VERSION + 1,
+        "relay_port": "9999",
+        "hologram": true,
+    })
+    .to_string();
+    let path = state_file("newer", &saved);
+    let (loaded, migration): (SavedState, _) = migrate::load(&path, &saved);
+
+    let migration = migration.expect("a newer file is reported");
+    assert_eq!(migration.from, STATE_VERSION + 1);
+    assert!(!migration.is_quiet());
+    assert!(migration.backup.is_some());
+    assert_eq!(loaded.version, STATE_VERSION);
+    assert_eq!(loaded.relay_port, "9999");
+}
+
+// Every scene and source a template names, renamed so no generated name can
+// clash with it.
+fn renamed(names: &BTreeSet<String>) -> BTreeMap<String, String> {
+    names
+        .iter()
+        .map(|name| (name.clone(), format!("\u{1}{}", name)))
+        .collect()
+}
+
+fn reversed(map: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    map.iter()
+        .map(|(old, new)| (new.clone(), old.clone()))
+        .collect()
+}
+
+proptest! {
+    // Renaming everything and back gives the same template, so remapping
+    // touches names and nothing else.
+    #[test]
+    fn show_template_remaps_back(template in show_template()) {
+        let mut template = template.0;
+        let original = serde_json::to_string(&template).unwrap();
+        let before = template.missing(&[], &[]);
+        let (scenes, sources) = (renamed(&before.scenes), renamed(&before.sources));
+
+        template.remap(&scenes, &sources);
+        let after = template.missing(&[], &[]);
+        prop_assert_eq!(&after.scenes, &scenes.values().cloned().collect::<BTreeSet<_>>());
+        prop_assert_eq!(&after.sources, &sources.values().cloned().collect::<BTreeSet<_>>());
+
+        template.remap(&reversed(&scenes), &reversed(&sources));
+        prop_assert_eq!(serde_json::to_string(&template).unwrap(), original);
+    }
+}
+
+#[test]
+fn show_template_remaps_scenes_and_sources_in_macros() {
+    let mut template = ShowTemplate {
+        name: "Friday".to_string(),
+        macros: vec![Macro {
+            name: "Open".to_string(),
+            steps: vec![
+                MacroStep::Do(Action::SetScene("Intro".to_string())),
+                MacroStep::Do(Action::SetMute("Cam Mic".to_string(), false)),
+                MacroStep::Do(Action::SetMute("mic".to_string(), true)),
+                MacroStep::If {
+                    condition: Condition::SceneIs("Intro".to_string()),
+                    then: vec![MacroStep::Do(Action::SetFilterEnabled(
+                        "Cam Mic".to_string(),
+                        "Intro".to_string(),
+                        true,
+                    ))],
+                    otherwise: vec![MacroStep::Do(Action::SetScene("Outro".to_string()))],
+                },
+            ],
+        }],
+        connection: Some(("10.0.0.2".to_string(), "4455".to_string())),
+        ..ShowTemplate::default()
+    };
+    let scenes = BTreeMap::from([("Intro".to_string(), "Opening".to_string())]);
+    let sources = BTreeMap::from([("Cam Mic".to_string(), "Lav".to_string())]);
+    template.remap(&scenes, &sources);
+
+    let expected = ShowTemplate {
+        name: "Friday".to_string(),
+        macros: vec![Macro {
+            name: "Open".to_string(),
+            steps: vec![
+                MacroStep::Do(Action::SetScene("Opening".to_string())),
+                MacroStep::Do(Action::SetMute("Lav".to_string(), false)),
+                // Follows whichever input is picked as the mic.
+                MacroStep::Do(Action::SetMute("mic".to_string(), true)),
+                MacroStep::If {
+                    condition: Condition::SceneIs("Opening".to_string()),
+                    // A filter's own name is never a scene's, whatever it's
+                    // called.
+                    then: vec![MacroStep::Do(Action::SetFilterEnabled(
+                        "Lav".to_string(),
+                        "Intro".to_string(),
+                        true,
+                    ))],
+                    otherwise: vec![MacroStep::Do(Action::SetScene("Outro".to_string()))],
+                },
+            ],
+        }],
+        connection: Some(("10.0.0.2".to_string(), "4455".to_string())),
+        ..ShowTemplate::default()
+    };
+    assert_eq!(
+        serde_json::to_string(&template).unwrap(),
+        serde_json::to_string(&expected).unwrap()
+    );
+}
@@ -0,0 +1,240 @@
+mod mock_obs;
+
+use egui::accesskit::{Node, Role};
+use mock_obs::{MockObs, ObsModel};
+use rec::{
+    config::{ConnectionConfig, RecConfig, SavedState},
+    logging::LogBuffer,
+    obs_worker::{Action, Worker},
+    ui::App,
+};
+use serde_json::json;
+use std::{
+    sync::{Arc, Once},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+// Keeps the app away from the user's own settings and off the network: every
+// run gets empty directories and a rec.toml that skips the update check.
+fn isolate() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let root = std::env::temp_dir().join(format!("rec-ui-tests-{}", std::process::id()));
+        for (var, dir) in [
+            ("XDG_CONFIG_HOME", "config"),
+            ("XDG_DATA_HOME", "data"),
+            ("XDG_CACHE_HOME", "cache"),
+        ] {
+            std::env::set_var(var, root.join(dir));
+        }
+        let path = RecConfig::path().unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "[ui]\ncheck_updates = false\n").unwrap();
+    });
+}
+
+// Runs the whole UI headless against the worker, one frame per step, and
+// finds widgets the way a screen reader would: by their accessible names.
+struct Harness {
+    ctx: egui::Context,
+    app: App,
+    // Everything the app sent, in order. Each action is also passed on to
+    // the worker.
+    sent: Vec<Action>,
+    app_rx: mpsc::Receiver<Action>,
+    worker_tx: mpsc::Sender<Action>,
+    events: Vec<egui::Event>,
+    nodes: Vec<Node>,
+}
+
+impl Harness {
+    fn start(mock: &MockObs) -> Self {
+        isolate();
+        let (app_tx, app_rx) = mpsc::channel(100);
+        let (worker_tx, action_rx) = mpsc::channel(100);
+        let (obs_info_tx, obs_info_rx) = mpsc::channel(100);
+        let worker_self_tx = worker_tx.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(Worker::new(obs_info_tx, worker_self_tx, Arc::new(|| {})).run(action_rx));
+        });
+        let saved = SavedState {
+            language: Some("en".to_string()),
+            connection: ConnectionConfig {
+                addr: "127.0.0.1".to_string(),
+                port: mock.port.to_string(),
+                pass: String::new(),
+            },
+            mic_input_name: Some("Mic".to_string()),
+            desktop_input_name: Some("Desktop".to_string()),
+            ..SavedState::default()
+        };
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        let app = App::new(&ctx, app_tx, obs_info_rx, LogBuffer::default(), saved);
+        let mut harness = Self {
+            ctx,
+            app,
+            sent: Vec::new(),
+            app_rx,
+            worker_tx,
+            events: Vec::new(),
+            nodes: Vec::new(),
+        };
+        harness.step();
+        harness
+    }
+
+    fn step(&mut self) {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(1280.0, 800.0),
+            )),
+            events: std::mem::take(&mut self.events),
+            ..egui::RawInput::default()
+        };
+        let output = self.ctx.run(input, |ctx| self.app.show(ctx));
+        if let Some(update) = output.platform_output.accesskit_update {
+            self.nodes = update.nodes.into_iter().map(|(_, node)| node).collect();
+        }
+        while let Ok(action) = self.app_rx.try_recv() {
+            self.worker_tx.try_send(action.clone()).unwrap();
+            self.sent.push(action);
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.name() == Some(name))
+    }
+
+    fn click(&mut self, name: &str) {
+        let bounds = self
+            .find(name)
+            .and_then(|node| node.bounds())
+            .unwrap_or_else(|| panic!("no widget named {:?}", name));
+        let pos = egui::pos2(
+            ((bounds.x0 + bounds.x1) / 2.0) as f32,
+            ((bounds.y0 + bounds.y1) / 2.0) as f32,
+        );
+        for pressed in [true, false] {
+            self.events.push(egui::Event::PointerMoved(pos));
+            self.events.push(egui::Event::PointerButton {
+                pos,
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            });
+            self.step();
+        }
+    }
+
+    // Steps frames, letting the worker and the mock run in between, until the
+    // UI shows what's expected.
+    async fn wait_until(&mut self, what: &str, mut done: impl FnMut(&Self) -> bool) {
+        let waited = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                self.step();
+                if done(self) {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        waited.unwrap_or_else(|_| panic!("timed out waiting for {}", what));
+    }
+
+    async fn log_in(&mut self) {
+        self.click("Log In");
+        self.wait_until("the connection", |harness| {
+            harness.find("Connected").is_some()
+        })
+        .await;
+    }
+}
+
+fn studio() -> ObsModel {
+    ObsModel {
+        inputs: vec![
+            ("Mic".to_string(), "wasapi_input_capture".to_string()),
+            ("Desktop".to_string(), "wasapi_output_capture".to_string()),
+        ],
+        muted: [("Mic".to_string(), false), ("Desktop".to_string(), false)].into(),
+        volumes: [("Mic".to_string(), 1.0), ("Desktop".to_string(), 1.0)].into(),
+        scenes: vec!["Live".to_string(), "BRB".to_string()],
+        current_scene: "Live".to_string(),
+        ..ObsModel::default()
+    }
+}
+
+#[tokio::test]
+async fn clicking_mute_sends_set_mute() {
+    let mock = MockObs::start(studio()).await;
+    let mut harness = Harness::start(&mock);
+    harness.log_in().await;
+
+    harness.click("Mute Mic");
+
+    assert!(harness
+        .sent
+        .iter()
+        .any(|action| matches!(action, Action::SetMute(name, true) if name == "Mic")));
+    let request = mock.wait_for_request("SetInputMute").await;
+    assert_eq!(request["inputName"], json!("Mic"));
+    assert_eq!(request["inputMuted"], json!(true));
+    harness
+        .wait_until("the button to show muted", |harness| {
+            harness.find("Mute Mic").and_then(Node::is_selected) == Some(true)
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn mute_from_obs_updates_the_button() {
+    let mock = MockObs::start(studio()).await;
+    let mut harness = Harness::start(&mock);
+    harness.log_in().await;
+    assert_eq!(
+        harness.find("Mute Desktop").and_then(Node::is_selected),
+        Some(false)
+    );
+
+    mock.emit(
+        "InputMuteStateChanged",
+        json!({ "inputName": "Desktop", "inputMuted": true }),
+    );
+
+    harness
+        .wait_until("the button to show muted", |harness| {
+            harness.find("Mute Desktop").and_then(Node::is_selected) == Some(true)
+        })
+        .await;
+    // Only the echo from OBS changed it; the UI sent nothing of its own.
+    assert!(!harness
+        .sent
+        .iter()
+        .any(|action| matches!(action, Action::SetMute(..))));
+}
+
+#[tokio::test]
+async fn scene_change_from_obs_shows_in_the_status_bar() {
+    let mock = MockObs::start(studio()).await;
+    let mut harness = Harness::start(&mock);
+    harness.log_in().await;
+
+    mock.emit("CurrentProgramSceneChanged", json!({ "sceneName": "BRB" }));
+
+    // Scene buttons carry the name too; the status bar shows it as text.
+    harness
+        .wait_until("the new scene", |harness| {
+            harness.nodes.iter().any(|node| {
+                matches!(node.role(), Role::StaticText | Role::Label) && node.name() == Some("BRB")
+            })
+        })
+        .await;
+}
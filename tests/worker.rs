@@ -0,0 +1,236 @@
+mod mock_obs;
+
+use mock_obs::{MockObs, ObsModel};
+use rec::{
+    obs_worker::{Action, Macro, MacroStep, ObsInfo, Worker},
+    state::ObsState,
+};
+use serde_json::json;
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+struct Session {
+    action_tx: mpsc::Sender<Action>,
+    obs_info_rx: mpsc::Receiver<ObsInfo>,
+    // Everything received so far, folded the same way the UI does it.
+    state: ObsState,
+}
+
+impl Session {
+    // Runs the worker on its own thread like `obs_worker::spawn`, but without
+    // touching the user's journal.
+    fn start() -> Self {
+        let (action_tx, action_rx) = mpsc::channel(10);
+        let (obs_info_tx, obs_info_rx) = mpsc::channel(10);
+        let worker_tx = action_tx.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(Worker::new(obs_info_tx, worker_tx, Arc::new(|| {})).run(action_rx));
+        });
+        Self {
+            action_tx,
+            obs_info_rx,
+            state: ObsState::default(),
+        }
+    }
+
+    async fn log_in(mock: &MockObs) -> Self {
+        let mut session = Self::start();
+        session
+            .send(Action::LogIn(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                mock.port,
+                String::new(),
+            ))
+            .await;
+        session
+            .wait_for(|info| matches!(info, ObsInfo::State(_)).then_some(()))
+            .await;
+        session
+    }
+
+    async fn send(&self, action: Action) {
+        self.action_tx.send(action).await.unwrap();
+    }
+
+    async fn wait_for<T>(&mut self, mut found: impl FnMut(&ObsInfo) -> Option<T>) -> T {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let info = self.obs_info_rx.recv().await.expect("worker stopped");
+                if let ObsInfo::Error(err) = &info {
+                    panic!("worker reported an error: {}", err);
+                }
+                self.state.apply(&info);
+                if let Some(value) = found(&info) {
+                    return value;
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for the worker")
+    }
+}
+
+fn studio() -> ObsModel {
+    ObsModel {
+        inputs: vec![
+            ("Mic".to_string(), "wasapi_input_capture".to_string()),
+            ("Desktop".to_string(), "wasapi_output_capture".to_string()),
+        ],
+        muted: [("Mic".to_string(), false), ("Desktop".to_string(), true)].into(),
+        volumes: [("Mic".to_string(), 0.5), ("Desktop".to_string(), 1.0)].into(),
+        scenes: vec!["Live".to_string(), "BRB".to_string()],
+        current_scene: "Live".to_string(),
+        ..ObsModel::default()
+    }
+}
+
+#[tokio::test]
+async fn log_in_syncs_initial_state() {
+    let mock = MockObs::start(studio()).await;
+    let session = Session::log_in(&mock).await;
+
+    let state = &session.state;
+    assert_eq!(state.current_scene.as_deref(), Some("Live"));
+    assert!(state.scenes.contains(&"BRB".to_string()));
+    assert_eq!(state.muted.get("Mic"), Some(&false));
+    assert_eq!(state.muted.get("Desktop"), Some(&true));
+    assert!((state.volumes["Mic"] - 50.0).abs() < 0.01);
+    assert!(!state.streaming);
+    assert!(!state.recording);
+}
+
+#[tokio::test]
+async fn set_mute_reaches_obs_and_echoes_back() {
+    let mock = MockObs::start(studio()).await;
+    let mut session = Session::log_in(&mock).await;
+
+    session.send(Action::SetMute("Mic".to_string(), true)).await;
+    session
+        .wait_for(|info| match info {
+            ObsInfo::MuteChanged(name, true) if name == "Mic" => Some(()),
+            _ => None,
+        })
+        .await;
+
+    assert_eq!(mock.model().muted.get("Mic"), Some(&true));
+    assert_eq!(session.state.muted.get("Mic"), Some(&true));
+}
+
+#[tokio::test]
+async fn aliases_resolve_to_input_names() {
+    let mock = MockObs::start(studio()).await;
+    let mut session = Session::log_in(&mock).await;
+
+    session
+        .send(Action::SetAlias("mic".to_string(), Some("Mic".to_string())))
+        .await;
+    session
+        .send(Action::SetVolume("mic".to_string(), 25.0))
+        .await;
+
+    let request = mock.wait_for_request("SetInputVolume").await;
+    assert_eq!(request["inputName"], json!("Mic"));
+    let volume = session
+        .wait_for(|info| match info {
+            ObsInfo::VolumeChanged(name, volume) if name == "Mic" => Some(*volume),
+            _ => None,
+        })
+        .await;
+    assert!((volume - 25.0).abs() < 0.01);
+}
+
+#[tokio::test]
+async fn stream_and_record_state_follow_obs() {
+    let mock = MockObs::start(studio()).await;
+    let mut session = Session::log_in(&mock).await;
+
+    session.send(Action::StartStream).await;
+    session
+        .wait_for(|info| matches!(info, ObsInfo::StreamState(true)).then_some(()))
+        .await;
+    session.send(Action::StartRecord).await;
+    session
+        .wait_for(|info| matches!(info, ObsInfo::RecordState(true)).then_some(()))
+        .await;
+
+    assert!(session.state.streaming);
+    assert!(session.state.recording);
+    assert!(mock.model().streaming);
+    assert!(mock.model().recording);
+}
+
+#[tokio::test]
+async fn injected_events_update_state() {
+    let mock = MockObs::start(studio()).await;
+    let mut session = Session::log_in(&mock).await;
+
+    mock.emit("CurrentProgramSceneChanged", json!({ "sceneName": "BRB" }));
+    session
+        .wait_for(|info| match info {
+            ObsInfo::SceneChanged(name) if name == "BRB" => Some(()),
+            _ => None,
+        })
+        .await;
+    mock.emit(
+        "InputNameChanged",
+        json!({ "oldInputName": "Mic", "inputName": "Podcast Mic" }),
+    );
+    session
+        .wait_for(|info| matches!(info, ObsInfo::InputRenamed(..)).then_some(()))
+        .await;
+
+    assert_eq!(session.state.current_scene.as_deref(), Some("BRB"));
+    assert_eq!(session.state.muted.get("Podcast Mic"), Some(&false));
+    assert!(!session.state.muted.contains_key("Mic"));
+}
+
+#[tokio::test]
+async fn exit_started_reports_connection_lost() {
+    let mock = MockObs::start(studio()).await;
+    let mut session = Session::log_in(&mock).await;
+
+    mock.emit("ExitStarted", serde_json::Value::Null);
+    session
+        .wait_for(|info| matches!(info, ObsInfo::ConnectionLost).then_some(()))
+        .await;
+}
+
+#[tokio::test]
+async fn macro_steps_go_to_obs_as_one_batch() {
+    let mock = MockObs::start(studio()).await;
+    let mut session = Session::log_in(&mock).await;
+
+    session
+        .send(Action::RunMacro(Macro {
+            name: "Start show".to_string(),
+            steps: vec![
+                MacroStep::Do(Action::SetMute("Mic".to_string(), true)),
+                MacroStep::Do(Action::SetVolume("Desktop".to_string(), 40.0)),
+                MacroStep::Do(Action::SetScene("BRB".to_string())),
+            ],
+        }))
+        .await;
+    session
+        .wait_for(|info| match info {
+            ObsInfo::SceneChanged(name) if name == "BRB" => Some(()),
+            _ => None,
+        })
+        .await;
+
+    let model = mock.model();
+    let batch = model.batches.last().expect("no batch was sent");
+    assert_eq!(
+        batch,
+        &["SetInputMute", "SetInputVolume", "SetCurrentProgramScene"]
+    );
+    assert_eq!(model.muted.get("Mic"), Some(&true));
+    assert!((model.volumes["Desktop"] - 0.4).abs() < 0.001);
+}